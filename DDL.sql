@@ -1,31 +1,455 @@
-DROP TABLE IF EXISTS question, answer;
+DROP TABLE IF EXISTS notification, notification_preference, question_follow, question, answer, question_close_vote, reputation_event, bounty, user_badge, question_revision, answer_revision, question_vote, answer_vote, question_tag, tag_synonym, tag_wiki_revision, tag, comment, user_session, users;
+
+-- users table
+CREATE TABLE IF NOT EXISTS users (
+    user_uuid UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    username VARCHAR(32) NOT NULL UNIQUE,
+    email VARCHAR(255) NOT NULL UNIQUE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    password_hash TEXT,
+    role VARCHAR(16) NOT NULL DEFAULT 'user' CHECK (role IN ('user', 'moderator', 'admin')),
+    reputation INTEGER NOT NULL DEFAULT 1
+);
+
+COMMENT ON TABLE users IS 'User table';
+
+COMMENT ON COLUMN users.user_uuid IS 'Generated identifier unique to each user';
+COMMENT ON COLUMN users.username IS 'Unique public name of the user';
+COMMENT ON COLUMN users.email IS 'Unique email address of the user';
+COMMENT ON COLUMN users.created_at IS 'Registration timestamp of the user';
+COMMENT ON COLUMN users.password_hash IS 'Argon2 hash of the password, missing for accounts predating passwords';
+COMMENT ON COLUMN users.role IS 'Permission level: user, moderator or admin';
+COMMENT ON COLUMN users.reputation IS 'Running total of reputation_event, rebuilt on recalculation';
+
+-- user_session table
+CREATE TABLE IF NOT EXISTS user_session (
+    token_hash VARCHAR(64) PRIMARY KEY,
+    user_uuid UUID NOT NULL REFERENCES users (user_uuid) ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    expires_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS user_session_user_uuid_idx ON user_session (user_uuid);
+
+COMMENT ON TABLE user_session IS 'Login session table';
+
+COMMENT ON COLUMN user_session.token_hash IS 'SHA-256 digest of the bearer token';
+COMMENT ON COLUMN user_session.user_uuid IS 'User the session belongs to';
+COMMENT ON COLUMN user_session.created_at IS 'Login timestamp';
+COMMENT ON COLUMN user_session.expires_at IS 'Time after which the token is no longer accepted';
+
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
 
 -- question table
 CREATE TABLE IF NOT EXISTS question (
     question_uuid UUID PRIMARY KEY DEFAULT gen_random_uuid(),
     title VARCHAR(255) NOT NULL,
     description VARCHAR(255) NOT NULL,
-    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    author_uuid UUID REFERENCES users (user_uuid) ON DELETE SET NULL,
+    score INTEGER NOT NULL DEFAULT 0,
+    accepted_answer_uuid UUID,
+    search_vector TSVECTOR GENERATED ALWAYS AS (
+        setweight(to_tsvector('english', title), 'A') ||
+        setweight(to_tsvector('english', description), 'B')
+    ) STORED,
+    state VARCHAR(16) NOT NULL DEFAULT 'open' CHECK (state IN ('open', 'closed', 'locked')),
+    close_reason VARCHAR(16)
+        CHECK (close_reason IN ('duplicate', 'off_topic', 'unclear', 'too_broad', 'opinion_based')),
+    duplicate_of UUID REFERENCES question (question_uuid) ON DELETE SET NULL,
+    deleted_at TIMESTAMP,
+    deleted_by UUID REFERENCES users (user_uuid) ON DELETE SET NULL,
+    CONSTRAINT question_closed_reason_check CHECK (state <> 'closed' OR close_reason IS NOT NULL),
+    CONSTRAINT question_open_reason_check CHECK (state <> 'open' OR close_reason IS NULL),
+    CONSTRAINT question_duplicate_of_check CHECK (
+        duplicate_of IS NULL OR (close_reason = 'duplicate' AND duplicate_of <> question_uuid)
+    )
 );
 
+CREATE INDEX IF NOT EXISTS question_author_uuid_idx ON question (author_uuid);
+CREATE INDEX IF NOT EXISTS question_search_vector_idx ON question USING GIN (search_vector);
+CREATE INDEX IF NOT EXISTS question_title_trgm_idx ON question USING GIN (title gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS question_deleted_at_idx ON question (deleted_at) WHERE deleted_at IS NOT NULL;
+
 COMMENT ON TABLE question IS 'Question table';
 
 COMMENT ON COLUMN question.question_uuid IS 'Generated identifier unique to each question';
 COMMENT ON COLUMN question.title IS 'Title of the question';
 COMMENT ON COLUMN question.description IS 'Description of the question';
 COMMENT ON COLUMN question.created_at IS 'Creation timestamp of the question';
+COMMENT ON COLUMN question.author_uuid IS 'User who asked the question';
+COMMENT ON COLUMN question.score IS 'Sum of the votes on the question, maintained alongside question_vote';
+COMMENT ON COLUMN question.accepted_answer_uuid IS 'Answer accepted by the asker, must belong to the question';
+COMMENT ON COLUMN question.search_vector IS 'Full-text search document, title weighted above description';
+COMMENT ON COLUMN question.state IS 'Lifecycle state: open, closed or locked';
+COMMENT ON COLUMN question.close_reason IS 'Why the question was closed, kept while it is locked';
+COMMENT ON COLUMN question.duplicate_of IS 'Question this one duplicates, for the duplicate close reason';
+COMMENT ON COLUMN question.deleted_at IS 'When the question was deleted, purged after the retention period';
+COMMENT ON COLUMN question.deleted_by IS 'User who deleted the question';
 
 -- answer table
 CREATE TABLE IF NOT EXISTS answer (
     answer_uuid UUID PRIMARY KEY DEFAULT gen_random_uuid(),
     question_uuid UUID NOT NULL REFERENCES question (question_uuid) ON DELETE CASCADE,
     content VARCHAR(255) NOT NULL,
-    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    author_uuid UUID REFERENCES users (user_uuid) ON DELETE SET NULL,
+    score INTEGER NOT NULL DEFAULT 0,
+    search_vector TSVECTOR GENERATED ALWAYS AS (setweight(to_tsvector('english', content), 'C')) STORED,
+    deleted_at TIMESTAMP,
+    deleted_by UUID REFERENCES users (user_uuid) ON DELETE SET NULL,
+    CONSTRAINT answer_uuid_question_uuid_key UNIQUE (answer_uuid, question_uuid)
 );
 
+CREATE INDEX IF NOT EXISTS answer_author_uuid_idx ON answer (author_uuid);
+CREATE INDEX IF NOT EXISTS answer_search_vector_idx ON answer USING GIN (search_vector);
+CREATE INDEX IF NOT EXISTS answer_deleted_at_idx ON answer (deleted_at) WHERE deleted_at IS NOT NULL;
+
+-- the accepted answer must be an answer of the same question
+ALTER TABLE question ADD CONSTRAINT question_accepted_answer_fkey
+    FOREIGN KEY (accepted_answer_uuid, question_uuid)
+    REFERENCES answer (answer_uuid, question_uuid)
+    ON DELETE SET NULL (accepted_answer_uuid);
+
 COMMENT ON TABLE answer IS 'Answer table';
 
 COMMENT ON COLUMN answer.answer_uuid IS 'Generated identifier unique to each answer ';
 COMMENT ON COLUMN answer.question_uuid IS 'Generated identifier unique to each question';
 COMMENT ON COLUMN answer.content IS 'Content of the answer';
 COMMENT ON COLUMN answer.created_at IS 'Creation timestamp of the answer';
+COMMENT ON COLUMN answer.author_uuid IS 'User who wrote the answer';
+COMMENT ON COLUMN answer.score IS 'Sum of the votes on the answer, maintained alongside answer_vote';
+COMMENT ON COLUMN answer.search_vector IS 'Full-text search document, weighted below questions';
+COMMENT ON COLUMN answer.deleted_at IS 'When the answer was deleted, purged after the retention period';
+COMMENT ON COLUMN answer.deleted_by IS 'User who deleted the answer';
+
+-- question_revision table
+CREATE TABLE IF NOT EXISTS question_revision (
+    revision_uuid UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    question_uuid UUID NOT NULL REFERENCES question (question_uuid) ON DELETE CASCADE,
+    revision INTEGER NOT NULL,
+    title VARCHAR(255) NOT NULL,
+    description VARCHAR(255) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (question_uuid, revision)
+);
+
+COMMENT ON TABLE question_revision IS 'Question revision table';
+
+COMMENT ON COLUMN question_revision.revision_uuid IS 'Generated identifier unique to each question revision';
+COMMENT ON COLUMN question_revision.question_uuid IS 'Question the revision belongs to';
+COMMENT ON COLUMN question_revision.revision IS 'Revision number, starting at 1 for the original question';
+COMMENT ON COLUMN question_revision.title IS 'Title of the question at this revision';
+COMMENT ON COLUMN question_revision.description IS 'Description of the question at this revision';
+COMMENT ON COLUMN question_revision.created_at IS 'Creation timestamp of the revision';
+
+-- answer_revision table
+CREATE TABLE IF NOT EXISTS answer_revision (
+    revision_uuid UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    answer_uuid UUID NOT NULL REFERENCES answer (answer_uuid) ON DELETE CASCADE,
+    revision INTEGER NOT NULL,
+    content VARCHAR(255) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (answer_uuid, revision)
+);
+
+COMMENT ON TABLE answer_revision IS 'Answer revision table';
+
+COMMENT ON COLUMN answer_revision.revision_uuid IS 'Generated identifier unique to each answer revision';
+COMMENT ON COLUMN answer_revision.answer_uuid IS 'Answer the revision belongs to';
+COMMENT ON COLUMN answer_revision.revision IS 'Revision number, starting at 1 for the original answer';
+COMMENT ON COLUMN answer_revision.content IS 'Content of the answer at this revision';
+COMMENT ON COLUMN answer_revision.created_at IS 'Creation timestamp of the revision';
+
+-- question_vote table
+CREATE TABLE IF NOT EXISTS question_vote (
+    question_uuid UUID NOT NULL REFERENCES question (question_uuid) ON DELETE CASCADE,
+    user_uuid UUID NOT NULL REFERENCES users (user_uuid) ON DELETE CASCADE,
+    value SMALLINT NOT NULL CHECK (value IN (-1, 1)),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (question_uuid, user_uuid)
+);
+
+COMMENT ON TABLE question_vote IS 'Question vote table';
+
+COMMENT ON COLUMN question_vote.question_uuid IS 'Question the vote is for';
+COMMENT ON COLUMN question_vote.user_uuid IS 'User who cast the vote';
+COMMENT ON COLUMN question_vote.value IS 'Upvote (1) or downvote (-1)';
+COMMENT ON COLUMN question_vote.created_at IS 'Timestamp of the latest vote';
+
+-- answer_vote table
+CREATE TABLE IF NOT EXISTS answer_vote (
+    answer_uuid UUID NOT NULL REFERENCES answer (answer_uuid) ON DELETE CASCADE,
+    user_uuid UUID NOT NULL REFERENCES users (user_uuid) ON DELETE CASCADE,
+    value SMALLINT NOT NULL CHECK (value IN (-1, 1)),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (answer_uuid, user_uuid)
+);
+
+COMMENT ON TABLE answer_vote IS 'Answer vote table';
+
+COMMENT ON COLUMN answer_vote.answer_uuid IS 'Answer the vote is for';
+COMMENT ON COLUMN answer_vote.user_uuid IS 'User who cast the vote';
+COMMENT ON COLUMN answer_vote.value IS 'Upvote (1) or downvote (-1)';
+COMMENT ON COLUMN answer_vote.created_at IS 'Timestamp of the latest vote';
+
+-- tag table
+CREATE TABLE IF NOT EXISTS tag (
+    tag_uuid UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(35) NOT NULL UNIQUE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    excerpt VARCHAR(255) NOT NULL DEFAULT '',
+    body TEXT NOT NULL DEFAULT ''
+);
+
+COMMENT ON TABLE tag IS 'Tag table';
+
+COMMENT ON COLUMN tag.tag_uuid IS 'Generated identifier unique to each tag';
+COMMENT ON COLUMN tag.name IS 'Unique lowercase name of the tag';
+COMMENT ON COLUMN tag.created_at IS 'Timestamp of the first use of the tag';
+COMMENT ON COLUMN tag.excerpt IS 'Short summary from the tag wiki';
+COMMENT ON COLUMN tag.body IS 'Full text of the tag wiki';
+
+-- question_tag table
+CREATE TABLE IF NOT EXISTS question_tag (
+    question_uuid UUID NOT NULL REFERENCES question (question_uuid) ON DELETE CASCADE,
+    tag_uuid UUID NOT NULL REFERENCES tag (tag_uuid) ON DELETE CASCADE,
+    PRIMARY KEY (question_uuid, tag_uuid)
+);
+
+CREATE INDEX IF NOT EXISTS question_tag_tag_uuid_idx ON question_tag (tag_uuid);
+
+COMMENT ON TABLE question_tag IS 'Tags attached to questions';
+
+COMMENT ON COLUMN question_tag.question_uuid IS 'Question carrying the tag';
+COMMENT ON COLUMN question_tag.tag_uuid IS 'Tag attached to the question';
+
+-- tag_synonym table
+CREATE TABLE IF NOT EXISTS tag_synonym (
+    synonym_uuid UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    source_name VARCHAR(35) NOT NULL UNIQUE,
+    target_tag_uuid UUID NOT NULL REFERENCES tag (tag_uuid) ON DELETE CASCADE,
+    proposed_by UUID REFERENCES users (user_uuid) ON DELETE SET NULL,
+    approved_by UUID REFERENCES users (user_uuid) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    approved_at TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS tag_synonym_target_tag_uuid_idx ON tag_synonym (target_tag_uuid);
+
+COMMENT ON TABLE tag_synonym IS 'Tag synonym table';
+
+COMMENT ON COLUMN tag_synonym.synonym_uuid IS 'Generated identifier unique to each synonym';
+COMMENT ON COLUMN tag_synonym.source_name IS 'Tag name rewritten to the target once approved';
+COMMENT ON COLUMN tag_synonym.target_tag_uuid IS 'Canonical tag the synonym is rewritten to';
+COMMENT ON COLUMN tag_synonym.proposed_by IS 'User who proposed the synonym';
+COMMENT ON COLUMN tag_synonym.approved_by IS 'Moderator who approved the synonym';
+COMMENT ON COLUMN tag_synonym.created_at IS 'Timestamp of the proposal';
+COMMENT ON COLUMN tag_synonym.approved_at IS 'Timestamp of the approval, NULL while pending';
+
+-- tag_wiki_revision table
+CREATE TABLE IF NOT EXISTS tag_wiki_revision (
+    revision_uuid UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tag_uuid UUID NOT NULL REFERENCES tag (tag_uuid) ON DELETE CASCADE,
+    revision INTEGER NOT NULL,
+    excerpt VARCHAR(255) NOT NULL,
+    body TEXT NOT NULL,
+    author_uuid UUID REFERENCES users (user_uuid) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (tag_uuid, revision)
+);
+
+COMMENT ON TABLE tag_wiki_revision IS 'Tag wiki revision table';
+
+COMMENT ON COLUMN tag_wiki_revision.revision_uuid IS 'Generated identifier unique to each wiki revision';
+COMMENT ON COLUMN tag_wiki_revision.tag_uuid IS 'Tag the wiki belongs to';
+COMMENT ON COLUMN tag_wiki_revision.revision IS 'Revision number, starting at 1 for the first edit';
+COMMENT ON COLUMN tag_wiki_revision.excerpt IS 'Excerpt of the wiki at this revision';
+COMMENT ON COLUMN tag_wiki_revision.body IS 'Body of the wiki at this revision';
+COMMENT ON COLUMN tag_wiki_revision.author_uuid IS 'User who made the edit';
+COMMENT ON COLUMN tag_wiki_revision.created_at IS 'Creation timestamp of the revision';
+
+-- comment table
+CREATE TABLE IF NOT EXISTS comment (
+    comment_uuid UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    question_uuid UUID REFERENCES question (question_uuid) ON DELETE CASCADE,
+    answer_uuid UUID REFERENCES answer (answer_uuid) ON DELETE CASCADE,
+    content VARCHAR(600) NOT NULL,
+    author_uuid UUID REFERENCES users (user_uuid) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT comment_parent_check CHECK (num_nonnulls(question_uuid, answer_uuid) = 1)
+);
+
+CREATE INDEX IF NOT EXISTS comment_question_uuid_idx ON comment (question_uuid);
+CREATE INDEX IF NOT EXISTS comment_answer_uuid_idx ON comment (answer_uuid);
+
+COMMENT ON TABLE comment IS 'Comment table';
+
+COMMENT ON COLUMN comment.comment_uuid IS 'Generated identifier unique to each comment';
+COMMENT ON COLUMN comment.question_uuid IS 'Question commented on, NULL for answer comments';
+COMMENT ON COLUMN comment.answer_uuid IS 'Answer commented on, NULL for question comments';
+COMMENT ON COLUMN comment.content IS 'Content of the comment';
+COMMENT ON COLUMN comment.author_uuid IS 'User who wrote the comment';
+COMMENT ON COLUMN comment.created_at IS 'Creation timestamp of the comment';
+
+-- question_close_vote table
+CREATE TABLE IF NOT EXISTS question_close_vote (
+    question_uuid UUID NOT NULL REFERENCES question (question_uuid) ON DELETE CASCADE,
+    user_uuid UUID NOT NULL REFERENCES users (user_uuid) ON DELETE CASCADE,
+    kind VARCHAR(8) NOT NULL CHECK (kind IN ('close', 'reopen')),
+    close_reason VARCHAR(16)
+        CHECK (close_reason IN ('duplicate', 'off_topic', 'unclear', 'too_broad', 'opinion_based')),
+    duplicate_of UUID REFERENCES question (question_uuid) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (question_uuid, user_uuid, kind),
+    CONSTRAINT question_close_vote_reason_check CHECK ((kind = 'close') = (close_reason IS NOT NULL))
+);
+
+COMMENT ON TABLE question_close_vote IS 'Pending close and reopen vote table';
+
+COMMENT ON COLUMN question_close_vote.question_uuid IS 'Question the vote is for';
+COMMENT ON COLUMN question_close_vote.user_uuid IS 'User who cast the vote';
+COMMENT ON COLUMN question_close_vote.kind IS 'Whether the vote is to close or to reopen the question';
+COMMENT ON COLUMN question_close_vote.close_reason IS 'Reason given for closing, NULL for reopen votes';
+COMMENT ON COLUMN question_close_vote.duplicate_of IS 'Question named by a duplicate vote';
+COMMENT ON COLUMN question_close_vote.created_at IS 'Timestamp of the vote';
+
+-- reputation_event table
+CREATE TABLE IF NOT EXISTS reputation_event (
+    event_uuid UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_uuid UUID NOT NULL REFERENCES users (user_uuid) ON DELETE CASCADE,
+    kind VARCHAR(32) NOT NULL CHECK (kind IN (
+        'question_upvoted', 'question_downvoted', 'answer_upvoted', 'answer_downvoted',
+        'answer_accepted', 'accepted_answer', 'bounty_offered', 'bounty_awarded', 'bounty_refunded'
+    )),
+    amount INTEGER NOT NULL,
+    question_uuid UUID REFERENCES question (question_uuid) ON DELETE SET NULL,
+    answer_uuid UUID REFERENCES answer (answer_uuid) ON DELETE SET NULL,
+    source_uuid UUID REFERENCES users (user_uuid) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS reputation_event_user_uuid_idx ON reputation_event (user_uuid, created_at);
+CREATE INDEX IF NOT EXISTS reputation_event_question_uuid_idx ON reputation_event (question_uuid);
+CREATE INDEX IF NOT EXISTS reputation_event_answer_uuid_idx ON reputation_event (answer_uuid);
+
+COMMENT ON TABLE reputation_event IS 'Reputation ledger table';
+
+COMMENT ON COLUMN reputation_event.event_uuid IS 'Generated identifier unique to each event';
+COMMENT ON COLUMN reputation_event.user_uuid IS 'User whose reputation changed';
+COMMENT ON COLUMN reputation_event.kind IS 'Vote, acceptance or bounty that caused the change';
+COMMENT ON COLUMN reputation_event.amount IS 'Reputation gained or lost, after the daily cap';
+COMMENT ON COLUMN reputation_event.question_uuid IS 'Question involved, NULL once it is purged';
+COMMENT ON COLUMN reputation_event.answer_uuid IS 'Answer involved, for answer votes and acceptances';
+COMMENT ON COLUMN reputation_event.source_uuid IS 'User who voted or accepted';
+COMMENT ON COLUMN reputation_event.created_at IS 'Timestamp of the event';
+
+-- bounty table
+CREATE TABLE IF NOT EXISTS bounty (
+    bounty_uuid UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    question_uuid UUID NOT NULL REFERENCES question (question_uuid) ON DELETE CASCADE,
+    offered_by UUID REFERENCES users (user_uuid) ON DELETE SET NULL,
+    amount INTEGER NOT NULL CHECK (amount > 0),
+    state VARCHAR(16) NOT NULL DEFAULT 'active' CHECK (state IN ('active', 'awarded', 'expired')),
+    started_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    expires_at TIMESTAMP NOT NULL,
+    awarded_answer_uuid UUID REFERENCES answer (answer_uuid) ON DELETE SET NULL,
+    awarded_at TIMESTAMP,
+    CONSTRAINT bounty_awarded_check CHECK ((state = 'awarded') = (awarded_at IS NOT NULL))
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS bounty_active_question_uuid_idx ON bounty (question_uuid) WHERE state = 'active';
+CREATE INDEX IF NOT EXISTS bounty_active_expires_at_idx ON bounty (expires_at) WHERE state = 'active';
+
+COMMENT ON TABLE bounty IS 'Reputation offered on questions';
+
+COMMENT ON COLUMN bounty.bounty_uuid IS 'Generated identifier unique to each bounty';
+COMMENT ON COLUMN bounty.question_uuid IS 'Question the bounty is on, at most one active bounty each';
+COMMENT ON COLUMN bounty.offered_by IS 'User whose reputation is held in escrow';
+COMMENT ON COLUMN bounty.amount IS 'Reputation taken from the offerer and paid out on award';
+COMMENT ON COLUMN bounty.state IS 'Lifecycle state: active, awarded or expired';
+COMMENT ON COLUMN bounty.started_at IS 'Timestamp of the offer';
+COMMENT ON COLUMN bounty.expires_at IS 'Time after which the amount is refunded unless awarded';
+COMMENT ON COLUMN bounty.awarded_answer_uuid IS 'Answer the bounty was awarded to';
+COMMENT ON COLUMN bounty.awarded_at IS 'Timestamp of the award';
+
+-- notification table
+CREATE TABLE IF NOT EXISTS notification (
+    notification_uuid UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_uuid UUID NOT NULL REFERENCES users (user_uuid) ON DELETE CASCADE,
+    kind VARCHAR(32) NOT NULL CHECK (kind IN (
+        'answer_posted', 'comment_posted', 'post_edited', 'answer_accepted', 'bounty_awarded'
+    )),
+    actor_uuid UUID REFERENCES users (user_uuid) ON DELETE SET NULL,
+    question_uuid UUID NOT NULL REFERENCES question (question_uuid) ON DELETE CASCADE,
+    answer_uuid UUID REFERENCES answer (answer_uuid) ON DELETE CASCADE,
+    comment_uuid UUID REFERENCES comment (comment_uuid) ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    read_at TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS notification_user_uuid_idx ON notification (user_uuid, created_at);
+CREATE INDEX IF NOT EXISTS notification_unread_idx ON notification (user_uuid) WHERE read_at IS NULL;
+
+COMMENT ON TABLE notification IS 'Inbox entries telling users about activity on posts';
+
+COMMENT ON COLUMN notification.notification_uuid IS 'Generated identifier unique to each notification';
+COMMENT ON COLUMN notification.user_uuid IS 'Recipient of the notification';
+COMMENT ON COLUMN notification.kind IS 'What happened, e.g. answer_posted';
+COMMENT ON COLUMN notification.actor_uuid IS 'User who caused the notification';
+COMMENT ON COLUMN notification.question_uuid IS 'Question the activity happened on';
+COMMENT ON COLUMN notification.answer_uuid IS 'Answer involved, if any';
+COMMENT ON COLUMN notification.comment_uuid IS 'Comment involved, if any';
+COMMENT ON COLUMN notification.created_at IS 'Timestamp of the activity';
+COMMENT ON COLUMN notification.read_at IS 'Timestamp the recipient read the notification, missing while unread';
+
+-- notification_preference table
+CREATE TABLE IF NOT EXISTS notification_preference (
+    user_uuid UUID NOT NULL REFERENCES users (user_uuid) ON DELETE CASCADE,
+    kind VARCHAR(32) NOT NULL CHECK (kind IN (
+        'answer_posted', 'comment_posted', 'post_edited', 'answer_accepted', 'bounty_awarded'
+    )),
+    enabled BOOLEAN NOT NULL,
+    PRIMARY KEY (user_uuid, kind)
+);
+
+COMMENT ON TABLE notification_preference IS 'Notification kinds users turned on or off, enabled when missing';
+
+COMMENT ON COLUMN notification_preference.user_uuid IS 'User the preference belongs to';
+COMMENT ON COLUMN notification_preference.kind IS 'Notification kind';
+COMMENT ON COLUMN notification_preference.enabled IS 'Whether notifications of the kind are created';
+
+-- question_follow table
+CREATE TABLE IF NOT EXISTS question_follow (
+    user_uuid UUID NOT NULL REFERENCES users (user_uuid) ON DELETE CASCADE,
+    question_uuid UUID NOT NULL REFERENCES question (question_uuid) ON DELETE CASCADE,
+    followed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (user_uuid, question_uuid)
+);
+
+CREATE INDEX IF NOT EXISTS question_follow_question_uuid_idx ON question_follow (question_uuid);
+
+COMMENT ON TABLE question_follow IS 'Questions bookmarked by users to follow their activity';
+
+COMMENT ON COLUMN question_follow.user_uuid IS 'User following the question';
+COMMENT ON COLUMN question_follow.question_uuid IS 'Followed question';
+COMMENT ON COLUMN question_follow.followed_at IS 'Timestamp of the follow';
+
+-- user_badge table
+CREATE TABLE IF NOT EXISTS user_badge (
+    user_uuid UUID NOT NULL REFERENCES users (user_uuid) ON DELETE CASCADE,
+    badge VARCHAR(32) NOT NULL CHECK (badge IN (
+        'first_question', 'first_answer', 'scholar', 'nice_question', 'good_question',
+        'great_question', 'nice_answer', 'good_answer', 'great_answer', 'necromancer'
+    )),
+    awarded_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (user_uuid, badge)
+);
+
+CREATE INDEX IF NOT EXISTS user_badge_badge_idx ON user_badge (badge, awarded_at);
+
+COMMENT ON TABLE user_badge IS 'Badges awarded to users';
+
+COMMENT ON COLUMN user_badge.user_uuid IS 'User holding the badge';
+COMMENT ON COLUMN user_badge.badge IS 'Name of the badge, defined by the application';
+COMMENT ON COLUMN user_badge.awarded_at IS 'Timestamp of the award';
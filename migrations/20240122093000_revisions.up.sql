@@ -0,0 +1,27 @@
+-- question_revision table
+CREATE TABLE IF NOT EXISTS question_revision (
+    revision_uuid UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    question_uuid UUID NOT NULL REFERENCES question (question_uuid) ON DELETE CASCADE,
+    revision INTEGER NOT NULL,
+    title VARCHAR(255) NOT NULL,
+    description VARCHAR(255) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (question_uuid, revision)
+);
+
+INSERT INTO question_revision ( question_uuid, revision, title, description, created_at )
+SELECT question_uuid, 1, title, description, created_at FROM question;
+
+
+-- answer_revision table
+CREATE TABLE IF NOT EXISTS answer_revision (
+    revision_uuid UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    answer_uuid UUID NOT NULL REFERENCES answer (answer_uuid) ON DELETE CASCADE,
+    revision INTEGER NOT NULL,
+    content VARCHAR(255) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (answer_uuid, revision)
+);
+
+INSERT INTO answer_revision ( answer_uuid, revision, content, created_at )
+SELECT answer_uuid, 1, content, created_at FROM answer;
@@ -0,0 +1,6 @@
+-- Add down migration script here
+
+ALTER TABLE question DROP COLUMN IF EXISTS author_uuid;
+ALTER TABLE answer DROP COLUMN IF EXISTS author_uuid;
+
+DROP TABLE IF EXISTS users;
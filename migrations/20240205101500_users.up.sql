@@ -0,0 +1,15 @@
+-- users table
+CREATE TABLE IF NOT EXISTS users (
+    user_uuid UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    username VARCHAR(32) NOT NULL UNIQUE,
+    email VARCHAR(255) NOT NULL UNIQUE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+
+-- authors
+ALTER TABLE question ADD COLUMN author_uuid UUID REFERENCES users (user_uuid) ON DELETE SET NULL;
+ALTER TABLE answer ADD COLUMN author_uuid UUID REFERENCES users (user_uuid) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS question_author_uuid_idx ON question (author_uuid);
+CREATE INDEX IF NOT EXISTS answer_author_uuid_idx ON answer (author_uuid);
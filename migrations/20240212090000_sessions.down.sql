@@ -0,0 +1,5 @@
+-- Add down migration script here
+
+DROP TABLE IF EXISTS user_session;
+
+ALTER TABLE users DROP COLUMN IF EXISTS password_hash;
@@ -0,0 +1,14 @@
+-- passwords
+-- Accounts registered before passwords were introduced have no hash and cannot log in.
+ALTER TABLE users ADD COLUMN password_hash TEXT;
+
+
+-- user_session table
+CREATE TABLE IF NOT EXISTS user_session (
+    token_hash VARCHAR(64) PRIMARY KEY,
+    user_uuid UUID NOT NULL REFERENCES users (user_uuid) ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    expires_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS user_session_user_uuid_idx ON user_session (user_uuid);
@@ -0,0 +1,6 @@
+-- Add down migration script here
+
+DROP TABLE IF EXISTS question_vote, answer_vote;
+
+ALTER TABLE question DROP COLUMN IF EXISTS score;
+ALTER TABLE answer DROP COLUMN IF EXISTS score;
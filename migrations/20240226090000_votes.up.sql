@@ -0,0 +1,24 @@
+-- scores
+-- Kept in step with the vote tables by VoteDao, inside the transaction that changes the vote.
+ALTER TABLE question ADD COLUMN score INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE answer ADD COLUMN score INTEGER NOT NULL DEFAULT 0;
+
+
+-- question_vote table
+CREATE TABLE IF NOT EXISTS question_vote (
+    question_uuid UUID NOT NULL REFERENCES question (question_uuid) ON DELETE CASCADE,
+    user_uuid UUID NOT NULL REFERENCES users (user_uuid) ON DELETE CASCADE,
+    value SMALLINT NOT NULL CHECK (value IN (-1, 1)),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (question_uuid, user_uuid)
+);
+
+
+-- answer_vote table
+CREATE TABLE IF NOT EXISTS answer_vote (
+    answer_uuid UUID NOT NULL REFERENCES answer (answer_uuid) ON DELETE CASCADE,
+    user_uuid UUID NOT NULL REFERENCES users (user_uuid) ON DELETE CASCADE,
+    value SMALLINT NOT NULL CHECK (value IN (-1, 1)),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (answer_uuid, user_uuid)
+);
@@ -0,0 +1,6 @@
+-- Add down migration script here
+
+ALTER TABLE question DROP CONSTRAINT IF EXISTS question_accepted_answer_fkey;
+ALTER TABLE question DROP COLUMN IF EXISTS accepted_answer_uuid;
+
+ALTER TABLE answer DROP CONSTRAINT IF EXISTS answer_uuid_question_uuid_key;
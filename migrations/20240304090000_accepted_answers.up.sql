@@ -0,0 +1,9 @@
+-- accepted answers
+-- The composite key ties the accepted answer to its own question. Deleting the
+-- answer only clears accepted_answer_uuid, which needs PostgreSQL 15 or later.
+ALTER TABLE answer ADD CONSTRAINT answer_uuid_question_uuid_key UNIQUE (answer_uuid, question_uuid);
+
+ALTER TABLE question ADD COLUMN accepted_answer_uuid UUID;
+ALTER TABLE question ADD CONSTRAINT question_accepted_answer_fkey
+    FOREIGN KEY (accepted_answer_uuid, question_uuid) REFERENCES answer (answer_uuid, question_uuid)
+    ON DELETE SET NULL (accepted_answer_uuid);
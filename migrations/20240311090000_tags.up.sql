@@ -0,0 +1,16 @@
+-- tag table
+CREATE TABLE IF NOT EXISTS tag (
+    tag_uuid UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(35) NOT NULL UNIQUE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+
+-- question_tag table
+CREATE TABLE IF NOT EXISTS question_tag (
+    question_uuid UUID NOT NULL REFERENCES question (question_uuid) ON DELETE CASCADE,
+    tag_uuid UUID NOT NULL REFERENCES tag (tag_uuid) ON DELETE CASCADE,
+    PRIMARY KEY (question_uuid, tag_uuid)
+);
+
+CREATE INDEX IF NOT EXISTS question_tag_tag_uuid_idx ON question_tag (tag_uuid);
@@ -0,0 +1,6 @@
+-- Add down migration script here
+
+DROP TABLE IF EXISTS tag_wiki_revision, tag_synonym;
+
+ALTER TABLE tag DROP COLUMN IF EXISTS excerpt;
+ALTER TABLE tag DROP COLUMN IF EXISTS body;
@@ -0,0 +1,32 @@
+-- tag_synonym table
+-- A synonym is pending until a moderator approves it. Approval merges the
+-- synonym tag into its target, after which incoming tags are rewritten.
+CREATE TABLE IF NOT EXISTS tag_synonym (
+    synonym_uuid UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    source_name VARCHAR(35) NOT NULL UNIQUE,
+    target_tag_uuid UUID NOT NULL REFERENCES tag (tag_uuid) ON DELETE CASCADE,
+    proposed_by UUID REFERENCES users (user_uuid) ON DELETE SET NULL,
+    approved_by UUID REFERENCES users (user_uuid) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    approved_at TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS tag_synonym_target_tag_uuid_idx ON tag_synonym (target_tag_uuid);
+
+
+-- tag wiki
+ALTER TABLE tag ADD COLUMN excerpt VARCHAR(255) NOT NULL DEFAULT '';
+ALTER TABLE tag ADD COLUMN body TEXT NOT NULL DEFAULT '';
+
+
+-- tag_wiki_revision table
+CREATE TABLE IF NOT EXISTS tag_wiki_revision (
+    revision_uuid UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tag_uuid UUID NOT NULL REFERENCES tag (tag_uuid) ON DELETE CASCADE,
+    revision INTEGER NOT NULL,
+    excerpt VARCHAR(255) NOT NULL,
+    body TEXT NOT NULL,
+    author_uuid UUID REFERENCES users (user_uuid) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (tag_uuid, revision)
+);
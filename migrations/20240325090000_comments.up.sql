@@ -0,0 +1,14 @@
+-- comment table
+-- Each comment belongs to exactly one question or answer and goes away with it.
+CREATE TABLE IF NOT EXISTS comment (
+    comment_uuid UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    question_uuid UUID REFERENCES question (question_uuid) ON DELETE CASCADE,
+    answer_uuid UUID REFERENCES answer (answer_uuid) ON DELETE CASCADE,
+    content VARCHAR(600) NOT NULL,
+    author_uuid UUID REFERENCES users (user_uuid) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT comment_parent_check CHECK (num_nonnulls(question_uuid, answer_uuid) = 1)
+);
+
+CREATE INDEX IF NOT EXISTS comment_question_uuid_idx ON comment (question_uuid);
+CREATE INDEX IF NOT EXISTS comment_answer_uuid_idx ON comment (answer_uuid);
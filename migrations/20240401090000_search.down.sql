@@ -0,0 +1,4 @@
+-- Add down migration script here
+
+ALTER TABLE answer DROP COLUMN IF EXISTS search_vector;
+ALTER TABLE question DROP COLUMN IF EXISTS search_vector;
@@ -0,0 +1,14 @@
+-- full-text search
+-- Matches in a title rank above matches in a description, which rank above
+-- matches in an answer.
+ALTER TABLE question ADD COLUMN search_vector TSVECTOR GENERATED ALWAYS AS (
+    setweight(to_tsvector('english', title), 'A') ||
+    setweight(to_tsvector('english', description), 'B')
+) STORED;
+
+ALTER TABLE answer ADD COLUMN search_vector TSVECTOR GENERATED ALWAYS AS (
+    setweight(to_tsvector('english', content), 'C')
+) STORED;
+
+CREATE INDEX IF NOT EXISTS question_search_vector_idx ON question USING GIN (search_vector);
+CREATE INDEX IF NOT EXISTS answer_search_vector_idx ON answer USING GIN (search_vector);
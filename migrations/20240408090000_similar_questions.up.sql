@@ -0,0 +1,5 @@
+-- similar questions
+-- Trigram similarity on titles finds questions asked in other words.
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX IF NOT EXISTS question_title_trgm_idx ON question USING GIN (title gin_trgm_ops);
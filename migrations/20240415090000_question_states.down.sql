@@ -0,0 +1,8 @@
+-- Add down migration script here
+
+DROP TABLE IF EXISTS question_close_vote;
+
+ALTER TABLE question
+    DROP COLUMN IF EXISTS duplicate_of,
+    DROP COLUMN IF EXISTS close_reason,
+    DROP COLUMN IF EXISTS state;
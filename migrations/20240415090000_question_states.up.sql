@@ -0,0 +1,30 @@
+-- question states
+-- Closed questions take no new answers until reopened; locked questions are
+-- frozen by a moderator. The close reason and duplicate link outlive a lock so
+-- that unlocking restores the previous state.
+ALTER TABLE question
+    ADD COLUMN IF NOT EXISTS state VARCHAR(16) NOT NULL DEFAULT 'open'
+        CHECK (state IN ('open', 'closed', 'locked')),
+    ADD COLUMN IF NOT EXISTS close_reason VARCHAR(16)
+        CHECK (close_reason IN ('duplicate', 'off_topic', 'unclear', 'too_broad', 'opinion_based')),
+    ADD COLUMN IF NOT EXISTS duplicate_of UUID REFERENCES question (question_uuid) ON DELETE SET NULL,
+    ADD CONSTRAINT question_closed_reason_check CHECK (state <> 'closed' OR close_reason IS NOT NULL),
+    ADD CONSTRAINT question_open_reason_check CHECK (state <> 'open' OR close_reason IS NULL),
+    ADD CONSTRAINT question_duplicate_of_check CHECK (
+        duplicate_of IS NULL OR (close_reason = 'duplicate' AND duplicate_of <> question_uuid)
+    );
+
+-- question close vote table
+-- Pending votes to close or reopen a question. They are consumed once the
+-- question changes state, so every round of voting starts from zero.
+CREATE TABLE IF NOT EXISTS question_close_vote (
+    question_uuid UUID NOT NULL REFERENCES question (question_uuid) ON DELETE CASCADE,
+    user_uuid UUID NOT NULL REFERENCES users (user_uuid) ON DELETE CASCADE,
+    kind VARCHAR(8) NOT NULL CHECK (kind IN ('close', 'reopen')),
+    close_reason VARCHAR(16)
+        CHECK (close_reason IN ('duplicate', 'off_topic', 'unclear', 'too_broad', 'opinion_based')),
+    duplicate_of UUID REFERENCES question (question_uuid) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (question_uuid, user_uuid, kind),
+    CONSTRAINT question_close_vote_reason_check CHECK ((kind = 'close') = (close_reason IS NOT NULL))
+);
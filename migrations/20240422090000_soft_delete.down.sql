@@ -0,0 +1,12 @@
+-- Add down migration script here
+
+DROP INDEX IF EXISTS answer_deleted_at_idx;
+DROP INDEX IF EXISTS question_deleted_at_idx;
+
+ALTER TABLE answer
+    DROP COLUMN IF EXISTS deleted_by,
+    DROP COLUMN IF EXISTS deleted_at;
+
+ALTER TABLE question
+    DROP COLUMN IF EXISTS deleted_by,
+    DROP COLUMN IF EXISTS deleted_at;
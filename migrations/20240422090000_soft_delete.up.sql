@@ -0,0 +1,13 @@
+-- soft delete
+-- Deleted posts are hidden but kept until the retention period has passed,
+-- so moderators can undo a deletion.
+ALTER TABLE question
+    ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMP,
+    ADD COLUMN IF NOT EXISTS deleted_by UUID REFERENCES users (user_uuid) ON DELETE SET NULL;
+
+ALTER TABLE answer
+    ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMP,
+    ADD COLUMN IF NOT EXISTS deleted_by UUID REFERENCES users (user_uuid) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS question_deleted_at_idx ON question (deleted_at) WHERE deleted_at IS NOT NULL;
+CREATE INDEX IF NOT EXISTS answer_deleted_at_idx ON answer (deleted_at) WHERE deleted_at IS NOT NULL;
@@ -0,0 +1,6 @@
+-- Add down migration script here
+
+DROP TABLE IF EXISTS reputation_event;
+
+ALTER TABLE users
+    DROP COLUMN IF EXISTS reputation;
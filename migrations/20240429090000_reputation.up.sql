@@ -0,0 +1,60 @@
+-- reputation
+-- Every change to a user's reputation is recorded in the ledger. The total on
+-- the user row is kept in step with it and can be rebuilt from it at any time.
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS reputation INTEGER NOT NULL DEFAULT 1;
+
+-- reputation event table
+-- Events are tied to the vote or acceptance that caused them and are removed
+-- when it is undone. Events outlive purged posts so totals stay stable.
+CREATE TABLE IF NOT EXISTS reputation_event (
+    event_uuid UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_uuid UUID NOT NULL REFERENCES users (user_uuid) ON DELETE CASCADE,
+    kind VARCHAR(32) NOT NULL CHECK (kind IN (
+        'question_upvoted', 'question_downvoted', 'answer_upvoted', 'answer_downvoted',
+        'answer_accepted', 'accepted_answer'
+    )),
+    amount INTEGER NOT NULL,
+    question_uuid UUID REFERENCES question (question_uuid) ON DELETE SET NULL,
+    answer_uuid UUID REFERENCES answer (answer_uuid) ON DELETE SET NULL,
+    source_uuid UUID REFERENCES users (user_uuid) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS reputation_event_user_uuid_idx ON reputation_event (user_uuid, created_at);
+CREATE INDEX IF NOT EXISTS reputation_event_question_uuid_idx ON reputation_event (question_uuid);
+CREATE INDEX IF NOT EXISTS reputation_event_answer_uuid_idx ON reputation_event (answer_uuid);
+
+-- Existing votes and acceptances are replayed into the ledger, without the
+-- daily cap, so that reputation starts out consistent with them.
+INSERT INTO reputation_event ( user_uuid, kind, amount, question_uuid, source_uuid, created_at )
+SELECT q.author_uuid,
+    CASE WHEN v.value > 0 THEN 'question_upvoted' ELSE 'question_downvoted' END,
+    CASE WHEN v.value > 0 THEN 10 ELSE -2 END,
+    q.question_uuid, v.user_uuid, v.created_at
+FROM question_vote v
+JOIN question q ON q.question_uuid = v.question_uuid
+WHERE q.author_uuid <> v.user_uuid;
+
+INSERT INTO reputation_event ( user_uuid, kind, amount, question_uuid, answer_uuid, source_uuid, created_at )
+SELECT a.author_uuid,
+    CASE WHEN v.value > 0 THEN 'answer_upvoted' ELSE 'answer_downvoted' END,
+    CASE WHEN v.value > 0 THEN 10 ELSE -2 END,
+    a.question_uuid, a.answer_uuid, v.user_uuid, v.created_at
+FROM answer_vote v
+JOIN answer a ON a.answer_uuid = v.answer_uuid
+WHERE a.author_uuid <> v.user_uuid;
+
+INSERT INTO reputation_event ( user_uuid, kind, amount, question_uuid, answer_uuid, source_uuid )
+SELECT accepted.user_uuid, accepted.kind, accepted.amount, q.question_uuid, a.answer_uuid, q.author_uuid
+FROM question q
+JOIN answer a ON a.answer_uuid = q.accepted_answer_uuid
+CROSS JOIN LATERAL (
+    VALUES (a.author_uuid, 'answer_accepted', 15), (q.author_uuid, 'accepted_answer', 2)
+) AS accepted ( user_uuid, kind, amount )
+WHERE a.author_uuid <> q.author_uuid;
+
+UPDATE users
+SET reputation = 1 + COALESCE(
+    (SELECT SUM(e.amount) FROM reputation_event e WHERE e.user_uuid = users.user_uuid), 0
+);
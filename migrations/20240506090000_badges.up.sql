@@ -0,0 +1,14 @@
+-- user badge table
+-- Badge definitions live in the application; a row records that the user has
+-- earned the badge. Each badge is awarded once, so re-evaluating is harmless.
+CREATE TABLE IF NOT EXISTS user_badge (
+    user_uuid UUID NOT NULL REFERENCES users (user_uuid) ON DELETE CASCADE,
+    badge VARCHAR(32) NOT NULL CHECK (badge IN (
+        'first_question', 'first_answer', 'scholar', 'nice_question', 'good_question',
+        'great_question', 'nice_answer', 'good_answer', 'great_answer', 'necromancer'
+    )),
+    awarded_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (user_uuid, badge)
+);
+
+CREATE INDEX IF NOT EXISTS user_badge_badge_idx ON user_badge (badge, awarded_at);
@@ -0,0 +1,19 @@
+-- Add down migration script here
+
+DELETE FROM reputation_event WHERE kind IN ('bounty_offered', 'bounty_awarded', 'bounty_refunded');
+
+-- Totals are rebuilt from the remaining ledger so escrowed and awarded
+-- amounts no longer count.
+UPDATE users
+SET reputation = 1 + COALESCE(
+    (SELECT SUM(e.amount) FROM reputation_event e WHERE e.user_uuid = users.user_uuid), 0
+);
+
+ALTER TABLE reputation_event
+    DROP CONSTRAINT IF EXISTS reputation_event_kind_check,
+    ADD CONSTRAINT reputation_event_kind_check CHECK (kind IN (
+        'question_upvoted', 'question_downvoted', 'answer_upvoted', 'answer_downvoted',
+        'answer_accepted', 'accepted_answer'
+    ));
+
+DROP TABLE IF EXISTS bounty;
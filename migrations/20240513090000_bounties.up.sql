@@ -0,0 +1,26 @@
+-- bounty table
+-- The amount is taken from the offerer when the bounty starts and handed to
+-- the awarded answer's author, or back to the offerer once it expires.
+CREATE TABLE IF NOT EXISTS bounty (
+    bounty_uuid UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    question_uuid UUID NOT NULL REFERENCES question (question_uuid) ON DELETE CASCADE,
+    offered_by UUID REFERENCES users (user_uuid) ON DELETE SET NULL,
+    amount INTEGER NOT NULL CHECK (amount > 0),
+    state VARCHAR(16) NOT NULL DEFAULT 'active' CHECK (state IN ('active', 'awarded', 'expired')),
+    started_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    expires_at TIMESTAMP NOT NULL,
+    awarded_answer_uuid UUID REFERENCES answer (answer_uuid) ON DELETE SET NULL,
+    awarded_at TIMESTAMP,
+    CONSTRAINT bounty_awarded_check CHECK ((state = 'awarded') = (awarded_at IS NOT NULL))
+);
+
+-- a question has at most one active bounty
+CREATE UNIQUE INDEX IF NOT EXISTS bounty_active_question_uuid_idx ON bounty (question_uuid) WHERE state = 'active';
+CREATE INDEX IF NOT EXISTS bounty_active_expires_at_idx ON bounty (expires_at) WHERE state = 'active';
+
+ALTER TABLE reputation_event
+    DROP CONSTRAINT IF EXISTS reputation_event_kind_check,
+    ADD CONSTRAINT reputation_event_kind_check CHECK (kind IN (
+        'question_upvoted', 'question_downvoted', 'answer_upvoted', 'answer_downvoted',
+        'answer_accepted', 'accepted_answer', 'bounty_offered', 'bounty_awarded', 'bounty_refunded'
+    ));
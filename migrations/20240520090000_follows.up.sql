@@ -0,0 +1,11 @@
+-- question follow table
+-- Following a question bookmarks it for the user and subscribes them to its
+-- activity. Rows go away with either the user or the question.
+CREATE TABLE IF NOT EXISTS question_follow (
+    user_uuid UUID NOT NULL REFERENCES users (user_uuid) ON DELETE CASCADE,
+    question_uuid UUID NOT NULL REFERENCES question (question_uuid) ON DELETE CASCADE,
+    followed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (user_uuid, question_uuid)
+);
+
+CREATE INDEX IF NOT EXISTS question_follow_question_uuid_idx ON question_follow (question_uuid);
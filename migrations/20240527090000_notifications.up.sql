@@ -0,0 +1,29 @@
+-- notification table
+-- One row per recipient. Notifications go away with the post they are about.
+CREATE TABLE IF NOT EXISTS notification (
+    notification_uuid UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_uuid UUID NOT NULL REFERENCES users (user_uuid) ON DELETE CASCADE,
+    kind VARCHAR(32) NOT NULL CHECK (kind IN (
+        'answer_posted', 'comment_posted', 'post_edited', 'answer_accepted', 'bounty_awarded'
+    )),
+    actor_uuid UUID REFERENCES users (user_uuid) ON DELETE SET NULL,
+    question_uuid UUID NOT NULL REFERENCES question (question_uuid) ON DELETE CASCADE,
+    answer_uuid UUID REFERENCES answer (answer_uuid) ON DELETE CASCADE,
+    comment_uuid UUID REFERENCES comment (comment_uuid) ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    read_at TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS notification_user_uuid_idx ON notification (user_uuid, created_at);
+CREATE INDEX IF NOT EXISTS notification_unread_idx ON notification (user_uuid) WHERE read_at IS NULL;
+
+-- notification preference table
+-- Only opt-outs need a row, a missing row means the kind is enabled.
+CREATE TABLE IF NOT EXISTS notification_preference (
+    user_uuid UUID NOT NULL REFERENCES users (user_uuid) ON DELETE CASCADE,
+    kind VARCHAR(32) NOT NULL CHECK (kind IN (
+        'answer_posted', 'comment_posted', 'post_edited', 'answer_accepted', 'bounty_awarded'
+    )),
+    enabled BOOLEAN NOT NULL,
+    PRIMARY KEY (user_uuid, kind)
+);
@@ -0,0 +1,6 @@
+-- Add down migration script here
+
+DROP INDEX IF EXISTS question_answer_count_idx;
+DROP INDEX IF EXISTS question_created_at_idx;
+
+ALTER TABLE question DROP COLUMN IF EXISTS answer_count;
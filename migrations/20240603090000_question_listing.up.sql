@@ -0,0 +1,12 @@
+-- question listing
+-- The number of answers is kept on the question, not counting deleted ones,
+-- so that every sort order of the listing is served by an index.
+ALTER TABLE question ADD COLUMN IF NOT EXISTS answer_count BIGINT NOT NULL DEFAULT 0;
+
+UPDATE question q
+SET answer_count = (
+    SELECT COUNT(*) FROM answer a WHERE a.question_uuid = q.question_uuid AND a.deleted_at IS NULL
+);
+
+CREATE INDEX IF NOT EXISTS question_created_at_idx ON question (created_at, question_uuid);
+CREATE INDEX IF NOT EXISTS question_answer_count_idx ON question (answer_count, created_at, question_uuid);
@@ -0,0 +1,68 @@
+use argon2::{
+    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use rand_core::{OsRng, RngCore};
+
+const TOKEN_BYTES: usize = 32;
+
+/// Hash of a throwaway password with the default parameters, verified against
+/// when there is no account to check so that failed logins take as long
+/// whether or not the username exists.
+pub const DUMMY_PASSWORD_HASH: &str =
+    "$argon2id$v=19$m=19456,t=2,p=1$T7jUABGbmreNfmWMrLuzJw$JK2YwRevFUDnZbIuGzcLP7WAMhWAder1CrVMQnBNxfw";
+
+/// Hashes a password into a PHC string with a random salt.
+pub fn hash_password(password: &str) -> Result<String, argon2::password_hash::Error> {
+    let salt = SaltString::generate(&mut OsRng);
+
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+}
+
+/// Checks a password against a stored PHC string. A malformed hash never
+/// matches.
+pub fn verify_password(password: &str, password_hash: &str) -> bool {
+    PasswordHash::new(password_hash)
+        .and_then(|hash| Argon2::default().verify_password(password.as_bytes(), &hash))
+        .is_ok()
+}
+
+/// Generates an opaque bearer token.
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    OsRng.fill_bytes(&mut bytes);
+
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn password_should_verify_against_its_hash() {
+        let hash = hash_password("correct horse").unwrap();
+
+        assert!(verify_password("correct horse", &hash));
+        assert!(!verify_password("wrong horse", &hash));
+    }
+
+    #[test]
+    fn verify_password_should_reject_malformed_hash() {
+        assert!(!verify_password("correct horse", "not a hash"));
+    }
+
+    #[test]
+    fn dummy_password_hash_should_be_well_formed() {
+        // A malformed hash would be rejected before hashing anything.
+        assert!(PasswordHash::new(DUMMY_PASSWORD_HASH).is_ok());
+    }
+
+    #[test]
+    fn generated_tokens_should_be_unique() {
+        assert_ne!(generate_token(), generate_token());
+    }
+}
@@ -0,0 +1,46 @@
+use rocket::tokio::sync::broadcast::{self, Receiver, Sender};
+
+use crate::models::QuestionEvent;
+
+/// Events a subscriber may fall behind by before it starts missing some.
+const QUESTION_EVENTS_CAPACITY: usize = 256;
+
+/// An event along with the question it happened on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuestionActivity {
+    pub question_uuid: String,
+    pub event: QuestionEvent,
+}
+
+/// In-process channel the handlers publish question activity to. Every
+/// subscriber receives the activity of all questions and keeps the ones it is
+/// interested in.
+pub struct QuestionEvents {
+    sender: Sender<QuestionActivity>,
+}
+
+impl QuestionEvents {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(QUESTION_EVENTS_CAPACITY);
+
+        Self { sender }
+    }
+
+    pub fn publish(&self, question_uuid: String, event: QuestionEvent) {
+        // Sending only fails when nobody is listening, which is fine.
+        let _ = self.sender.send(QuestionActivity {
+            question_uuid,
+            event,
+        });
+    }
+
+    pub fn subscribe(&self) -> Receiver<QuestionActivity> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for QuestionEvents {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,127 @@
+use rocket::{
+    http::Status,
+    request::{FromParam, FromRequest, Outcome, Request},
+    State,
+};
+use sqlx::types::Uuid;
+
+use crate::{
+    models::{Actor, DBError},
+    persistance::session_dao::SessionDao,
+};
+
+use super::{handlers_inner::HandlerError, APIError};
+
+/// A path segment that has been validated as a UUID.
+///
+/// Routes take this as `Result<UuidParam, String>` so a malformed id can be
+/// reported as a 400 instead of being forwarded to a 404.
+#[derive(Debug, PartialEq)]
+pub struct UuidParam(pub Uuid);
+
+impl<'a> FromParam<'a> for UuidParam {
+    type Error = String;
+
+    fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+        Uuid::parse_str(param)
+            .map(UuidParam)
+            .map_err(|_| format!("Could not parse UUID: {param}"))
+    }
+}
+
+/// The user owning the session of an `Authorization: Bearer <token>` header.
+///
+/// Routes take this as `Result<AuthenticatedUser, APIError>` so a missing or
+/// expired session is reported as a 401 instead of going to a catcher.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthenticatedUser {
+    pub actor: Actor,
+    pub token: String,
+}
+
+fn bearer_token(header: Option<&str>) -> Option<&str> {
+    header
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for AuthenticatedUser {
+    type Error = APIError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let Some(token) = bearer_token(request.headers().get_one("Authorization")) else {
+            return Outcome::Error((
+                Status::Unauthorized,
+                APIError::Unauthorized("Missing bearer token".to_string()),
+            ));
+        };
+
+        let session_dao = match request
+            .guard::<&State<Box<dyn SessionDao + Send + Sync>>>()
+            .await
+        {
+            Outcome::Success(session_dao) => session_dao,
+            _ => {
+                error!("Session DAO is not managed");
+
+                return Outcome::Error((
+                    Status::InternalServerError,
+                    HandlerError::default_internal_error().into(),
+                ));
+            }
+        };
+
+        match session_dao.get_session_user(token.to_string()).await {
+            Ok(actor) => Outcome::Success(AuthenticatedUser {
+                actor,
+                token: token.to_string(),
+            }),
+            Err(DBError::NotFound(_)) => Outcome::Error((
+                Status::Unauthorized,
+                APIError::Unauthorized("Session is invalid or has expired".to_string()),
+            )),
+            Err(e) => Outcome::Error((
+                Status::InternalServerError,
+                HandlerError::from_db_error(e).into(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uuid_param_should_parse_valid_uuid() {
+        let result = UuidParam::from_param("b068cd2f-edac-479e-98f1-c5f91008dcbd");
+
+        assert_eq!(
+            result,
+            Ok(UuidParam(
+                Uuid::parse_str("b068cd2f-edac-479e-98f1-c5f91008dcbd").unwrap()
+            ))
+        );
+    }
+
+    #[test]
+    fn uuid_param_should_reject_malformed_uuid() {
+        let result = UuidParam::from_param("malformed");
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn bearer_token_should_be_extracted_from_header() {
+        assert_eq!(bearer_token(Some("Bearer abc123")), Some("abc123"));
+    }
+
+    #[test]
+    fn bearer_token_should_reject_other_schemes() {
+        assert_eq!(bearer_token(None), None);
+        assert_eq!(bearer_token(Some("Basic abc123")), None);
+        assert_eq!(bearer_token(Some("Bearer ")), None);
+    }
+}
@@ -1,11 +1,44 @@
+#![allow(clippy::borrowed_box)]
+
 use crate::{
-    models::{Answer, AnswerDetail, AnswerId, DBError, Question, QuestionDetail, QuestionId},
-    persistance::{answer_dao::AnswerDao, question_dao::QuestionDao},
+    models::{
+        Actor, Answer, AnswerDetail, AnswerId, AnswerRevision, AnswerUpdate, Badge, BadgeRecipient,
+        Bounty, BountyAward, BountyOffer, CloseReason, CloseVote, Comment, CommentDetail,
+        CommentId, CommentParent, Credentials, DBError, FeaturedQuestion, FieldChange,
+        FollowedQuestion, NotificationEvent, NotificationInbox, NotificationKind,
+        NotificationPreference, Page, PageRequest, Privilege, PrivilegeTable, PrivilegeThreshold,
+        Question, QuestionDetail, QuestionEvent, QuestionId, QuestionQuery, QuestionRevision,
+        QuestionStatus, QuestionUpdate, ReputationEvent, ReputationRecalculation, RevisionDiff,
+        RoleUpdate, SearchResult, Session, SimilarQuestion, SynonymProposal, TagDetail, TagSummary,
+        TagSynonym, TagWikiRevision, TagWikiUpdate, UnreadCount, User, UserBadge, UserDetail,
+        UserId, Vote, VoteSummary, DUPLICATE_SIMILARITY, MAX_BOUNTY, MAX_COMMENT_LENGTH,
+        MAX_SEARCH_QUERY_LENGTH, MAX_TAGS_PER_QUESTION, MAX_TAG_EXCERPT_LENGTH, MAX_TAG_LENGTH,
+        MIN_BOUNTY,
+    },
+    persistance::{
+        answer_dao::AnswerDao, badge_dao::BadgeDao, bounty_dao::BountyDao, comment_dao::CommentDao,
+        follow_dao::FollowDao, notification_dao::NotificationDao, question_dao::QuestionDao,
+        reputation_dao::ReputationDao, search_dao::SearchDao, session_dao::SessionDao,
+        tag_dao::TagDao, user_dao::UserDao, vote_dao::VoteDao,
+    },
+};
+
+use super::{
+    auth,
+    events::QuestionEvents,
+    policy::{self, PostAction},
+    search_query,
 };
 
 #[derive(Debug, PartialEq)]
 pub enum HandlerError {
     BadRequest(String),
+    Unauthorized(String),
+    Forbidden(String),
+    NotFound(String),
+    Conflict(String),
+    /// The question being asked resembles these existing ones.
+    PossibleDuplicate(Vec<SimilarQuestion>),
     InternalError(String),
 }
 
@@ -13,47 +46,489 @@ impl HandlerError {
     pub fn default_internal_error() -> Self {
         HandlerError::InternalError("Something went wrong! Please try again.".to_string())
     }
+
+    pub fn from_db_error(e: DBError) -> Self {
+        error!("{e:?}");
+
+        match e {
+            DBError::InvalidUUID(s) => HandlerError::BadRequest(s),
+            DBError::InvalidCursor(s) => HandlerError::BadRequest(s),
+            DBError::NotFound(s) => HandlerError::NotFound(s),
+            DBError::Conflict(s) => HandlerError::Conflict(s),
+            _ => HandlerError::default_internal_error(),
+        }
+    }
+}
+
+fn validate_page(page: &PageRequest) -> Result<(), HandlerError> {
+    match page.limit {
+        Some(limit) if limit < 1 => Err(HandlerError::BadRequest(format!(
+            "Limit must be a positive number: {limit}"
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Lists the fields whose value differs between two revisions, given as
+/// `(field, from, to)` triples.
+fn field_changes(fields: &[(&str, &str, &str)]) -> Vec<FieldChange> {
+    fields
+        .iter()
+        .filter(|(_, from, to)| from != to)
+        .map(|(field, from, to)| FieldChange {
+            field: field.to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+        })
+        .collect()
+}
+
+/// Looks up the author of a question and checks that the actor may act on it.
+async fn authorize_question(
+    question_uuid: &str,
+    action: PostAction,
+    actor: &Actor,
+    question_dao: &Box<dyn QuestionDao + Send + Sync>,
+) -> Result<(), HandlerError> {
+    let question = question_dao
+        .get_question(question_uuid.to_string())
+        .await
+        .map_err(HandlerError::from_db_error)?;
+
+    policy::authorize_post(actor, action, "question", question.author_uuid.as_deref())
+}
+
+/// Looks up the author of a question and checks that the actor may edit it.
+async fn authorize_question_edit(
+    question_uuid: &str,
+    actor: &Actor,
+    privileges: &PrivilegeTable,
+    question_dao: &Box<dyn QuestionDao + Send + Sync>,
+) -> Result<(), HandlerError> {
+    let question = question_dao
+        .get_question(question_uuid.to_string())
+        .await
+        .map_err(HandlerError::from_db_error)?;
+
+    policy::authorize_edit(
+        actor,
+        "question",
+        question.author_uuid.as_deref(),
+        privileges,
+    )
+}
+
+/// Looks up the author of an answer and checks that the actor may act on it.
+async fn authorize_answer(
+    answer_uuid: &str,
+    action: PostAction,
+    actor: &Actor,
+    answer_dao: &Box<dyn AnswerDao + Send + Sync>,
+) -> Result<AnswerDetail, HandlerError> {
+    let answer = answer_dao
+        .get_answer(answer_uuid.to_string())
+        .await
+        .map_err(HandlerError::from_db_error)?;
+
+    policy::authorize_post(actor, action, "answer", answer.author_uuid.as_deref())?;
+
+    Ok(answer)
+}
+
+/// Looks up the author of an answer and checks that the actor may edit it.
+async fn authorize_answer_edit(
+    answer_uuid: &str,
+    actor: &Actor,
+    privileges: &PrivilegeTable,
+    answer_dao: &Box<dyn AnswerDao + Send + Sync>,
+) -> Result<(), HandlerError> {
+    let answer = answer_dao
+        .get_answer(answer_uuid.to_string())
+        .await
+        .map_err(HandlerError::from_db_error)?;
+
+    policy::authorize_edit(actor, "answer", answer.author_uuid.as_deref(), privileges)
+}
+
+/// Users may always comment on their own posts and on the answers to their
+/// questions; commenting anywhere else takes a privilege.
+async fn authorize_comment(
+    parent: &CommentParent,
+    actor: &Actor,
+    privileges: &PrivilegeTable,
+    question_dao: &Box<dyn QuestionDao + Send + Sync>,
+    answer_dao: &Box<dyn AnswerDao + Send + Sync>,
+) -> Result<(), HandlerError> {
+    let privileged = policy::authorize_privilege(actor, Privilege::CommentEverywhere, privileges);
+
+    if privileged.is_ok() {
+        return privileged;
+    }
+
+    let question_uuid = match parent {
+        CommentParent::Question(question_uuid) => question_uuid.clone(),
+        CommentParent::Answer(answer_uuid) => {
+            let answer = answer_dao
+                .get_answer(answer_uuid.clone())
+                .await
+                .map_err(HandlerError::from_db_error)?;
+
+            if answer.author_uuid.as_deref() == Some(actor.user_uuid.as_str()) {
+                return Ok(());
+            }
+
+            answer.question_uuid
+        }
+    };
+
+    let question = question_dao
+        .get_question(question_uuid)
+        .await
+        .map_err(HandlerError::from_db_error)?;
+
+    if question.author_uuid.as_deref() == Some(actor.user_uuid.as_str()) {
+        Ok(())
+    } else {
+        privileged
+    }
+}
+
+/// Deleted posts are only listed for moderators.
+fn authorize_include_deleted(
+    include_deleted: bool,
+    actor: Option<&Actor>,
+) -> Result<(), HandlerError> {
+    match (include_deleted, actor) {
+        (false, _) => Ok(()),
+        (true, Some(actor)) => policy::authorize_moderator(actor, "list deleted posts"),
+        (true, None) => Err(HandlerError::Unauthorized(
+            "Log in as a moderator to list deleted posts".to_string(),
+        )),
+    }
+}
+
+/// Tells the users concerned about an event. The write it follows has already
+/// gone through, so a failure is only logged.
+async fn notify(
+    notification_dao: &Box<dyn NotificationDao + Send + Sync>,
+    event: NotificationEvent,
+) {
+    if let Err(e) = notification_dao.notify(event).await {
+        error!("{e:?}");
+    }
+}
+
+/// Lowercases a tag and checks its format.
+pub(super) fn normalize_tag(tag: &str) -> Result<String, HandlerError> {
+    let tag = tag.trim().to_lowercase();
+
+    let tag_is_valid = (1..=MAX_TAG_LENGTH).contains(&tag.len())
+        && tag.starts_with(|c: char| c.is_ascii_alphanumeric())
+        && tag
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '+' | '#'));
+
+    if tag_is_valid {
+        Ok(tag)
+    } else {
+        Err(HandlerError::BadRequest(format!(
+            "Tag must be 1 to {MAX_TAG_LENGTH} letters, digits, '-', '.', '+' or '#', starting with a letter or digit: {tag}"
+        )))
+    }
+}
+
+/// Normalizes and deduplicates tags, then checks their number.
+fn normalize_tags(tags: &[String]) -> Result<Vec<String>, HandlerError> {
+    let mut normalized: Vec<String> = Vec::new();
+
+    for tag in tags {
+        let tag = normalize_tag(tag)?;
+
+        if !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+
+    if normalized.len() > MAX_TAGS_PER_QUESTION {
+        return Err(HandlerError::BadRequest(format!(
+            "A question can have at most {MAX_TAGS_PER_QUESTION} tags"
+        )));
+    }
+
+    Ok(normalized)
 }
 
 pub async fn create_question(
-    question: Question,
+    mut question: Question,
+    check_duplicates: bool,
     question_dao: &Box<dyn QuestionDao + Sync + Send>,
 ) -> Result<QuestionDetail, HandlerError> {
+    question.tags = normalize_tags(&question.tags)?;
+
+    if check_duplicates {
+        let duplicates: Vec<SimilarQuestion> = question_dao
+            .get_similar_questions(question.title.clone(), Some(question.description.clone()))
+            .await
+            .map_err(HandlerError::from_db_error)?
+            .into_iter()
+            .filter(|q| q.similarity >= DUPLICATE_SIMILARITY)
+            .collect();
+
+        if !duplicates.is_empty() {
+            return Err(HandlerError::PossibleDuplicate(duplicates));
+        }
+    }
+
     let question = question_dao.create_question(question).await;
 
     match question {
         Ok(question) => Ok(question),
         Err(e) => {
             error!("{e:?}");
-            Err(HandlerError::default_internal_error())
+
+            match e {
+                DBError::InvalidUUID(s) => Err(HandlerError::BadRequest(s)),
+                _ => Err(HandlerError::default_internal_error()),
+            }
         }
     }
 }
 
+pub async fn get_similar_questions(
+    title: String,
+    description: Option<String>,
+    question_dao: &Box<dyn QuestionDao + Sync + Send>,
+) -> Result<Vec<SimilarQuestion>, HandlerError> {
+    if title.trim().is_empty() {
+        return Err(HandlerError::BadRequest(
+            "Title must not be empty".to_string(),
+        ));
+    }
+
+    question_dao
+        .get_similar_questions(title, description.filter(|d| !d.trim().is_empty()))
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+pub async fn get_question(
+    question_id: QuestionId,
+    question_dao: &Box<dyn QuestionDao + Sync + Send>,
+) -> Result<QuestionDetail, HandlerError> {
+    question_dao
+        .get_question(question_id.question_uuid)
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
 pub async fn get_questions(
+    query: QuestionQuery,
+    page: PageRequest,
+    actor: Option<&Actor>,
     question_dao: &Box<dyn QuestionDao + Sync + Send>,
-) -> Result<Vec<QuestionDetail>, HandlerError> {
-    let questions = question_dao.get_questions().await;
+) -> Result<Page<QuestionDetail>, HandlerError> {
+    validate_page(&page)?;
+    authorize_include_deleted(query.include_deleted, actor)?;
+
+    let follower = match (query.followed, actor) {
+        (false, _) => None,
+        (true, Some(actor)) => Some(actor.user_uuid.clone()),
+        (true, None) => {
+            return Err(HandlerError::Unauthorized(
+                "Log in to list followed questions".to_string(),
+            ))
+        }
+    };
+
+    if let (Some(after), Some(before)) = (query.created_after, query.created_before) {
+        if after >= before {
+            return Err(HandlerError::BadRequest(format!(
+                "created_after ({after}) must be earlier than created_before ({before})"
+            )));
+        }
+    }
+
+    let questions = question_dao.get_questions(query, page, follower).await;
 
     match questions {
         Ok(questions) => Ok(questions),
         Err(e) => {
             error!("{e:?}");
-            Err(HandlerError::default_internal_error())
+
+            match e {
+                DBError::InvalidCursor(s) => Err(HandlerError::BadRequest(s)),
+                _ => Err(HandlerError::default_internal_error()),
+            }
         }
     }
 }
 
+pub async fn update_question(
+    question_id: QuestionId,
+    update: QuestionUpdate,
+    actor: &Actor,
+    privileges: &PrivilegeTable,
+    question_dao: &Box<dyn QuestionDao + Send + Sync>,
+    notification_dao: &Box<dyn NotificationDao + Send + Sync>,
+    events: &QuestionEvents,
+) -> Result<QuestionDetail, HandlerError> {
+    if update.title.is_none() && update.description.is_none() {
+        return Err(HandlerError::BadRequest(
+            "At least one of title or description must be provided".to_string(),
+        ));
+    }
+
+    authorize_question_edit(&question_id.question_uuid, actor, privileges, question_dao).await?;
+
+    let question = question_dao
+        .update_question(question_id.question_uuid, update)
+        .await
+        .map_err(HandlerError::from_db_error)?;
+
+    notify(
+        notification_dao,
+        NotificationEvent {
+            kind: NotificationKind::PostEdited,
+            actor_uuid: Some(actor.user_uuid.clone()),
+            question_uuid: Some(question.question_uuid.clone()),
+            answer_uuid: None,
+            comment_uuid: None,
+        },
+    )
+    .await;
+
+    events.publish(
+        question.question_uuid.clone(),
+        QuestionEvent::QuestionEdited {
+            question: question.clone(),
+        },
+    );
+
+    Ok(question)
+}
+
+pub async fn get_question_revisions(
+    question_id: QuestionId,
+    include_deleted: bool,
+    actor: Option<&Actor>,
+    question_dao: &Box<dyn QuestionDao + Send + Sync>,
+) -> Result<Vec<QuestionRevision>, HandlerError> {
+    authorize_include_deleted(include_deleted, actor)?;
+
+    question_dao
+        .get_question_revisions(question_id.question_uuid, include_deleted)
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+pub async fn diff_question_revisions(
+    question_id: QuestionId,
+    from_revision: i32,
+    to_revision: i32,
+    include_deleted: bool,
+    actor: Option<&Actor>,
+    question_dao: &Box<dyn QuestionDao + Send + Sync>,
+) -> Result<RevisionDiff, HandlerError> {
+    authorize_include_deleted(include_deleted, actor)?;
+
+    let from = question_dao
+        .get_question_revision(
+            question_id.question_uuid.clone(),
+            from_revision,
+            include_deleted,
+        )
+        .await
+        .map_err(HandlerError::from_db_error)?;
+    let to = question_dao
+        .get_question_revision(question_id.question_uuid, to_revision, include_deleted)
+        .await
+        .map_err(HandlerError::from_db_error)?;
+
+    Ok(RevisionDiff {
+        from_revision,
+        to_revision,
+        changes: field_changes(&[
+            ("title", &from.title, &to.title),
+            ("description", &from.description, &to.description),
+        ]),
+    })
+}
+
+/// Restores a question to an earlier revision. The rollback is itself
+/// recorded as a new revision, so history is never rewritten.
+pub async fn rollback_question(
+    question_id: QuestionId,
+    revision: i32,
+    actor: &Actor,
+    privileges: &PrivilegeTable,
+    question_dao: &Box<dyn QuestionDao + Send + Sync>,
+    notification_dao: &Box<dyn NotificationDao + Send + Sync>,
+    events: &QuestionEvents,
+) -> Result<QuestionDetail, HandlerError> {
+    authorize_question_edit(&question_id.question_uuid, actor, privileges, question_dao).await?;
+
+    let target = question_dao
+        .get_question_revision(question_id.question_uuid.clone(), revision, false)
+        .await
+        .map_err(HandlerError::from_db_error)?;
+
+    let question = question_dao
+        .update_question(
+            question_id.question_uuid,
+            QuestionUpdate {
+                title: Some(target.title),
+                description: Some(target.description),
+            },
+        )
+        .await
+        .map_err(HandlerError::from_db_error)?;
+
+    notify(
+        notification_dao,
+        NotificationEvent {
+            kind: NotificationKind::PostEdited,
+            actor_uuid: Some(actor.user_uuid.clone()),
+            question_uuid: Some(question.question_uuid.clone()),
+            answer_uuid: None,
+            comment_uuid: None,
+        },
+    )
+    .await;
+
+    events.publish(
+        question.question_uuid.clone(),
+        QuestionEvent::QuestionEdited {
+            question: question.clone(),
+        },
+    );
+
+    Ok(question)
+}
+
 pub async fn delete_question(
     question_id: QuestionId,
+    actor: &Actor,
     question_dao: &Box<dyn QuestionDao + Send + Sync>,
+    events: &QuestionEvents,
 ) -> Result<(), HandlerError> {
+    authorize_question(
+        &question_id.question_uuid,
+        PostAction::Delete,
+        actor,
+        question_dao,
+    )
+    .await?;
+
     let result = question_dao
-        .delete_question(question_id.question_uuid)
+        .delete_question(question_id.question_uuid.clone(), actor.user_uuid.clone())
         .await;
 
     match result {
-        Ok(()) => Ok(()),
+        Ok(()) => {
+            events.publish(question_id.question_uuid, QuestionEvent::QuestionDeleted);
+
+            Ok(())
+        }
         Err(e) => {
             error!("{e:?}");
             Err(HandlerError::default_internal_error())
@@ -61,297 +536,5298 @@ pub async fn delete_question(
     }
 }
 
+pub async fn undelete_question(
+    question_id: QuestionId,
+    actor: &Actor,
+    question_dao: &Box<dyn QuestionDao + Send + Sync>,
+) -> Result<QuestionDetail, HandlerError> {
+    policy::authorize_moderator(actor, "undelete posts")?;
+
+    question_dao
+        .undelete_question(question_id.question_uuid)
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+fn validate_close_vote(question_uuid: &str, vote: &CloseVote) -> Result<(), HandlerError> {
+    match (vote.reason, vote.duplicate_of.as_deref()) {
+        (CloseReason::Duplicate, None) => Err(HandlerError::BadRequest(
+            "A duplicate must name the question it duplicates".to_string(),
+        )),
+        (CloseReason::Duplicate, Some(duplicate_of)) if duplicate_of == question_uuid => Err(
+            HandlerError::BadRequest("A question cannot duplicate itself".to_string()),
+        ),
+        (CloseReason::Duplicate, Some(_)) | (_, None) => Ok(()),
+        (reason, Some(_)) => Err(HandlerError::BadRequest(format!(
+            "Only duplicates name another question, not: {}",
+            reason.as_str()
+        ))),
+    }
+}
+
+/// Moderators' votes are binding, everyone else's count towards
+/// `CLOSE_VOTES_NEEDED`.
+pub async fn vote_to_close(
+    question_id: QuestionId,
+    vote: CloseVote,
+    actor: &Actor,
+    privileges: &PrivilegeTable,
+    question_dao: &Box<dyn QuestionDao + Send + Sync>,
+) -> Result<QuestionStatus, HandlerError> {
+    validate_close_vote(&question_id.question_uuid, &vote)?;
+    policy::authorize_privilege(actor, Privilege::CloseVote, privileges)?;
+
+    question_dao
+        .vote_to_close(
+            question_id.question_uuid,
+            actor.user_uuid.clone(),
+            vote,
+            actor.role.is_moderator(),
+        )
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+pub async fn vote_to_reopen(
+    question_id: QuestionId,
+    actor: &Actor,
+    privileges: &PrivilegeTable,
+    question_dao: &Box<dyn QuestionDao + Send + Sync>,
+) -> Result<QuestionStatus, HandlerError> {
+    policy::authorize_privilege(actor, Privilege::CloseVote, privileges)?;
+
+    question_dao
+        .vote_to_reopen(
+            question_id.question_uuid,
+            actor.user_uuid.clone(),
+            actor.role.is_moderator(),
+        )
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+pub async fn lock_question(
+    question_id: QuestionId,
+    actor: &Actor,
+    question_dao: &Box<dyn QuestionDao + Send + Sync>,
+) -> Result<QuestionStatus, HandlerError> {
+    policy::authorize_moderator(actor, "lock questions")?;
+
+    question_dao
+        .lock_question(question_id.question_uuid)
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+pub async fn unlock_question(
+    question_id: QuestionId,
+    actor: &Actor,
+    question_dao: &Box<dyn QuestionDao + Send + Sync>,
+) -> Result<QuestionStatus, HandlerError> {
+    policy::authorize_moderator(actor, "unlock questions")?;
+
+    question_dao
+        .unlock_question(question_id.question_uuid)
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
 pub async fn create_answer(
     answer: Answer,
     answer_dao: &Box<dyn AnswerDao + Send + Sync>,
+    notification_dao: &Box<dyn NotificationDao + Send + Sync>,
+    events: &QuestionEvents,
 ) -> Result<AnswerDetail, HandlerError> {
     let answer = answer_dao.create_answer(answer).await;
 
     match answer {
-        Ok(answer) => Ok(answer),
+        Ok(answer) => {
+            notify(
+                notification_dao,
+                NotificationEvent {
+                    kind: NotificationKind::AnswerPosted,
+                    actor_uuid: answer.author_uuid.clone(),
+                    question_uuid: Some(answer.question_uuid.clone()),
+                    answer_uuid: Some(answer.answer_uuid.clone()),
+                    comment_uuid: None,
+                },
+            )
+            .await;
+
+            events.publish(
+                answer.question_uuid.clone(),
+                QuestionEvent::AnswerPosted {
+                    answer: answer.clone(),
+                },
+            );
+
+            Ok(answer)
+        }
         Err(e) => {
             error!("{e:?}");
 
             match e {
                 DBError::InvalidUUID(s) => Err(HandlerError::BadRequest(s)),
+                DBError::Conflict(s) => Err(HandlerError::Conflict(s)),
                 _ => Err(HandlerError::default_internal_error()),
             }
         }
     }
 }
 
+pub async fn get_answer(
+    answer_id: AnswerId,
+    answer_dao: &Box<dyn AnswerDao + Send + Sync>,
+) -> Result<AnswerDetail, HandlerError> {
+    answer_dao
+        .get_answer(answer_id.answer_uuid)
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
 pub async fn get_answers(
     question_id: QuestionId,
+    include_deleted: bool,
+    page: PageRequest,
+    actor: Option<&Actor>,
     answer_dao: &Box<dyn AnswerDao + Send + Sync>,
-) -> Result<Vec<AnswerDetail>, HandlerError> {
-    let answers = answer_dao.get_answers(question_id.question_uuid).await;
+) -> Result<Page<AnswerDetail>, HandlerError> {
+    validate_page(&page)?;
+    authorize_include_deleted(include_deleted, actor)?;
+
+    let answers = answer_dao
+        .get_answers(question_id.question_uuid, include_deleted, page)
+        .await;
 
     match answers {
         Ok(answers) => Ok(answers),
         Err(e) => {
             error!("{e:?}");
-            Err(HandlerError::default_internal_error())
+
+            match e {
+                DBError::InvalidCursor(s) => Err(HandlerError::BadRequest(s)),
+                _ => Err(HandlerError::default_internal_error()),
+            }
         }
     }
 }
 
+pub async fn update_answer(
+    answer_id: AnswerId,
+    update: AnswerUpdate,
+    actor: &Actor,
+    privileges: &PrivilegeTable,
+    answer_dao: &Box<dyn AnswerDao + Send + Sync>,
+    notification_dao: &Box<dyn NotificationDao + Send + Sync>,
+    events: &QuestionEvents,
+) -> Result<AnswerDetail, HandlerError> {
+    authorize_answer_edit(&answer_id.answer_uuid, actor, privileges, answer_dao).await?;
+
+    let answer = answer_dao
+        .update_answer(answer_id.answer_uuid, update)
+        .await
+        .map_err(HandlerError::from_db_error)?;
+
+    notify(
+        notification_dao,
+        NotificationEvent {
+            kind: NotificationKind::PostEdited,
+            actor_uuid: Some(actor.user_uuid.clone()),
+            question_uuid: Some(answer.question_uuid.clone()),
+            answer_uuid: Some(answer.answer_uuid.clone()),
+            comment_uuid: None,
+        },
+    )
+    .await;
+
+    events.publish(
+        answer.question_uuid.clone(),
+        QuestionEvent::AnswerEdited {
+            answer: answer.clone(),
+        },
+    );
+
+    Ok(answer)
+}
+
+pub async fn get_answer_revisions(
+    answer_id: AnswerId,
+    include_deleted: bool,
+    actor: Option<&Actor>,
+    answer_dao: &Box<dyn AnswerDao + Send + Sync>,
+) -> Result<Vec<AnswerRevision>, HandlerError> {
+    authorize_include_deleted(include_deleted, actor)?;
+
+    answer_dao
+        .get_answer_revisions(answer_id.answer_uuid, include_deleted)
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+pub async fn diff_answer_revisions(
+    answer_id: AnswerId,
+    from_revision: i32,
+    to_revision: i32,
+    include_deleted: bool,
+    actor: Option<&Actor>,
+    answer_dao: &Box<dyn AnswerDao + Send + Sync>,
+) -> Result<RevisionDiff, HandlerError> {
+    authorize_include_deleted(include_deleted, actor)?;
+
+    let from = answer_dao
+        .get_answer_revision(
+            answer_id.answer_uuid.clone(),
+            from_revision,
+            include_deleted,
+        )
+        .await
+        .map_err(HandlerError::from_db_error)?;
+    let to = answer_dao
+        .get_answer_revision(answer_id.answer_uuid, to_revision, include_deleted)
+        .await
+        .map_err(HandlerError::from_db_error)?;
+
+    Ok(RevisionDiff {
+        from_revision,
+        to_revision,
+        changes: field_changes(&[("content", &from.content, &to.content)]),
+    })
+}
+
+/// Restores an answer to an earlier revision. The rollback is itself
+/// recorded as a new revision, so history is never rewritten.
+pub async fn rollback_answer(
+    answer_id: AnswerId,
+    revision: i32,
+    actor: &Actor,
+    privileges: &PrivilegeTable,
+    answer_dao: &Box<dyn AnswerDao + Send + Sync>,
+    notification_dao: &Box<dyn NotificationDao + Send + Sync>,
+    events: &QuestionEvents,
+) -> Result<AnswerDetail, HandlerError> {
+    authorize_answer_edit(&answer_id.answer_uuid, actor, privileges, answer_dao).await?;
+
+    let target = answer_dao
+        .get_answer_revision(answer_id.answer_uuid.clone(), revision, false)
+        .await
+        .map_err(HandlerError::from_db_error)?;
+
+    let answer = answer_dao
+        .update_answer(
+            answer_id.answer_uuid,
+            AnswerUpdate {
+                content: target.content,
+            },
+        )
+        .await
+        .map_err(HandlerError::from_db_error)?;
+
+    notify(
+        notification_dao,
+        NotificationEvent {
+            kind: NotificationKind::PostEdited,
+            actor_uuid: Some(actor.user_uuid.clone()),
+            question_uuid: Some(answer.question_uuid.clone()),
+            answer_uuid: Some(answer.answer_uuid.clone()),
+            comment_uuid: None,
+        },
+    )
+    .await;
+
+    events.publish(
+        answer.question_uuid.clone(),
+        QuestionEvent::AnswerEdited {
+            answer: answer.clone(),
+        },
+    );
+
+    Ok(answer)
+}
+
 pub async fn delete_answer(
     answer_id: AnswerId,
+    actor: &Actor,
     answer_dao: &Box<dyn AnswerDao + Send + Sync>,
+    events: &QuestionEvents,
 ) -> Result<(), HandlerError> {
-    let result = answer_dao.delete_answer(answer_id.answer_uuid).await;
+    let answer = authorize_answer(
+        &answer_id.answer_uuid,
+        PostAction::Delete,
+        actor,
+        answer_dao,
+    )
+    .await?;
+
+    let result = answer_dao
+        .delete_answer(answer_id.answer_uuid, actor.user_uuid.clone())
+        .await;
 
     if result.is_ok() {
+        events.publish(
+            answer.question_uuid,
+            QuestionEvent::AnswerDeleted {
+                answer_uuid: answer.answer_uuid,
+            },
+        );
+
         Ok(())
     } else {
         Err(HandlerError::default_internal_error())
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::models::QuestionId;
+pub async fn undelete_answer(
+    answer_id: AnswerId,
+    actor: &Actor,
+    answer_dao: &Box<dyn AnswerDao + Send + Sync>,
+) -> Result<AnswerDetail, HandlerError> {
+    policy::authorize_moderator(actor, "undelete posts")?;
 
-    use super::*;
-    use tokio::sync::Mutex;
+    answer_dao
+        .undelete_answer(answer_id.answer_uuid)
+        .await
+        .map_err(HandlerError::from_db_error)
+}
 
-    struct QuestionDaoMock {
-        create_question_response: Mutex<Option<Result<QuestionDetail, DBError>>>,
-        get_questions_response: Mutex<Option<Result<Vec<QuestionDetail>, DBError>>>,
-        delete_question_response: Mutex<Option<Result<(), DBError>>>,
+fn validate_comment(comment: &Comment) -> Result<(), HandlerError> {
+    if comment.content.trim().is_empty() || comment.content.chars().count() > MAX_COMMENT_LENGTH {
+        Err(HandlerError::BadRequest(format!(
+            "Comment must be 1 to {MAX_COMMENT_LENGTH} characters"
+        )))
+    } else {
+        Ok(())
     }
+}
 
-    impl QuestionDaoMock {
-        fn new() -> Self {
-            Self {
-                create_question_response: Mutex::new(None),
-                get_questions_response: Mutex::new(None),
-                delete_question_response: Mutex::new(None),
-            }
-        }
+#[allow(clippy::too_many_arguments)]
+pub async fn create_comment(
+    parent: CommentParent,
+    comment: Comment,
+    actor: &Actor,
+    privileges: &PrivilegeTable,
+    question_dao: &Box<dyn QuestionDao + Send + Sync>,
+    answer_dao: &Box<dyn AnswerDao + Send + Sync>,
+    comment_dao: &Box<dyn CommentDao + Send + Sync>,
+    notification_dao: &Box<dyn NotificationDao + Send + Sync>,
+) -> Result<CommentDetail, HandlerError> {
+    validate_comment(&comment)?;
+    authorize_comment(&parent, actor, privileges, question_dao, answer_dao).await?;
 
-        fn mock_create_question(&mut self, response: Result<QuestionDetail, DBError>) {
-            self.create_question_response = Mutex::new(Some(response));
-        }
+    let comment = comment_dao
+        .create_comment(parent, comment)
+        .await
+        .map_err(HandlerError::from_db_error)?;
 
-        fn mock_get_questions(&mut self, response: Result<Vec<QuestionDetail>, DBError>) {
-            self.get_questions_response = Mutex::new(Some(response));
-        }
+    notify(
+        notification_dao,
+        NotificationEvent {
+            kind: NotificationKind::CommentPosted,
+            actor_uuid: Some(actor.user_uuid.clone()),
+            question_uuid: comment.question_uuid.clone(),
+            answer_uuid: comment.answer_uuid.clone(),
+            comment_uuid: Some(comment.comment_uuid.clone()),
+        },
+    )
+    .await;
 
-        fn mock_delete_question(&mut self, response: Result<(), DBError>) {
-            self.delete_question_response = Mutex::new(Some(response));
-        }
-    }
+    Ok(comment)
+}
 
-    #[async_trait]
-    impl QuestionDao for QuestionDaoMock {
-        async fn create_question(&self, _: Question) -> Result<QuestionDetail, DBError> {
-            self.create_question_response
-                .lock()
-                .await
-                .take()
-                .expect("create question response should not be None.")
-        }
+pub async fn get_comments(
+    parent: CommentParent,
+    include_deleted: bool,
+    page: PageRequest,
+    actor: Option<&Actor>,
+    comment_dao: &Box<dyn CommentDao + Send + Sync>,
+) -> Result<Page<CommentDetail>, HandlerError> {
+    validate_page(&page)?;
+    authorize_include_deleted(include_deleted, actor)?;
 
-        async fn get_questions(&self) -> Result<Vec<QuestionDetail>, DBError> {
-            self.get_questions_response
-                .lock()
-                .await
-                .take()
-                .expect("get questions response should not be None")
-        }
+    comment_dao
+        .get_comments(parent, include_deleted, page)
+        .await
+        .map_err(HandlerError::from_db_error)
+}
 
-        async fn delete_question(&self, _: String) -> Result<(), DBError> {
-            self.delete_question_response
-                .lock()
-                .await
-                .take()
-                .expect("delete question response should not be None")
+pub async fn delete_comment(
+    comment_id: CommentId,
+    actor: &Actor,
+    comment_dao: &Box<dyn CommentDao + Send + Sync>,
+) -> Result<(), HandlerError> {
+    let comment = comment_dao
+        .get_comment(comment_id.comment_uuid.clone())
+        .await
+        .map_err(HandlerError::from_db_error)?;
+
+    policy::authorize_post(
+        actor,
+        PostAction::Delete,
+        "comment",
+        comment.author_uuid.as_deref(),
+    )?;
+
+    comment_dao
+        .delete_comment(comment_id.comment_uuid)
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+/// Checks that the actor asked the question the answer belongs to, returning
+/// the answer's question UUID.
+async fn authorize_accept(
+    answer_uuid: &str,
+    actor: &Actor,
+    question_dao: &Box<dyn QuestionDao + Send + Sync>,
+    answer_dao: &Box<dyn AnswerDao + Send + Sync>,
+) -> Result<String, HandlerError> {
+    let answer = answer_dao
+        .get_answer(answer_uuid.to_string())
+        .await
+        .map_err(HandlerError::from_db_error)?;
+    let question = question_dao
+        .get_question(answer.question_uuid)
+        .await
+        .map_err(HandlerError::from_db_error)?;
+
+    policy::authorize_accept(actor, question.author_uuid.as_deref())?;
+
+    Ok(question.question_uuid)
+}
+
+pub async fn accept_answer(
+    answer_id: AnswerId,
+    actor: &Actor,
+    question_dao: &Box<dyn QuestionDao + Send + Sync>,
+    answer_dao: &Box<dyn AnswerDao + Send + Sync>,
+    notification_dao: &Box<dyn NotificationDao + Send + Sync>,
+) -> Result<QuestionDetail, HandlerError> {
+    let question_uuid =
+        authorize_accept(&answer_id.answer_uuid, actor, question_dao, answer_dao).await?;
+
+    let question = question_dao
+        .accept_answer(question_uuid, answer_id.answer_uuid.clone())
+        .await
+        .map_err(HandlerError::from_db_error)?;
+
+    notify(
+        notification_dao,
+        NotificationEvent {
+            kind: NotificationKind::AnswerAccepted,
+            actor_uuid: Some(actor.user_uuid.clone()),
+            question_uuid: Some(question.question_uuid.clone()),
+            answer_uuid: Some(answer_id.answer_uuid),
+            comment_uuid: None,
+        },
+    )
+    .await;
+
+    Ok(question)
+}
+
+pub async fn unaccept_answer(
+    answer_id: AnswerId,
+    actor: &Actor,
+    question_dao: &Box<dyn QuestionDao + Send + Sync>,
+    answer_dao: &Box<dyn AnswerDao + Send + Sync>,
+) -> Result<QuestionDetail, HandlerError> {
+    let question_uuid =
+        authorize_accept(&answer_id.answer_uuid, actor, question_dao, answer_dao).await?;
+
+    question_dao
+        .unaccept_answer(question_uuid, answer_id.answer_uuid)
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+fn validate_vote(vote: &Vote) -> Result<(), HandlerError> {
+    if vote.value == 1 || vote.value == -1 {
+        Ok(())
+    } else {
+        Err(HandlerError::BadRequest(format!(
+            "Vote must be 1 or -1: {}",
+            vote.value
+        )))
+    }
+}
+
+/// Upvotes are open to every user, downvotes take a privilege.
+fn authorize_vote(
+    vote: &Vote,
+    actor: &Actor,
+    privileges: &PrivilegeTable,
+) -> Result<(), HandlerError> {
+    if vote.value < 0 {
+        policy::authorize_privilege(actor, Privilege::VoteDown, privileges)
+    } else {
+        Ok(())
+    }
+}
+
+pub async fn vote_question(
+    question_id: QuestionId,
+    vote: Vote,
+    actor: &Actor,
+    privileges: &PrivilegeTable,
+    vote_dao: &Box<dyn VoteDao + Send + Sync>,
+    events: &QuestionEvents,
+) -> Result<VoteSummary, HandlerError> {
+    validate_vote(&vote)?;
+    authorize_vote(&vote, actor, privileges)?;
+
+    let summary = vote_dao
+        .vote_question(
+            question_id.question_uuid.clone(),
+            actor.user_uuid.clone(),
+            vote.value,
+        )
+        .await
+        .map_err(HandlerError::from_db_error)?;
+
+    events.publish(
+        question_id.question_uuid,
+        QuestionEvent::QuestionVoted {
+            score: summary.score,
+        },
+    );
+
+    Ok(summary)
+}
+
+pub async fn retract_question_vote(
+    question_id: QuestionId,
+    actor: &Actor,
+    vote_dao: &Box<dyn VoteDao + Send + Sync>,
+    events: &QuestionEvents,
+) -> Result<VoteSummary, HandlerError> {
+    let summary = vote_dao
+        .retract_question_vote(question_id.question_uuid.clone(), actor.user_uuid.clone())
+        .await
+        .map_err(HandlerError::from_db_error)?;
+
+    events.publish(
+        question_id.question_uuid,
+        QuestionEvent::QuestionVoted {
+            score: summary.score,
+        },
+    );
+
+    Ok(summary)
+}
+
+/// Publishes the new score of an answer to the watchers of its question. The
+/// vote has already gone through, so failing to find the question is only
+/// logged.
+async fn publish_answer_score(
+    answer_uuid: String,
+    score: i32,
+    answer_dao: &Box<dyn AnswerDao + Send + Sync>,
+    events: &QuestionEvents,
+) {
+    match answer_dao.get_answer(answer_uuid.clone()).await {
+        Ok(answer) => events.publish(
+            answer.question_uuid,
+            QuestionEvent::AnswerVoted { answer_uuid, score },
+        ),
+        Err(e) => error!("{e:?}"),
+    }
+}
+
+pub async fn vote_answer(
+    answer_id: AnswerId,
+    vote: Vote,
+    actor: &Actor,
+    privileges: &PrivilegeTable,
+    vote_dao: &Box<dyn VoteDao + Send + Sync>,
+    answer_dao: &Box<dyn AnswerDao + Send + Sync>,
+    events: &QuestionEvents,
+) -> Result<VoteSummary, HandlerError> {
+    validate_vote(&vote)?;
+    authorize_vote(&vote, actor, privileges)?;
+
+    let summary = vote_dao
+        .vote_answer(
+            answer_id.answer_uuid.clone(),
+            actor.user_uuid.clone(),
+            vote.value,
+        )
+        .await
+        .map_err(HandlerError::from_db_error)?;
+
+    publish_answer_score(answer_id.answer_uuid, summary.score, answer_dao, events).await;
+
+    Ok(summary)
+}
+
+pub async fn retract_answer_vote(
+    answer_id: AnswerId,
+    actor: &Actor,
+    vote_dao: &Box<dyn VoteDao + Send + Sync>,
+    answer_dao: &Box<dyn AnswerDao + Send + Sync>,
+    events: &QuestionEvents,
+) -> Result<VoteSummary, HandlerError> {
+    let summary = vote_dao
+        .retract_answer_vote(answer_id.answer_uuid.clone(), actor.user_uuid.clone())
+        .await
+        .map_err(HandlerError::from_db_error)?;
+
+    publish_answer_score(answer_id.answer_uuid, summary.score, answer_dao, events).await;
+
+    Ok(summary)
+}
+
+pub async fn get_tags(
+    page: PageRequest,
+    tag_dao: &Box<dyn TagDao + Send + Sync>,
+) -> Result<Page<TagSummary>, HandlerError> {
+    validate_page(&page)?;
+
+    tag_dao
+        .get_tags(page)
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+pub async fn get_tag(
+    name: String,
+    tag_dao: &Box<dyn TagDao + Send + Sync>,
+) -> Result<TagDetail, HandlerError> {
+    tag_dao
+        .get_tag(name.to_lowercase())
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+pub async fn update_tag_wiki(
+    name: String,
+    update: TagWikiUpdate,
+    actor: &Actor,
+    privileges: &PrivilegeTable,
+    tag_dao: &Box<dyn TagDao + Send + Sync>,
+) -> Result<TagDetail, HandlerError> {
+    if update.excerpt.is_none() && update.body.is_none() {
+        return Err(HandlerError::BadRequest(
+            "At least one of excerpt or body must be provided".to_string(),
+        ));
+    }
+
+    if let Some(excerpt) = &update.excerpt {
+        if excerpt.chars().count() > MAX_TAG_EXCERPT_LENGTH {
+            return Err(HandlerError::BadRequest(format!(
+                "Excerpt must be at most {MAX_TAG_EXCERPT_LENGTH} characters"
+            )));
         }
     }
 
-    struct AnswerDaoMock {
-        create_answer_response: Mutex<Option<Result<AnswerDetail, DBError>>>,
-        get_answers_response: Mutex<Option<Result<Vec<AnswerDetail>, DBError>>>,
-        delete_answer_response: Mutex<Option<Result<(), DBError>>>,
+    policy::authorize_privilege(actor, Privilege::EditPosts, privileges)?;
+
+    tag_dao
+        .update_tag_wiki(name.to_lowercase(), update, actor.user_uuid.clone())
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+pub async fn get_tag_wiki_revisions(
+    name: String,
+    tag_dao: &Box<dyn TagDao + Send + Sync>,
+) -> Result<Vec<TagWikiRevision>, HandlerError> {
+    tag_dao
+        .get_tag_wiki_revisions(name.to_lowercase())
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+pub async fn get_tag_synonyms(
+    name: String,
+    tag_dao: &Box<dyn TagDao + Send + Sync>,
+) -> Result<Vec<TagSynonym>, HandlerError> {
+    tag_dao
+        .get_tag_synonyms(name.to_lowercase())
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+pub async fn propose_tag_synonym(
+    name: String,
+    proposal: SynonymProposal,
+    actor: &Actor,
+    tag_dao: &Box<dyn TagDao + Send + Sync>,
+) -> Result<TagSynonym, HandlerError> {
+    let name = name.to_lowercase();
+    let synonym = normalize_tag(&proposal.synonym)?;
+
+    if synonym == name {
+        return Err(HandlerError::BadRequest(format!(
+            "A tag cannot be a synonym of itself: {name}"
+        )));
     }
 
-    impl AnswerDaoMock {
+    tag_dao
+        .propose_tag_synonym(name, synonym, actor.user_uuid.clone())
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+pub async fn approve_tag_synonym(
+    name: String,
+    synonym: String,
+    actor: &Actor,
+    tag_dao: &Box<dyn TagDao + Send + Sync>,
+) -> Result<TagSynonym, HandlerError> {
+    policy::authorize_moderator(actor, "approve tag synonyms")?;
+
+    tag_dao
+        .approve_tag_synonym(
+            name.to_lowercase(),
+            synonym.to_lowercase(),
+            actor.user_uuid.clone(),
+        )
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+pub async fn search(
+    query: String,
+    page: PageRequest,
+    search_dao: &Box<dyn SearchDao + Send + Sync>,
+) -> Result<Page<SearchResult>, HandlerError> {
+    validate_page(&page)?;
+
+    let query = query.trim();
+
+    if query.is_empty() || query.chars().count() > MAX_SEARCH_QUERY_LENGTH {
+        return Err(HandlerError::BadRequest(format!(
+            "Search query must be 1 to {MAX_SEARCH_QUERY_LENGTH} characters"
+        )));
+    }
+
+    let query = search_query::parse(query)?;
+
+    search_dao
+        .search(query, page)
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+fn validate_user(user: &User) -> Result<(), HandlerError> {
+    let username_is_valid = (3..=32).contains(&user.username.len())
+        && user
+            .username
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+
+    if !username_is_valid {
+        return Err(HandlerError::BadRequest(format!(
+            "Username must be 3 to 32 letters, digits, '_' or '-': {}",
+            user.username
+        )));
+    }
+
+    let email_is_valid = user.email.len() <= 255
+        && matches!(user.email.split_once('@'), Some((local, domain)) if !local.is_empty() && domain.contains('.'));
+
+    if !email_is_valid {
+        return Err(HandlerError::BadRequest(format!(
+            "Invalid email address: {}",
+            user.email
+        )));
+    }
+
+    if !(8..=128).contains(&user.password.chars().count()) {
+        return Err(HandlerError::BadRequest(
+            "Password must be 8 to 128 characters".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+pub async fn create_user(
+    user: User,
+    user_dao: &Box<dyn UserDao + Send + Sync>,
+) -> Result<UserDetail, HandlerError> {
+    validate_user(&user)?;
+
+    // Argon2 is deliberately slow, so it is kept off the async workers.
+    let password = user.password.clone();
+    let password_hash =
+        match tokio::task::spawn_blocking(move || auth::hash_password(&password)).await {
+            Ok(Ok(password_hash)) => password_hash,
+            e => {
+                error!("{e:?}");
+                return Err(HandlerError::default_internal_error());
+            }
+        };
+
+    user_dao
+        .create_user(user, password_hash)
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+pub async fn get_user(
+    user_id: UserId,
+    user_dao: &Box<dyn UserDao + Send + Sync>,
+) -> Result<UserDetail, HandlerError> {
+    user_dao
+        .get_user(user_id.user_uuid)
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+pub async fn update_user_role(
+    user_id: UserId,
+    role_update: RoleUpdate,
+    actor: &Actor,
+    user_dao: &Box<dyn UserDao + Send + Sync>,
+) -> Result<UserDetail, HandlerError> {
+    policy::authorize_role_change(actor, &user_id.user_uuid)?;
+
+    user_dao
+        .update_role(user_id.user_uuid, role_update.role)
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+pub fn get_privileges(privileges: &PrivilegeTable) -> Vec<PrivilegeThreshold> {
+    privileges.thresholds()
+}
+
+pub async fn get_reputation_history(
+    user_id: UserId,
+    page: PageRequest,
+    reputation_dao: &Box<dyn ReputationDao + Send + Sync>,
+) -> Result<Page<ReputationEvent>, HandlerError> {
+    validate_page(&page)?;
+
+    reputation_dao
+        .get_reputation_history(user_id.user_uuid, page)
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+pub async fn recalculate_reputation(
+    actor: &Actor,
+    reputation_dao: &Box<dyn ReputationDao + Send + Sync>,
+) -> Result<ReputationRecalculation, HandlerError> {
+    policy::authorize_moderator(actor, "recalculate reputation")?;
+
+    reputation_dao
+        .recalculate_reputation()
+        .await
+        .map(|corrected_users| ReputationRecalculation { corrected_users })
+        .map_err(HandlerError::from_db_error)
+}
+
+pub async fn get_user_badges(
+    user_id: UserId,
+    badge_dao: &Box<dyn BadgeDao + Send + Sync>,
+) -> Result<Vec<UserBadge>, HandlerError> {
+    badge_dao
+        .get_user_badges(user_id.user_uuid)
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+pub async fn get_badge_recipients(
+    name: String,
+    page: PageRequest,
+    badge_dao: &Box<dyn BadgeDao + Send + Sync>,
+) -> Result<Page<BadgeRecipient>, HandlerError> {
+    validate_page(&page)?;
+
+    let badge = name
+        .parse::<Badge>()
+        .map_err(|_| HandlerError::NotFound(format!("Badge not found: {name}")))?;
+
+    badge_dao
+        .get_badge_recipients(badge, page)
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+pub async fn start_bounty(
+    question_id: QuestionId,
+    offer: BountyOffer,
+    actor: &Actor,
+    privileges: &PrivilegeTable,
+    bounty_dao: &Box<dyn BountyDao + Send + Sync>,
+) -> Result<Bounty, HandlerError> {
+    if !(MIN_BOUNTY..=MAX_BOUNTY).contains(&offer.amount) {
+        return Err(HandlerError::BadRequest(format!(
+            "Bounty must be {MIN_BOUNTY} to {MAX_BOUNTY} reputation"
+        )));
+    }
+
+    policy::authorize_privilege(actor, Privilege::OfferBounty, privileges)?;
+
+    bounty_dao
+        .start_bounty(
+            question_id.question_uuid,
+            actor.user_uuid.clone(),
+            offer.amount,
+        )
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+pub async fn award_bounty(
+    question_id: QuestionId,
+    award: BountyAward,
+    actor: &Actor,
+    answer_dao: &Box<dyn AnswerDao + Send + Sync>,
+    bounty_dao: &Box<dyn BountyDao + Send + Sync>,
+    notification_dao: &Box<dyn NotificationDao + Send + Sync>,
+) -> Result<Bounty, HandlerError> {
+    let bounty = bounty_dao
+        .get_active_bounty(question_id.question_uuid)
+        .await
+        .map_err(HandlerError::from_db_error)?;
+
+    policy::authorize_bounty_award(actor, bounty.offered_by.as_deref())?;
+
+    let answer = answer_dao
+        .get_answer(award.answer_uuid)
+        .await
+        .map_err(HandlerError::from_db_error)?;
+
+    if answer.question_uuid != bounty.question_uuid {
+        return Err(HandlerError::BadRequest(format!(
+            "Answer {} does not belong to question {}",
+            answer.answer_uuid, bounty.question_uuid
+        )));
+    }
+
+    if answer.author_uuid.as_deref() == Some(actor.user_uuid.as_str()) {
+        return Err(HandlerError::BadRequest(
+            "A bounty cannot be awarded to one's own answer".to_string(),
+        ));
+    }
+
+    let bounty = bounty_dao
+        .award_bounty(bounty.bounty_uuid, answer.answer_uuid)
+        .await
+        .map_err(HandlerError::from_db_error)?;
+
+    notify(
+        notification_dao,
+        NotificationEvent {
+            kind: NotificationKind::BountyAwarded,
+            actor_uuid: Some(actor.user_uuid.clone()),
+            question_uuid: Some(bounty.question_uuid.clone()),
+            answer_uuid: bounty.awarded_answer_uuid.clone(),
+            comment_uuid: None,
+        },
+    )
+    .await;
+
+    Ok(bounty)
+}
+
+pub async fn get_featured_questions(
+    page: PageRequest,
+    bounty_dao: &Box<dyn BountyDao + Send + Sync>,
+) -> Result<Page<FeaturedQuestion>, HandlerError> {
+    validate_page(&page)?;
+
+    bounty_dao
+        .get_featured_questions(page)
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+pub async fn follow_question(
+    question_id: QuestionId,
+    actor: &Actor,
+    follow_dao: &Box<dyn FollowDao + Send + Sync>,
+) -> Result<FollowedQuestion, HandlerError> {
+    follow_dao
+        .follow_question(question_id.question_uuid, actor.user_uuid.clone())
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+pub async fn unfollow_question(
+    question_id: QuestionId,
+    actor: &Actor,
+    follow_dao: &Box<dyn FollowDao + Send + Sync>,
+) -> Result<(), HandlerError> {
+    follow_dao
+        .unfollow_question(question_id.question_uuid, actor.user_uuid.clone())
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+pub async fn get_followed_questions(
+    page: PageRequest,
+    actor: &Actor,
+    follow_dao: &Box<dyn FollowDao + Send + Sync>,
+) -> Result<Page<FollowedQuestion>, HandlerError> {
+    validate_page(&page)?;
+
+    follow_dao
+        .get_followed_questions(actor.user_uuid.clone(), page)
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+pub async fn get_notifications(
+    unread_only: bool,
+    page: PageRequest,
+    actor: &Actor,
+    notification_dao: &Box<dyn NotificationDao + Send + Sync>,
+) -> Result<NotificationInbox, HandlerError> {
+    validate_page(&page)?;
+
+    notification_dao
+        .get_notifications(actor.user_uuid.clone(), unread_only, page)
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+pub async fn mark_notification_read(
+    notification_uuid: String,
+    actor: &Actor,
+    notification_dao: &Box<dyn NotificationDao + Send + Sync>,
+) -> Result<UnreadCount, HandlerError> {
+    notification_dao
+        .mark_read(actor.user_uuid.clone(), notification_uuid)
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+pub async fn mark_all_notifications_read(
+    actor: &Actor,
+    notification_dao: &Box<dyn NotificationDao + Send + Sync>,
+) -> Result<UnreadCount, HandlerError> {
+    notification_dao
+        .mark_all_read(actor.user_uuid.clone())
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+pub async fn get_notification_preferences(
+    actor: &Actor,
+    notification_dao: &Box<dyn NotificationDao + Send + Sync>,
+) -> Result<Vec<NotificationPreference>, HandlerError> {
+    notification_dao
+        .get_preferences(actor.user_uuid.clone())
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+pub async fn update_notification_preferences(
+    preferences: Vec<NotificationPreference>,
+    actor: &Actor,
+    notification_dao: &Box<dyn NotificationDao + Send + Sync>,
+) -> Result<Vec<NotificationPreference>, HandlerError> {
+    if preferences.is_empty() {
+        return Err(HandlerError::BadRequest(
+            "At least one preference must be provided".to_string(),
+        ));
+    }
+
+    notification_dao
+        .update_preferences(actor.user_uuid.clone(), preferences)
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+pub async fn login(
+    credentials: Credentials,
+    user_dao: &Box<dyn UserDao + Send + Sync>,
+    session_dao: &Box<dyn SessionDao + Send + Sync>,
+) -> Result<Session, HandlerError> {
+    let invalid_credentials =
+        || HandlerError::Unauthorized("Invalid username or password".to_string());
+
+    let stored = match user_dao.get_credentials(credentials.username).await {
+        Ok(stored) => Some(stored),
+        Err(DBError::NotFound(_)) => None,
+        Err(e) => return Err(HandlerError::from_db_error(e)),
+    };
+
+    // Accounts that do not exist or have no password are still checked, against
+    // a dummy hash, so the response time does not tell them apart.
+    let password_hash = stored.as_ref().and_then(|s| s.password_hash.clone());
+    let has_password = password_hash.is_some();
+    let password_hash = password_hash.unwrap_or_else(|| auth::DUMMY_PASSWORD_HASH.to_string());
+
+    let password = credentials.password;
+    let verified =
+        tokio::task::spawn_blocking(move || auth::verify_password(&password, &password_hash))
+            .await
+            .map_err(|e| {
+                error!("{e:?}");
+                HandlerError::default_internal_error()
+            })?;
+
+    match stored {
+        Some(stored) if has_password && verified => session_dao
+            .create_session(stored.user_uuid, auth::generate_token())
+            .await
+            .map_err(HandlerError::from_db_error),
+        _ => Err(invalid_credentials()),
+    }
+}
+
+pub async fn logout(
+    token: String,
+    session_dao: &Box<dyn SessionDao + Send + Sync>,
+) -> Result<(), HandlerError> {
+    session_dao
+        .delete_session(token)
+        .await
+        .map_err(HandlerError::from_db_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{
+        BadgeClass, BountyState, CloseReason, Notification, QuestionId, QuestionState,
+        ReputationKind, Role, SearchQuery, StoredCredentials,
+    };
+
+    use super::*;
+    use std::sync::Arc;
+    use tokio::sync::Mutex;
+
+    struct QuestionDaoMock {
+        create_question_response: Mutex<Option<Result<QuestionDetail, DBError>>>,
+        get_question_response: Mutex<Option<Result<QuestionDetail, DBError>>>,
+        get_questions_response: Mutex<Option<Result<Page<QuestionDetail>, DBError>>>,
+        delete_question_response: Mutex<Option<Result<(), DBError>>>,
+        update_question_response: Mutex<Option<Result<QuestionDetail, DBError>>>,
+        get_question_revisions_response: Mutex<Option<Result<Vec<QuestionRevision>, DBError>>>,
+        get_question_revision_response: Mutex<Vec<Result<QuestionRevision, DBError>>>,
+        accept_answer_response: Mutex<Option<Result<QuestionDetail, DBError>>>,
+        unaccept_answer_response: Mutex<Option<Result<QuestionDetail, DBError>>>,
+        get_similar_questions_response: Mutex<Option<Result<Vec<SimilarQuestion>, DBError>>>,
+        vote_to_close_response: Mutex<Option<Result<QuestionStatus, DBError>>>,
+        vote_to_reopen_response: Mutex<Option<Result<QuestionStatus, DBError>>>,
+        lock_question_response: Mutex<Option<Result<QuestionStatus, DBError>>>,
+        unlock_question_response: Mutex<Option<Result<QuestionStatus, DBError>>>,
+        undelete_question_response: Mutex<Option<Result<QuestionDetail, DBError>>>,
+    }
+
+    impl QuestionDaoMock {
         fn new() -> Self {
             Self {
-                create_answer_response: Mutex::new(None),
-                get_answers_response: Mutex::new(None),
-                delete_answer_response: Mutex::new(None),
+                create_question_response: Mutex::new(None),
+                get_question_response: Mutex::new(None),
+                get_questions_response: Mutex::new(None),
+                delete_question_response: Mutex::new(None),
+                update_question_response: Mutex::new(None),
+                get_question_revisions_response: Mutex::new(None),
+                get_question_revision_response: Mutex::new(Vec::new()),
+                accept_answer_response: Mutex::new(None),
+                unaccept_answer_response: Mutex::new(None),
+                get_similar_questions_response: Mutex::new(None),
+                vote_to_close_response: Mutex::new(None),
+                vote_to_reopen_response: Mutex::new(None),
+                lock_question_response: Mutex::new(None),
+                unlock_question_response: Mutex::new(None),
+                undelete_question_response: Mutex::new(None),
             }
         }
 
-        pub fn mock_create_answer(&mut self, response: Result<AnswerDetail, DBError>) {
-            self.create_answer_response = Mutex::new(Some(response));
+        fn mock_create_question(&mut self, response: Result<QuestionDetail, DBError>) {
+            self.create_question_response = Mutex::new(Some(response));
         }
 
-        pub fn mock_get_answers(&mut self, response: Result<Vec<AnswerDetail>, DBError>) {
-            self.get_answers_response = Mutex::new(Some(response));
+        fn mock_get_question(&mut self, response: Result<QuestionDetail, DBError>) {
+            self.get_question_response = Mutex::new(Some(response));
         }
 
-        pub fn mock_delete_answer(&mut self, response: Result<(), DBError>) {
-            self.delete_answer_response = Mutex::new(Some(response));
+        fn mock_get_questions(&mut self, response: Result<Page<QuestionDetail>, DBError>) {
+            self.get_questions_response = Mutex::new(Some(response));
         }
-    }
 
-    #[async_trait]
-    impl AnswerDao for AnswerDaoMock {
-        async fn create_answer(&self, _: Answer) -> Result<AnswerDetail, DBError> {
-            self.create_answer_response
-                .lock()
-                .await
-                .take()
-                .expect("create answer response should not be None")
+        fn mock_delete_question(&mut self, response: Result<(), DBError>) {
+            self.delete_question_response = Mutex::new(Some(response));
         }
 
-        async fn get_answers(&self, _: String) -> Result<Vec<AnswerDetail>, DBError> {
-            self.get_answers_response
-                .lock()
-                .await
-                .take()
-                .expect("get answers response should not be None")
+        fn mock_update_question(&mut self, response: Result<QuestionDetail, DBError>) {
+            self.update_question_response = Mutex::new(Some(response));
+        }
+
+        fn mock_get_question_revisions(
+            &mut self,
+            response: Result<Vec<QuestionRevision>, DBError>,
+        ) {
+            self.get_question_revisions_response = Mutex::new(Some(response));
+        }
+
+        fn mock_get_question_revision(&mut self, response: Result<QuestionRevision, DBError>) {
+            self.get_question_revision_response.get_mut().push(response);
+        }
+
+        fn mock_accept_answer(&mut self, response: Result<QuestionDetail, DBError>) {
+            self.accept_answer_response = Mutex::new(Some(response));
+        }
+
+        fn mock_unaccept_answer(&mut self, response: Result<QuestionDetail, DBError>) {
+            self.unaccept_answer_response = Mutex::new(Some(response));
+        }
+
+        fn mock_get_similar_questions(&mut self, response: Result<Vec<SimilarQuestion>, DBError>) {
+            self.get_similar_questions_response = Mutex::new(Some(response));
         }
 
-        async fn delete_answer(&self, _: String) -> Result<(), DBError> {
-            self.delete_answer_response
-                .lock()
-                .await
-                .take()
-                .expect("delete answer response should not be None")
+        fn mock_vote_to_close(&mut self, response: Result<QuestionStatus, DBError>) {
+            self.vote_to_close_response = Mutex::new(Some(response));
+        }
+
+        fn mock_vote_to_reopen(&mut self, response: Result<QuestionStatus, DBError>) {
+            self.vote_to_reopen_response = Mutex::new(Some(response));
+        }
+
+        fn mock_lock_question(&mut self, response: Result<QuestionStatus, DBError>) {
+            self.lock_question_response = Mutex::new(Some(response));
+        }
+
+        fn mock_unlock_question(&mut self, response: Result<QuestionStatus, DBError>) {
+            self.unlock_question_response = Mutex::new(Some(response));
+        }
+
+        fn mock_undelete_question(&mut self, response: Result<QuestionDetail, DBError>) {
+            self.undelete_question_response = Mutex::new(Some(response));
+        }
+    }
+
+    #[async_trait]
+    impl QuestionDao for QuestionDaoMock {
+        async fn create_question(&self, _: Question) -> Result<QuestionDetail, DBError> {
+            self.create_question_response
+                .lock()
+                .await
+                .take()
+                .expect("create question response should not be None.")
+        }
+
+        async fn get_question(&self, _: String) -> Result<QuestionDetail, DBError> {
+            self.get_question_response
+                .lock()
+                .await
+                .take()
+                .expect("get question response should not be None")
+        }
+
+        async fn get_questions(
+            &self,
+            _: QuestionQuery,
+            _: PageRequest,
+            _: Option<String>,
+        ) -> Result<Page<QuestionDetail>, DBError> {
+            self.get_questions_response
+                .lock()
+                .await
+                .take()
+                .expect("get questions response should not be None")
+        }
+
+        async fn delete_question(&self, _: String, _: String) -> Result<(), DBError> {
+            self.delete_question_response
+                .lock()
+                .await
+                .take()
+                .expect("delete question response should not be None")
+        }
+
+        async fn update_question(
+            &self,
+            _: String,
+            _: QuestionUpdate,
+        ) -> Result<QuestionDetail, DBError> {
+            self.update_question_response
+                .lock()
+                .await
+                .take()
+                .expect("update question response should not be None")
+        }
+
+        async fn get_question_revisions(
+            &self,
+            _: String,
+            _: bool,
+        ) -> Result<Vec<QuestionRevision>, DBError> {
+            self.get_question_revisions_response
+                .lock()
+                .await
+                .take()
+                .expect("get question revisions response should not be None")
+        }
+
+        async fn get_question_revision(
+            &self,
+            _: String,
+            _: i32,
+            _: bool,
+        ) -> Result<QuestionRevision, DBError> {
+            let mut responses = self.get_question_revision_response.lock().await;
+
+            assert!(
+                !responses.is_empty(),
+                "get question revision responses should not be empty"
+            );
+
+            responses.remove(0)
+        }
+
+        async fn accept_answer(&self, _: String, _: String) -> Result<QuestionDetail, DBError> {
+            self.accept_answer_response
+                .lock()
+                .await
+                .take()
+                .expect("accept answer response should not be None")
+        }
+
+        async fn unaccept_answer(&self, _: String, _: String) -> Result<QuestionDetail, DBError> {
+            self.unaccept_answer_response
+                .lock()
+                .await
+                .take()
+                .expect("unaccept answer response should not be None")
+        }
+
+        async fn get_similar_questions(
+            &self,
+            _: String,
+            _: Option<String>,
+        ) -> Result<Vec<SimilarQuestion>, DBError> {
+            self.get_similar_questions_response
+                .lock()
+                .await
+                .take()
+                .expect("get similar questions response should not be None")
+        }
+
+        async fn vote_to_close(
+            &self,
+            _: String,
+            _: String,
+            _: CloseVote,
+            _: bool,
+        ) -> Result<QuestionStatus, DBError> {
+            self.vote_to_close_response
+                .lock()
+                .await
+                .take()
+                .expect("vote to close response should not be None")
+        }
+
+        async fn vote_to_reopen(
+            &self,
+            _: String,
+            _: String,
+            _: bool,
+        ) -> Result<QuestionStatus, DBError> {
+            self.vote_to_reopen_response
+                .lock()
+                .await
+                .take()
+                .expect("vote to reopen response should not be None")
+        }
+
+        async fn lock_question(&self, _: String) -> Result<QuestionStatus, DBError> {
+            self.lock_question_response
+                .lock()
+                .await
+                .take()
+                .expect("lock question response should not be None")
+        }
+
+        async fn unlock_question(&self, _: String) -> Result<QuestionStatus, DBError> {
+            self.unlock_question_response
+                .lock()
+                .await
+                .take()
+                .expect("unlock question response should not be None")
+        }
+
+        async fn undelete_question(&self, _: String) -> Result<QuestionDetail, DBError> {
+            self.undelete_question_response
+                .lock()
+                .await
+                .take()
+                .expect("undelete question response should not be None")
+        }
+
+        async fn purge_deleted_questions(&self, _: i32) -> Result<u64, DBError> {
+            Ok(0)
+        }
+    }
+
+    struct AnswerDaoMock {
+        create_answer_response: Mutex<Option<Result<AnswerDetail, DBError>>>,
+        get_answer_response: Mutex<Option<Result<AnswerDetail, DBError>>>,
+        get_answers_response: Mutex<Option<Result<Page<AnswerDetail>, DBError>>>,
+        delete_answer_response: Mutex<Option<Result<(), DBError>>>,
+        update_answer_response: Mutex<Option<Result<AnswerDetail, DBError>>>,
+        get_answer_revisions_response: Mutex<Option<Result<Vec<AnswerRevision>, DBError>>>,
+        get_answer_revision_response: Mutex<Vec<Result<AnswerRevision, DBError>>>,
+        undelete_answer_response: Mutex<Option<Result<AnswerDetail, DBError>>>,
+    }
+
+    impl AnswerDaoMock {
+        fn new() -> Self {
+            Self {
+                create_answer_response: Mutex::new(None),
+                get_answer_response: Mutex::new(None),
+                get_answers_response: Mutex::new(None),
+                delete_answer_response: Mutex::new(None),
+                update_answer_response: Mutex::new(None),
+                get_answer_revisions_response: Mutex::new(None),
+                get_answer_revision_response: Mutex::new(Vec::new()),
+                undelete_answer_response: Mutex::new(None),
+            }
+        }
+
+        pub fn mock_create_answer(&mut self, response: Result<AnswerDetail, DBError>) {
+            self.create_answer_response = Mutex::new(Some(response));
+        }
+
+        pub fn mock_get_answer(&mut self, response: Result<AnswerDetail, DBError>) {
+            self.get_answer_response = Mutex::new(Some(response));
+        }
+
+        pub fn mock_get_answers(&mut self, response: Result<Page<AnswerDetail>, DBError>) {
+            self.get_answers_response = Mutex::new(Some(response));
+        }
+
+        pub fn mock_delete_answer(&mut self, response: Result<(), DBError>) {
+            self.delete_answer_response = Mutex::new(Some(response));
+        }
+
+        pub fn mock_update_answer(&mut self, response: Result<AnswerDetail, DBError>) {
+            self.update_answer_response = Mutex::new(Some(response));
+        }
+
+        pub fn mock_get_answer_revisions(
+            &mut self,
+            response: Result<Vec<AnswerRevision>, DBError>,
+        ) {
+            self.get_answer_revisions_response = Mutex::new(Some(response));
+        }
+
+        pub fn mock_get_answer_revision(&mut self, response: Result<AnswerRevision, DBError>) {
+            self.get_answer_revision_response.get_mut().push(response);
+        }
+
+        pub fn mock_undelete_answer(&mut self, response: Result<AnswerDetail, DBError>) {
+            self.undelete_answer_response = Mutex::new(Some(response));
+        }
+    }
+
+    #[async_trait]
+    impl AnswerDao for AnswerDaoMock {
+        async fn create_answer(&self, _: Answer) -> Result<AnswerDetail, DBError> {
+            self.create_answer_response
+                .lock()
+                .await
+                .take()
+                .expect("create answer response should not be None")
+        }
+
+        async fn get_answer(&self, _: String) -> Result<AnswerDetail, DBError> {
+            self.get_answer_response
+                .lock()
+                .await
+                .take()
+                .expect("get answer response should not be None")
+        }
+
+        async fn get_answers(
+            &self,
+            _: String,
+            _: bool,
+            _: PageRequest,
+        ) -> Result<Page<AnswerDetail>, DBError> {
+            self.get_answers_response
+                .lock()
+                .await
+                .take()
+                .expect("get answers response should not be None")
+        }
+
+        async fn delete_answer(&self, _: String, _: String) -> Result<(), DBError> {
+            self.delete_answer_response
+                .lock()
+                .await
+                .take()
+                .expect("delete answer response should not be None")
+        }
+
+        async fn update_answer(&self, _: String, _: AnswerUpdate) -> Result<AnswerDetail, DBError> {
+            self.update_answer_response
+                .lock()
+                .await
+                .take()
+                .expect("update answer response should not be None")
+        }
+
+        async fn get_answer_revisions(
+            &self,
+            _: String,
+            _: bool,
+        ) -> Result<Vec<AnswerRevision>, DBError> {
+            self.get_answer_revisions_response
+                .lock()
+                .await
+                .take()
+                .expect("get answer revisions response should not be None")
+        }
+
+        async fn get_answer_revision(
+            &self,
+            _: String,
+            _: i32,
+            _: bool,
+        ) -> Result<AnswerRevision, DBError> {
+            let mut responses = self.get_answer_revision_response.lock().await;
+
+            assert!(
+                !responses.is_empty(),
+                "get answer revision responses should not be empty"
+            );
+
+            responses.remove(0)
+        }
+
+        async fn undelete_answer(&self, _: String) -> Result<AnswerDetail, DBError> {
+            self.undelete_answer_response
+                .lock()
+                .await
+                .take()
+                .expect("undelete answer response should not be None")
+        }
+
+        async fn purge_deleted_answers(&self, _: i32) -> Result<u64, DBError> {
+            Ok(0)
+        }
+    }
+
+    struct UserDaoMock {
+        create_user_response: Mutex<Option<Result<UserDetail, DBError>>>,
+        get_user_response: Mutex<Option<Result<UserDetail, DBError>>>,
+        get_credentials_response: Mutex<Option<Result<StoredCredentials, DBError>>>,
+        update_role_response: Mutex<Option<Result<UserDetail, DBError>>>,
+    }
+
+    impl UserDaoMock {
+        fn new() -> Self {
+            Self {
+                create_user_response: Mutex::new(None),
+                get_user_response: Mutex::new(None),
+                get_credentials_response: Mutex::new(None),
+                update_role_response: Mutex::new(None),
+            }
+        }
+
+        fn mock_create_user(&mut self, response: Result<UserDetail, DBError>) {
+            self.create_user_response = Mutex::new(Some(response));
+        }
+
+        fn mock_get_user(&mut self, response: Result<UserDetail, DBError>) {
+            self.get_user_response = Mutex::new(Some(response));
+        }
+
+        fn mock_get_credentials(&mut self, response: Result<StoredCredentials, DBError>) {
+            self.get_credentials_response = Mutex::new(Some(response));
+        }
+
+        fn mock_update_role(&mut self, response: Result<UserDetail, DBError>) {
+            self.update_role_response = Mutex::new(Some(response));
+        }
+    }
+
+    #[async_trait]
+    impl UserDao for UserDaoMock {
+        async fn create_user(&self, _: User, _: String) -> Result<UserDetail, DBError> {
+            self.create_user_response
+                .lock()
+                .await
+                .take()
+                .expect("create user response should not be None")
+        }
+
+        async fn get_user(&self, _: String) -> Result<UserDetail, DBError> {
+            self.get_user_response
+                .lock()
+                .await
+                .take()
+                .expect("get user response should not be None")
+        }
+
+        async fn get_credentials(&self, _: String) -> Result<StoredCredentials, DBError> {
+            self.get_credentials_response
+                .lock()
+                .await
+                .take()
+                .expect("get credentials response should not be None")
+        }
+
+        async fn update_role(&self, _: String, _: Role) -> Result<UserDetail, DBError> {
+            self.update_role_response
+                .lock()
+                .await
+                .take()
+                .expect("update role response should not be None")
+        }
+    }
+
+    struct SessionDaoMock {
+        create_session_response: Mutex<Option<Result<Session, DBError>>>,
+        delete_session_response: Mutex<Option<Result<(), DBError>>>,
+    }
+
+    impl SessionDaoMock {
+        fn new() -> Self {
+            Self {
+                create_session_response: Mutex::new(None),
+                delete_session_response: Mutex::new(None),
+            }
+        }
+
+        fn mock_create_session(&mut self, response: Result<Session, DBError>) {
+            self.create_session_response = Mutex::new(Some(response));
+        }
+
+        fn mock_delete_session(&mut self, response: Result<(), DBError>) {
+            self.delete_session_response = Mutex::new(Some(response));
+        }
+    }
+
+    #[async_trait]
+    impl SessionDao for SessionDaoMock {
+        async fn create_session(&self, _: String, _: String) -> Result<Session, DBError> {
+            self.create_session_response
+                .lock()
+                .await
+                .take()
+                .expect("create session response should not be None")
+        }
+
+        async fn get_session_user(&self, _: String) -> Result<Actor, DBError> {
+            // Sessions are resolved by the request guard, so no handler asks.
+            Ok(actor())
+        }
+
+        async fn delete_session(&self, _: String) -> Result<(), DBError> {
+            self.delete_session_response
+                .lock()
+                .await
+                .take()
+                .expect("delete session response should not be None")
+        }
+    }
+
+    struct VoteDaoMock {
+        vote_question_response: Mutex<Option<Result<VoteSummary, DBError>>>,
+        retract_question_vote_response: Mutex<Option<Result<VoteSummary, DBError>>>,
+        vote_answer_response: Mutex<Option<Result<VoteSummary, DBError>>>,
+        retract_answer_vote_response: Mutex<Option<Result<VoteSummary, DBError>>>,
+    }
+
+    impl VoteDaoMock {
+        fn new() -> Self {
+            Self {
+                vote_question_response: Mutex::new(None),
+                retract_question_vote_response: Mutex::new(None),
+                vote_answer_response: Mutex::new(None),
+                retract_answer_vote_response: Mutex::new(None),
+            }
+        }
+
+        fn mock_vote_question(&mut self, response: Result<VoteSummary, DBError>) {
+            self.vote_question_response = Mutex::new(Some(response));
+        }
+
+        fn mock_retract_question_vote(&mut self, response: Result<VoteSummary, DBError>) {
+            self.retract_question_vote_response = Mutex::new(Some(response));
+        }
+
+        fn mock_vote_answer(&mut self, response: Result<VoteSummary, DBError>) {
+            self.vote_answer_response = Mutex::new(Some(response));
+        }
+
+        fn mock_retract_answer_vote(&mut self, response: Result<VoteSummary, DBError>) {
+            self.retract_answer_vote_response = Mutex::new(Some(response));
+        }
+    }
+
+    #[async_trait]
+    impl VoteDao for VoteDaoMock {
+        async fn vote_question(
+            &self,
+            _: String,
+            _: String,
+            _: i16,
+        ) -> Result<VoteSummary, DBError> {
+            self.vote_question_response
+                .lock()
+                .await
+                .take()
+                .expect("vote question response should not be None")
+        }
+
+        async fn retract_question_vote(
+            &self,
+            _: String,
+            _: String,
+        ) -> Result<VoteSummary, DBError> {
+            self.retract_question_vote_response
+                .lock()
+                .await
+                .take()
+                .expect("retract question vote response should not be None")
+        }
+
+        async fn vote_answer(&self, _: String, _: String, _: i16) -> Result<VoteSummary, DBError> {
+            self.vote_answer_response
+                .lock()
+                .await
+                .take()
+                .expect("vote answer response should not be None")
+        }
+
+        async fn retract_answer_vote(&self, _: String, _: String) -> Result<VoteSummary, DBError> {
+            self.retract_answer_vote_response
+                .lock()
+                .await
+                .take()
+                .expect("retract answer vote response should not be None")
+        }
+    }
+
+    struct TagDaoMock {
+        get_tags_response: Mutex<Option<Result<Page<TagSummary>, DBError>>>,
+        get_tag_response: Mutex<Option<Result<TagDetail, DBError>>>,
+        update_tag_wiki_response: Mutex<Option<Result<TagDetail, DBError>>>,
+        propose_tag_synonym_response: Mutex<Option<Result<TagSynonym, DBError>>>,
+        approve_tag_synonym_response: Mutex<Option<Result<TagSynonym, DBError>>>,
+    }
+
+    impl TagDaoMock {
+        fn new() -> Self {
+            Self {
+                get_tags_response: Mutex::new(None),
+                get_tag_response: Mutex::new(None),
+                update_tag_wiki_response: Mutex::new(None),
+                propose_tag_synonym_response: Mutex::new(None),
+                approve_tag_synonym_response: Mutex::new(None),
+            }
+        }
+
+        fn mock_get_tags(&mut self, response: Result<Page<TagSummary>, DBError>) {
+            self.get_tags_response = Mutex::new(Some(response));
+        }
+
+        fn mock_get_tag(&mut self, response: Result<TagDetail, DBError>) {
+            self.get_tag_response = Mutex::new(Some(response));
+        }
+
+        fn mock_update_tag_wiki(&mut self, response: Result<TagDetail, DBError>) {
+            self.update_tag_wiki_response = Mutex::new(Some(response));
+        }
+
+        fn mock_propose_tag_synonym(&mut self, response: Result<TagSynonym, DBError>) {
+            self.propose_tag_synonym_response = Mutex::new(Some(response));
+        }
+
+        fn mock_approve_tag_synonym(&mut self, response: Result<TagSynonym, DBError>) {
+            self.approve_tag_synonym_response = Mutex::new(Some(response));
+        }
+    }
+
+    #[async_trait]
+    impl TagDao for TagDaoMock {
+        async fn get_tags(&self, _: PageRequest) -> Result<Page<TagSummary>, DBError> {
+            self.get_tags_response
+                .lock()
+                .await
+                .take()
+                .expect("get tags response should not be None")
+        }
+
+        async fn get_tag(&self, _: String) -> Result<TagDetail, DBError> {
+            self.get_tag_response
+                .lock()
+                .await
+                .take()
+                .expect("get tag response should not be None")
+        }
+
+        async fn update_tag_wiki(
+            &self,
+            _: String,
+            _: TagWikiUpdate,
+            _: String,
+        ) -> Result<TagDetail, DBError> {
+            self.update_tag_wiki_response
+                .lock()
+                .await
+                .take()
+                .expect("update tag wiki response should not be None")
+        }
+
+        async fn propose_tag_synonym(
+            &self,
+            _: String,
+            _: String,
+            _: String,
+        ) -> Result<TagSynonym, DBError> {
+            self.propose_tag_synonym_response
+                .lock()
+                .await
+                .take()
+                .expect("propose tag synonym response should not be None")
+        }
+
+        async fn approve_tag_synonym(
+            &self,
+            _: String,
+            _: String,
+            _: String,
+        ) -> Result<TagSynonym, DBError> {
+            self.approve_tag_synonym_response
+                .lock()
+                .await
+                .take()
+                .expect("approve tag synonym response should not be None")
+        }
+
+        async fn get_tag_wiki_revisions(&self, _: String) -> Result<Vec<TagWikiRevision>, DBError> {
+            Ok(Vec::new())
+        }
+
+        async fn get_tag_synonyms(&self, _: String) -> Result<Vec<TagSynonym>, DBError> {
+            Ok(Vec::new())
+        }
+    }
+
+    struct CommentDaoMock {
+        create_comment_response: Mutex<Option<Result<CommentDetail, DBError>>>,
+        get_comment_response: Mutex<Option<Result<CommentDetail, DBError>>>,
+        delete_comment_response: Mutex<Option<Result<(), DBError>>>,
+    }
+
+    impl CommentDaoMock {
+        fn new() -> Self {
+            Self {
+                create_comment_response: Mutex::new(None),
+                get_comment_response: Mutex::new(None),
+                delete_comment_response: Mutex::new(None),
+            }
+        }
+
+        fn mock_create_comment(&mut self, response: Result<CommentDetail, DBError>) {
+            self.create_comment_response = Mutex::new(Some(response));
+        }
+
+        fn mock_get_comment(&mut self, response: Result<CommentDetail, DBError>) {
+            self.get_comment_response = Mutex::new(Some(response));
+        }
+
+        fn mock_delete_comment(&mut self, response: Result<(), DBError>) {
+            self.delete_comment_response = Mutex::new(Some(response));
+        }
+    }
+
+    #[async_trait]
+    impl CommentDao for CommentDaoMock {
+        async fn create_comment(
+            &self,
+            _: CommentParent,
+            _: Comment,
+        ) -> Result<CommentDetail, DBError> {
+            self.create_comment_response
+                .lock()
+                .await
+                .take()
+                .expect("create comment response should not be None")
+        }
+
+        async fn get_comment(&self, _: String) -> Result<CommentDetail, DBError> {
+            self.get_comment_response
+                .lock()
+                .await
+                .take()
+                .expect("get comment response should not be None")
+        }
+
+        async fn get_comments(
+            &self,
+            _: CommentParent,
+            _: bool,
+            _: PageRequest,
+        ) -> Result<Page<CommentDetail>, DBError> {
+            Ok(Page {
+                items: Vec::new(),
+                next_cursor: None,
+            })
+        }
+
+        async fn delete_comment(&self, _: String) -> Result<(), DBError> {
+            self.delete_comment_response
+                .lock()
+                .await
+                .take()
+                .expect("delete comment response should not be None")
+        }
+    }
+
+    fn authored_comment() -> CommentDetail {
+        CommentDetail {
+            comment_uuid: "123".to_string(),
+            question_uuid: Some("456".to_string()),
+            answer_uuid: None,
+            content: "test comment".to_string(),
+            created_at: "now".to_string(),
+            author_uuid: Some("789".to_string()),
+        }
+    }
+
+    struct SearchDaoMock {
+        search_response: Mutex<Option<Result<Page<SearchResult>, DBError>>>,
+    }
+
+    impl SearchDaoMock {
+        fn new() -> Self {
+            Self {
+                search_response: Mutex::new(None),
+            }
+        }
+
+        fn mock_search(&mut self, response: Result<Page<SearchResult>, DBError>) {
+            self.search_response = Mutex::new(Some(response));
+        }
+    }
+
+    #[async_trait]
+    impl SearchDao for SearchDaoMock {
+        async fn search(
+            &self,
+            _: SearchQuery,
+            _: PageRequest,
+        ) -> Result<Page<SearchResult>, DBError> {
+            self.search_response
+                .lock()
+                .await
+                .take()
+                .expect("search response should not be None")
+        }
+    }
+
+    struct ReputationDaoMock {
+        get_reputation_history_response: Mutex<Option<Result<Page<ReputationEvent>, DBError>>>,
+        recalculate_reputation_response: Mutex<Option<Result<u64, DBError>>>,
+    }
+
+    impl ReputationDaoMock {
+        fn new() -> Self {
+            Self {
+                get_reputation_history_response: Mutex::new(None),
+                recalculate_reputation_response: Mutex::new(None),
+            }
+        }
+
+        fn mock_get_reputation_history(
+            &mut self,
+            response: Result<Page<ReputationEvent>, DBError>,
+        ) {
+            self.get_reputation_history_response = Mutex::new(Some(response));
+        }
+
+        fn mock_recalculate_reputation(&mut self, response: Result<u64, DBError>) {
+            self.recalculate_reputation_response = Mutex::new(Some(response));
+        }
+    }
+
+    #[async_trait]
+    impl ReputationDao for ReputationDaoMock {
+        async fn get_reputation_history(
+            &self,
+            _: String,
+            _: PageRequest,
+        ) -> Result<Page<ReputationEvent>, DBError> {
+            self.get_reputation_history_response
+                .lock()
+                .await
+                .take()
+                .expect("get reputation history response should not be None")
+        }
+
+        async fn recalculate_reputation(&self) -> Result<u64, DBError> {
+            self.recalculate_reputation_response
+                .lock()
+                .await
+                .take()
+                .expect("recalculate reputation response should not be None")
+        }
+    }
+
+    struct BadgeDaoMock {
+        get_user_badges_response: Mutex<Option<Result<Vec<UserBadge>, DBError>>>,
+        get_badge_recipients_response: Mutex<Option<Result<Page<BadgeRecipient>, DBError>>>,
+    }
+
+    impl BadgeDaoMock {
+        fn new() -> Self {
+            Self {
+                get_user_badges_response: Mutex::new(None),
+                get_badge_recipients_response: Mutex::new(None),
+            }
+        }
+
+        fn mock_get_user_badges(&mut self, response: Result<Vec<UserBadge>, DBError>) {
+            self.get_user_badges_response = Mutex::new(Some(response));
+        }
+
+        fn mock_get_badge_recipients(&mut self, response: Result<Page<BadgeRecipient>, DBError>) {
+            self.get_badge_recipients_response = Mutex::new(Some(response));
+        }
+    }
+
+    #[async_trait]
+    impl BadgeDao for BadgeDaoMock {
+        async fn get_user_badges(&self, _: String) -> Result<Vec<UserBadge>, DBError> {
+            self.get_user_badges_response
+                .lock()
+                .await
+                .take()
+                .expect("get user badges response should not be None")
+        }
+
+        async fn get_badge_recipients(
+            &self,
+            _: Badge,
+            _: PageRequest,
+        ) -> Result<Page<BadgeRecipient>, DBError> {
+            self.get_badge_recipients_response
+                .lock()
+                .await
+                .take()
+                .expect("get badge recipients response should not be None")
+        }
+
+        async fn award_badges(&self) -> Result<u64, DBError> {
+            Ok(0)
+        }
+    }
+
+    struct BountyDaoMock {
+        start_bounty_response: Mutex<Option<Result<Bounty, DBError>>>,
+        get_active_bounty_response: Mutex<Option<Result<Bounty, DBError>>>,
+        award_bounty_response: Mutex<Option<Result<Bounty, DBError>>>,
+        get_featured_questions_response: Mutex<Option<Result<Page<FeaturedQuestion>, DBError>>>,
+    }
+
+    impl BountyDaoMock {
+        fn new() -> Self {
+            Self {
+                start_bounty_response: Mutex::new(None),
+                get_active_bounty_response: Mutex::new(None),
+                award_bounty_response: Mutex::new(None),
+                get_featured_questions_response: Mutex::new(None),
+            }
+        }
+
+        fn mock_start_bounty(&mut self, response: Result<Bounty, DBError>) {
+            self.start_bounty_response = Mutex::new(Some(response));
+        }
+
+        fn mock_get_active_bounty(&mut self, response: Result<Bounty, DBError>) {
+            self.get_active_bounty_response = Mutex::new(Some(response));
+        }
+
+        fn mock_award_bounty(&mut self, response: Result<Bounty, DBError>) {
+            self.award_bounty_response = Mutex::new(Some(response));
+        }
+
+        fn mock_get_featured_questions(
+            &mut self,
+            response: Result<Page<FeaturedQuestion>, DBError>,
+        ) {
+            self.get_featured_questions_response = Mutex::new(Some(response));
+        }
+    }
+
+    #[async_trait]
+    impl BountyDao for BountyDaoMock {
+        async fn start_bounty(&self, _: String, _: String, _: i32) -> Result<Bounty, DBError> {
+            self.start_bounty_response
+                .lock()
+                .await
+                .take()
+                .expect("start bounty response should not be None")
+        }
+
+        async fn get_active_bounty(&self, _: String) -> Result<Bounty, DBError> {
+            self.get_active_bounty_response
+                .lock()
+                .await
+                .take()
+                .expect("get active bounty response should not be None")
+        }
+
+        async fn award_bounty(&self, _: String, _: String) -> Result<Bounty, DBError> {
+            self.award_bounty_response
+                .lock()
+                .await
+                .take()
+                .expect("award bounty response should not be None")
+        }
+
+        async fn get_featured_questions(
+            &self,
+            _: PageRequest,
+        ) -> Result<Page<FeaturedQuestion>, DBError> {
+            self.get_featured_questions_response
+                .lock()
+                .await
+                .take()
+                .expect("get featured questions response should not be None")
+        }
+
+        async fn expire_bounties(&self) -> Result<u64, DBError> {
+            Ok(0)
+        }
+    }
+
+    fn bounty() -> Bounty {
+        Bounty {
+            bounty_uuid: "321".to_string(),
+            question_uuid: "456".to_string(),
+            offered_by: Some("789".to_string()),
+            amount: 50,
+            state: BountyState::Active,
+            started_at: "now".to_string(),
+            expires_at: "later".to_string(),
+            awarded_answer_uuid: None,
+            awarded_at: None,
+        }
+    }
+
+    struct FollowDaoMock {
+        follow_question_response: Mutex<Option<Result<FollowedQuestion, DBError>>>,
+        unfollow_question_response: Mutex<Option<Result<(), DBError>>>,
+        get_followed_questions_response: Mutex<Option<Result<Page<FollowedQuestion>, DBError>>>,
+    }
+
+    impl FollowDaoMock {
+        fn new() -> Self {
+            Self {
+                follow_question_response: Mutex::new(None),
+                unfollow_question_response: Mutex::new(None),
+                get_followed_questions_response: Mutex::new(None),
+            }
+        }
+
+        fn mock_follow_question(&mut self, response: Result<FollowedQuestion, DBError>) {
+            self.follow_question_response = Mutex::new(Some(response));
+        }
+
+        fn mock_unfollow_question(&mut self, response: Result<(), DBError>) {
+            self.unfollow_question_response = Mutex::new(Some(response));
+        }
+
+        fn mock_get_followed_questions(
+            &mut self,
+            response: Result<Page<FollowedQuestion>, DBError>,
+        ) {
+            self.get_followed_questions_response = Mutex::new(Some(response));
+        }
+    }
+
+    #[async_trait]
+    impl FollowDao for FollowDaoMock {
+        async fn follow_question(&self, _: String, _: String) -> Result<FollowedQuestion, DBError> {
+            self.follow_question_response
+                .lock()
+                .await
+                .take()
+                .expect("follow question response should not be None")
+        }
+
+        async fn unfollow_question(&self, _: String, _: String) -> Result<(), DBError> {
+            self.unfollow_question_response
+                .lock()
+                .await
+                .take()
+                .expect("unfollow question response should not be None")
+        }
+
+        async fn get_followed_questions(
+            &self,
+            _: String,
+            _: PageRequest,
+        ) -> Result<Page<FollowedQuestion>, DBError> {
+            self.get_followed_questions_response
+                .lock()
+                .await
+                .take()
+                .expect("get followed questions response should not be None")
+        }
+    }
+
+    fn followed_question() -> FollowedQuestion {
+        FollowedQuestion {
+            question_uuid: "456".to_string(),
+            title: "test title".to_string(),
+            followed_at: "now".to_string(),
+        }
+    }
+
+    /// Records the events it is told about instead of storing notifications,
+    /// so tests can check what a handler produced.
+    struct NotificationDaoMock {
+        events: Arc<Mutex<Vec<NotificationEvent>>>,
+        notify_fails: bool,
+        get_notifications_response: Mutex<Option<Result<NotificationInbox, DBError>>>,
+        mark_read_response: Mutex<Option<Result<UnreadCount, DBError>>>,
+        get_preferences_response: Mutex<Option<Result<Vec<NotificationPreference>, DBError>>>,
+    }
+
+    impl NotificationDaoMock {
+        fn new() -> Self {
+            Self {
+                events: Arc::new(Mutex::new(vec![])),
+                notify_fails: false,
+                get_notifications_response: Mutex::new(None),
+                mark_read_response: Mutex::new(None),
+                get_preferences_response: Mutex::new(None),
+            }
+        }
+
+        fn mock_get_notifications(&mut self, response: Result<NotificationInbox, DBError>) {
+            self.get_notifications_response = Mutex::new(Some(response));
+        }
+
+        fn mock_mark_read(&mut self, response: Result<UnreadCount, DBError>) {
+            self.mark_read_response = Mutex::new(Some(response));
+        }
+
+        fn mock_get_preferences(&mut self, response: Result<Vec<NotificationPreference>, DBError>) {
+            self.get_preferences_response = Mutex::new(Some(response));
+        }
+    }
+
+    #[async_trait]
+    impl NotificationDao for NotificationDaoMock {
+        async fn notify(&self, event: NotificationEvent) -> Result<u64, DBError> {
+            if self.notify_fails {
+                return Err(DBError::Other(Box::new(std::io::Error::other("oh no!"))));
+            }
+
+            self.events.lock().await.push(event);
+
+            Ok(1)
+        }
+
+        async fn get_notifications(
+            &self,
+            _: String,
+            _: bool,
+            _: PageRequest,
+        ) -> Result<NotificationInbox, DBError> {
+            self.get_notifications_response
+                .lock()
+                .await
+                .take()
+                .expect("get notifications response should not be None")
+        }
+
+        async fn mark_read(&self, _: String, _: String) -> Result<UnreadCount, DBError> {
+            self.mark_read_response
+                .lock()
+                .await
+                .take()
+                .expect("mark read response should not be None")
+        }
+
+        async fn mark_all_read(&self, _: String) -> Result<UnreadCount, DBError> {
+            Ok(UnreadCount { unread_count: 0 })
+        }
+
+        async fn get_preferences(&self, _: String) -> Result<Vec<NotificationPreference>, DBError> {
+            self.get_preferences_response
+                .lock()
+                .await
+                .take()
+                .expect("get preferences response should not be None")
+        }
+
+        async fn update_preferences(
+            &self,
+            _: String,
+            preferences: Vec<NotificationPreference>,
+        ) -> Result<Vec<NotificationPreference>, DBError> {
+            Ok(preferences)
+        }
+    }
+
+    fn events() -> QuestionEvents {
+        QuestionEvents::new()
+    }
+
+    fn notifications() -> Box<dyn NotificationDao + Send + Sync> {
+        Box::new(NotificationDaoMock::new())
+    }
+
+    fn notification() -> Notification {
+        Notification {
+            notification_uuid: "321".to_string(),
+            kind: NotificationKind::AnswerPosted,
+            actor_uuid: Some("789".to_string()),
+            question_uuid: "456".to_string(),
+            answer_uuid: Some("123".to_string()),
+            comment_uuid: None,
+            created_at: "now".to_string(),
+            read_at: None,
+        }
+    }
+
+    fn search_result() -> SearchResult {
+        SearchResult {
+            question_uuid: "123".to_string(),
+            answer_uuid: None,
+            title: "test title".to_string(),
+            snippet: "test <mark>description</mark>".to_string(),
+            created_at: "now".to_string(),
+            author_uuid: Some("789".to_string()),
+            score: 0,
+        }
+    }
+
+    fn tag_detail() -> TagDetail {
+        TagDetail {
+            name: "postgresql".to_string(),
+            question_count: 1,
+            created_at: "now".to_string(),
+            excerpt: "".to_string(),
+            body: "".to_string(),
+            synonyms: vec![],
+        }
+    }
+
+    fn tag_synonym(approved: bool) -> TagSynonym {
+        TagSynonym {
+            synonym: "postgres".to_string(),
+            tag: "postgresql".to_string(),
+            approved,
+            proposed_by: Some("789".to_string()),
+            approved_by: None,
+            created_at: "now".to_string(),
+        }
+    }
+
+    fn actor() -> Actor {
+        Actor {
+            user_uuid: "789".to_string(),
+            role: Role::User,
+            reputation: 10_000,
+        }
+    }
+
+    fn newcomer() -> Actor {
+        Actor {
+            reputation: 1,
+            ..actor()
+        }
+    }
+
+    fn authored_question() -> QuestionDetail {
+        QuestionDetail {
+            question_uuid: "123".to_string(),
+            title: "test title".to_string(),
+            description: "test description".to_string(),
+            created_at: "now".to_string(),
+            author_uuid: Some("789".to_string()),
+            score: 0,
+            accepted_answer_uuid: None,
+            tags: vec![],
+            comment_count: 0,
+            state: QuestionState::Open,
+            close_reason: None,
+            duplicate_of: None,
+            deleted_at: None,
+        }
+    }
+
+    fn authored_answer() -> AnswerDetail {
+        AnswerDetail {
+            answer_uuid: "123".to_string(),
+            question_uuid: "456".to_string(),
+            content: "test content".to_string(),
+            created_at: "now".to_string(),
+            author_uuid: Some("789".to_string()),
+            score: 0,
+            is_accepted: false,
+            comment_count: 0,
+            deleted_at: None,
+        }
+    }
+
+    fn user_detail() -> UserDetail {
+        UserDetail {
+            user_uuid: "789".to_string(),
+            username: "test_user".to_string(),
+            role: Role::User,
+            created_at: "now".to_string(),
+            question_count: 0,
+            answer_count: 0,
+            reputation: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn create_question_should_return_error() {
+        let question = Question {
+            title: "test title".to_string(),
+            description: "test description".to_string(),
+            author_uuid: None,
+            tags: vec![],
+        };
+        let mut mock_dao = QuestionDaoMock::new();
+
+        mock_dao.mock_create_question(Err(DBError::Other(Box::new(std::io::Error::other(
+            "oh no!",
+        )))));
+
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
+        let result = create_question(question, false, &dao).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::InternalError("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn create_question_should_return_bad_request_error_for_invalid_author() {
+        let question = Question {
+            title: "test title".to_string(),
+            description: "test description".to_string(),
+            author_uuid: Some("malformed".to_string()),
+            tags: vec![],
+        };
+        let mut mock_dao = QuestionDaoMock::new();
+
+        mock_dao.mock_create_question(Err(DBError::InvalidUUID("test".to_string())));
+
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
+        let result = create_question(question, false, &dao).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::BadRequest("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn create_question_should_return_question() {
+        let question = Question {
+            title: "test title".to_string(),
+            description: "test description".to_string(),
+            author_uuid: None,
+            tags: vec![],
+        };
+        let question_detail = QuestionDetail {
+            question_uuid: "123".to_string(),
+            title: question.title.clone(),
+            description: question.description.clone(),
+            created_at: "now".to_string(),
+            author_uuid: None,
+            score: 0,
+            accepted_answer_uuid: None,
+            tags: vec![],
+            comment_count: 0,
+            state: QuestionState::Open,
+            close_reason: None,
+            duplicate_of: None,
+            deleted_at: None,
+        };
+        let mut mock_dao = QuestionDaoMock::new();
+
+        mock_dao.mock_create_question(Ok(question_detail.clone()));
+
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
+        let result = create_question(question, false, &dao).await;
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), question_detail);
+    }
+
+    #[tokio::test]
+    async fn get_question_should_return_not_found_error() {
+        let question_id = QuestionId {
+            question_uuid: "123".to_string(),
+        };
+        let mut mock_dao = QuestionDaoMock::new();
+
+        mock_dao.mock_get_question(Err(DBError::NotFound("test".to_string())));
+
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
+        let result = get_question(question_id, &dao).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::NotFound("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn get_question_should_return_bad_request_error() {
+        let question_id = QuestionId {
+            question_uuid: "malformed".to_string(),
+        };
+        let mut mock_dao = QuestionDaoMock::new();
+
+        mock_dao.mock_get_question(Err(DBError::InvalidUUID("test".to_string())));
+
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
+        let result = get_question(question_id, &dao).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::BadRequest("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn get_question_should_return_question() {
+        let question_detail = QuestionDetail {
+            question_uuid: "123".to_string(),
+            title: "test title".to_string(),
+            description: "test description".to_string(),
+            created_at: "now".to_string(),
+            author_uuid: None,
+            score: 0,
+            accepted_answer_uuid: None,
+            tags: vec![],
+            comment_count: 0,
+            state: QuestionState::Open,
+            close_reason: None,
+            duplicate_of: None,
+            deleted_at: None,
+        };
+        let question_id = QuestionId {
+            question_uuid: "123".to_string(),
+        };
+        let mut mock_dao = QuestionDaoMock::new();
+
+        mock_dao.mock_get_question(Ok(question_detail.clone()));
+
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
+        let result = get_question(question_id, &dao).await;
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), question_detail);
+    }
+
+    #[tokio::test]
+    async fn get_questions_should_return_error() {
+        let mut mock_dao = QuestionDaoMock::new();
+
+        mock_dao.mock_get_questions(Err(DBError::InvalidUUID("test".to_string())));
+
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
+        let result =
+            get_questions(QuestionQuery::default(), PageRequest::default(), None, &dao).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::InternalError("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn get_questions_should_reject_non_positive_limit() {
+        let page = PageRequest {
+            cursor: None,
+            limit: Some(0),
+        };
+        let mock_dao = QuestionDaoMock::new();
+
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
+        let result = get_questions(QuestionQuery::default(), page, None, &dao).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::BadRequest("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn get_questions_should_return_bad_request_error_for_invalid_cursor() {
+        let page = PageRequest {
+            cursor: Some("invalid".to_string()),
+            limit: None,
+        };
+        let mut mock_dao = QuestionDaoMock::new();
+
+        mock_dao.mock_get_questions(Err(DBError::InvalidCursor("test".to_string())));
+
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
+        let result = get_questions(QuestionQuery::default(), page, None, &dao).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::BadRequest("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn get_questions_should_reject_empty_date_range() {
+        let now = sqlx::types::time::OffsetDateTime::now_utc();
+        let now = sqlx::types::time::PrimitiveDateTime::new(now.date(), now.time());
+        let query = QuestionQuery {
+            created_after: Some(now),
+            created_before: Some(now),
+            ..QuestionQuery::default()
+        };
+        let mock_dao = QuestionDaoMock::new();
+
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
+        let result = get_questions(query, PageRequest::default(), None, &dao).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::BadRequest("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn get_questions_should_return_questions() {
+        let question_detail = QuestionDetail {
+            question_uuid: "123".to_string(),
+            title: "test title".to_string(),
+            description: "test description".to_string(),
+            created_at: "now".to_string(),
+            author_uuid: None,
+            score: 0,
+            accepted_answer_uuid: None,
+            tags: vec![],
+            comment_count: 0,
+            state: QuestionState::Open,
+            close_reason: None,
+            duplicate_of: None,
+            deleted_at: None,
+        };
+        let mut mock_dao = QuestionDaoMock::new();
+
+        mock_dao.mock_get_questions(Ok(Page {
+            items: vec![question_detail.clone()],
+            next_cursor: None,
+        }));
+
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
+        let result =
+            get_questions(QuestionQuery::default(), PageRequest::default(), None, &dao).await;
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().items, vec![question_detail]);
+    }
+
+    #[tokio::test]
+    async fn update_question_should_reject_empty_update() {
+        let question_id = QuestionId {
+            question_uuid: "123".to_string(),
+        };
+        let update = QuestionUpdate {
+            title: None,
+            description: None,
+        };
+        let mock_dao = QuestionDaoMock::new();
+
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
+        let result = update_question(
+            question_id,
+            update,
+            &actor(),
+            &PrivilegeTable::default(),
+            &dao,
+            &notifications(),
+            &events(),
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::BadRequest("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn update_question_should_return_not_found_error() {
+        let question_id = QuestionId {
+            question_uuid: "123".to_string(),
+        };
+        let update = QuestionUpdate {
+            title: Some("new title".to_string()),
+            description: None,
+        };
+        let mut mock_dao = QuestionDaoMock::new();
+
+        mock_dao.mock_get_question(Err(DBError::NotFound("test".to_string())));
+
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
+        let result = update_question(
+            question_id,
+            update,
+            &actor(),
+            &PrivilegeTable::default(),
+            &dao,
+            &notifications(),
+            &events(),
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::NotFound("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn update_question_should_return_question() {
+        let question_id = QuestionId {
+            question_uuid: "123".to_string(),
+        };
+        let update = QuestionUpdate {
+            title: Some("new title".to_string()),
+            description: None,
+        };
+        let question_detail = QuestionDetail {
+            question_uuid: "123".to_string(),
+            title: "new title".to_string(),
+            description: "test description".to_string(),
+            created_at: "now".to_string(),
+            author_uuid: None,
+            score: 0,
+            accepted_answer_uuid: None,
+            tags: vec![],
+            comment_count: 0,
+            state: QuestionState::Open,
+            close_reason: None,
+            duplicate_of: None,
+            deleted_at: None,
+        };
+        let mut mock_dao = QuestionDaoMock::new();
+
+        mock_dao.mock_get_question(Ok(authored_question()));
+        mock_dao.mock_update_question(Ok(question_detail.clone()));
+
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
+        let result = update_question(
+            question_id,
+            update,
+            &actor(),
+            &PrivilegeTable::default(),
+            &dao,
+            &notifications(),
+            &events(),
+        )
+        .await;
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), question_detail);
+    }
+
+    #[tokio::test]
+    async fn get_question_revisions_should_return_revisions() {
+        let question_id = QuestionId {
+            question_uuid: "123".to_string(),
+        };
+        let question_revision = QuestionRevision {
+            question_uuid: "123".to_string(),
+            revision: 1,
+            title: "test title".to_string(),
+            description: "test description".to_string(),
+            created_at: "now".to_string(),
+        };
+        let mut mock_dao = QuestionDaoMock::new();
+
+        mock_dao.mock_get_question_revisions(Ok(vec![question_revision.clone()]));
+
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
+        let result = get_question_revisions(question_id, false, None, &dao).await;
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), vec![question_revision]);
+    }
+
+    #[tokio::test]
+    async fn diff_question_revisions_should_return_changed_fields() {
+        let question_id = QuestionId {
+            question_uuid: "123".to_string(),
+        };
+        let mut mock_dao = QuestionDaoMock::new();
+
+        mock_dao.mock_get_question_revision(Ok(QuestionRevision {
+            question_uuid: "123".to_string(),
+            revision: 1,
+            title: "old title".to_string(),
+            description: "test description".to_string(),
+            created_at: "then".to_string(),
+        }));
+        mock_dao.mock_get_question_revision(Ok(QuestionRevision {
+            question_uuid: "123".to_string(),
+            revision: 2,
+            title: "new title".to_string(),
+            description: "test description".to_string(),
+            created_at: "now".to_string(),
+        }));
+
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
+        let result = diff_question_revisions(question_id, 1, 2, false, None, &dao).await;
+
+        assert!(result.is_ok());
+        assert_eq!(
+            result.unwrap(),
+            RevisionDiff {
+                from_revision: 1,
+                to_revision: 2,
+                changes: vec![FieldChange {
+                    field: "title".to_string(),
+                    from: "old title".to_string(),
+                    to: "new title".to_string(),
+                }],
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn rollback_question_should_return_not_found_error() {
+        let question_id = QuestionId {
+            question_uuid: "123".to_string(),
+        };
+        let mut mock_dao = QuestionDaoMock::new();
+
+        mock_dao.mock_get_question(Ok(authored_question()));
+        mock_dao.mock_get_question_revision(Err(DBError::NotFound("test".to_string())));
+
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
+        let notification_dao: Box<dyn NotificationDao + Send + Sync> =
+            Box::new(NotificationDaoMock::new());
+        let result = rollback_question(
+            question_id,
+            7,
+            &actor(),
+            &PrivilegeTable::default(),
+            &dao,
+            &notification_dao,
+            &events(),
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::NotFound("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn rollback_question_should_return_question() {
+        let question_id = QuestionId {
+            question_uuid: "123".to_string(),
+        };
+        let question_detail = QuestionDetail {
+            question_uuid: "123".to_string(),
+            title: "old title".to_string(),
+            description: "test description".to_string(),
+            created_at: "now".to_string(),
+            author_uuid: None,
+            score: 0,
+            accepted_answer_uuid: None,
+            tags: vec![],
+            comment_count: 0,
+            state: QuestionState::Open,
+            close_reason: None,
+            duplicate_of: None,
+            deleted_at: None,
+        };
+        let mut mock_dao = QuestionDaoMock::new();
+
+        mock_dao.mock_get_question(Ok(authored_question()));
+        mock_dao.mock_get_question_revision(Ok(QuestionRevision {
+            question_uuid: "123".to_string(),
+            revision: 1,
+            title: "old title".to_string(),
+            description: "test description".to_string(),
+            created_at: "then".to_string(),
+        }));
+        mock_dao.mock_update_question(Ok(question_detail.clone()));
+
+        let notification_dao = NotificationDaoMock::new();
+        let notified = notification_dao.events.clone();
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
+        let notification_dao: Box<dyn NotificationDao + Send + Sync> = Box::new(notification_dao);
+        let result = rollback_question(
+            question_id,
+            1,
+            &actor(),
+            &PrivilegeTable::default(),
+            &dao,
+            &notification_dao,
+            &events(),
+        )
+        .await;
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), question_detail);
+        assert_eq!(
+            *notified.lock().await,
+            vec![NotificationEvent {
+                kind: NotificationKind::PostEdited,
+                actor_uuid: Some("789".to_string()),
+                question_uuid: Some("123".to_string()),
+                answer_uuid: None,
+                comment_uuid: None,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_question_should_return_error() {
+        let question_id = QuestionId {
+            question_uuid: "123".to_string(),
+        };
+        let mut mock_dao = QuestionDaoMock::new();
+
+        mock_dao.mock_get_question(Ok(authored_question()));
+        mock_dao.mock_delete_question(Err(DBError::InvalidUUID("test".to_string())));
+
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
+        let result = delete_question(question_id, &actor(), &dao, &events()).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::InternalError("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_question_should_succeed() {
+        let question_id = QuestionId {
+            question_uuid: "123".to_string(),
+        };
+        let mut mock_dao = QuestionDaoMock::new();
+
+        mock_dao.mock_get_question(Ok(authored_question()));
+        mock_dao.mock_delete_question(Ok(()));
+
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
+        let result = delete_question(question_id, &actor(), &dao, &events()).await;
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), ());
+    }
+
+    #[tokio::test]
+    async fn delete_question_should_return_forbidden_error_for_other_users() {
+        let question_id = QuestionId {
+            question_uuid: "123".to_string(),
+        };
+        let mut mock_dao = QuestionDaoMock::new();
+
+        mock_dao.mock_get_question(Ok(QuestionDetail {
+            author_uuid: Some("456".to_string()),
+            ..authored_question()
+        }));
+
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
+        let result = delete_question(question_id, &actor(), &dao, &events()).await;
+
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::Forbidden("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_question_should_succeed_for_moderators() {
+        let question_id = QuestionId {
+            question_uuid: "123".to_string(),
+        };
+        let moderator = Actor {
+            role: Role::Moderator,
+            ..actor()
+        };
+        let mut mock_dao = QuestionDaoMock::new();
+
+        mock_dao.mock_get_question(Ok(QuestionDetail {
+            author_uuid: Some("456".to_string()),
+            ..authored_question()
+        }));
+        mock_dao.mock_delete_question(Ok(()));
+
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
+        let result = delete_question(question_id, &moderator, &dao, &events()).await;
+
+        assert!(result.is_ok());
+    }
+
+    fn question_status(state: QuestionState) -> QuestionStatus {
+        QuestionStatus {
+            question_uuid: "123".to_string(),
+            state,
+            close_reason: None,
+            duplicate_of: None,
+            close_votes: 0,
+            reopen_votes: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn vote_to_close_should_require_duplicate_of_for_duplicates_only() {
+        let question_id = || QuestionId {
+            question_uuid: "123".to_string(),
+        };
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(QuestionDaoMock::new());
+
+        for (reason, duplicate_of) in [
+            (CloseReason::Duplicate, None),
+            (CloseReason::Duplicate, Some("123")),
+            (CloseReason::OffTopic, Some("456")),
+        ] {
+            let vote = CloseVote {
+                reason,
+                duplicate_of: duplicate_of.map(str::to_string),
+            };
+            let result = vote_to_close(
+                question_id(),
+                vote,
+                &actor(),
+                &PrivilegeTable::default(),
+                &dao,
+            )
+            .await;
+
+            assert_eq!(
+                std::mem::discriminant(&result.unwrap_err()),
+                std::mem::discriminant(&HandlerError::BadRequest("".to_string()))
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn vote_to_close_should_return_status() {
+        let question_id = QuestionId {
+            question_uuid: "123".to_string(),
+        };
+        let vote = CloseVote {
+            reason: CloseReason::Duplicate,
+            duplicate_of: Some("456".to_string()),
+        };
+        let status = QuestionStatus {
+            close_votes: 1,
+            ..question_status(QuestionState::Open)
+        };
+        let mut mock_dao = QuestionDaoMock::new();
+
+        mock_dao.mock_vote_to_close(Ok(status.clone()));
+
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
+        let result = vote_to_close(
+            question_id,
+            vote,
+            &actor(),
+            &PrivilegeTable::default(),
+            &dao,
+        )
+        .await;
+
+        assert_eq!(result, Ok(status));
+    }
+
+    #[tokio::test]
+    async fn vote_to_close_should_return_forbidden_error_without_privilege() {
+        let question_id = QuestionId {
+            question_uuid: "123".to_string(),
+        };
+        let vote = CloseVote {
+            reason: CloseReason::OffTopic,
+            duplicate_of: None,
+        };
+
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(QuestionDaoMock::new());
+        let result = vote_to_close(
+            question_id,
+            vote,
+            &newcomer(),
+            &PrivilegeTable::default(),
+            &dao,
+        )
+        .await;
+
+        assert_eq!(
+            result.unwrap_err(),
+            HandlerError::Forbidden(
+                "Missing the close_vote privilege, which requires 3000 reputation (you have 1)"
+                    .to_string()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn vote_to_reopen_should_return_conflict_error() {
+        let question_id = QuestionId {
+            question_uuid: "123".to_string(),
+        };
+        let mut mock_dao = QuestionDaoMock::new();
+
+        mock_dao.mock_vote_to_reopen(Err(DBError::Conflict("test".to_string())));
+
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
+        let result = vote_to_reopen(question_id, &actor(), &PrivilegeTable::default(), &dao).await;
+
+        assert_eq!(result, Err(HandlerError::Conflict("test".to_string())));
+    }
+
+    #[tokio::test]
+    async fn lock_question_should_return_forbidden_error_for_users() {
+        let question_id = QuestionId {
+            question_uuid: "123".to_string(),
+        };
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(QuestionDaoMock::new());
+        let result = lock_question(question_id, &actor(), &dao).await;
+
+        assert_eq!(
+            result,
+            Err(HandlerError::Forbidden(
+                "Only a moderator can lock questions".to_string()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn lock_and_unlock_question_should_succeed_for_moderators() {
+        let question_id = || QuestionId {
+            question_uuid: "123".to_string(),
+        };
+        let moderator = Actor {
+            role: Role::Moderator,
+            ..actor()
+        };
+        let mut mock_dao = QuestionDaoMock::new();
+
+        mock_dao.mock_lock_question(Ok(question_status(QuestionState::Locked)));
+        mock_dao.mock_unlock_question(Ok(question_status(QuestionState::Open)));
+
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
+
+        assert_eq!(
+            lock_question(question_id(), &moderator, &dao).await,
+            Ok(question_status(QuestionState::Locked))
+        );
+        assert_eq!(
+            unlock_question(question_id(), &moderator, &dao).await,
+            Ok(question_status(QuestionState::Open))
+        );
+    }
+
+    #[tokio::test]
+    async fn get_questions_should_require_a_moderator_to_include_deleted() {
+        let query = || QuestionQuery {
+            include_deleted: true,
+            ..QuestionQuery::default()
+        };
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(QuestionDaoMock::new());
+
+        assert_eq!(
+            get_questions(query(), PageRequest::default(), None, &dao).await,
+            Err(HandlerError::Unauthorized(
+                "Log in as a moderator to list deleted posts".to_string()
+            ))
+        );
+        assert_eq!(
+            get_questions(query(), PageRequest::default(), Some(&actor()), &dao).await,
+            Err(HandlerError::Forbidden(
+                "Only a moderator can list deleted posts".to_string()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn undelete_question_should_return_forbidden_error_for_users() {
+        let question_id = QuestionId {
+            question_uuid: "123".to_string(),
+        };
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(QuestionDaoMock::new());
+        let result = undelete_question(question_id, &actor(), &dao).await;
+
+        assert_eq!(
+            result,
+            Err(HandlerError::Forbidden(
+                "Only a moderator can undelete posts".to_string()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn undelete_question_should_succeed_for_moderators() {
+        let question_id = QuestionId {
+            question_uuid: "123".to_string(),
+        };
+        let moderator = Actor {
+            role: Role::Moderator,
+            ..actor()
+        };
+        let mut mock_dao = QuestionDaoMock::new();
+
+        mock_dao.mock_undelete_question(Ok(authored_question()));
+
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
+        let result = undelete_question(question_id, &moderator, &dao).await;
+
+        assert_eq!(result, Ok(authored_question()));
+    }
+
+    #[tokio::test]
+    async fn undelete_answer_should_return_not_found_error() {
+        let answer_id = AnswerId {
+            answer_uuid: "123".to_string(),
+        };
+        let moderator = Actor {
+            role: Role::Moderator,
+            ..actor()
+        };
+        let mut mock_dao = AnswerDaoMock::new();
+
+        mock_dao.mock_undelete_answer(Err(DBError::NotFound("test".to_string())));
+
+        let dao: Box<dyn AnswerDao + Send + Sync> = Box::new(mock_dao);
+        let result = undelete_answer(answer_id, &moderator, &dao).await;
+
+        assert_eq!(result, Err(HandlerError::NotFound("test".to_string())));
+    }
+
+    #[tokio::test]
+    async fn create_answer_should_return_conflict_error_for_closed_questions() {
+        let answer = Answer {
+            question_uuid: "123".to_string(),
+            content: "test content".to_string(),
+            author_uuid: None,
+        };
+        let mut mock_dao = AnswerDaoMock::new();
+
+        mock_dao.mock_create_answer(Err(DBError::Conflict(
+            "Question is closed and does not accept answers: 123".to_string(),
+        )));
+
+        let dao: Box<dyn AnswerDao + Send + Sync> = Box::new(mock_dao);
+        let result = create_answer(answer, &dao, &notifications(), &events()).await;
+
+        assert_eq!(
+            result,
+            Err(HandlerError::Conflict(
+                "Question is closed and does not accept answers: 123".to_string()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn create_answer_should_return_bad_request_error() {
+        let answer = Answer {
+            question_uuid: "123".to_string(),
+            content: "test content".to_string(),
+            author_uuid: None,
+        };
+        let mut mock_dao = AnswerDaoMock::new();
+
+        mock_dao.mock_create_answer(Err(DBError::InvalidUUID("test".to_string())));
+
+        let dao: Box<dyn AnswerDao + Send + Sync> = Box::new(mock_dao);
+        let result = create_answer(answer, &dao, &notifications(), &events()).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::BadRequest("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn create_answer_should_return_internal_error() {
+        let answer = Answer {
+            question_uuid: "123".to_string(),
+            content: "test content".to_string(),
+            author_uuid: None,
+        };
+        let mut mock_dao = AnswerDaoMock::new();
+
+        mock_dao.mock_create_answer(Err(DBError::Other(Box::new(std::io::Error::other(
+            "oh no!",
+        )))));
+
+        let dao: Box<dyn AnswerDao + Send + Sync> = Box::new(mock_dao);
+        let result = create_answer(answer, &dao, &notifications(), &events()).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::InternalError("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn create_answer_should_return_answer() {
+        let answer = Answer {
+            question_uuid: "123".to_string(),
+            content: "test content".to_string(),
+            author_uuid: None,
+        };
+        let answer_detail = AnswerDetail {
+            answer_uuid: "456".to_string(),
+            question_uuid: answer.question_uuid.clone(),
+            content: answer.content.clone(),
+            created_at: "now".to_string(),
+            author_uuid: None,
+            score: 0,
+            is_accepted: false,
+            comment_count: 0,
+            deleted_at: None,
+        };
+        let mut mock_dao = AnswerDaoMock::new();
+
+        mock_dao.mock_create_answer(Ok(answer_detail.clone()));
+
+        let dao: Box<dyn AnswerDao + Send + Sync> = Box::new(mock_dao);
+        let result = create_answer(answer, &dao, &notifications(), &events()).await;
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), answer_detail);
+    }
+
+    #[tokio::test]
+    async fn get_answer_should_return_not_found_error() {
+        let answer_id = AnswerId {
+            answer_uuid: "456".to_string(),
+        };
+        let mut mock_dao = AnswerDaoMock::new();
+
+        mock_dao.mock_get_answer(Err(DBError::NotFound("test".to_string())));
+
+        let dao: Box<dyn AnswerDao + Send + Sync> = Box::new(mock_dao);
+        let result = get_answer(answer_id, &dao).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::NotFound("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn get_answer_should_return_answer() {
+        let answer_detail = AnswerDetail {
+            answer_uuid: "456".to_string(),
+            question_uuid: "123".to_string(),
+            content: "test content".to_string(),
+            created_at: "now".to_string(),
+            author_uuid: None,
+            score: 0,
+            is_accepted: false,
+            comment_count: 0,
+            deleted_at: None,
+        };
+        let answer_id = AnswerId {
+            answer_uuid: "456".to_string(),
+        };
+        let mut mock_dao = AnswerDaoMock::new();
+
+        mock_dao.mock_get_answer(Ok(answer_detail.clone()));
+
+        let dao: Box<dyn AnswerDao + Send + Sync> = Box::new(mock_dao);
+        let result = get_answer(answer_id, &dao).await;
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), answer_detail);
+    }
+
+    #[tokio::test]
+    async fn get_answers_should_return_error() {
+        let question_id = QuestionId {
+            question_uuid: "123".to_string(),
+        };
+        let mut mock_dao = AnswerDaoMock::new();
+
+        mock_dao.mock_get_answers(Err(DBError::InvalidUUID("test".to_string())));
+
+        let dao: Box<dyn AnswerDao + Send + Sync> = Box::new(mock_dao);
+        let result = get_answers(question_id, false, PageRequest::default(), None, &dao).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::InternalError("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn get_answers_should_return_answers() {
+        let answer_detail = AnswerDetail {
+            question_uuid: "123".to_string(),
+            answer_uuid: "456".to_string(),
+            content: "test content".to_string(),
+            created_at: "now".to_string(),
+            author_uuid: None,
+            score: 0,
+            is_accepted: false,
+            comment_count: 0,
+            deleted_at: None,
+        };
+        let question_id = QuestionId {
+            question_uuid: "123".to_string(),
+        };
+        let mut mock_dao = AnswerDaoMock::new();
+
+        mock_dao.mock_get_answers(Ok(Page {
+            items: vec![answer_detail.clone()],
+            next_cursor: None,
+        }));
+
+        let dao: Box<dyn AnswerDao + Send + Sync> = Box::new(mock_dao);
+        let result = get_answers(question_id, false, PageRequest::default(), None, &dao).await;
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().items, vec![answer_detail]);
+    }
+
+    #[tokio::test]
+    async fn update_answer_should_return_not_found_error() {
+        let answer_id = AnswerId {
+            answer_uuid: "456".to_string(),
+        };
+        let update = AnswerUpdate {
+            content: "new content".to_string(),
+        };
+        let mut mock_dao = AnswerDaoMock::new();
+
+        mock_dao.mock_get_answer(Err(DBError::NotFound("test".to_string())));
+
+        let dao: Box<dyn AnswerDao + Send + Sync> = Box::new(mock_dao);
+        let result = update_answer(
+            answer_id,
+            update,
+            &actor(),
+            &PrivilegeTable::default(),
+            &dao,
+            &notifications(),
+            &events(),
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::NotFound("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn update_answer_should_return_answer() {
+        let answer_id = AnswerId {
+            answer_uuid: "456".to_string(),
+        };
+        let update = AnswerUpdate {
+            content: "new content".to_string(),
+        };
+        let answer_detail = AnswerDetail {
+            answer_uuid: "456".to_string(),
+            question_uuid: "123".to_string(),
+            content: "new content".to_string(),
+            created_at: "now".to_string(),
+            author_uuid: None,
+            score: 0,
+            is_accepted: false,
+            comment_count: 0,
+            deleted_at: None,
+        };
+        let mut mock_dao = AnswerDaoMock::new();
+
+        mock_dao.mock_get_answer(Ok(authored_answer()));
+        mock_dao.mock_update_answer(Ok(answer_detail.clone()));
+
+        let dao: Box<dyn AnswerDao + Send + Sync> = Box::new(mock_dao);
+        let result = update_answer(
+            answer_id,
+            update,
+            &actor(),
+            &PrivilegeTable::default(),
+            &dao,
+            &notifications(),
+            &events(),
+        )
+        .await;
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), answer_detail);
+    }
+
+    #[tokio::test]
+    async fn get_answer_revisions_should_return_not_found_error() {
+        let answer_id = AnswerId {
+            answer_uuid: "456".to_string(),
+        };
+        let mut mock_dao = AnswerDaoMock::new();
+
+        mock_dao.mock_get_answer_revisions(Err(DBError::NotFound("test".to_string())));
+
+        let dao: Box<dyn AnswerDao + Send + Sync> = Box::new(mock_dao);
+        let result = get_answer_revisions(answer_id, false, None, &dao).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::NotFound("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn diff_answer_revisions_should_return_no_changes_for_identical_revisions() {
+        let answer_id = AnswerId {
+            answer_uuid: "456".to_string(),
+        };
+        let revision = AnswerRevision {
+            answer_uuid: "456".to_string(),
+            revision: 1,
+            content: "test content".to_string(),
+            created_at: "now".to_string(),
+        };
+        let mut mock_dao = AnswerDaoMock::new();
+
+        mock_dao.mock_get_answer_revision(Ok(revision.clone()));
+        mock_dao.mock_get_answer_revision(Ok(revision));
+
+        let dao: Box<dyn AnswerDao + Send + Sync> = Box::new(mock_dao);
+        let result = diff_answer_revisions(answer_id, 1, 1, false, None, &dao).await;
+
+        assert!(result.is_ok());
+        assert!(result.unwrap().changes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn rollback_answer_should_return_answer() {
+        let answer_id = AnswerId {
+            answer_uuid: "456".to_string(),
+        };
+        let answer_detail = AnswerDetail {
+            answer_uuid: "456".to_string(),
+            question_uuid: "123".to_string(),
+            content: "old content".to_string(),
+            created_at: "now".to_string(),
+            author_uuid: None,
+            score: 0,
+            is_accepted: false,
+            comment_count: 0,
+            deleted_at: None,
+        };
+        let mut mock_dao = AnswerDaoMock::new();
+
+        mock_dao.mock_get_answer(Ok(authored_answer()));
+        mock_dao.mock_get_answer_revision(Ok(AnswerRevision {
+            answer_uuid: "456".to_string(),
+            revision: 1,
+            content: "old content".to_string(),
+            created_at: "then".to_string(),
+        }));
+        mock_dao.mock_update_answer(Ok(answer_detail.clone()));
+
+        let notification_dao = NotificationDaoMock::new();
+        let notified = notification_dao.events.clone();
+        let dao: Box<dyn AnswerDao + Send + Sync> = Box::new(mock_dao);
+        let notification_dao: Box<dyn NotificationDao + Send + Sync> = Box::new(notification_dao);
+        let result = rollback_answer(
+            answer_id,
+            1,
+            &actor(),
+            &PrivilegeTable::default(),
+            &dao,
+            &notification_dao,
+            &events(),
+        )
+        .await;
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), answer_detail);
+        assert_eq!(
+            *notified.lock().await,
+            vec![NotificationEvent {
+                kind: NotificationKind::PostEdited,
+                actor_uuid: Some("789".to_string()),
+                question_uuid: Some("123".to_string()),
+                answer_uuid: Some("456".to_string()),
+                comment_uuid: None,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_answer_should_return_error() {
+        let answer_id = AnswerId {
+            answer_uuid: "123".to_string(),
+        };
+        let mut mock_dao = AnswerDaoMock::new();
+
+        mock_dao.mock_get_answer(Ok(authored_answer()));
+        mock_dao.mock_delete_answer(Err(DBError::InvalidUUID("test".to_string())));
+
+        let dao: Box<dyn AnswerDao + Send + Sync> = Box::new(mock_dao);
+        let result = delete_answer(answer_id, &actor(), &dao, &events()).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::InternalError("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_answer_should_succeed() {
+        let answer_id = AnswerId {
+            answer_uuid: "123".to_string(),
+        };
+        let mut mock_dao = AnswerDaoMock::new();
+
+        mock_dao.mock_get_answer(Ok(authored_answer()));
+        mock_dao.mock_delete_answer(Ok(()));
+
+        let dao: Box<dyn AnswerDao + Send + Sync> = Box::new(mock_dao);
+        let events = events();
+        let mut receiver = events.subscribe();
+        let result = delete_answer(answer_id, &actor(), &dao, &events).await;
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), ());
+
+        let activity = receiver.try_recv().unwrap();
+        assert_eq!(activity.question_uuid, "456");
+        assert_eq!(
+            activity.event,
+            QuestionEvent::AnswerDeleted {
+                answer_uuid: "123".to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn create_user_should_reject_invalid_username() {
+        let user = User {
+            username: "no spaces allowed".to_string(),
+            email: "test@example.com".to_string(),
+            password: "password123".to_string(),
+        };
+        let mock_dao = UserDaoMock::new();
+
+        let dao: Box<dyn UserDao + Send + Sync> = Box::new(mock_dao);
+        let result = create_user(user, &dao).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::BadRequest("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn create_user_should_reject_invalid_email() {
+        let user = User {
+            username: "test_user".to_string(),
+            email: "not-an-email".to_string(),
+            password: "password123".to_string(),
+        };
+        let mock_dao = UserDaoMock::new();
+
+        let dao: Box<dyn UserDao + Send + Sync> = Box::new(mock_dao);
+        let result = create_user(user, &dao).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::BadRequest("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn create_user_should_return_conflict_error() {
+        let user = User {
+            username: "test_user".to_string(),
+            email: "test@example.com".to_string(),
+            password: "password123".to_string(),
+        };
+        let mut mock_dao = UserDaoMock::new();
+
+        mock_dao.mock_create_user(Err(DBError::Conflict("test".to_string())));
+
+        let dao: Box<dyn UserDao + Send + Sync> = Box::new(mock_dao);
+        let result = create_user(user, &dao).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::Conflict("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn create_user_should_return_user() {
+        let user = User {
+            username: "test_user".to_string(),
+            email: "test@example.com".to_string(),
+            password: "password123".to_string(),
+        };
+        let mut mock_dao = UserDaoMock::new();
+
+        mock_dao.mock_create_user(Ok(user_detail()));
+
+        let dao: Box<dyn UserDao + Send + Sync> = Box::new(mock_dao);
+        let result = create_user(user, &dao).await;
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), user_detail());
+    }
+
+    #[tokio::test]
+    async fn get_user_should_return_not_found_error() {
+        let user_id = UserId {
+            user_uuid: "789".to_string(),
+        };
+        let mut mock_dao = UserDaoMock::new();
+
+        mock_dao.mock_get_user(Err(DBError::NotFound("test".to_string())));
+
+        let dao: Box<dyn UserDao + Send + Sync> = Box::new(mock_dao);
+        let result = get_user(user_id, &dao).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::NotFound("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn create_user_should_reject_short_password() {
+        let user = User {
+            username: "test_user".to_string(),
+            email: "test@example.com".to_string(),
+            password: "short".to_string(),
+        };
+
+        let dao: Box<dyn UserDao + Send + Sync> = Box::new(UserDaoMock::new());
+
+        let result = create_user(user, &dao).await;
+
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::BadRequest("".to_string()))
+        );
+    }
+
+    fn credentials(password: &str) -> Credentials {
+        Credentials {
+            username: "test_user".to_string(),
+            password: password.to_string(),
+        }
+    }
+
+    fn stored_credentials(password: &str) -> StoredCredentials {
+        StoredCredentials {
+            user_uuid: "789".to_string(),
+            password_hash: Some(auth::hash_password(password).unwrap()),
+        }
+    }
+
+    #[tokio::test]
+    async fn login_should_return_unauthorized_error_for_unknown_user() {
+        let mut user_dao = UserDaoMock::new();
+        user_dao.mock_get_credentials(Err(DBError::NotFound("test".to_string())));
+
+        let user_dao: Box<dyn UserDao + Send + Sync> = Box::new(user_dao);
+        let session_dao: Box<dyn SessionDao + Send + Sync> = Box::new(SessionDaoMock::new());
+
+        let result = login(credentials("password123"), &user_dao, &session_dao).await;
+
+        assert_eq!(
+            result.unwrap_err(),
+            HandlerError::Unauthorized("Invalid username or password".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn login_should_return_unauthorized_error_for_wrong_password() {
+        let mut user_dao = UserDaoMock::new();
+        user_dao.mock_get_credentials(Ok(stored_credentials("password123")));
+
+        let user_dao: Box<dyn UserDao + Send + Sync> = Box::new(user_dao);
+        let session_dao: Box<dyn SessionDao + Send + Sync> = Box::new(SessionDaoMock::new());
+
+        let result = login(credentials("wrong password"), &user_dao, &session_dao).await;
+
+        assert_eq!(
+            result.unwrap_err(),
+            HandlerError::Unauthorized("Invalid username or password".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn login_should_return_unauthorized_error_for_user_without_password() {
+        let mut user_dao = UserDaoMock::new();
+        user_dao.mock_get_credentials(Ok(StoredCredentials {
+            user_uuid: "789".to_string(),
+            password_hash: None,
+        }));
+
+        let user_dao: Box<dyn UserDao + Send + Sync> = Box::new(user_dao);
+        let session_dao: Box<dyn SessionDao + Send + Sync> = Box::new(SessionDaoMock::new());
+
+        let result = login(credentials("password123"), &user_dao, &session_dao).await;
+
+        assert_eq!(
+            result.unwrap_err(),
+            HandlerError::Unauthorized("Invalid username or password".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn login_should_return_session() {
+        let session = Session {
+            token: "token".to_string(),
+            user_uuid: "789".to_string(),
+            expires_at: "later".to_string(),
+        };
+
+        let mut user_dao = UserDaoMock::new();
+        user_dao.mock_get_credentials(Ok(stored_credentials("password123")));
+
+        let mut session_dao = SessionDaoMock::new();
+        session_dao.mock_create_session(Ok(session.clone()));
+
+        let user_dao: Box<dyn UserDao + Send + Sync> = Box::new(user_dao);
+        let session_dao: Box<dyn SessionDao + Send + Sync> = Box::new(session_dao);
+
+        let result = login(credentials("password123"), &user_dao, &session_dao).await;
+
+        assert_eq!(result.unwrap(), session);
+    }
+
+    #[tokio::test]
+    async fn logout_should_succeed() {
+        let mut session_dao = SessionDaoMock::new();
+        session_dao.mock_delete_session(Ok(()));
+
+        let session_dao: Box<dyn SessionDao + Send + Sync> = Box::new(session_dao);
+
+        let result = logout("token".to_string(), &session_dao).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn update_answer_should_return_forbidden_error_for_other_users() {
+        let answer_id = AnswerId {
+            answer_uuid: "123".to_string(),
+        };
+        let update = AnswerUpdate {
+            content: "new content".to_string(),
+        };
+        let mut mock_dao = AnswerDaoMock::new();
+
+        mock_dao.mock_get_answer(Ok(AnswerDetail {
+            author_uuid: None,
+            ..authored_answer()
+        }));
+
+        let dao: Box<dyn AnswerDao + Send + Sync> = Box::new(mock_dao);
+        let result = update_answer(
+            answer_id,
+            update,
+            &newcomer(),
+            &PrivilegeTable::default(),
+            &dao,
+            &notifications(),
+            &events(),
+        )
+        .await;
+
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::Forbidden("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn update_answer_should_succeed_for_other_users_with_edit_privilege() {
+        let answer_id = AnswerId {
+            answer_uuid: "123".to_string(),
+        };
+        let update = AnswerUpdate {
+            content: "new content".to_string(),
+        };
+        let answer_detail = AnswerDetail {
+            author_uuid: Some("456".to_string()),
+            ..authored_answer()
+        };
+        let mut mock_dao = AnswerDaoMock::new();
+
+        mock_dao.mock_get_answer(Ok(answer_detail.clone()));
+        mock_dao.mock_update_answer(Ok(answer_detail.clone()));
+
+        let dao: Box<dyn AnswerDao + Send + Sync> = Box::new(mock_dao);
+        let result = update_answer(
+            answer_id,
+            update,
+            &actor(),
+            &PrivilegeTable::default(),
+            &dao,
+            &notifications(),
+            &events(),
+        )
+        .await;
+
+        assert_eq!(result.unwrap(), answer_detail);
+    }
+
+    #[tokio::test]
+    async fn update_user_role_should_return_forbidden_error_for_non_admins() {
+        let user_id = UserId {
+            user_uuid: "456".to_string(),
+        };
+        let dao: Box<dyn UserDao + Send + Sync> = Box::new(UserDaoMock::new());
+
+        let result = update_user_role(
+            user_id,
+            RoleUpdate {
+                role: Role::Moderator,
+            },
+            &actor(),
+            &dao,
+        )
+        .await;
+
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::Forbidden("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn update_user_role_should_return_user() {
+        let user_id = UserId {
+            user_uuid: "456".to_string(),
+        };
+        let admin = Actor {
+            role: Role::Admin,
+            ..actor()
+        };
+        let moderator_detail = UserDetail {
+            role: Role::Moderator,
+            ..user_detail()
+        };
+        let mut mock_dao = UserDaoMock::new();
+
+        mock_dao.mock_update_role(Ok(moderator_detail.clone()));
+
+        let dao: Box<dyn UserDao + Send + Sync> = Box::new(mock_dao);
+        let result = update_user_role(
+            user_id,
+            RoleUpdate {
+                role: Role::Moderator,
+            },
+            &admin,
+            &dao,
+        )
+        .await;
+
+        assert_eq!(result.unwrap(), moderator_detail);
+    }
+
+    #[tokio::test]
+    async fn get_reputation_history_should_return_events() {
+        let user_id = UserId {
+            user_uuid: "789".to_string(),
+        };
+        let event = ReputationEvent {
+            event_uuid: "1".to_string(),
+            kind: ReputationKind::AnswerAccepted,
+            amount: 15,
+            question_uuid: Some("123".to_string()),
+            answer_uuid: Some("456".to_string()),
+            created_at: "now".to_string(),
+        };
+        let mut mock_dao = ReputationDaoMock::new();
+
+        mock_dao.mock_get_reputation_history(Ok(Page {
+            items: vec![event.clone()],
+            next_cursor: None,
+        }));
+
+        let dao: Box<dyn ReputationDao + Send + Sync> = Box::new(mock_dao);
+        let result = get_reputation_history(user_id, PageRequest::default(), &dao).await;
+
+        assert_eq!(result.unwrap().items, vec![event]);
+    }
+
+    #[tokio::test]
+    async fn get_reputation_history_should_return_not_found_error() {
+        let user_id = UserId {
+            user_uuid: "789".to_string(),
+        };
+        let mut mock_dao = ReputationDaoMock::new();
+
+        mock_dao.mock_get_reputation_history(Err(DBError::NotFound("test".to_string())));
+
+        let dao: Box<dyn ReputationDao + Send + Sync> = Box::new(mock_dao);
+        let result = get_reputation_history(user_id, PageRequest::default(), &dao).await;
+
+        assert_eq!(result, Err(HandlerError::NotFound("test".to_string())));
+    }
+
+    #[tokio::test]
+    async fn get_user_badges_should_return_badges() {
+        let user_id = UserId {
+            user_uuid: "789".to_string(),
+        };
+        let badge = UserBadge {
+            badge: Badge::FirstAnswer,
+            class: BadgeClass::Bronze,
+            awarded_at: "now".to_string(),
+        };
+        let mut mock_dao = BadgeDaoMock::new();
+
+        mock_dao.mock_get_user_badges(Ok(vec![badge.clone()]));
+
+        let dao: Box<dyn BadgeDao + Send + Sync> = Box::new(mock_dao);
+        let result = get_user_badges(user_id, &dao).await;
+
+        assert_eq!(result, Ok(vec![badge]));
+    }
+
+    #[tokio::test]
+    async fn get_badge_recipients_should_return_not_found_error_for_unknown_badges() {
+        let dao: Box<dyn BadgeDao + Send + Sync> = Box::new(BadgeDaoMock::new());
+        let result =
+            get_badge_recipients("teacher".to_string(), PageRequest::default(), &dao).await;
+
+        assert_eq!(
+            result.unwrap_err(),
+            HandlerError::NotFound("Badge not found: teacher".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn get_badge_recipients_should_return_recipients() {
+        let recipient = BadgeRecipient {
+            user_uuid: "789".to_string(),
+            username: "user".to_string(),
+            awarded_at: "now".to_string(),
+        };
+        let mut mock_dao = BadgeDaoMock::new();
+
+        mock_dao.mock_get_badge_recipients(Ok(Page {
+            items: vec![recipient.clone()],
+            next_cursor: None,
+        }));
+
+        let dao: Box<dyn BadgeDao + Send + Sync> = Box::new(mock_dao);
+        let result =
+            get_badge_recipients("necromancer".to_string(), PageRequest::default(), &dao).await;
+
+        assert_eq!(result.unwrap().items, vec![recipient]);
+    }
+
+    #[tokio::test]
+    async fn start_bounty_should_reject_out_of_range_amounts() {
+        let dao: Box<dyn BountyDao + Send + Sync> = Box::new(BountyDaoMock::new());
+
+        for amount in [MIN_BOUNTY - 1, MAX_BOUNTY + 1] {
+            let question_id = QuestionId {
+                question_uuid: "456".to_string(),
+            };
+            let result = start_bounty(
+                question_id,
+                BountyOffer { amount },
+                &actor(),
+                &PrivilegeTable::default(),
+                &dao,
+            )
+            .await;
+
+            assert_eq!(
+                result.unwrap_err(),
+                HandlerError::BadRequest("Bounty must be 50 to 500 reputation".to_string())
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn start_bounty_should_return_forbidden_error_without_privilege() {
+        let question_id = QuestionId {
+            question_uuid: "456".to_string(),
+        };
+        let dao: Box<dyn BountyDao + Send + Sync> = Box::new(BountyDaoMock::new());
+
+        let result = start_bounty(
+            question_id,
+            BountyOffer { amount: 50 },
+            &newcomer(),
+            &PrivilegeTable::default(),
+            &dao,
+        )
+        .await;
+
+        assert_eq!(
+            result.unwrap_err(),
+            HandlerError::Forbidden(
+                "Missing the offer_bounty privilege, which requires 75 reputation (you have 1)"
+                    .to_string()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn start_bounty_should_return_bounty() {
+        let question_id = QuestionId {
+            question_uuid: "456".to_string(),
+        };
+        let mut mock_dao = BountyDaoMock::new();
+
+        mock_dao.mock_start_bounty(Ok(bounty()));
+
+        let dao: Box<dyn BountyDao + Send + Sync> = Box::new(mock_dao);
+        let result = start_bounty(
+            question_id,
+            BountyOffer { amount: 50 },
+            &actor(),
+            &PrivilegeTable::default(),
+            &dao,
+        )
+        .await;
+
+        assert_eq!(result, Ok(bounty()));
+    }
+
+    #[tokio::test]
+    async fn award_bounty_should_return_forbidden_error_for_other_users() {
+        let question_id = QuestionId {
+            question_uuid: "456".to_string(),
+        };
+        let mut mock_dao = BountyDaoMock::new();
+
+        mock_dao.mock_get_active_bounty(Ok(Bounty {
+            offered_by: Some("123".to_string()),
+            ..bounty()
+        }));
+
+        let answer_dao: Box<dyn AnswerDao + Send + Sync> = Box::new(AnswerDaoMock::new());
+        let dao: Box<dyn BountyDao + Send + Sync> = Box::new(mock_dao);
+        let award = BountyAward {
+            answer_uuid: "123".to_string(),
+        };
+        let result = award_bounty(
+            question_id,
+            award,
+            &actor(),
+            &answer_dao,
+            &dao,
+            &notifications(),
+        )
+        .await;
+
+        assert_eq!(
+            result.unwrap_err(),
+            HandlerError::Forbidden(
+                "Only the user who offered the bounty can award it".to_string()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn award_bounty_should_reject_own_answer() {
+        let question_id = QuestionId {
+            question_uuid: "456".to_string(),
+        };
+        let mut mock_answer_dao = AnswerDaoMock::new();
+        let mut mock_dao = BountyDaoMock::new();
+
+        mock_answer_dao.mock_get_answer(Ok(authored_answer()));
+        mock_dao.mock_get_active_bounty(Ok(bounty()));
+
+        let answer_dao: Box<dyn AnswerDao + Send + Sync> = Box::new(mock_answer_dao);
+        let dao: Box<dyn BountyDao + Send + Sync> = Box::new(mock_dao);
+        let award = BountyAward {
+            answer_uuid: "123".to_string(),
+        };
+        let result = award_bounty(
+            question_id,
+            award,
+            &actor(),
+            &answer_dao,
+            &dao,
+            &notifications(),
+        )
+        .await;
+
+        assert_eq!(
+            result.unwrap_err(),
+            HandlerError::BadRequest("A bounty cannot be awarded to one's own answer".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn award_bounty_should_return_awarded_bounty() {
+        let question_id = QuestionId {
+            question_uuid: "456".to_string(),
+        };
+        let awarded = Bounty {
+            state: BountyState::Awarded,
+            awarded_answer_uuid: Some("123".to_string()),
+            awarded_at: Some("now".to_string()),
+            ..bounty()
+        };
+        let mut mock_answer_dao = AnswerDaoMock::new();
+        let mut mock_dao = BountyDaoMock::new();
+
+        mock_answer_dao.mock_get_answer(Ok(AnswerDetail {
+            author_uuid: Some("123".to_string()),
+            ..authored_answer()
+        }));
+        mock_dao.mock_get_active_bounty(Ok(bounty()));
+        mock_dao.mock_award_bounty(Ok(awarded.clone()));
+
+        let answer_dao: Box<dyn AnswerDao + Send + Sync> = Box::new(mock_answer_dao);
+        let dao: Box<dyn BountyDao + Send + Sync> = Box::new(mock_dao);
+        let award = BountyAward {
+            answer_uuid: "123".to_string(),
+        };
+        let result = award_bounty(
+            question_id,
+            award,
+            &actor(),
+            &answer_dao,
+            &dao,
+            &notifications(),
+        )
+        .await;
+
+        assert_eq!(result, Ok(awarded));
+    }
+
+    #[tokio::test]
+    async fn get_featured_questions_should_return_questions() {
+        let featured = FeaturedQuestion {
+            question_uuid: "456".to_string(),
+            title: "test title".to_string(),
+            score: 0,
+            bounty_amount: 50,
+            bounty_expires_at: "later".to_string(),
+        };
+        let mut mock_dao = BountyDaoMock::new();
+
+        mock_dao.mock_get_featured_questions(Ok(Page {
+            items: vec![featured.clone()],
+            next_cursor: None,
+        }));
+
+        let dao: Box<dyn BountyDao + Send + Sync> = Box::new(mock_dao);
+        let result = get_featured_questions(PageRequest::default(), &dao).await;
+
+        assert_eq!(result.unwrap().items, vec![featured]);
+    }
+
+    #[tokio::test]
+    async fn get_questions_should_require_a_session_to_list_followed_questions() {
+        let query = QuestionQuery {
+            followed: true,
+            ..QuestionQuery::default()
+        };
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(QuestionDaoMock::new());
+
+        assert_eq!(
+            get_questions(query, PageRequest::default(), None, &dao).await,
+            Err(HandlerError::Unauthorized(
+                "Log in to list followed questions".to_string()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn get_questions_should_list_followed_questions_of_the_actor() {
+        let query = QuestionQuery {
+            followed: true,
+            ..QuestionQuery::default()
+        };
+        let mut mock_dao = QuestionDaoMock::new();
+
+        mock_dao.mock_get_questions(Ok(Page {
+            items: vec![],
+            next_cursor: None,
+        }));
+
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
+        let result = get_questions(query, PageRequest::default(), Some(&newcomer()), &dao).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn follow_question_should_return_followed_question() {
+        let question_id = QuestionId {
+            question_uuid: "456".to_string(),
+        };
+        let mut mock_dao = FollowDaoMock::new();
+
+        mock_dao.mock_follow_question(Ok(followed_question()));
+
+        let dao: Box<dyn FollowDao + Send + Sync> = Box::new(mock_dao);
+        let result = follow_question(question_id, &newcomer(), &dao).await;
+
+        assert_eq!(result, Ok(followed_question()));
+    }
+
+    #[tokio::test]
+    async fn follow_question_should_return_not_found_error() {
+        let question_id = QuestionId {
+            question_uuid: "456".to_string(),
+        };
+        let mut mock_dao = FollowDaoMock::new();
+
+        mock_dao.mock_follow_question(Err(DBError::NotFound(
+            "Question not found: 456".to_string(),
+        )));
+
+        let dao: Box<dyn FollowDao + Send + Sync> = Box::new(mock_dao);
+        let result = follow_question(question_id, &actor(), &dao).await;
+
+        assert_eq!(
+            result,
+            Err(HandlerError::NotFound(
+                "Question not found: 456".to_string()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn unfollow_question_should_return_error() {
+        let question_id = QuestionId {
+            question_uuid: "456".to_string(),
+        };
+        let mut mock_dao = FollowDaoMock::new();
+
+        mock_dao.mock_unfollow_question(Err(DBError::InvalidUUID("test".to_string())));
+
+        let dao: Box<dyn FollowDao + Send + Sync> = Box::new(mock_dao);
+        let result = unfollow_question(question_id, &actor(), &dao).await;
+
+        assert_eq!(result, Err(HandlerError::BadRequest("test".to_string())));
+    }
+
+    #[tokio::test]
+    async fn get_followed_questions_should_return_questions() {
+        let mut mock_dao = FollowDaoMock::new();
+
+        mock_dao.mock_get_followed_questions(Ok(Page {
+            items: vec![followed_question()],
+            next_cursor: None,
+        }));
+
+        let dao: Box<dyn FollowDao + Send + Sync> = Box::new(mock_dao);
+        let result = get_followed_questions(PageRequest::default(), &actor(), &dao).await;
+
+        assert_eq!(result.unwrap().items, vec![followed_question()]);
+    }
+
+    #[tokio::test]
+    async fn create_answer_should_notify_about_new_answer() {
+        let answer = Answer {
+            question_uuid: "456".to_string(),
+            content: "test content".to_string(),
+            author_uuid: Some("789".to_string()),
+        };
+        let mut answer_dao = AnswerDaoMock::new();
+        let notification_dao = NotificationDaoMock::new();
+        let notified = notification_dao.events.clone();
+
+        answer_dao.mock_create_answer(Ok(authored_answer()));
+
+        let answer_dao: Box<dyn AnswerDao + Send + Sync> = Box::new(answer_dao);
+        let notification_dao: Box<dyn NotificationDao + Send + Sync> = Box::new(notification_dao);
+        let result = create_answer(answer, &answer_dao, &notification_dao, &events()).await;
+
+        assert_eq!(result, Ok(authored_answer()));
+        assert_eq!(
+            *notified.lock().await,
+            vec![NotificationEvent {
+                kind: NotificationKind::AnswerPosted,
+                actor_uuid: Some("789".to_string()),
+                question_uuid: Some("456".to_string()),
+                answer_uuid: Some("123".to_string()),
+                comment_uuid: None,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn create_answer_should_return_answer_when_notifying_fails() {
+        let answer = Answer {
+            question_uuid: "456".to_string(),
+            content: "test content".to_string(),
+            author_uuid: Some("789".to_string()),
+        };
+        let mut answer_dao = AnswerDaoMock::new();
+        let notification_dao = NotificationDaoMock {
+            notify_fails: true,
+            ..NotificationDaoMock::new()
+        };
+
+        answer_dao.mock_create_answer(Ok(authored_answer()));
+
+        let answer_dao: Box<dyn AnswerDao + Send + Sync> = Box::new(answer_dao);
+        let notification_dao: Box<dyn NotificationDao + Send + Sync> = Box::new(notification_dao);
+        let result = create_answer(answer, &answer_dao, &notification_dao, &events()).await;
+
+        assert_eq!(result, Ok(authored_answer()));
+    }
+
+    #[tokio::test]
+    async fn create_comment_should_notify_about_new_comment() {
+        let comment = Comment {
+            content: "test comment".to_string(),
+            author_uuid: Some("789".to_string()),
+        };
+        let mut question_dao = QuestionDaoMock::new();
+        let mut comment_dao = CommentDaoMock::new();
+        let notification_dao = NotificationDaoMock::new();
+        let notified = notification_dao.events.clone();
+
+        question_dao.mock_get_question(Ok(authored_question()));
+        comment_dao.mock_create_comment(Ok(authored_comment()));
+
+        let question_dao: Box<dyn QuestionDao + Send + Sync> = Box::new(question_dao);
+        let answer_dao: Box<dyn AnswerDao + Send + Sync> = Box::new(AnswerDaoMock::new());
+        let comment_dao: Box<dyn CommentDao + Send + Sync> = Box::new(comment_dao);
+        let notification_dao: Box<dyn NotificationDao + Send + Sync> = Box::new(notification_dao);
+        let result = create_comment(
+            CommentParent::Question("456".to_string()),
+            comment,
+            &actor(),
+            &PrivilegeTable::default(),
+            &question_dao,
+            &answer_dao,
+            &comment_dao,
+            &notification_dao,
+        )
+        .await;
+
+        assert_eq!(result, Ok(authored_comment()));
+        assert_eq!(
+            *notified.lock().await,
+            vec![NotificationEvent {
+                kind: NotificationKind::CommentPosted,
+                actor_uuid: Some("789".to_string()),
+                question_uuid: Some("456".to_string()),
+                answer_uuid: None,
+                comment_uuid: Some("123".to_string()),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn get_notifications_should_return_inbox() {
+        let inbox = NotificationInbox {
+            items: vec![notification()],
+            next_cursor: None,
+            unread_count: 1,
+        };
+        let mut mock_dao = NotificationDaoMock::new();
+
+        mock_dao.mock_get_notifications(Ok(inbox.clone()));
+
+        let dao: Box<dyn NotificationDao + Send + Sync> = Box::new(mock_dao);
+        let result = get_notifications(false, PageRequest::default(), &actor(), &dao).await;
+
+        assert_eq!(result, Ok(inbox));
+    }
+
+    #[tokio::test]
+    async fn get_notifications_should_return_bad_request_error_for_invalid_limit() {
+        let page = PageRequest {
+            cursor: None,
+            limit: Some(0),
+        };
+        let dao: Box<dyn NotificationDao + Send + Sync> = Box::new(NotificationDaoMock::new());
+        let result = get_notifications(false, page, &actor(), &dao).await;
+
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::BadRequest("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn mark_notification_read_should_return_not_found_error() {
+        let mut mock_dao = NotificationDaoMock::new();
+
+        mock_dao.mock_mark_read(Err(DBError::NotFound(
+            "Notification not found: 321".to_string(),
+        )));
+
+        let dao: Box<dyn NotificationDao + Send + Sync> = Box::new(mock_dao);
+        let result = mark_notification_read("321".to_string(), &actor(), &dao).await;
+
+        assert_eq!(
+            result,
+            Err(HandlerError::NotFound(
+                "Notification not found: 321".to_string()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn get_notification_preferences_should_return_preferences() {
+        let preferences = vec![NotificationPreference {
+            kind: NotificationKind::PostEdited,
+            enabled: false,
+        }];
+        let mut mock_dao = NotificationDaoMock::new();
+
+        mock_dao.mock_get_preferences(Ok(preferences.clone()));
+
+        let dao: Box<dyn NotificationDao + Send + Sync> = Box::new(mock_dao);
+        let result = get_notification_preferences(&actor(), &dao).await;
+
+        assert_eq!(result, Ok(preferences));
+    }
+
+    #[tokio::test]
+    async fn update_notification_preferences_should_reject_empty_preferences() {
+        let dao: Box<dyn NotificationDao + Send + Sync> = Box::new(NotificationDaoMock::new());
+        let result = update_notification_preferences(vec![], &actor(), &dao).await;
+
+        assert_eq!(
+            result,
+            Err(HandlerError::BadRequest(
+                "At least one preference must be provided".to_string()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn recalculate_reputation_should_return_forbidden_error_for_users() {
+        let dao: Box<dyn ReputationDao + Send + Sync> = Box::new(ReputationDaoMock::new());
+        let result = recalculate_reputation(&actor(), &dao).await;
+
+        assert_eq!(
+            result,
+            Err(HandlerError::Forbidden(
+                "Only a moderator can recalculate reputation".to_string()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn recalculate_reputation_should_succeed_for_moderators() {
+        let moderator = Actor {
+            role: Role::Moderator,
+            ..actor()
+        };
+        let mut mock_dao = ReputationDaoMock::new();
+
+        mock_dao.mock_recalculate_reputation(Ok(2));
+
+        let dao: Box<dyn ReputationDao + Send + Sync> = Box::new(mock_dao);
+        let result = recalculate_reputation(&moderator, &dao).await;
+
+        assert_eq!(result, Ok(ReputationRecalculation { corrected_users: 2 }));
+    }
+
+    #[tokio::test]
+    async fn vote_question_should_reject_invalid_value() {
+        let question_id = QuestionId {
+            question_uuid: "123".to_string(),
+        };
+        let dao: Box<dyn VoteDao + Send + Sync> = Box::new(VoteDaoMock::new());
+
+        let result = vote_question(
+            question_id,
+            Vote { value: 2 },
+            &actor(),
+            &PrivilegeTable::default(),
+            &dao,
+            &events(),
+        )
+        .await;
+
+        assert_eq!(
+            result.unwrap_err(),
+            HandlerError::BadRequest("Vote must be 1 or -1: 2".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn vote_question_should_return_forbidden_error_for_downvotes_without_privilege() {
+        let question_id = QuestionId {
+            question_uuid: "123".to_string(),
+        };
+        let dao: Box<dyn VoteDao + Send + Sync> = Box::new(VoteDaoMock::new());
+
+        let result = vote_question(
+            question_id,
+            Vote { value: -1 },
+            &newcomer(),
+            &PrivilegeTable::default(),
+            &dao,
+            &events(),
+        )
+        .await;
+
+        assert_eq!(
+            result.unwrap_err(),
+            HandlerError::Forbidden(
+                "Missing the vote_down privilege, which requires 125 reputation (you have 1)"
+                    .to_string()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn vote_question_should_return_not_found_error() {
+        let question_id = QuestionId {
+            question_uuid: "123".to_string(),
+        };
+        let mut mock_dao = VoteDaoMock::new();
+
+        mock_dao.mock_vote_question(Err(DBError::NotFound("test".to_string())));
+
+        let dao: Box<dyn VoteDao + Send + Sync> = Box::new(mock_dao);
+        let result = vote_question(
+            question_id,
+            Vote { value: 1 },
+            &actor(),
+            &PrivilegeTable::default(),
+            &dao,
+            &events(),
+        )
+        .await;
+
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::NotFound("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn vote_question_should_return_summary() {
+        let question_id = QuestionId {
+            question_uuid: "123".to_string(),
+        };
+        let summary = VoteSummary {
+            score: 3,
+            vote: Some(-1),
+        };
+        let mut mock_dao = VoteDaoMock::new();
+
+        mock_dao.mock_vote_question(Ok(summary.clone()));
+
+        let dao: Box<dyn VoteDao + Send + Sync> = Box::new(mock_dao);
+        let events = events();
+        let mut receiver = events.subscribe();
+        let result = vote_question(
+            question_id,
+            Vote { value: -1 },
+            &actor(),
+            &PrivilegeTable::default(),
+            &dao,
+            &events,
+        )
+        .await;
+
+        assert_eq!(result.unwrap(), summary);
+
+        let activity = receiver.try_recv().unwrap();
+        assert_eq!(activity.question_uuid, "123");
+        assert_eq!(activity.event, QuestionEvent::QuestionVoted { score: 3 });
+    }
+
+    #[tokio::test]
+    async fn retract_question_vote_should_return_summary() {
+        let question_id = QuestionId {
+            question_uuid: "123".to_string(),
+        };
+        let summary = VoteSummary {
+            score: 2,
+            vote: None,
+        };
+        let mut mock_dao = VoteDaoMock::new();
+
+        mock_dao.mock_retract_question_vote(Ok(summary.clone()));
+
+        let dao: Box<dyn VoteDao + Send + Sync> = Box::new(mock_dao);
+        let events = events();
+        let mut receiver = events.subscribe();
+        let result = retract_question_vote(question_id, &actor(), &dao, &events).await;
+
+        assert_eq!(result.unwrap(), summary);
+        assert_eq!(
+            receiver.try_recv().unwrap().event,
+            QuestionEvent::QuestionVoted { score: 2 }
+        );
+    }
+
+    #[tokio::test]
+    async fn vote_answer_should_reject_invalid_value() {
+        let answer_id = AnswerId {
+            answer_uuid: "123".to_string(),
+        };
+        let dao: Box<dyn VoteDao + Send + Sync> = Box::new(VoteDaoMock::new());
+        let answer_dao: Box<dyn AnswerDao + Send + Sync> = Box::new(AnswerDaoMock::new());
+
+        let result = vote_answer(
+            answer_id,
+            Vote { value: 0 },
+            &actor(),
+            &PrivilegeTable::default(),
+            &dao,
+            &answer_dao,
+            &events(),
+        )
+        .await;
+
+        assert_eq!(
+            result.unwrap_err(),
+            HandlerError::BadRequest("Vote must be 1 or -1: 0".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn vote_answer_should_return_forbidden_error_for_downvotes_without_privilege() {
+        let answer_id = AnswerId {
+            answer_uuid: "123".to_string(),
+        };
+        let dao: Box<dyn VoteDao + Send + Sync> = Box::new(VoteDaoMock::new());
+        let answer_dao: Box<dyn AnswerDao + Send + Sync> = Box::new(AnswerDaoMock::new());
+
+        let result = vote_answer(
+            answer_id,
+            Vote { value: -1 },
+            &newcomer(),
+            &PrivilegeTable::default(),
+            &dao,
+            &answer_dao,
+            &events(),
+        )
+        .await;
+
+        assert_eq!(
+            result.unwrap_err(),
+            HandlerError::Forbidden(
+                "Missing the vote_down privilege, which requires 125 reputation (you have 1)"
+                    .to_string()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn vote_answer_should_return_not_found_error() {
+        let answer_id = AnswerId {
+            answer_uuid: "123".to_string(),
+        };
+        let mut mock_dao = VoteDaoMock::new();
+
+        mock_dao.mock_vote_answer(Err(DBError::NotFound("test".to_string())));
+
+        let dao: Box<dyn VoteDao + Send + Sync> = Box::new(mock_dao);
+        let answer_dao: Box<dyn AnswerDao + Send + Sync> = Box::new(AnswerDaoMock::new());
+        let result = vote_answer(
+            answer_id,
+            Vote { value: 1 },
+            &actor(),
+            &PrivilegeTable::default(),
+            &dao,
+            &answer_dao,
+            &events(),
+        )
+        .await;
+
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::NotFound("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn vote_answer_should_return_summary() {
+        let answer_id = AnswerId {
+            answer_uuid: "123".to_string(),
+        };
+        let summary = VoteSummary {
+            score: 5,
+            vote: Some(1),
+        };
+        let mut mock_dao = VoteDaoMock::new();
+        let mut answer_dao = AnswerDaoMock::new();
+        let events = events();
+        let mut receiver = events.subscribe();
+
+        mock_dao.mock_vote_answer(Ok(summary.clone()));
+        answer_dao.mock_get_answer(Ok(authored_answer()));
+
+        let dao: Box<dyn VoteDao + Send + Sync> = Box::new(mock_dao);
+        let answer_dao: Box<dyn AnswerDao + Send + Sync> = Box::new(answer_dao);
+        let result = vote_answer(
+            answer_id,
+            Vote { value: 1 },
+            &actor(),
+            &PrivilegeTable::default(),
+            &dao,
+            &answer_dao,
+            &events,
+        )
+        .await;
+
+        assert_eq!(result.unwrap(), summary);
+
+        let activity = receiver.try_recv().unwrap();
+        assert_eq!(activity.question_uuid, "456");
+        assert_eq!(
+            activity.event,
+            QuestionEvent::AnswerVoted {
+                answer_uuid: "123".to_string(),
+                score: 5,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn retract_answer_vote_should_return_not_found_error() {
+        let answer_id = AnswerId {
+            answer_uuid: "123".to_string(),
+        };
+        let mut mock_dao = VoteDaoMock::new();
+
+        mock_dao.mock_retract_answer_vote(Err(DBError::NotFound("test".to_string())));
+
+        let dao: Box<dyn VoteDao + Send + Sync> = Box::new(mock_dao);
+        let answer_dao: Box<dyn AnswerDao + Send + Sync> = Box::new(AnswerDaoMock::new());
+        let result = retract_answer_vote(answer_id, &actor(), &dao, &answer_dao, &events()).await;
+
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::NotFound("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn retract_answer_vote_should_return_summary() {
+        let answer_id = AnswerId {
+            answer_uuid: "123".to_string(),
+        };
+        let summary = VoteSummary {
+            score: 0,
+            vote: None,
+        };
+        let mut mock_dao = VoteDaoMock::new();
+        let mut answer_dao = AnswerDaoMock::new();
+        let events = events();
+        let mut receiver = events.subscribe();
+
+        mock_dao.mock_retract_answer_vote(Ok(summary.clone()));
+        answer_dao.mock_get_answer(Ok(authored_answer()));
+
+        let dao: Box<dyn VoteDao + Send + Sync> = Box::new(mock_dao);
+        let answer_dao: Box<dyn AnswerDao + Send + Sync> = Box::new(answer_dao);
+        let result = retract_answer_vote(answer_id, &actor(), &dao, &answer_dao, &events).await;
+
+        assert_eq!(result.unwrap(), summary);
+        assert_eq!(
+            receiver.try_recv().unwrap().event,
+            QuestionEvent::AnswerVoted {
+                answer_uuid: "123".to_string(),
+                score: 0,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn accept_answer_should_return_forbidden_error_for_other_users() {
+        let answer_id = AnswerId {
+            answer_uuid: "123".to_string(),
+        };
+        let mut question_dao = QuestionDaoMock::new();
+        let mut answer_dao = AnswerDaoMock::new();
+
+        answer_dao.mock_get_answer(Ok(authored_answer()));
+        question_dao.mock_get_question(Ok(QuestionDetail {
+            author_uuid: Some("456".to_string()),
+            ..authored_question()
+        }));
+
+        let question_dao: Box<dyn QuestionDao + Send + Sync> = Box::new(question_dao);
+        let answer_dao: Box<dyn AnswerDao + Send + Sync> = Box::new(answer_dao);
+        let result = accept_answer(
+            answer_id,
+            &actor(),
+            &question_dao,
+            &answer_dao,
+            &notifications(),
+        )
+        .await;
+
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::Forbidden("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn accept_answer_should_return_question() {
+        let answer_id = AnswerId {
+            answer_uuid: "123".to_string(),
+        };
+        let accepted = QuestionDetail {
+            accepted_answer_uuid: Some("123".to_string()),
+            ..authored_question()
+        };
+        let mut question_dao = QuestionDaoMock::new();
+        let mut answer_dao = AnswerDaoMock::new();
+
+        answer_dao.mock_get_answer(Ok(authored_answer()));
+        question_dao.mock_get_question(Ok(authored_question()));
+        question_dao.mock_accept_answer(Ok(accepted.clone()));
+
+        let question_dao: Box<dyn QuestionDao + Send + Sync> = Box::new(question_dao);
+        let answer_dao: Box<dyn AnswerDao + Send + Sync> = Box::new(answer_dao);
+        let result = accept_answer(
+            answer_id,
+            &actor(),
+            &question_dao,
+            &answer_dao,
+            &notifications(),
+        )
+        .await;
+
+        assert_eq!(result.unwrap(), accepted);
+    }
+
+    #[tokio::test]
+    async fn unaccept_answer_should_return_not_found_error() {
+        let answer_id = AnswerId {
+            answer_uuid: "123".to_string(),
+        };
+        let mut answer_dao = AnswerDaoMock::new();
+
+        answer_dao.mock_get_answer(Err(DBError::NotFound("test".to_string())));
+
+        let question_dao: Box<dyn QuestionDao + Send + Sync> = Box::new(QuestionDaoMock::new());
+        let answer_dao: Box<dyn AnswerDao + Send + Sync> = Box::new(answer_dao);
+        let result = unaccept_answer(answer_id, &actor(), &question_dao, &answer_dao).await;
+
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::NotFound("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn unaccept_answer_should_return_question() {
+        let answer_id = AnswerId {
+            answer_uuid: "123".to_string(),
+        };
+        let mut question_dao = QuestionDaoMock::new();
+        let mut answer_dao = AnswerDaoMock::new();
+
+        answer_dao.mock_get_answer(Ok(authored_answer()));
+        question_dao.mock_get_question(Ok(authored_question()));
+        question_dao.mock_unaccept_answer(Ok(authored_question()));
+
+        let question_dao: Box<dyn QuestionDao + Send + Sync> = Box::new(question_dao);
+        let answer_dao: Box<dyn AnswerDao + Send + Sync> = Box::new(answer_dao);
+        let result = unaccept_answer(answer_id, &actor(), &question_dao, &answer_dao).await;
+
+        assert_eq!(result.unwrap(), authored_question());
+    }
+
+    fn similar_question(similarity: f32) -> SimilarQuestion {
+        SimilarQuestion {
+            question_uuid: "456".to_string(),
+            title: "test title".to_string(),
+            created_at: "now".to_string(),
+            score: 0,
+            answer_count: 0,
+            similarity,
         }
     }
 
     #[tokio::test]
-    async fn create_question_should_return_error() {
+    async fn create_question_should_return_possible_duplicates() {
         let question = Question {
             title: "test title".to_string(),
             description: "test description".to_string(),
+            author_uuid: Some("789".to_string()),
+            tags: vec![],
         };
         let mut mock_dao = QuestionDaoMock::new();
 
-        mock_dao.mock_create_question(Err(DBError::InvalidUUID("test".to_string())));
+        mock_dao.mock_get_similar_questions(Ok(vec![similar_question(0.9), similar_question(0.4)]));
 
         let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
-        let result = create_question(question, &dao).await;
+        let result = create_question(question, true, &dao).await;
 
-        assert!(result.is_err());
         assert_eq!(
-            std::mem::discriminant(&result.unwrap_err()),
-            std::mem::discriminant(&HandlerError::InternalError("".to_string()))
+            result.unwrap_err(),
+            HandlerError::PossibleDuplicate(vec![similar_question(0.9)])
         );
     }
 
     #[tokio::test]
-    async fn create_question_should_return_question() {
+    async fn create_question_should_create_question_without_close_duplicates() {
         let question = Question {
             title: "test title".to_string(),
             description: "test description".to_string(),
-        };
-        let question_detail = QuestionDetail {
-            question_uuid: "123".to_string(),
-            title: question.title.clone(),
-            description: question.description.clone(),
-            created_at: "now".to_string(),
+            author_uuid: Some("789".to_string()),
+            tags: vec![],
         };
         let mut mock_dao = QuestionDaoMock::new();
 
-        mock_dao.mock_create_question(Ok(question_detail.clone()));
+        mock_dao.mock_get_similar_questions(Ok(vec![similar_question(0.4)]));
+        mock_dao.mock_create_question(Ok(authored_question()));
 
         let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
-        let result = create_question(question, &dao).await;
+        let result = create_question(question, true, &dao).await;
 
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), question_detail);
+        assert_eq!(result.unwrap(), authored_question());
     }
 
     #[tokio::test]
-    async fn get_questions_should_return_error() {
-        let mut mock_dao = QuestionDaoMock::new();
+    async fn get_similar_questions_should_reject_blank_title() {
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(QuestionDaoMock::new());
+        let result = get_similar_questions(" ".to_string(), None, &dao).await;
 
-        mock_dao.mock_get_questions(Err(DBError::InvalidUUID("test".to_string())));
+        assert_eq!(
+            result.unwrap_err(),
+            HandlerError::BadRequest("Title must not be empty".to_string())
+        );
+    }
 
-        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
-        let result = get_questions(&dao).await;
+    #[test]
+    fn normalize_tags_should_lowercase_and_deduplicate() {
+        let tags = vec![
+            "Rust".to_string(),
+            " tokio ".to_string(),
+            "rust".to_string(),
+            "c++".to_string(),
+        ];
 
-        assert!(result.is_err());
         assert_eq!(
-            std::mem::discriminant(&result.unwrap_err()),
-            std::mem::discriminant(&HandlerError::InternalError("".to_string()))
+            normalize_tags(&tags),
+            Ok(vec![
+                "rust".to_string(),
+                "tokio".to_string(),
+                "c++".to_string()
+            ])
         );
     }
 
+    #[test]
+    fn normalize_tags_should_reject_invalid_tags() {
+        for tag in ["", "has space", "-leading", "emoji🦀", &"a".repeat(36)] {
+            assert_eq!(
+                std::mem::discriminant(&normalize_tags(&[tag.to_string()]).unwrap_err()),
+                std::mem::discriminant(&HandlerError::BadRequest("".to_string()))
+            );
+        }
+    }
+
     #[tokio::test]
-    async fn get_questions_should_return_questions() {
-        let question_detail = QuestionDetail {
-            question_uuid: "123".to_string(),
+    async fn create_question_should_reject_too_many_tags() {
+        let question = Question {
             title: "test title".to_string(),
             description: "test description".to_string(),
-            created_at: "now".to_string(),
+            author_uuid: None,
+            tags: (1..=6).map(|i| format!("tag{i}")).collect(),
         };
-        let mut mock_dao = QuestionDaoMock::new();
 
-        mock_dao.mock_get_questions(Ok(vec![question_detail.clone()]));
+        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(QuestionDaoMock::new());
+        let result = create_question(question, false, &dao).await;
 
-        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
-        let result = get_questions(&dao).await;
+        assert_eq!(
+            result.unwrap_err(),
+            HandlerError::BadRequest("A question can have at most 5 tags".to_string())
+        );
+    }
 
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), vec![question_detail]);
+    #[tokio::test]
+    async fn get_tags_should_return_bad_request_error_for_invalid_cursor() {
+        let mut mock_dao = TagDaoMock::new();
+
+        mock_dao.mock_get_tags(Err(DBError::InvalidCursor("test".to_string())));
+
+        let dao: Box<dyn TagDao + Send + Sync> = Box::new(mock_dao);
+        let result = get_tags(PageRequest::default(), &dao).await;
+
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::BadRequest("".to_string()))
+        );
     }
 
     #[tokio::test]
-    async fn delete_question_should_return_error() {
-        let question_id = QuestionId {
-            question_uuid: "123".to_string(),
+    async fn get_tags_should_return_tags() {
+        let page = Page {
+            items: vec![TagSummary {
+                name: "rust".to_string(),
+                question_count: 2,
+                created_at: "now".to_string(),
+            }],
+            next_cursor: None,
         };
-        let mut mock_dao = QuestionDaoMock::new();
+        let mut mock_dao = TagDaoMock::new();
 
-        mock_dao.mock_delete_question(Err(DBError::InvalidUUID("test".to_string())));
+        mock_dao.mock_get_tags(Ok(page.clone()));
 
-        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
-        let result = delete_question(question_id, &dao).await;
+        let dao: Box<dyn TagDao + Send + Sync> = Box::new(mock_dao);
+        let result = get_tags(PageRequest::default(), &dao).await;
+
+        assert_eq!(result.unwrap(), page);
+    }
+
+    #[tokio::test]
+    async fn get_tag_should_return_not_found_error() {
+        let mut mock_dao = TagDaoMock::new();
+
+        mock_dao.mock_get_tag(Err(DBError::NotFound("test".to_string())));
+
+        let dao: Box<dyn TagDao + Send + Sync> = Box::new(mock_dao);
+        let result = get_tag("postgresql".to_string(), &dao).await;
 
-        assert!(result.is_err());
         assert_eq!(
             std::mem::discriminant(&result.unwrap_err()),
-            std::mem::discriminant(&HandlerError::InternalError("".to_string()))
+            std::mem::discriminant(&HandlerError::NotFound("".to_string()))
         );
     }
 
     #[tokio::test]
-    async fn delete_question_should_succeed() {
-        let question_id = QuestionId {
-            question_uuid: "123".to_string(),
+    async fn update_tag_wiki_should_reject_empty_update() {
+        let update = TagWikiUpdate {
+            excerpt: None,
+            body: None,
         };
-        let mut mock_dao = QuestionDaoMock::new();
 
-        mock_dao.mock_delete_question(Ok(()));
+        let dao: Box<dyn TagDao + Send + Sync> = Box::new(TagDaoMock::new());
+        let result = update_tag_wiki(
+            "postgresql".to_string(),
+            update,
+            &actor(),
+            &PrivilegeTable::default(),
+            &dao,
+        )
+        .await;
 
-        let dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_dao);
-        let result = delete_question(question_id, &dao).await;
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::BadRequest("".to_string()))
+        );
+    }
 
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), ());
+    #[tokio::test]
+    async fn update_tag_wiki_should_reject_long_excerpt() {
+        let update = TagWikiUpdate {
+            excerpt: Some("a".repeat(256)),
+            body: None,
+        };
+
+        let dao: Box<dyn TagDao + Send + Sync> = Box::new(TagDaoMock::new());
+        let result = update_tag_wiki(
+            "postgresql".to_string(),
+            update,
+            &actor(),
+            &PrivilegeTable::default(),
+            &dao,
+        )
+        .await;
+
+        assert_eq!(
+            result.unwrap_err(),
+            HandlerError::BadRequest("Excerpt must be at most 255 characters".to_string())
+        );
     }
 
     #[tokio::test]
-    async fn create_answer_should_return_bad_request_error() {
-        let answer = Answer {
-            question_uuid: "123".to_string(),
-            content: "test content".to_string(),
+    async fn update_tag_wiki_should_return_forbidden_error_without_privilege() {
+        let update = TagWikiUpdate {
+            excerpt: Some("An open source relational database".to_string()),
+            body: None,
         };
-        let mut mock_dao = AnswerDaoMock::new();
 
-        mock_dao.mock_create_answer(Err(DBError::InvalidUUID("test".to_string())));
+        let dao: Box<dyn TagDao + Send + Sync> = Box::new(TagDaoMock::new());
+        let result = update_tag_wiki(
+            "postgresql".to_string(),
+            update,
+            &newcomer(),
+            &PrivilegeTable::default(),
+            &dao,
+        )
+        .await;
 
-        let dao: Box<dyn AnswerDao + Send + Sync> = Box::new(mock_dao);
-        let result = create_answer(answer, &dao).await;
+        assert_eq!(
+            result.unwrap_err(),
+            HandlerError::Forbidden(
+                "Missing the edit_posts privilege, which requires 2000 reputation (you have 1)"
+                    .to_string()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn update_tag_wiki_should_return_tag() {
+        let update = TagWikiUpdate {
+            excerpt: Some("An open source relational database".to_string()),
+            body: None,
+        };
+        let updated = TagDetail {
+            excerpt: "An open source relational database".to_string(),
+            ..tag_detail()
+        };
+        let mut mock_dao = TagDaoMock::new();
+
+        mock_dao.mock_update_tag_wiki(Ok(updated.clone()));
+
+        let dao: Box<dyn TagDao + Send + Sync> = Box::new(mock_dao);
+        let result = update_tag_wiki(
+            "postgresql".to_string(),
+            update,
+            &actor(),
+            &PrivilegeTable::default(),
+            &dao,
+        )
+        .await;
+
+        assert_eq!(result.unwrap(), updated);
+    }
+
+    #[tokio::test]
+    async fn propose_tag_synonym_should_reject_tag_itself() {
+        let proposal = SynonymProposal {
+            synonym: "PostgreSQL".to_string(),
+        };
+
+        let dao: Box<dyn TagDao + Send + Sync> = Box::new(TagDaoMock::new());
+        let result = propose_tag_synonym("postgresql".to_string(), proposal, &actor(), &dao).await;
 
-        assert!(result.is_err());
         assert_eq!(
             std::mem::discriminant(&result.unwrap_err()),
             std::mem::discriminant(&HandlerError::BadRequest("".to_string()))
@@ -359,124 +5835,371 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn create_answer_should_return_internal_error() {
-        let answer = Answer {
-            question_uuid: "123".to_string(),
-            content: "test content".to_string(),
+    async fn propose_tag_synonym_should_return_conflict_error() {
+        let proposal = SynonymProposal {
+            synonym: "postgres".to_string(),
         };
-        let mut mock_dao = AnswerDaoMock::new();
+        let mut mock_dao = TagDaoMock::new();
 
-        mock_dao.mock_create_answer(Err(DBError::Other(Box::new(std::io::Error::new(
-            std::io::ErrorKind::Other,
-            "oh no!",
-        )))));
+        mock_dao.mock_propose_tag_synonym(Err(DBError::Conflict("test".to_string())));
 
-        let dao: Box<dyn AnswerDao + Send + Sync> = Box::new(mock_dao);
-        let result = create_answer(answer, &dao).await;
+        let dao: Box<dyn TagDao + Send + Sync> = Box::new(mock_dao);
+        let result = propose_tag_synonym("postgresql".to_string(), proposal, &actor(), &dao).await;
 
-        assert!(result.is_err());
         assert_eq!(
             std::mem::discriminant(&result.unwrap_err()),
-            std::mem::discriminant(&HandlerError::InternalError("".to_string()))
+            std::mem::discriminant(&HandlerError::Conflict("".to_string()))
         );
     }
 
     #[tokio::test]
-    async fn create_answer_should_return_answer() {
-        let answer = Answer {
-            question_uuid: "123".to_string(),
-            content: "test content".to_string(),
+    async fn propose_tag_synonym_should_return_pending_synonym() {
+        let proposal = SynonymProposal {
+            synonym: "Postgres".to_string(),
         };
-        let answer_detail = AnswerDetail {
-            answer_uuid: "456".to_string(),
-            question_uuid: answer.question_uuid.clone(),
-            content: answer.content.clone(),
-            created_at: "now".to_string(),
+        let mut mock_dao = TagDaoMock::new();
+
+        mock_dao.mock_propose_tag_synonym(Ok(tag_synonym(false)));
+
+        let dao: Box<dyn TagDao + Send + Sync> = Box::new(mock_dao);
+        let result = propose_tag_synonym("postgresql".to_string(), proposal, &actor(), &dao).await;
+
+        assert_eq!(result.unwrap(), tag_synonym(false));
+    }
+
+    #[tokio::test]
+    async fn approve_tag_synonym_should_return_forbidden_error_for_users() {
+        let dao: Box<dyn TagDao + Send + Sync> = Box::new(TagDaoMock::new());
+        let result = approve_tag_synonym(
+            "postgresql".to_string(),
+            "postgres".to_string(),
+            &actor(),
+            &dao,
+        )
+        .await;
+
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::Forbidden("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn approve_tag_synonym_should_return_approved_synonym() {
+        let moderator = Actor {
+            role: Role::Moderator,
+            ..actor()
         };
-        let mut mock_dao = AnswerDaoMock::new();
+        let mut mock_dao = TagDaoMock::new();
 
-        mock_dao.mock_create_answer(Ok(answer_detail.clone()));
+        mock_dao.mock_approve_tag_synonym(Ok(tag_synonym(true)));
 
-        let dao: Box<dyn AnswerDao + Send + Sync> = Box::new(mock_dao);
-        let result = create_answer(answer, &dao).await;
+        let dao: Box<dyn TagDao + Send + Sync> = Box::new(mock_dao);
+        let result = approve_tag_synonym(
+            "postgresql".to_string(),
+            "postgres".to_string(),
+            &moderator,
+            &dao,
+        )
+        .await;
 
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), answer_detail);
+        assert_eq!(result.unwrap(), tag_synonym(true));
     }
 
     #[tokio::test]
-    async fn get_answers_should_return_error() {
-        let question_id = QuestionId {
-            question_uuid: "123".to_string(),
+    async fn create_comment_should_reject_blank_and_long_content() {
+        for content in ["  ".to_string(), "a".repeat(601)] {
+            let comment = Comment {
+                content,
+                author_uuid: Some("789".to_string()),
+            };
+
+            let question_dao: Box<dyn QuestionDao + Send + Sync> = Box::new(QuestionDaoMock::new());
+            let answer_dao: Box<dyn AnswerDao + Send + Sync> = Box::new(AnswerDaoMock::new());
+            let dao: Box<dyn CommentDao + Send + Sync> = Box::new(CommentDaoMock::new());
+            let result = create_comment(
+                CommentParent::Question("456".to_string()),
+                comment,
+                &actor(),
+                &PrivilegeTable::default(),
+                &question_dao,
+                &answer_dao,
+                &dao,
+                &notifications(),
+            )
+            .await;
+
+            assert_eq!(
+                result.unwrap_err(),
+                HandlerError::BadRequest("Comment must be 1 to 600 characters".to_string())
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn create_comment_should_return_not_found_error() {
+        let comment = Comment {
+            content: "test comment".to_string(),
+            author_uuid: Some("789".to_string()),
         };
-        let mut mock_dao = AnswerDaoMock::new();
+        let mut mock_dao = CommentDaoMock::new();
 
-        mock_dao.mock_get_answers(Err(DBError::InvalidUUID("test".to_string())));
+        mock_dao.mock_create_comment(Err(DBError::NotFound("test".to_string())));
 
-        let dao: Box<dyn AnswerDao + Send + Sync> = Box::new(mock_dao);
-        let result = get_answers(question_id, &dao).await;
+        let question_dao: Box<dyn QuestionDao + Send + Sync> = Box::new(QuestionDaoMock::new());
+        let answer_dao: Box<dyn AnswerDao + Send + Sync> = Box::new(AnswerDaoMock::new());
+        let dao: Box<dyn CommentDao + Send + Sync> = Box::new(mock_dao);
+        let result = create_comment(
+            CommentParent::Answer("456".to_string()),
+            comment,
+            &actor(),
+            &PrivilegeTable::default(),
+            &question_dao,
+            &answer_dao,
+            &dao,
+            &notifications(),
+        )
+        .await;
 
-        assert!(result.is_err());
         assert_eq!(
             std::mem::discriminant(&result.unwrap_err()),
-            std::mem::discriminant(&HandlerError::InternalError("".to_string()))
+            std::mem::discriminant(&HandlerError::NotFound("".to_string()))
         );
     }
 
     #[tokio::test]
-    async fn get_answers_should_return_answers() {
-        let answer_detail = AnswerDetail {
-            question_uuid: "123".to_string(),
-            answer_uuid: "456".to_string(),
-            content: "test content".to_string(),
-            created_at: "now".to_string(),
+    async fn create_comment_should_return_comment() {
+        let comment = Comment {
+            content: "test comment".to_string(),
+            author_uuid: Some("789".to_string()),
         };
-        let question_id = QuestionId {
-            question_uuid: "123".to_string(),
+        let mut mock_dao = CommentDaoMock::new();
+
+        mock_dao.mock_create_comment(Ok(authored_comment()));
+
+        let question_dao: Box<dyn QuestionDao + Send + Sync> = Box::new(QuestionDaoMock::new());
+        let answer_dao: Box<dyn AnswerDao + Send + Sync> = Box::new(AnswerDaoMock::new());
+        let dao: Box<dyn CommentDao + Send + Sync> = Box::new(mock_dao);
+        let result = create_comment(
+            CommentParent::Question("456".to_string()),
+            comment,
+            &actor(),
+            &PrivilegeTable::default(),
+            &question_dao,
+            &answer_dao,
+            &dao,
+            &notifications(),
+        )
+        .await;
+
+        assert_eq!(result.unwrap(), authored_comment());
+    }
+
+    #[tokio::test]
+    async fn create_comment_should_return_forbidden_error_without_privilege() {
+        let comment = Comment {
+            content: "test comment".to_string(),
+            author_uuid: Some("789".to_string()),
         };
-        let mut mock_dao = AnswerDaoMock::new();
+        let mut mock_question_dao = QuestionDaoMock::new();
 
-        mock_dao.mock_get_answers(Ok(vec![answer_detail.clone()]));
+        mock_question_dao.mock_get_question(Ok(QuestionDetail {
+            author_uuid: Some("456".to_string()),
+            ..authored_question()
+        }));
 
-        let dao: Box<dyn AnswerDao + Send + Sync> = Box::new(mock_dao);
-        let result = get_answers(question_id, &dao).await;
+        let question_dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_question_dao);
+        let answer_dao: Box<dyn AnswerDao + Send + Sync> = Box::new(AnswerDaoMock::new());
+        let dao: Box<dyn CommentDao + Send + Sync> = Box::new(CommentDaoMock::new());
+        let result = create_comment(
+            CommentParent::Question("123".to_string()),
+            comment,
+            &newcomer(),
+            &PrivilegeTable::default(),
+            &question_dao,
+            &answer_dao,
+            &dao,
+            &notifications(),
+        )
+        .await;
 
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), vec![answer_detail]);
+        assert_eq!(
+            result.unwrap_err(),
+            HandlerError::Forbidden(
+                "Missing the comment_everywhere privilege, which requires 50 reputation (you have 1)"
+                    .to_string()
+            )
+        );
     }
 
     #[tokio::test]
-    async fn delete_answer_should_return_error() {
-        let answer_id = AnswerId {
-            answer_uuid: "123".to_string(),
+    async fn create_comment_should_allow_answers_to_own_question_without_privilege() {
+        let comment = Comment {
+            content: "test comment".to_string(),
+            author_uuid: Some("789".to_string()),
         };
-        let mut mock_dao = AnswerDaoMock::new();
+        let mut mock_question_dao = QuestionDaoMock::new();
+        let mut mock_answer_dao = AnswerDaoMock::new();
+        let mut mock_dao = CommentDaoMock::new();
 
-        mock_dao.mock_delete_answer(Err(DBError::InvalidUUID("test".to_string())));
+        mock_question_dao.mock_get_question(Ok(authored_question()));
+        mock_answer_dao.mock_get_answer(Ok(AnswerDetail {
+            author_uuid: Some("456".to_string()),
+            ..authored_answer()
+        }));
+        mock_dao.mock_create_comment(Ok(authored_comment()));
 
-        let dao: Box<dyn AnswerDao + Send + Sync> = Box::new(mock_dao);
-        let result = delete_answer(answer_id, &dao).await;
+        let question_dao: Box<dyn QuestionDao + Send + Sync> = Box::new(mock_question_dao);
+        let answer_dao: Box<dyn AnswerDao + Send + Sync> = Box::new(mock_answer_dao);
+        let dao: Box<dyn CommentDao + Send + Sync> = Box::new(mock_dao);
+        let result = create_comment(
+            CommentParent::Answer("123".to_string()),
+            comment,
+            &newcomer(),
+            &PrivilegeTable::default(),
+            &question_dao,
+            &answer_dao,
+            &dao,
+            &notifications(),
+        )
+        .await;
+
+        assert_eq!(result.unwrap(), authored_comment());
+    }
+
+    #[tokio::test]
+    async fn get_comments_should_require_a_moderator_to_include_deleted() {
+        let parent = || CommentParent::Question("123".to_string());
+        let moderator = Actor {
+            role: Role::Moderator,
+            ..actor()
+        };
+        let dao: Box<dyn CommentDao + Send + Sync> = Box::new(CommentDaoMock::new());
+
+        assert_eq!(
+            get_comments(parent(), true, PageRequest::default(), None, &dao).await,
+            Err(HandlerError::Unauthorized(
+                "Log in as a moderator to list deleted posts".to_string()
+            ))
+        );
+        assert_eq!(
+            get_comments(parent(), true, PageRequest::default(), Some(&actor()), &dao).await,
+            Err(HandlerError::Forbidden(
+                "Only a moderator can list deleted posts".to_string()
+            ))
+        );
+        assert!(get_comments(
+            parent(),
+            true,
+            PageRequest::default(),
+            Some(&moderator),
+            &dao
+        )
+        .await
+        .is_ok());
+    }
+
+    #[tokio::test]
+    async fn delete_comment_should_return_forbidden_error_for_other_users() {
+        let comment_id = CommentId {
+            comment_uuid: "123".to_string(),
+        };
+        let mut mock_dao = CommentDaoMock::new();
+
+        mock_dao.mock_get_comment(Ok(CommentDetail {
+            author_uuid: Some("456".to_string()),
+            ..authored_comment()
+        }));
+
+        let dao: Box<dyn CommentDao + Send + Sync> = Box::new(mock_dao);
+        let result = delete_comment(comment_id, &actor(), &dao).await;
 
-        assert!(result.is_err());
         assert_eq!(
             std::mem::discriminant(&result.unwrap_err()),
-            std::mem::discriminant(&HandlerError::InternalError("".to_string()))
+            std::mem::discriminant(&HandlerError::Forbidden("".to_string()))
         );
     }
 
     #[tokio::test]
-    async fn delete_answer_should_succeed() {
-        let answer_id = AnswerId {
-            answer_uuid: "123".to_string(),
+    async fn delete_comment_should_succeed_for_moderators() {
+        let comment_id = CommentId {
+            comment_uuid: "123".to_string(),
         };
-        let mut mock_dao = AnswerDaoMock::new();
+        let moderator = Actor {
+            role: Role::Moderator,
+            ..actor()
+        };
+        let mut mock_dao = CommentDaoMock::new();
 
-        mock_dao.mock_delete_answer(Ok(()));
+        mock_dao.mock_get_comment(Ok(CommentDetail {
+            author_uuid: Some("456".to_string()),
+            ..authored_comment()
+        }));
+        mock_dao.mock_delete_comment(Ok(()));
 
-        let dao: Box<dyn AnswerDao + Send + Sync> = Box::new(mock_dao);
-        let result = delete_answer(answer_id, &dao).await;
+        let dao: Box<dyn CommentDao + Send + Sync> = Box::new(mock_dao);
+        let result = delete_comment(comment_id, &moderator, &dao).await;
 
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), ());
+    }
+
+    #[tokio::test]
+    async fn search_should_reject_blank_and_long_queries() {
+        for query in ["  ".to_string(), "a".repeat(201)] {
+            let dao: Box<dyn SearchDao + Send + Sync> = Box::new(SearchDaoMock::new());
+            let result = search(query, PageRequest::default(), &dao).await;
+
+            assert_eq!(
+                result.unwrap_err(),
+                HandlerError::BadRequest("Search query must be 1 to 200 characters".to_string())
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn search_should_reject_invalid_queries() {
+        let dao: Box<dyn SearchDao + Send + Sync> = Box::new(SearchDaoMock::new());
+        let result = search(
+            "[rust] answers:none".to_string(),
+            PageRequest::default(),
+            &dao,
+        )
+        .await;
+
+        assert_eq!(
+            result.unwrap_err(),
+            HandlerError::BadRequest("Invalid number in search query: answers:none".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn search_should_return_bad_request_error_for_invalid_cursor() {
+        let mut mock_dao = SearchDaoMock::new();
+
+        mock_dao.mock_search(Err(DBError::InvalidCursor("test".to_string())));
+
+        let dao: Box<dyn SearchDao + Send + Sync> = Box::new(mock_dao);
+        let result = search("description".to_string(), PageRequest::default(), &dao).await;
+
+        assert_eq!(
+            std::mem::discriminant(&result.unwrap_err()),
+            std::mem::discriminant(&HandlerError::BadRequest("".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn search_should_return_results() {
+        let page = Page {
+            items: vec![search_result()],
+            next_cursor: None,
+        };
+        let mut mock_dao = SearchDaoMock::new();
+
+        mock_dao.mock_search(Ok(page.clone()));
+
+        let dao: Box<dyn SearchDao + Send + Sync> = Box::new(mock_dao);
+        let result = search("description".to_string(), PageRequest::default(), &dao).await;
+
+        assert_eq!(result.unwrap(), page);
     }
 }
@@ -1,17 +1,53 @@
 use crate::{
     models::*,
-    persistance::{answer_dao::AnswerDao, question_dao::QuestionDao},
+    persistance::{
+        answer_dao::AnswerDao, badge_dao::BadgeDao, bounty_dao::BountyDao, comment_dao::CommentDao,
+        follow_dao::FollowDao, notification_dao::NotificationDao, question_dao::QuestionDao,
+        reputation_dao::ReputationDao, search_dao::SearchDao, session_dao::SessionDao,
+        tag_dao::TagDao, user_dao::UserDao, vote_dao::VoteDao,
+    },
+};
+use rocket::{
+    form,
+    http::Header,
+    request::Request,
+    response::{
+        self,
+        stream::{Event, EventStream},
+        Responder, Response,
+    },
+    serde::json::Json,
+    tokio::{select, sync::broadcast::error::RecvError},
+    Shutdown, State,
 };
-use rocket::{serde::json::Json, State};
 
-use self::handlers_inner::HandlerError;
+pub use self::events::QuestionEvents;
+use self::{
+    guards::{AuthenticatedUser, UuidParam},
+    handlers_inner::HandlerError,
+};
 
+mod auth;
+mod events;
+mod guards;
 mod handlers_inner;
+mod policy;
+mod search_query;
 
-#[derive(Responder)]
+#[derive(Responder, Debug)]
 pub enum APIError {
     #[response(status = 400)]
     BadRequest(String),
+    #[response(status = 401)]
+    Unauthorized(String),
+    #[response(status = 403)]
+    Forbidden(String),
+    #[response(status = 404)]
+    NotFound(String),
+    #[response(status = 409)]
+    Conflict(String),
+    #[response(status = 409)]
+    PossibleDuplicate(Json<PossibleDuplicates>),
     #[response(status = 500)]
     InternalError(String),
 }
@@ -20,71 +56,1463 @@ impl From<HandlerError> for APIError {
     fn from(value: HandlerError) -> Self {
         match value {
             HandlerError::BadRequest(s) => Self::BadRequest(s),
+            HandlerError::Unauthorized(s) => Self::Unauthorized(s),
+            HandlerError::Forbidden(s) => Self::Forbidden(s),
+            HandlerError::NotFound(s) => Self::NotFound(s),
+            HandlerError::Conflict(s) => Self::Conflict(s),
+            HandlerError::PossibleDuplicate(similar_questions) => {
+                Self::PossibleDuplicate(Json(PossibleDuplicates {
+                    message: "This question looks like a duplicate. Ask it without check_duplicates to post it anyway".to_string(),
+                    similar_questions,
+                }))
+            }
             HandlerError::InternalError(s) => Self::InternalError(s),
         }
     }
 }
 
-#[post("/question", data = "<question>")]
+/// Wraps the response of a route that is kept only for backwards compatibility
+/// and marks it with a `Deprecation` header.
+pub struct Deprecated<R>(pub R);
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for Deprecated<R> {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'o> {
+        Response::build_from(self.0.respond_to(request)?)
+            .header(Header::new("Deprecation", "true"))
+            .ok()
+    }
+}
+
+/// With `check_duplicates=true`, the question is only created if no existing
+/// question is too similar; otherwise the similar questions are returned.
+#[post("/question?<check_duplicates>", data = "<question>")]
 pub async fn create_question(
     question: Json<Question>,
+    check_duplicates: bool,
+    user: Result<AuthenticatedUser, APIError>,
+    question_dao: &State<Box<dyn QuestionDao + Sync + Send>>,
+) -> Result<Json<QuestionDetail>, APIError> {
+    let mut question = question.0;
+    question.author_uuid = Some(user?.actor.user_uuid);
+
+    match handlers_inner::create_question(question, check_duplicates, question_dao.inner()).await {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[get("/questions/similar?<title>&<description>")]
+pub async fn get_similar_questions(
+    title: Option<String>,
+    description: Option<String>,
+    question_dao: &State<Box<dyn QuestionDao + Sync + Send>>,
+) -> Result<Json<Vec<SimilarQuestion>>, APIError> {
+    match handlers_inner::get_similar_questions(
+        title.unwrap_or_default(),
+        description,
+        question_dao.inner(),
+    )
+    .await
+    {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[get("/question/<question_uuid>")]
+pub async fn get_question(
+    question_uuid: Result<UuidParam, String>,
     question_dao: &State<Box<dyn QuestionDao + Sync + Send>>,
 ) -> Result<Json<QuestionDetail>, APIError> {
-    match handlers_inner::create_question(question.0, question_dao.inner()).await {
+    let question_id = QuestionId {
+        question_uuid: question_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+
+    match handlers_inner::get_question(question_id, question_dao.inner()).await {
         Ok(res) => Ok(Json(res)),
         Err(err) => Err(err.into()),
     }
 }
 
-#[get("/questions")]
+/// Streams the activity on a question as server-sent events, until the client
+/// goes away or the server shuts down.
+#[get("/questions/<question_uuid>/events")]
+pub async fn question_events(
+    question_uuid: Result<UuidParam, String>,
+    events: &State<QuestionEvents>,
+    question_dao: &State<Box<dyn QuestionDao + Sync + Send>>,
+    mut shutdown: Shutdown,
+) -> Result<EventStream![], APIError> {
+    let question_id = QuestionId {
+        question_uuid: question_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+
+    // Subscribing before the lookup so nothing published in between is missed.
+    let mut receiver = events.subscribe();
+    let question_uuid = handlers_inner::get_question(question_id, question_dao.inner())
+        .await
+        .map_err(APIError::from)?
+        .question_uuid;
+
+    Ok(EventStream! {
+        loop {
+            let activity = select! {
+                activity = receiver.recv() => match activity {
+                    Ok(activity) => activity,
+                    Err(RecvError::Closed) => break,
+                    // A slow client skips what it missed rather than holding
+                    // up the others.
+                    Err(RecvError::Lagged(_)) => continue,
+                },
+                _ = &mut shutdown => break,
+            };
+
+            if activity.question_uuid == question_uuid {
+                yield Event::json(&activity.event);
+            }
+        }
+    })
+}
+
+#[get("/questions?<cursor>&<limit>&<query..>")]
 pub async fn get_questions(
+    cursor: Option<String>,
+    limit: Option<i64>,
+    query: form::Result<'_, QuestionQuery>,
+    user: Result<AuthenticatedUser, APIError>,
     question_dao: &State<Box<dyn QuestionDao + Sync + Send>>,
-) -> Result<Json<Vec<QuestionDetail>>, APIError> {
-    match handlers_inner::get_questions(question_dao.inner()).await {
+) -> Result<Json<Page<QuestionDetail>>, APIError> {
+    let query = query.map_err(|e| APIError::BadRequest(e.to_string()))?;
+    let page = PageRequest { cursor, limit };
+    // Listing is public, a session is only needed for deleted or followed
+    // questions.
+    let user = if query.include_deleted || query.followed {
+        Some(user?)
+    } else {
+        None
+    };
+    let actor = user.as_ref().map(|user| &user.actor);
+
+    match handlers_inner::get_questions(query, page, actor, question_dao.inner()).await {
         Ok(res) => Ok(Json(res)),
         Err(err) => Err(err.into()),
     }
 }
 
-#[delete("/question", data = "<question_uuid>")]
+#[patch("/question/<question_uuid>", data = "<update>")]
+pub async fn update_question(
+    question_uuid: Result<UuidParam, String>,
+    update: Json<QuestionUpdate>,
+    user: Result<AuthenticatedUser, APIError>,
+    privileges: &State<PrivilegeTable>,
+    question_dao: &State<Box<dyn QuestionDao + Sync + Send>>,
+    notification_dao: &State<Box<dyn NotificationDao + Send + Sync>>,
+    events: &State<QuestionEvents>,
+) -> Result<Json<QuestionDetail>, APIError> {
+    let user = user?;
+
+    let question_id = QuestionId {
+        question_uuid: question_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+
+    match handlers_inner::update_question(
+        question_id,
+        update.0,
+        &user.actor,
+        privileges.inner(),
+        question_dao.inner(),
+        notification_dao.inner(),
+        events.inner(),
+    )
+    .await
+    {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[get("/questions/<question_uuid>/revisions?<include_deleted>")]
+pub async fn get_question_revisions(
+    question_uuid: Result<UuidParam, String>,
+    include_deleted: Option<bool>,
+    user: Result<AuthenticatedUser, APIError>,
+    question_dao: &State<Box<dyn QuestionDao + Sync + Send>>,
+) -> Result<Json<Vec<QuestionRevision>>, APIError> {
+    let question_id = QuestionId {
+        question_uuid: question_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+    let include_deleted = include_deleted.unwrap_or_default();
+    let user = if include_deleted { Some(user?) } else { None };
+    let actor = user.as_ref().map(|user| &user.actor);
+
+    match handlers_inner::get_question_revisions(
+        question_id,
+        include_deleted,
+        actor,
+        question_dao.inner(),
+    )
+    .await
+    {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[get("/questions/<question_uuid>/revisions/diff?<from>&<to>&<include_deleted>")]
+pub async fn diff_question_revisions(
+    question_uuid: Result<UuidParam, String>,
+    from: i32,
+    to: i32,
+    include_deleted: Option<bool>,
+    user: Result<AuthenticatedUser, APIError>,
+    question_dao: &State<Box<dyn QuestionDao + Sync + Send>>,
+) -> Result<Json<RevisionDiff>, APIError> {
+    let question_id = QuestionId {
+        question_uuid: question_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+    let include_deleted = include_deleted.unwrap_or_default();
+    let user = if include_deleted { Some(user?) } else { None };
+    let actor = user.as_ref().map(|user| &user.actor);
+
+    match handlers_inner::diff_question_revisions(
+        question_id,
+        from,
+        to,
+        include_deleted,
+        actor,
+        question_dao.inner(),
+    )
+    .await
+    {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[post("/questions/<question_uuid>/revisions/<revision>/rollback")]
+pub async fn rollback_question(
+    question_uuid: Result<UuidParam, String>,
+    revision: i32,
+    user: Result<AuthenticatedUser, APIError>,
+    privileges: &State<PrivilegeTable>,
+    question_dao: &State<Box<dyn QuestionDao + Sync + Send>>,
+    notification_dao: &State<Box<dyn NotificationDao + Send + Sync>>,
+    events: &State<QuestionEvents>,
+) -> Result<Json<QuestionDetail>, APIError> {
+    let user = user?;
+
+    let question_id = QuestionId {
+        question_uuid: question_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+
+    match handlers_inner::rollback_question(
+        question_id,
+        revision,
+        &user.actor,
+        privileges.inner(),
+        question_dao.inner(),
+        notification_dao.inner(),
+        events.inner(),
+    )
+    .await
+    {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[delete("/questions/<question_uuid>")]
 pub async fn delete_question(
-    question_uuid: Json<QuestionId>,
+    question_uuid: Result<UuidParam, String>,
+    user: Result<AuthenticatedUser, APIError>,
     question_dao: &State<Box<dyn QuestionDao + Sync + Send>>,
+    events: &State<QuestionEvents>,
 ) -> Result<(), APIError> {
-    match handlers_inner::delete_question(question_uuid.0, question_dao.inner()).await {
+    let user = user?;
+
+    let question_id = QuestionId {
+        question_uuid: question_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+
+    match handlers_inner::delete_question(
+        question_id,
+        &user.actor,
+        question_dao.inner(),
+        events.inner(),
+    )
+    .await
+    {
         Ok(_) => Ok(()),
         Err(e) => Err(e.into()),
     }
 }
 
+#[delete("/question", data = "<question_uuid>")]
+pub async fn delete_question_legacy(
+    question_uuid: Json<QuestionId>,
+    user: Result<AuthenticatedUser, APIError>,
+    question_dao: &State<Box<dyn QuestionDao + Sync + Send>>,
+    events: &State<QuestionEvents>,
+) -> Deprecated<Result<(), APIError>> {
+    let user = match user {
+        Ok(user) => user,
+        Err(e) => return Deprecated(Err(e)),
+    };
+
+    match handlers_inner::delete_question(
+        question_uuid.0,
+        &user.actor,
+        question_dao.inner(),
+        events.inner(),
+    )
+    .await
+    {
+        Ok(_) => Deprecated(Ok(())),
+        Err(e) => Deprecated(Err(e.into())),
+    }
+}
+
+#[post("/questions/<question_uuid>/undelete")]
+pub async fn undelete_question(
+    question_uuid: Result<UuidParam, String>,
+    user: Result<AuthenticatedUser, APIError>,
+    question_dao: &State<Box<dyn QuestionDao + Send + Sync>>,
+) -> Result<Json<QuestionDetail>, APIError> {
+    let user = user?;
+    let question_id = QuestionId {
+        question_uuid: question_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+
+    match handlers_inner::undelete_question(question_id, &user.actor, question_dao.inner()).await {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[post("/questions/<question_uuid>/close", data = "<vote>")]
+pub async fn vote_to_close(
+    question_uuid: Result<UuidParam, String>,
+    vote: Json<CloseVote>,
+    user: Result<AuthenticatedUser, APIError>,
+    privileges: &State<PrivilegeTable>,
+    question_dao: &State<Box<dyn QuestionDao + Send + Sync>>,
+) -> Result<Json<QuestionStatus>, APIError> {
+    let user = user?;
+    let question_id = QuestionId {
+        question_uuid: question_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+
+    match handlers_inner::vote_to_close(
+        question_id,
+        vote.0,
+        &user.actor,
+        privileges.inner(),
+        question_dao.inner(),
+    )
+    .await
+    {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[post("/questions/<question_uuid>/reopen")]
+pub async fn vote_to_reopen(
+    question_uuid: Result<UuidParam, String>,
+    user: Result<AuthenticatedUser, APIError>,
+    privileges: &State<PrivilegeTable>,
+    question_dao: &State<Box<dyn QuestionDao + Send + Sync>>,
+) -> Result<Json<QuestionStatus>, APIError> {
+    let user = user?;
+    let question_id = QuestionId {
+        question_uuid: question_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+
+    match handlers_inner::vote_to_reopen(
+        question_id,
+        &user.actor,
+        privileges.inner(),
+        question_dao.inner(),
+    )
+    .await
+    {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[post("/questions/<question_uuid>/lock")]
+pub async fn lock_question(
+    question_uuid: Result<UuidParam, String>,
+    user: Result<AuthenticatedUser, APIError>,
+    question_dao: &State<Box<dyn QuestionDao + Send + Sync>>,
+) -> Result<Json<QuestionStatus>, APIError> {
+    let user = user?;
+    let question_id = QuestionId {
+        question_uuid: question_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+
+    match handlers_inner::lock_question(question_id, &user.actor, question_dao.inner()).await {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[delete("/questions/<question_uuid>/lock")]
+pub async fn unlock_question(
+    question_uuid: Result<UuidParam, String>,
+    user: Result<AuthenticatedUser, APIError>,
+    question_dao: &State<Box<dyn QuestionDao + Send + Sync>>,
+) -> Result<Json<QuestionStatus>, APIError> {
+    let user = user?;
+    let question_id = QuestionId {
+        question_uuid: question_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+
+    match handlers_inner::unlock_question(question_id, &user.actor, question_dao.inner()).await {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
 #[post("/answer", data = "<answer>")]
 pub async fn create_answer(
     answer: Json<Answer>,
+    user: Result<AuthenticatedUser, APIError>,
+    answer_dao: &State<Box<dyn AnswerDao + Send + Sync>>,
+    notification_dao: &State<Box<dyn NotificationDao + Send + Sync>>,
+    events: &State<QuestionEvents>,
+) -> Result<Json<AnswerDetail>, APIError> {
+    let mut answer = answer.0;
+    answer.author_uuid = Some(user?.actor.user_uuid);
+
+    match handlers_inner::create_answer(
+        answer,
+        answer_dao.inner(),
+        notification_dao.inner(),
+        events.inner(),
+    )
+    .await
+    {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[get("/answer/<answer_uuid>")]
+pub async fn get_answer(
+    answer_uuid: Result<UuidParam, String>,
     answer_dao: &State<Box<dyn AnswerDao + Send + Sync>>,
 ) -> Result<Json<AnswerDetail>, APIError> {
-    match handlers_inner::create_answer(answer.0, answer_dao.inner()).await {
+    let answer_id = AnswerId {
+        answer_uuid: answer_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+
+    match handlers_inner::get_answer(answer_id, answer_dao.inner()).await {
         Ok(res) => Ok(Json(res)),
         Err(err) => Err(err.into()),
     }
 }
 
-#[get("/answers", data = "<question_uuid>")]
+#[patch("/answer/<answer_uuid>", data = "<update>")]
+pub async fn update_answer(
+    answer_uuid: Result<UuidParam, String>,
+    update: Json<AnswerUpdate>,
+    user: Result<AuthenticatedUser, APIError>,
+    privileges: &State<PrivilegeTable>,
+    answer_dao: &State<Box<dyn AnswerDao + Send + Sync>>,
+    notification_dao: &State<Box<dyn NotificationDao + Send + Sync>>,
+    events: &State<QuestionEvents>,
+) -> Result<Json<AnswerDetail>, APIError> {
+    let user = user?;
+
+    let answer_id = AnswerId {
+        answer_uuid: answer_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+
+    match handlers_inner::update_answer(
+        answer_id,
+        update.0,
+        &user.actor,
+        privileges.inner(),
+        answer_dao.inner(),
+        notification_dao.inner(),
+        events.inner(),
+    )
+    .await
+    {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[get("/answers/<answer_uuid>/revisions?<include_deleted>")]
+pub async fn get_answer_revisions(
+    answer_uuid: Result<UuidParam, String>,
+    include_deleted: Option<bool>,
+    user: Result<AuthenticatedUser, APIError>,
+    answer_dao: &State<Box<dyn AnswerDao + Send + Sync>>,
+) -> Result<Json<Vec<AnswerRevision>>, APIError> {
+    let answer_id = AnswerId {
+        answer_uuid: answer_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+    let include_deleted = include_deleted.unwrap_or_default();
+    let user = if include_deleted { Some(user?) } else { None };
+    let actor = user.as_ref().map(|user| &user.actor);
+
+    match handlers_inner::get_answer_revisions(
+        answer_id,
+        include_deleted,
+        actor,
+        answer_dao.inner(),
+    )
+    .await
+    {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[get("/answers/<answer_uuid>/revisions/diff?<from>&<to>&<include_deleted>")]
+pub async fn diff_answer_revisions(
+    answer_uuid: Result<UuidParam, String>,
+    from: i32,
+    to: i32,
+    include_deleted: Option<bool>,
+    user: Result<AuthenticatedUser, APIError>,
+    answer_dao: &State<Box<dyn AnswerDao + Send + Sync>>,
+) -> Result<Json<RevisionDiff>, APIError> {
+    let answer_id = AnswerId {
+        answer_uuid: answer_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+    let include_deleted = include_deleted.unwrap_or_default();
+    let user = if include_deleted { Some(user?) } else { None };
+    let actor = user.as_ref().map(|user| &user.actor);
+
+    match handlers_inner::diff_answer_revisions(
+        answer_id,
+        from,
+        to,
+        include_deleted,
+        actor,
+        answer_dao.inner(),
+    )
+    .await
+    {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[post("/answers/<answer_uuid>/revisions/<revision>/rollback")]
+pub async fn rollback_answer(
+    answer_uuid: Result<UuidParam, String>,
+    revision: i32,
+    user: Result<AuthenticatedUser, APIError>,
+    privileges: &State<PrivilegeTable>,
+    answer_dao: &State<Box<dyn AnswerDao + Send + Sync>>,
+    notification_dao: &State<Box<dyn NotificationDao + Send + Sync>>,
+    events: &State<QuestionEvents>,
+) -> Result<Json<AnswerDetail>, APIError> {
+    let user = user?;
+
+    let answer_id = AnswerId {
+        answer_uuid: answer_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+
+    match handlers_inner::rollback_answer(
+        answer_id,
+        revision,
+        &user.actor,
+        privileges.inner(),
+        answer_dao.inner(),
+        notification_dao.inner(),
+        events.inner(),
+    )
+    .await
+    {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[get("/questions/<question_uuid>/answers?<cursor>&<limit>&<include_deleted>")]
 pub async fn get_answers(
+    question_uuid: Result<UuidParam, String>,
+    cursor: Option<String>,
+    limit: Option<i64>,
+    include_deleted: Option<bool>,
+    user: Result<AuthenticatedUser, APIError>,
+    answer_dao: &State<Box<dyn AnswerDao + Send + Sync>>,
+) -> Result<Json<Page<AnswerDetail>>, APIError> {
+    let question_id = QuestionId {
+        question_uuid: question_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+    let include_deleted = include_deleted.unwrap_or_default();
+    let page = PageRequest { cursor, limit };
+    let user = if include_deleted { Some(user?) } else { None };
+    let actor = user.as_ref().map(|user| &user.actor);
+
+    match handlers_inner::get_answers(
+        question_id,
+        include_deleted,
+        page,
+        actor,
+        answer_dao.inner(),
+    )
+    .await
+    {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Keeps the original bare array response, so it is limited to a single page of
+/// the maximum size.
+#[get("/answers", data = "<question_uuid>")]
+pub async fn get_answers_legacy(
     question_uuid: Json<QuestionId>,
     answer_dao: &State<Box<dyn AnswerDao + Send + Sync>>,
-) -> Result<Json<Vec<AnswerDetail>>, APIError> {
-    match handlers_inner::get_answers(question_uuid.0, answer_dao.inner()).await {
+) -> Deprecated<Result<Json<Vec<AnswerDetail>>, APIError>> {
+    let page = PageRequest {
+        cursor: None,
+        limit: Some(MAX_PAGE_LIMIT),
+    };
+
+    match handlers_inner::get_answers(question_uuid.0, false, page, None, answer_dao.inner()).await
+    {
+        Ok(res) => Deprecated(Ok(Json(res.items))),
+        Err(err) => Deprecated(Err(err.into())),
+    }
+}
+
+#[delete("/answers/<answer_uuid>")]
+pub async fn delete_answer(
+    answer_uuid: Result<UuidParam, String>,
+    user: Result<AuthenticatedUser, APIError>,
+    answer_dao: &State<Box<dyn AnswerDao + Send + Sync>>,
+    events: &State<QuestionEvents>,
+) -> Result<(), APIError> {
+    let user = user?;
+
+    let answer_id = AnswerId {
+        answer_uuid: answer_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+
+    match handlers_inner::delete_answer(answer_id, &user.actor, answer_dao.inner(), events.inner())
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[post("/answers/<answer_uuid>/undelete")]
+pub async fn undelete_answer(
+    answer_uuid: Result<UuidParam, String>,
+    user: Result<AuthenticatedUser, APIError>,
+    answer_dao: &State<Box<dyn AnswerDao + Send + Sync>>,
+) -> Result<Json<AnswerDetail>, APIError> {
+    let user = user?;
+    let answer_id = AnswerId {
+        answer_uuid: answer_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+
+    match handlers_inner::undelete_answer(answer_id, &user.actor, answer_dao.inner()).await {
         Ok(res) => Ok(Json(res)),
         Err(err) => Err(err.into()),
     }
 }
 
 #[delete("/answer", data = "<answer_uuid>")]
-pub async fn delete_answer(
+pub async fn delete_answer_legacy(
     answer_uuid: Json<AnswerId>,
+    user: Result<AuthenticatedUser, APIError>,
+    answer_dao: &State<Box<dyn AnswerDao + Send + Sync>>,
+    events: &State<QuestionEvents>,
+) -> Deprecated<Result<(), APIError>> {
+    let user = match user {
+        Ok(user) => user,
+        Err(e) => return Deprecated(Err(e)),
+    };
+
+    match handlers_inner::delete_answer(
+        answer_uuid.0,
+        &user.actor,
+        answer_dao.inner(),
+        events.inner(),
+    )
+    .await
+    {
+        Ok(_) => Deprecated(Ok(())),
+        Err(e) => Deprecated(Err(e.into())),
+    }
+}
+
+#[post("/questions/<question_uuid>/vote", data = "<vote>")]
+pub async fn vote_question(
+    question_uuid: Result<UuidParam, String>,
+    vote: Json<Vote>,
+    user: Result<AuthenticatedUser, APIError>,
+    privileges: &State<PrivilegeTable>,
+    vote_dao: &State<Box<dyn VoteDao + Send + Sync>>,
+    events: &State<QuestionEvents>,
+) -> Result<Json<VoteSummary>, APIError> {
+    let user = user?;
+    let question_id = QuestionId {
+        question_uuid: question_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+
+    match handlers_inner::vote_question(
+        question_id,
+        vote.0,
+        &user.actor,
+        privileges.inner(),
+        vote_dao.inner(),
+        events.inner(),
+    )
+    .await
+    {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[delete("/questions/<question_uuid>/vote")]
+pub async fn retract_question_vote(
+    question_uuid: Result<UuidParam, String>,
+    user: Result<AuthenticatedUser, APIError>,
+    vote_dao: &State<Box<dyn VoteDao + Send + Sync>>,
+    events: &State<QuestionEvents>,
+) -> Result<Json<VoteSummary>, APIError> {
+    let user = user?;
+    let question_id = QuestionId {
+        question_uuid: question_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+
+    match handlers_inner::retract_question_vote(
+        question_id,
+        &user.actor,
+        vote_dao.inner(),
+        events.inner(),
+    )
+    .await
+    {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[post("/questions/<question_uuid>/comments", data = "<comment>")]
+#[allow(clippy::too_many_arguments)]
+pub async fn create_question_comment(
+    question_uuid: Result<UuidParam, String>,
+    comment: Json<Comment>,
+    user: Result<AuthenticatedUser, APIError>,
+    privileges: &State<PrivilegeTable>,
+    question_dao: &State<Box<dyn QuestionDao + Send + Sync>>,
+    answer_dao: &State<Box<dyn AnswerDao + Send + Sync>>,
+    comment_dao: &State<Box<dyn CommentDao + Send + Sync>>,
+    notification_dao: &State<Box<dyn NotificationDao + Send + Sync>>,
+) -> Result<Json<CommentDetail>, APIError> {
+    let user = user?;
+    let mut comment = comment.0;
+    comment.author_uuid = Some(user.actor.user_uuid.clone());
+
+    let parent =
+        CommentParent::Question(question_uuid.map_err(APIError::BadRequest)?.0.to_string());
+
+    match handlers_inner::create_comment(
+        parent,
+        comment,
+        &user.actor,
+        privileges.inner(),
+        question_dao.inner(),
+        answer_dao.inner(),
+        comment_dao.inner(),
+        notification_dao.inner(),
+    )
+    .await
+    {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[post("/answers/<answer_uuid>/comments", data = "<comment>")]
+#[allow(clippy::too_many_arguments)]
+pub async fn create_answer_comment(
+    answer_uuid: Result<UuidParam, String>,
+    comment: Json<Comment>,
+    user: Result<AuthenticatedUser, APIError>,
+    privileges: &State<PrivilegeTable>,
+    question_dao: &State<Box<dyn QuestionDao + Send + Sync>>,
+    answer_dao: &State<Box<dyn AnswerDao + Send + Sync>>,
+    comment_dao: &State<Box<dyn CommentDao + Send + Sync>>,
+    notification_dao: &State<Box<dyn NotificationDao + Send + Sync>>,
+) -> Result<Json<CommentDetail>, APIError> {
+    let user = user?;
+    let mut comment = comment.0;
+    comment.author_uuid = Some(user.actor.user_uuid.clone());
+
+    let parent = CommentParent::Answer(answer_uuid.map_err(APIError::BadRequest)?.0.to_string());
+
+    match handlers_inner::create_comment(
+        parent,
+        comment,
+        &user.actor,
+        privileges.inner(),
+        question_dao.inner(),
+        answer_dao.inner(),
+        comment_dao.inner(),
+        notification_dao.inner(),
+    )
+    .await
+    {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[get("/questions/<question_uuid>/comments?<cursor>&<limit>&<include_deleted>")]
+pub async fn get_question_comments(
+    question_uuid: Result<UuidParam, String>,
+    cursor: Option<String>,
+    limit: Option<i64>,
+    include_deleted: Option<bool>,
+    user: Result<AuthenticatedUser, APIError>,
+    comment_dao: &State<Box<dyn CommentDao + Send + Sync>>,
+) -> Result<Json<Page<CommentDetail>>, APIError> {
+    let parent =
+        CommentParent::Question(question_uuid.map_err(APIError::BadRequest)?.0.to_string());
+    let include_deleted = include_deleted.unwrap_or_default();
+    let page = PageRequest { cursor, limit };
+    let user = if include_deleted { Some(user?) } else { None };
+    let actor = user.as_ref().map(|user| &user.actor);
+
+    match handlers_inner::get_comments(parent, include_deleted, page, actor, comment_dao.inner())
+        .await
+    {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[get("/answers/<answer_uuid>/comments?<cursor>&<limit>&<include_deleted>")]
+pub async fn get_answer_comments(
+    answer_uuid: Result<UuidParam, String>,
+    cursor: Option<String>,
+    limit: Option<i64>,
+    include_deleted: Option<bool>,
+    user: Result<AuthenticatedUser, APIError>,
+    comment_dao: &State<Box<dyn CommentDao + Send + Sync>>,
+) -> Result<Json<Page<CommentDetail>>, APIError> {
+    let parent = CommentParent::Answer(answer_uuid.map_err(APIError::BadRequest)?.0.to_string());
+    let include_deleted = include_deleted.unwrap_or_default();
+    let page = PageRequest { cursor, limit };
+    let user = if include_deleted { Some(user?) } else { None };
+    let actor = user.as_ref().map(|user| &user.actor);
+
+    match handlers_inner::get_comments(parent, include_deleted, page, actor, comment_dao.inner())
+        .await
+    {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[delete("/comments/<comment_uuid>")]
+pub async fn delete_comment(
+    comment_uuid: Result<UuidParam, String>,
+    user: Result<AuthenticatedUser, APIError>,
+    comment_dao: &State<Box<dyn CommentDao + Send + Sync>>,
+) -> Result<(), APIError> {
+    let user = user?;
+
+    let comment_id = CommentId {
+        comment_uuid: comment_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+
+    match handlers_inner::delete_comment(comment_id, &user.actor, comment_dao.inner()).await {
+        Ok(_) => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[post("/answers/<answer_uuid>/accept")]
+pub async fn accept_answer(
+    answer_uuid: Result<UuidParam, String>,
+    user: Result<AuthenticatedUser, APIError>,
+    question_dao: &State<Box<dyn QuestionDao + Sync + Send>>,
+    answer_dao: &State<Box<dyn AnswerDao + Send + Sync>>,
+    notification_dao: &State<Box<dyn NotificationDao + Send + Sync>>,
+) -> Result<Json<QuestionDetail>, APIError> {
+    let user = user?;
+    let answer_id = AnswerId {
+        answer_uuid: answer_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+
+    match handlers_inner::accept_answer(
+        answer_id,
+        &user.actor,
+        question_dao.inner(),
+        answer_dao.inner(),
+        notification_dao.inner(),
+    )
+    .await
+    {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[delete("/answers/<answer_uuid>/accept")]
+pub async fn unaccept_answer(
+    answer_uuid: Result<UuidParam, String>,
+    user: Result<AuthenticatedUser, APIError>,
+    question_dao: &State<Box<dyn QuestionDao + Sync + Send>>,
+    answer_dao: &State<Box<dyn AnswerDao + Send + Sync>>,
+) -> Result<Json<QuestionDetail>, APIError> {
+    let user = user?;
+    let answer_id = AnswerId {
+        answer_uuid: answer_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+
+    match handlers_inner::unaccept_answer(
+        answer_id,
+        &user.actor,
+        question_dao.inner(),
+        answer_dao.inner(),
+    )
+    .await
+    {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[post("/answers/<answer_uuid>/vote", data = "<vote>")]
+pub async fn vote_answer(
+    answer_uuid: Result<UuidParam, String>,
+    vote: Json<Vote>,
+    user: Result<AuthenticatedUser, APIError>,
+    privileges: &State<PrivilegeTable>,
+    vote_dao: &State<Box<dyn VoteDao + Send + Sync>>,
+    answer_dao: &State<Box<dyn AnswerDao + Send + Sync>>,
+    events: &State<QuestionEvents>,
+) -> Result<Json<VoteSummary>, APIError> {
+    let user = user?;
+    let answer_id = AnswerId {
+        answer_uuid: answer_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+
+    match handlers_inner::vote_answer(
+        answer_id,
+        vote.0,
+        &user.actor,
+        privileges.inner(),
+        vote_dao.inner(),
+        answer_dao.inner(),
+        events.inner(),
+    )
+    .await
+    {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[delete("/answers/<answer_uuid>/vote")]
+pub async fn retract_answer_vote(
+    answer_uuid: Result<UuidParam, String>,
+    user: Result<AuthenticatedUser, APIError>,
+    vote_dao: &State<Box<dyn VoteDao + Send + Sync>>,
+    answer_dao: &State<Box<dyn AnswerDao + Send + Sync>>,
+    events: &State<QuestionEvents>,
+) -> Result<Json<VoteSummary>, APIError> {
+    let user = user?;
+    let answer_id = AnswerId {
+        answer_uuid: answer_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+
+    match handlers_inner::retract_answer_vote(
+        answer_id,
+        &user.actor,
+        vote_dao.inner(),
+        answer_dao.inner(),
+        events.inner(),
+    )
+    .await
+    {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[get("/tags?<cursor>&<limit>")]
+pub async fn get_tags(
+    cursor: Option<String>,
+    limit: Option<i64>,
+    tag_dao: &State<Box<dyn TagDao + Send + Sync>>,
+) -> Result<Json<Page<TagSummary>>, APIError> {
+    let page = PageRequest { cursor, limit };
+
+    match handlers_inner::get_tags(page, tag_dao.inner()).await {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Approved synonyms resolve to their canonical tag.
+#[get("/tags/<name>")]
+pub async fn get_tag(
+    name: String,
+    tag_dao: &State<Box<dyn TagDao + Send + Sync>>,
+) -> Result<Json<TagDetail>, APIError> {
+    match handlers_inner::get_tag(name, tag_dao.inner()).await {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[patch("/tags/<name>/wiki", data = "<update>")]
+pub async fn update_tag_wiki(
+    name: String,
+    update: Json<TagWikiUpdate>,
+    user: Result<AuthenticatedUser, APIError>,
+    privileges: &State<PrivilegeTable>,
+    tag_dao: &State<Box<dyn TagDao + Send + Sync>>,
+) -> Result<Json<TagDetail>, APIError> {
+    let user = user?;
+
+    match handlers_inner::update_tag_wiki(
+        name,
+        update.0,
+        &user.actor,
+        privileges.inner(),
+        tag_dao.inner(),
+    )
+    .await
+    {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[get("/tags/<name>/wiki/revisions")]
+pub async fn get_tag_wiki_revisions(
+    name: String,
+    tag_dao: &State<Box<dyn TagDao + Send + Sync>>,
+) -> Result<Json<Vec<TagWikiRevision>>, APIError> {
+    match handlers_inner::get_tag_wiki_revisions(name, tag_dao.inner()).await {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[get("/tags/<name>/synonyms")]
+pub async fn get_tag_synonyms(
+    name: String,
+    tag_dao: &State<Box<dyn TagDao + Send + Sync>>,
+) -> Result<Json<Vec<TagSynonym>>, APIError> {
+    match handlers_inner::get_tag_synonyms(name, tag_dao.inner()).await {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[post("/tags/<name>/synonyms", data = "<proposal>")]
+pub async fn propose_tag_synonym(
+    name: String,
+    proposal: Json<SynonymProposal>,
+    user: Result<AuthenticatedUser, APIError>,
+    tag_dao: &State<Box<dyn TagDao + Send + Sync>>,
+) -> Result<Json<TagSynonym>, APIError> {
+    let user = user?;
+
+    match handlers_inner::propose_tag_synonym(name, proposal.0, &user.actor, tag_dao.inner()).await
+    {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[post("/tags/<name>/synonyms/<synonym>/approve")]
+pub async fn approve_tag_synonym(
+    name: String,
+    synonym: String,
+    user: Result<AuthenticatedUser, APIError>,
+    tag_dao: &State<Box<dyn TagDao + Send + Sync>>,
+) -> Result<Json<TagSynonym>, APIError> {
+    let user = user?;
+
+    match handlers_inner::approve_tag_synonym(name, synonym, &user.actor, tag_dao.inner()).await {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// `q` is a search query such as `[rust] is:question "exact phrase" -excluded`.
+#[get("/search?<q>&<cursor>&<limit>")]
+pub async fn search(
+    q: Option<String>,
+    cursor: Option<String>,
+    limit: Option<i64>,
+    search_dao: &State<Box<dyn SearchDao + Send + Sync>>,
+) -> Result<Json<Page<SearchResult>>, APIError> {
+    let page = PageRequest { cursor, limit };
+
+    match handlers_inner::search(q.unwrap_or_default(), page, search_dao.inner()).await {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[post("/users", data = "<user>")]
+pub async fn create_user(
+    user: Json<User>,
+    user_dao: &State<Box<dyn UserDao + Send + Sync>>,
+) -> Result<Json<UserDetail>, APIError> {
+    match handlers_inner::create_user(user.0, user_dao.inner()).await {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[get("/users/<user_uuid>")]
+pub async fn get_user(
+    user_uuid: Result<UuidParam, String>,
+    user_dao: &State<Box<dyn UserDao + Send + Sync>>,
+) -> Result<Json<UserDetail>, APIError> {
+    let user_id = UserId {
+        user_uuid: user_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+
+    match handlers_inner::get_user(user_id, user_dao.inner()).await {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[put("/users/<user_uuid>/role", data = "<role_update>")]
+pub async fn update_user_role(
+    user_uuid: Result<UuidParam, String>,
+    role_update: Json<RoleUpdate>,
+    user: Result<AuthenticatedUser, APIError>,
+    user_dao: &State<Box<dyn UserDao + Send + Sync>>,
+) -> Result<Json<UserDetail>, APIError> {
+    let user = user?;
+    let user_id = UserId {
+        user_uuid: user_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+
+    match handlers_inner::update_user_role(user_id, role_update.0, &user.actor, user_dao.inner())
+        .await
+    {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[get("/privileges")]
+pub fn get_privileges(privileges: &State<PrivilegeTable>) -> Json<Vec<PrivilegeThreshold>> {
+    Json(handlers_inner::get_privileges(privileges.inner()))
+}
+
+#[get("/users/<user_uuid>/reputation?<cursor>&<limit>")]
+pub async fn get_reputation_history(
+    user_uuid: Result<UuidParam, String>,
+    cursor: Option<String>,
+    limit: Option<i64>,
+    reputation_dao: &State<Box<dyn ReputationDao + Send + Sync>>,
+) -> Result<Json<Page<ReputationEvent>>, APIError> {
+    let user_id = UserId {
+        user_uuid: user_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+    let page = PageRequest { cursor, limit };
+
+    match handlers_inner::get_reputation_history(user_id, page, reputation_dao.inner()).await {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[post("/reputation/recalculate")]
+pub async fn recalculate_reputation(
+    user: Result<AuthenticatedUser, APIError>,
+    reputation_dao: &State<Box<dyn ReputationDao + Send + Sync>>,
+) -> Result<Json<ReputationRecalculation>, APIError> {
+    let user = user?;
+
+    match handlers_inner::recalculate_reputation(&user.actor, reputation_dao.inner()).await {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[get("/users/<user_uuid>/badges")]
+pub async fn get_user_badges(
+    user_uuid: Result<UuidParam, String>,
+    badge_dao: &State<Box<dyn BadgeDao + Send + Sync>>,
+) -> Result<Json<Vec<UserBadge>>, APIError> {
+    let user_id = UserId {
+        user_uuid: user_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+
+    match handlers_inner::get_user_badges(user_id, badge_dao.inner()).await {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[get("/badges/<name>?<cursor>&<limit>")]
+pub async fn get_badge_recipients(
+    name: String,
+    cursor: Option<String>,
+    limit: Option<i64>,
+    badge_dao: &State<Box<dyn BadgeDao + Send + Sync>>,
+) -> Result<Json<Page<BadgeRecipient>>, APIError> {
+    let page = PageRequest { cursor, limit };
+
+    match handlers_inner::get_badge_recipients(name, page, badge_dao.inner()).await {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[post("/questions/<question_uuid>/bounty", data = "<offer>")]
+pub async fn start_bounty(
+    question_uuid: Result<UuidParam, String>,
+    offer: Json<BountyOffer>,
+    user: Result<AuthenticatedUser, APIError>,
+    privileges: &State<PrivilegeTable>,
+    bounty_dao: &State<Box<dyn BountyDao + Send + Sync>>,
+) -> Result<Json<Bounty>, APIError> {
+    let question_id = QuestionId {
+        question_uuid: question_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+    let user = user?;
+
+    match handlers_inner::start_bounty(
+        question_id,
+        offer.0,
+        &user.actor,
+        privileges.inner(),
+        bounty_dao.inner(),
+    )
+    .await
+    {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[post("/questions/<question_uuid>/bounty/award", data = "<award>")]
+pub async fn award_bounty(
+    question_uuid: Result<UuidParam, String>,
+    award: Json<BountyAward>,
+    user: Result<AuthenticatedUser, APIError>,
     answer_dao: &State<Box<dyn AnswerDao + Send + Sync>>,
+    bounty_dao: &State<Box<dyn BountyDao + Send + Sync>>,
+    notification_dao: &State<Box<dyn NotificationDao + Send + Sync>>,
+) -> Result<Json<Bounty>, APIError> {
+    let question_id = QuestionId {
+        question_uuid: question_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+    let user = user?;
+
+    match handlers_inner::award_bounty(
+        question_id,
+        award.0,
+        &user.actor,
+        answer_dao.inner(),
+        bounty_dao.inner(),
+        notification_dao.inner(),
+    )
+    .await
+    {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[get("/questions/featured?<cursor>&<limit>")]
+pub async fn get_featured_questions(
+    cursor: Option<String>,
+    limit: Option<i64>,
+    bounty_dao: &State<Box<dyn BountyDao + Send + Sync>>,
+) -> Result<Json<Page<FeaturedQuestion>>, APIError> {
+    let page = PageRequest { cursor, limit };
+
+    match handlers_inner::get_featured_questions(page, bounty_dao.inner()).await {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[post("/questions/<question_uuid>/follow")]
+pub async fn follow_question(
+    question_uuid: Result<UuidParam, String>,
+    user: Result<AuthenticatedUser, APIError>,
+    follow_dao: &State<Box<dyn FollowDao + Send + Sync>>,
+) -> Result<Json<FollowedQuestion>, APIError> {
+    let question_id = QuestionId {
+        question_uuid: question_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+    let user = user?;
+
+    match handlers_inner::follow_question(question_id, &user.actor, follow_dao.inner()).await {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[delete("/questions/<question_uuid>/follow")]
+pub async fn unfollow_question(
+    question_uuid: Result<UuidParam, String>,
+    user: Result<AuthenticatedUser, APIError>,
+    follow_dao: &State<Box<dyn FollowDao + Send + Sync>>,
+) -> Result<(), APIError> {
+    let question_id = QuestionId {
+        question_uuid: question_uuid.map_err(APIError::BadRequest)?.0.to_string(),
+    };
+    let user = user?;
+
+    match handlers_inner::unfollow_question(question_id, &user.actor, follow_dao.inner()).await {
+        Ok(_) => Ok(()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[get("/follows?<cursor>&<limit>")]
+pub async fn get_followed_questions(
+    cursor: Option<String>,
+    limit: Option<i64>,
+    user: Result<AuthenticatedUser, APIError>,
+    follow_dao: &State<Box<dyn FollowDao + Send + Sync>>,
+) -> Result<Json<Page<FollowedQuestion>>, APIError> {
+    let page = PageRequest { cursor, limit };
+    let user = user?;
+
+    match handlers_inner::get_followed_questions(page, &user.actor, follow_dao.inner()).await {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[get("/notifications?<cursor>&<limit>&<unread>")]
+pub async fn get_notifications(
+    cursor: Option<String>,
+    limit: Option<i64>,
+    unread: Option<bool>,
+    user: Result<AuthenticatedUser, APIError>,
+    notification_dao: &State<Box<dyn NotificationDao + Send + Sync>>,
+) -> Result<Json<NotificationInbox>, APIError> {
+    let page = PageRequest { cursor, limit };
+    let user = user?;
+
+    match handlers_inner::get_notifications(
+        unread.unwrap_or_default(),
+        page,
+        &user.actor,
+        notification_dao.inner(),
+    )
+    .await
+    {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[post("/notifications/<notification_uuid>/read")]
+pub async fn mark_notification_read(
+    notification_uuid: Result<UuidParam, String>,
+    user: Result<AuthenticatedUser, APIError>,
+    notification_dao: &State<Box<dyn NotificationDao + Send + Sync>>,
+) -> Result<Json<UnreadCount>, APIError> {
+    let notification_uuid = notification_uuid
+        .map_err(APIError::BadRequest)?
+        .0
+        .to_string();
+    let user = user?;
+
+    match handlers_inner::mark_notification_read(
+        notification_uuid,
+        &user.actor,
+        notification_dao.inner(),
+    )
+    .await
+    {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[post("/notifications/read")]
+pub async fn mark_all_notifications_read(
+    user: Result<AuthenticatedUser, APIError>,
+    notification_dao: &State<Box<dyn NotificationDao + Send + Sync>>,
+) -> Result<Json<UnreadCount>, APIError> {
+    let user = user?;
+
+    match handlers_inner::mark_all_notifications_read(&user.actor, notification_dao.inner()).await {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[get("/notifications/preferences")]
+pub async fn get_notification_preferences(
+    user: Result<AuthenticatedUser, APIError>,
+    notification_dao: &State<Box<dyn NotificationDao + Send + Sync>>,
+) -> Result<Json<Vec<NotificationPreference>>, APIError> {
+    let user = user?;
+
+    match handlers_inner::get_notification_preferences(&user.actor, notification_dao.inner()).await
+    {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[put("/notifications/preferences", data = "<preferences>")]
+pub async fn update_notification_preferences(
+    preferences: Json<Vec<NotificationPreference>>,
+    user: Result<AuthenticatedUser, APIError>,
+    notification_dao: &State<Box<dyn NotificationDao + Send + Sync>>,
+) -> Result<Json<Vec<NotificationPreference>>, APIError> {
+    let user = user?;
+
+    match handlers_inner::update_notification_preferences(
+        preferences.0,
+        &user.actor,
+        notification_dao.inner(),
+    )
+    .await
+    {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[post("/login", data = "<credentials>")]
+pub async fn login(
+    credentials: Json<Credentials>,
+    user_dao: &State<Box<dyn UserDao + Send + Sync>>,
+    session_dao: &State<Box<dyn SessionDao + Send + Sync>>,
+) -> Result<Json<Session>, APIError> {
+    match handlers_inner::login(credentials.0, user_dao.inner(), session_dao.inner()).await {
+        Ok(res) => Ok(Json(res)),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[post("/logout")]
+pub async fn logout(
+    user: Result<AuthenticatedUser, APIError>,
+    session_dao: &State<Box<dyn SessionDao + Send + Sync>>,
 ) -> Result<(), APIError> {
-    match handlers_inner::delete_answer(answer_uuid.0, answer_dao.inner()).await {
+    match handlers_inner::logout(user?.token, session_dao.inner()).await {
         Ok(_) => Ok(()),
         Err(e) => Err(e.into()),
     }
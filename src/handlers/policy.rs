@@ -0,0 +1,249 @@
+use crate::models::{Actor, Privilege, PrivilegeTable, Role};
+
+use super::handlers_inner::HandlerError;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PostAction {
+    Edit,
+    Delete,
+}
+
+impl PostAction {
+    fn as_str(&self) -> &'static str {
+        match self {
+            PostAction::Edit => "edit",
+            PostAction::Delete => "delete",
+        }
+    }
+}
+
+/// Authors may act on their own posts, moderators and admins on any post.
+/// Posts whose author account is gone can only be handled by moderators.
+pub fn authorize_post(
+    actor: &Actor,
+    action: PostAction,
+    post: &str,
+    author_uuid: Option<&str>,
+) -> Result<(), HandlerError> {
+    if actor.role.is_moderator() || author_uuid == Some(actor.user_uuid.as_str()) {
+        Ok(())
+    } else {
+        Err(HandlerError::Forbidden(format!(
+            "Only the author or a moderator can {} this {post}",
+            action.as_str()
+        )))
+    }
+}
+
+/// Like [`authorize_post`], except that users with the edit privilege may
+/// also edit posts of others.
+pub fn authorize_edit(
+    actor: &Actor,
+    post: &str,
+    author_uuid: Option<&str>,
+    privileges: &PrivilegeTable,
+) -> Result<(), HandlerError> {
+    authorize_post(actor, PostAction::Edit, post, author_uuid)
+        .or_else(|_| authorize_privilege(actor, Privilege::EditPosts, privileges))
+}
+
+/// Moderators hold every privilege; other users earn them with reputation.
+pub fn authorize_privilege(
+    actor: &Actor,
+    privilege: Privilege,
+    privileges: &PrivilegeTable,
+) -> Result<(), HandlerError> {
+    let required = privileges.required(privilege);
+
+    if actor.role.is_moderator() || actor.reputation >= required {
+        Ok(())
+    } else {
+        Err(HandlerError::Forbidden(format!(
+            "Missing the {} privilege, which requires {required} reputation (you have {})",
+            privilege.as_str(),
+            actor.reputation
+        )))
+    }
+}
+
+/// Only the asker decides which answer solved their question; unlike edits,
+/// moderators cannot do it for them.
+pub fn authorize_accept(
+    actor: &Actor,
+    question_author_uuid: Option<&str>,
+) -> Result<(), HandlerError> {
+    if question_author_uuid == Some(actor.user_uuid.as_str()) {
+        Ok(())
+    } else {
+        Err(HandlerError::Forbidden(
+            "Only the author of the question can accept an answer".to_string(),
+        ))
+    }
+}
+
+/// Bounties are awarded by the user who offered them, like acceptances.
+pub fn authorize_bounty_award(actor: &Actor, offered_by: Option<&str>) -> Result<(), HandlerError> {
+    if offered_by == Some(actor.user_uuid.as_str()) {
+        Ok(())
+    } else {
+        Err(HandlerError::Forbidden(
+            "Only the user who offered the bounty can award it".to_string(),
+        ))
+    }
+}
+
+/// Actions reserved to moderators and admins, described by `action` in the
+/// error, e.g. "approve tag synonyms".
+pub fn authorize_moderator(actor: &Actor, action: &str) -> Result<(), HandlerError> {
+    if actor.role.is_moderator() {
+        Ok(())
+    } else {
+        Err(HandlerError::Forbidden(format!(
+            "Only a moderator can {action}"
+        )))
+    }
+}
+
+/// Only admins may change roles, and not their own, so there is always an
+/// admin left to undo a mistake.
+pub fn authorize_role_change(actor: &Actor, user_uuid: &str) -> Result<(), HandlerError> {
+    if actor.role != Role::Admin {
+        return Err(HandlerError::Forbidden(
+            "Only an admin can change roles".to_string(),
+        ));
+    }
+
+    if actor.user_uuid == user_uuid {
+        return Err(HandlerError::Forbidden(
+            "Admins cannot change their own role".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn actor(role: Role) -> Actor {
+        Actor {
+            user_uuid: "789".to_string(),
+            role,
+            reputation: 1,
+        }
+    }
+
+    #[test]
+    fn author_should_be_allowed_to_act_on_own_post() {
+        let result = authorize_post(
+            &actor(Role::User),
+            PostAction::Delete,
+            "question",
+            Some("789"),
+        );
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn user_should_be_forbidden_to_act_on_others_post() {
+        let result = authorize_post(&actor(Role::User), PostAction::Edit, "answer", Some("123"));
+
+        assert_eq!(
+            result,
+            Err(HandlerError::Forbidden(
+                "Only the author or a moderator can edit this answer".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn user_should_be_forbidden_to_act_on_orphaned_post() {
+        let result = authorize_post(&actor(Role::User), PostAction::Delete, "question", None);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn moderators_and_admins_should_be_allowed_to_act_on_any_post() {
+        for role in [Role::Moderator, Role::Admin] {
+            let result = authorize_post(&actor(role), PostAction::Delete, "question", None);
+
+            assert!(result.is_ok());
+        }
+    }
+
+    #[test]
+    fn user_with_edit_privilege_should_edit_others_post() {
+        let privileges = PrivilegeTable::default();
+        let editor = Actor {
+            reputation: privileges.edit_posts,
+            ..actor(Role::User)
+        };
+
+        assert!(authorize_edit(&editor, "answer", Some("123"), &privileges).is_ok());
+        assert!(authorize_edit(&actor(Role::User), "answer", Some("789"), &privileges).is_ok());
+        assert_eq!(
+            authorize_edit(&actor(Role::User), "answer", Some("123"), &privileges),
+            Err(HandlerError::Forbidden(
+                "Missing the edit_posts privilege, which requires 2000 reputation (you have 1)"
+                    .to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn privileges_should_require_reputation_unless_moderator() {
+        let privileges = PrivilegeTable::default();
+        let trusted = Actor {
+            reputation: privileges.close_vote,
+            ..actor(Role::User)
+        };
+
+        assert!(authorize_privilege(&trusted, Privilege::CloseVote, &privileges).is_ok());
+        assert!(
+            authorize_privilege(&actor(Role::Moderator), Privilege::CloseVote, &privileges).is_ok()
+        );
+        assert_eq!(
+            authorize_privilege(&actor(Role::User), Privilege::VoteDown, &privileges),
+            Err(HandlerError::Forbidden(
+                "Missing the vote_down privilege, which requires 125 reputation (you have 1)"
+                    .to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn only_question_author_should_accept_answers() {
+        assert!(authorize_accept(&actor(Role::User), Some("789")).is_ok());
+        assert!(authorize_accept(&actor(Role::Admin), Some("123")).is_err());
+        assert!(authorize_accept(&actor(Role::User), None).is_err());
+    }
+
+    #[test]
+    fn only_bounty_offerer_should_award_bounty() {
+        assert!(authorize_bounty_award(&actor(Role::User), Some("789")).is_ok());
+        assert!(authorize_bounty_award(&actor(Role::Moderator), Some("123")).is_err());
+        assert!(authorize_bounty_award(&actor(Role::User), None).is_err());
+    }
+
+    #[test]
+    fn only_moderators_and_admins_should_moderate() {
+        assert!(authorize_moderator(&actor(Role::Moderator), "approve tag synonyms").is_ok());
+        assert!(authorize_moderator(&actor(Role::Admin), "approve tag synonyms").is_ok());
+        assert_eq!(
+            authorize_moderator(&actor(Role::User), "approve tag synonyms"),
+            Err(HandlerError::Forbidden(
+                "Only a moderator can approve tag synonyms".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn only_admins_should_change_roles_of_others() {
+        assert!(authorize_role_change(&actor(Role::Admin), "123").is_ok());
+        assert!(authorize_role_change(&actor(Role::Admin), "789").is_err());
+        assert!(authorize_role_change(&actor(Role::Moderator), "123").is_err());
+    }
+}
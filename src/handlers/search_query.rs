@@ -0,0 +1,330 @@
+use rocket::time::{Date, Month};
+
+use crate::models::{Bounds, PostType, SearchQuery, SearchTerm};
+
+use super::handlers_inner::{normalize_tag, HandlerError};
+
+/// Parses a search query into its terms. Errors quote the offending token.
+pub fn parse(input: &str) -> Result<SearchQuery, HandlerError> {
+    let mut terms = Vec::new();
+    let mut rest = input.trim_start();
+
+    while !rest.is_empty() {
+        let (term, remainder) = parse_term(rest)?;
+
+        terms.push(term);
+        rest = remainder.trim_start();
+    }
+
+    Ok(SearchQuery { terms })
+}
+
+fn invalid(problem: &str, token: &str) -> HandlerError {
+    HandlerError::BadRequest(format!("{problem} in search query: {token}"))
+}
+
+/// Parses the term at the start of `input` and returns it with the rest of the
+/// input.
+fn parse_term(input: &str) -> Result<(SearchTerm, &str), HandlerError> {
+    let (excluded, body) = match input.strip_prefix('-') {
+        Some(body) if body.starts_with(|c: char| !c.is_whitespace()) => (true, body),
+        _ => (false, input),
+    };
+
+    if let Some(phrase) = body.strip_prefix('"') {
+        let Some((phrase, rest)) = phrase.split_once('"') else {
+            return Err(invalid("Unterminated phrase", input.trim_end()));
+        };
+
+        if phrase.trim().is_empty() {
+            return Err(invalid("Empty phrase", &input[..input.len() - rest.len()]));
+        }
+
+        let phrase = phrase.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        return Ok((SearchTerm::Phrase { phrase, excluded }, rest));
+    }
+
+    let end = body
+        .find(|c: char| c.is_whitespace() || c == '"')
+        .unwrap_or(body.len());
+    let (word, rest) = body.split_at(end);
+    let token = &input[..input.len() - rest.len()];
+
+    if let Some(tag) = word.strip_prefix('[') {
+        let Some(tag) = tag.strip_suffix(']') else {
+            return Err(invalid("Unterminated tag", token));
+        };
+
+        let tag = normalize_tag(tag).map_err(|_| invalid("Invalid tag", token))?;
+
+        return Ok((SearchTerm::Tag { tag, excluded }, rest));
+    }
+
+    match word.split_once(':') {
+        // A trailing colon, as in `error:`, or a path like `std::io` is text.
+        Some((name, value))
+            if !name.is_empty()
+                && name.chars().all(|c| c.is_ascii_alphabetic())
+                && !value.is_empty()
+                && !value.starts_with(':') =>
+        {
+            if excluded {
+                return Err(invalid("Operators cannot be excluded", token));
+            }
+
+            let term = parse_operator(name, value, token)?;
+
+            Ok((term, rest))
+        }
+        _ => Ok((
+            SearchTerm::Word {
+                word: word.to_string(),
+                excluded,
+            },
+            rest,
+        )),
+    }
+}
+
+fn parse_operator(name: &str, value: &str, token: &str) -> Result<SearchTerm, HandlerError> {
+    match name.to_lowercase().as_str() {
+        "is" => match value.to_lowercase().as_str() {
+            "question" => Ok(SearchTerm::PostType(PostType::Question)),
+            "answer" => Ok(SearchTerm::PostType(PostType::Answer)),
+            _ => Err(invalid("Unknown post type", token)),
+        },
+        "answers" => {
+            parse_bounds(value, token, "Invalid number", parse_number).map(SearchTerm::Answers)
+        }
+        "score" => {
+            parse_bounds(value, token, "Invalid number", parse_number).map(SearchTerm::Score)
+        }
+        "created" => {
+            parse_bounds(value, token, "Invalid date", parse_period).map(SearchTerm::Created)
+        }
+        _ => Err(invalid("Unknown operator", token)),
+    }
+}
+
+/// Parses `value`, `from..to`, `from..` or `..to`. `parse` returns the first
+/// and last value covered by a bound, which differ for periods such as a
+/// whole year.
+fn parse_bounds<T: PartialOrd + Copy>(
+    value: &str,
+    token: &str,
+    problem: &str,
+    parse: impl Fn(&str) -> Option<(T, T)>,
+) -> Result<Bounds<T>, HandlerError> {
+    let bound = |s: &str| parse(s).ok_or_else(|| invalid(problem, token));
+
+    let bounds = match value.split_once("..") {
+        Some(("", "")) => return Err(invalid("Empty range", token)),
+        Some((from, to)) => Bounds {
+            min: (!from.is_empty())
+                .then(|| bound(from).map(|(first, _)| first))
+                .transpose()?,
+            max: (!to.is_empty())
+                .then(|| bound(to).map(|(_, last)| last))
+                .transpose()?,
+        },
+        None => {
+            let (first, last) = bound(value)?;
+
+            Bounds {
+                min: Some(first),
+                max: Some(last),
+            }
+        }
+    };
+
+    match (bounds.min, bounds.max) {
+        (Some(min), Some(max)) if min > max => Err(invalid("Empty range", token)),
+        _ => Ok(bounds),
+    }
+}
+
+fn parse_number(value: &str) -> Option<(i64, i64)> {
+    value.parse().ok().map(|n| (n, n))
+}
+
+/// Parses `YYYY`, `YYYY-MM` or `YYYY-MM-DD` into its first and last day.
+fn parse_period(value: &str) -> Option<(Date, Date)> {
+    let parts: Vec<&str> = value.split('-').collect();
+
+    let year = match parts.first() {
+        Some(year) if year.len() == 4 => year.parse::<i32>().ok()?,
+        _ => return None,
+    };
+    let month = |m: &str| m.parse::<u8>().ok().and_then(|m| Month::try_from(m).ok());
+
+    match parts.as_slice() {
+        [_] => Some((
+            Date::from_calendar_date(year, Month::January, 1).ok()?,
+            Date::from_calendar_date(year, Month::December, 31).ok()?,
+        )),
+        [_, m] => {
+            let month = month(m)?;
+
+            Some((
+                Date::from_calendar_date(year, month, 1).ok()?,
+                Date::from_calendar_date(year, month, month.length(year)).ok()?,
+            ))
+        }
+        [_, m, d] => {
+            let date = Date::from_calendar_date(year, month(m)?, d.parse().ok()?).ok()?;
+
+            Some((date, date))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: Month, day: u8) -> Date {
+        Date::from_calendar_date(year, month, day).unwrap()
+    }
+
+    fn word(word: &str, excluded: bool) -> SearchTerm {
+        SearchTerm::Word {
+            word: word.to_string(),
+            excluded,
+        }
+    }
+
+    fn error(message: &str) -> Result<SearchQuery, HandlerError> {
+        Err(HandlerError::BadRequest(message.to_string()))
+    }
+
+    #[test]
+    fn parse_should_read_every_kind_of_term() {
+        let query = parse(
+            r#"[Rust] -[python] is:question answers:0 score:-1..5 created:2024.. "exact  phrase" -excluded -"not this" std::io"#,
+        );
+
+        assert_eq!(
+            query,
+            Ok(SearchQuery {
+                terms: vec![
+                    SearchTerm::Tag {
+                        tag: "rust".to_string(),
+                        excluded: false
+                    },
+                    SearchTerm::Tag {
+                        tag: "python".to_string(),
+                        excluded: true
+                    },
+                    SearchTerm::PostType(PostType::Question),
+                    SearchTerm::Answers(Bounds {
+                        min: Some(0),
+                        max: Some(0)
+                    }),
+                    SearchTerm::Score(Bounds {
+                        min: Some(-1),
+                        max: Some(5)
+                    }),
+                    SearchTerm::Created(Bounds {
+                        min: Some(date(2024, Month::January, 1)),
+                        max: None
+                    }),
+                    SearchTerm::Phrase {
+                        phrase: "exact phrase".to_string(),
+                        excluded: false
+                    },
+                    word("excluded", true),
+                    SearchTerm::Phrase {
+                        phrase: "not this".to_string(),
+                        excluded: true
+                    },
+                    word("std::io", false),
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn parse_should_expand_periods_to_whole_days() {
+        let bounds = |value: &str| match parse(&format!("created:{value}")) {
+            Ok(SearchQuery { terms }) => match terms.as_slice() {
+                [SearchTerm::Created(bounds)] => *bounds,
+                terms => panic!("Unexpected terms: {terms:?}"),
+            },
+            Err(e) => panic!("Unexpected error: {e:?}"),
+        };
+
+        assert_eq!(
+            bounds("2024-02"),
+            Bounds {
+                min: Some(date(2024, Month::February, 1)),
+                max: Some(date(2024, Month::February, 29))
+            }
+        );
+        assert_eq!(
+            bounds("..2023"),
+            Bounds {
+                min: None,
+                max: Some(date(2023, Month::December, 31))
+            }
+        );
+        assert_eq!(
+            bounds("2024-03-15..2024-04"),
+            Bounds {
+                min: Some(date(2024, Month::March, 15)),
+                max: Some(date(2024, Month::April, 30))
+            }
+        );
+    }
+
+    #[test]
+    fn parse_should_treat_trailing_colons_and_lone_dashes_as_text() {
+        assert_eq!(
+            parse("TypeError: -"),
+            Ok(SearchQuery {
+                terms: vec![word("TypeError:", false), word("-", false)]
+            })
+        );
+    }
+
+    #[test]
+    fn parse_should_point_at_the_offending_token() {
+        assert_eq!(
+            parse("rust foo:bar"),
+            error("Unknown operator in search query: foo:bar")
+        );
+        assert_eq!(
+            parse("is:comment"),
+            error("Unknown post type in search query: is:comment")
+        );
+        assert_eq!(
+            parse("answers:many"),
+            error("Invalid number in search query: answers:many")
+        );
+        assert_eq!(
+            parse("created:2024-13"),
+            error("Invalid date in search query: created:2024-13")
+        );
+        assert_eq!(
+            parse("score:5..1"),
+            error("Empty range in search query: score:5..1")
+        );
+        assert_eq!(
+            parse("-is:answer"),
+            error("Operators cannot be excluded in search query: -is:answer")
+        );
+        assert_eq!(
+            parse("[rust tokio"),
+            error("Unterminated tag in search query: [rust")
+        );
+        assert_eq!(
+            parse("[bad!tag]"),
+            error("Invalid tag in search query: [bad!tag]")
+        );
+        assert_eq!(
+            parse(r#"rust "exact phrase "#),
+            error(r#"Unterminated phrase in search query: "exact phrase"#)
+        );
+    }
+}
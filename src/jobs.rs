@@ -0,0 +1,76 @@
+use std::time::Duration;
+
+use crate::persistance::{
+    answer_dao::AnswerDao, badge_dao::BadgeDao, bounty_dao::BountyDao, question_dao::QuestionDao,
+};
+
+/// How often posts past their retention period are looked for.
+const PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+/// How often every user's badges are re-evaluated.
+const BADGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+/// How often bounties past their expiry are looked for.
+const BOUNTY_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Hard-deletes posts that were deleted more than `retention_days` ago, at
+/// startup and then every `PURGE_INTERVAL`.
+pub fn spawn_purge(
+    question_dao: Box<dyn QuestionDao + Send + Sync>,
+    answer_dao: Box<dyn AnswerDao + Send + Sync>,
+    retention_days: i32,
+) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(PURGE_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            match question_dao.purge_deleted_questions(retention_days).await {
+                Ok(0) => {}
+                Ok(count) => info!("Purged {count} deleted questions"),
+                Err(e) => error!("Could not purge deleted questions: {e:?}"),
+            }
+
+            match answer_dao.purge_deleted_answers(retention_days).await {
+                Ok(0) => {}
+                Ok(count) => info!("Purged {count} deleted answers"),
+                Err(e) => error!("Could not purge deleted answers: {e:?}"),
+            }
+        }
+    });
+}
+
+/// Awards badges missed by the event-driven checks, such as those earned
+/// before a badge was introduced, at startup and then every `BADGE_INTERVAL`.
+pub fn spawn_badge_awards(badge_dao: Box<dyn BadgeDao + Send + Sync>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(BADGE_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            match badge_dao.award_badges().await {
+                Ok(0) => {}
+                Ok(count) => info!("Awarded {count} badges"),
+                Err(e) => error!("Could not award badges: {e:?}"),
+            }
+        }
+    });
+}
+
+/// Expires bounties that ran out without being awarded, refunding their
+/// offerers, at startup and then every `BOUNTY_INTERVAL`.
+pub fn spawn_bounty_expiry(bounty_dao: Box<dyn BountyDao + Send + Sync>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(BOUNTY_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            match bounty_dao.expire_bounties().await {
+                Ok(0) => {}
+                Ok(count) => info!("Expired {count} bounties"),
+                Err(e) => error!("Could not expire bounties: {e:?}"),
+            }
+        }
+    });
+}
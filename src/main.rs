@@ -7,15 +7,28 @@ extern crate pretty_env_logger;
 
 mod cors;
 mod handlers;
+mod jobs;
 mod models;
 mod persistance;
 
 use cors::*;
 use dotenvy::dotenv;
 use handlers::*;
+use models::{Privilege, PrivilegeTable, DEFAULT_DELETED_POST_RETENTION_DAYS};
 use persistance::{
     answer_dao::{AnswerDao, AnswerDaoImpl},
+    badge_dao::{BadgeDao, BadgeDaoImpl},
+    bounty_dao::{BountyDao, BountyDaoImpl},
+    comment_dao::{CommentDao, CommentDaoImpl},
+    follow_dao::{FollowDao, FollowDaoImpl},
+    notification_dao::{NotificationDao, NotificationDaoImpl},
     question_dao::{QuestionDao, QuestionDaoImpl},
+    reputation_dao::{ReputationDao, ReputationDaoImpl},
+    search_dao::{SearchDao, SearchDaoImpl},
+    session_dao::{SessionDao, SessionDaoImpl},
+    tag_dao::{TagDao, TagDaoImpl},
+    user_dao::{UserDao, UserDaoImpl},
+    vote_dao::{VoteDao, VoteDaoImpl},
 };
 use sqlx::postgres::PgPoolOptions;
 
@@ -30,22 +43,137 @@ async fn rocket() -> _ {
         .await
         .expect("Failed to create Postgres connection pool!");
 
+    let retention_days = std::env::var("DELETED_POST_RETENTION_DAYS")
+        .map(|days| {
+            days.parse::<u16>()
+                .expect("DELETED_POST_RETENTION_DAYS must be a number of days.")
+                .into()
+        })
+        .unwrap_or(DEFAULT_DELETED_POST_RETENTION_DAYS);
+
+    let mut privileges = PrivilegeTable::default();
+
+    for privilege in Privilege::ALL {
+        let var = format!("PRIVILEGE_{}", privilege.as_str().to_uppercase());
+
+        if let Ok(reputation) = std::env::var(&var) {
+            let reputation = reputation
+                .parse::<i32>()
+                .unwrap_or_else(|_| panic!("{var} must be a reputation amount."));
+
+            privileges.set(privilege, reputation);
+        }
+    }
+
+    jobs::spawn_purge(
+        Box::new(QuestionDaoImpl::new(pool.clone())),
+        Box::new(AnswerDaoImpl::new(pool.clone())),
+        retention_days,
+    );
+    jobs::spawn_badge_awards(Box::new(BadgeDaoImpl::new(pool.clone())));
+    jobs::spawn_bounty_expiry(Box::new(BountyDaoImpl::new(pool.clone())));
+
     let question_dao = QuestionDaoImpl::new(pool.clone());
-    let answer_dao = AnswerDaoImpl::new(pool);
+    let answer_dao = AnswerDaoImpl::new(pool.clone());
+    let user_dao = UserDaoImpl::new(pool.clone());
+    let session_dao = SessionDaoImpl::new(pool.clone());
+    let vote_dao = VoteDaoImpl::new(pool.clone());
+    let tag_dao = TagDaoImpl::new(pool.clone());
+    let comment_dao = CommentDaoImpl::new(pool.clone());
+    let reputation_dao = ReputationDaoImpl::new(pool.clone());
+    let badge_dao = BadgeDaoImpl::new(pool.clone());
+    let bounty_dao = BountyDaoImpl::new(pool.clone());
+    let follow_dao = FollowDaoImpl::new(pool.clone());
+    let notification_dao = NotificationDaoImpl::new(pool.clone());
+    let search_dao = SearchDaoImpl::new(pool);
 
     rocket::build()
         .mount(
             "/",
             routes![
                 create_question,
+                get_similar_questions,
+                get_question,
                 get_questions,
+                question_events,
+                update_question,
+                get_question_revisions,
+                diff_question_revisions,
+                rollback_question,
                 delete_question,
+                delete_question_legacy,
+                undelete_question,
+                vote_to_close,
+                vote_to_reopen,
+                lock_question,
+                unlock_question,
+                start_bounty,
+                award_bounty,
+                get_featured_questions,
+                follow_question,
+                unfollow_question,
+                get_followed_questions,
                 create_answer,
+                get_answer,
+                update_answer,
+                get_answer_revisions,
+                diff_answer_revisions,
+                rollback_answer,
                 get_answers,
-                delete_answer
+                get_answers_legacy,
+                delete_answer,
+                delete_answer_legacy,
+                undelete_answer,
+                create_question_comment,
+                create_answer_comment,
+                get_question_comments,
+                get_answer_comments,
+                delete_comment,
+                accept_answer,
+                unaccept_answer,
+                vote_question,
+                retract_question_vote,
+                vote_answer,
+                retract_answer_vote,
+                get_tags,
+                get_tag,
+                update_tag_wiki,
+                get_tag_wiki_revisions,
+                get_tag_synonyms,
+                propose_tag_synonym,
+                approve_tag_synonym,
+                search,
+                create_user,
+                get_user,
+                update_user_role,
+                get_privileges,
+                get_reputation_history,
+                recalculate_reputation,
+                get_user_badges,
+                get_badge_recipients,
+                get_notifications,
+                mark_notification_read,
+                mark_all_notifications_read,
+                get_notification_preferences,
+                update_notification_preferences,
+                login,
+                logout
             ],
         )
         .attach(CORS)
+        .manage(privileges)
+        .manage(QuestionEvents::new())
         .manage(Box::new(question_dao) as Box<dyn QuestionDao + Send + Sync>)
         .manage(Box::new(answer_dao) as Box<dyn AnswerDao + Send + Sync>)
+        .manage(Box::new(user_dao) as Box<dyn UserDao + Send + Sync>)
+        .manage(Box::new(session_dao) as Box<dyn SessionDao + Send + Sync>)
+        .manage(Box::new(vote_dao) as Box<dyn VoteDao + Send + Sync>)
+        .manage(Box::new(tag_dao) as Box<dyn TagDao + Send + Sync>)
+        .manage(Box::new(comment_dao) as Box<dyn CommentDao + Send + Sync>)
+        .manage(Box::new(reputation_dao) as Box<dyn ReputationDao + Send + Sync>)
+        .manage(Box::new(badge_dao) as Box<dyn BadgeDao + Send + Sync>)
+        .manage(Box::new(bounty_dao) as Box<dyn BountyDao + Send + Sync>)
+        .manage(Box::new(follow_dao) as Box<dyn FollowDao + Send + Sync>)
+        .manage(Box::new(notification_dao) as Box<dyn NotificationDao + Send + Sync>)
+        .manage(Box::new(search_dao) as Box<dyn SearchDao + Send + Sync>)
 }
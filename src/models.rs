@@ -1,10 +1,18 @@
+use std::str::FromStr;
+
 use serde::{Deserialize, Serialize};
+use sqlx::types::time::{Date, PrimitiveDateTime};
 use thiserror::Error;
 
 #[derive(Serialize, Deserialize)]
 pub struct Question {
     pub title: String,
     pub description: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Set from the authenticated user rather than the request body.
+    #[serde(skip_deserializing)]
+    pub author_uuid: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -13,6 +21,16 @@ pub struct QuestionDetail {
     pub title: String,
     pub description: String,
     pub created_at: String,
+    pub author_uuid: Option<String>,
+    pub score: i32,
+    pub accepted_answer_uuid: Option<String>,
+    pub tags: Vec<String>,
+    pub comment_count: i64,
+    pub state: QuestionState,
+    pub close_reason: Option<CloseReason>,
+    pub duplicate_of: Option<String>,
+    /// Only set on deleted questions listed for moderators.
+    pub deleted_at: Option<String>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -20,10 +38,265 @@ pub struct QuestionId {
     pub question_uuid: String,
 }
 
+#[derive(FromFormField, Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum QuestionSort {
+    #[default]
+    #[field(value = "newest")]
+    Newest,
+    #[field(value = "oldest")]
+    Oldest,
+    #[field(value = "most_answered")]
+    MostAnswered,
+}
+
+impl QuestionSort {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuestionSort::Newest => "newest",
+            QuestionSort::Oldest => "oldest",
+            QuestionSort::MostAnswered => "most_answered",
+        }
+    }
+}
+
+/// Sorting and filtering options for question listings. Timestamps use the
+/// `YYYY-MM-DDTHH:MM[:SS]` format when passed as query parameters.
+#[derive(FromForm, Debug, Clone, Default, PartialEq)]
+pub struct QuestionQuery {
+    #[field(default = QuestionSort::Newest)]
+    pub sort: QuestionSort,
+    pub created_after: Option<PrimitiveDateTime>,
+    pub created_before: Option<PrimitiveDateTime>,
+    pub unanswered: bool,
+    /// Space separated tags, e.g. `tagged=rust+tokio`. Only questions carrying
+    /// all of them are listed.
+    pub tagged: Option<String>,
+    /// Also lists deleted questions. Reserved to moderators.
+    pub include_deleted: bool,
+    /// Only lists the questions followed by the session user.
+    pub followed: bool,
+}
+
+impl QuestionQuery {
+    /// Distinct tags to filter on, lowercased like stored tags.
+    pub fn tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = self
+            .tagged
+            .as_deref()
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(str::to_lowercase)
+            .collect();
+
+        tags.sort();
+        tags.dedup();
+        tags
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct QuestionUpdate {
+    pub title: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct QuestionRevision {
+    pub question_uuid: String,
+    pub revision: i32,
+    pub title: String,
+    pub description: String,
+    pub created_at: String,
+}
+
+/// An existing question resembling one being asked, with a `similarity`
+/// between 0 and 1.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SimilarQuestion {
+    pub question_uuid: String,
+    pub title: String,
+    pub created_at: String,
+    pub score: i32,
+    pub answer_count: i64,
+    pub similarity: f32,
+}
+
+/// Body of the `409 Conflict` returned when a new question looks like a
+/// duplicate.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PossibleDuplicates {
+    pub message: String,
+    pub similar_questions: Vec<SimilarQuestion>,
+}
+
+pub const MAX_SIMILAR_QUESTIONS: i64 = 5;
+/// Similarity from which an existing question holds back a new one until the
+/// asker confirms it is not a duplicate.
+pub const DUPLICATE_SIMILARITY: f32 = 0.6;
+
+/// Closed questions take no new answers; locked questions are frozen
+/// altogether until a moderator unlocks them.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum QuestionState {
+    #[default]
+    Open,
+    Closed,
+    Locked,
+}
+
+impl QuestionState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuestionState::Open => "open",
+            QuestionState::Closed => "closed",
+            QuestionState::Locked => "locked",
+        }
+    }
+}
+
+impl FromStr for QuestionState {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "open" => Ok(QuestionState::Open),
+            "closed" => Ok(QuestionState::Closed),
+            "locked" => Ok(QuestionState::Locked),
+            _ => Err(format!("Unknown question state: {s}")),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CloseReason {
+    Duplicate,
+    OffTopic,
+    Unclear,
+    TooBroad,
+    OpinionBased,
+}
+
+impl CloseReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CloseReason::Duplicate => "duplicate",
+            CloseReason::OffTopic => "off_topic",
+            CloseReason::Unclear => "unclear",
+            CloseReason::TooBroad => "too_broad",
+            CloseReason::OpinionBased => "opinion_based",
+        }
+    }
+}
+
+impl FromStr for CloseReason {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "duplicate" => Ok(CloseReason::Duplicate),
+            "off_topic" => Ok(CloseReason::OffTopic),
+            "unclear" => Ok(CloseReason::Unclear),
+            "too_broad" => Ok(CloseReason::TooBroad),
+            "opinion_based" => Ok(CloseReason::OpinionBased),
+            _ => Err(format!("Unknown close reason: {s}")),
+        }
+    }
+}
+
+/// A vote to close a question. `duplicate_of` is required for, and only
+/// allowed with, the `duplicate` reason.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CloseVote {
+    pub reason: CloseReason,
+    pub duplicate_of: Option<String>,
+}
+
+/// State of a question with the votes pending to change it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct QuestionStatus {
+    pub question_uuid: String,
+    pub state: QuestionState,
+    pub close_reason: Option<CloseReason>,
+    pub duplicate_of: Option<String>,
+    pub close_votes: i64,
+    pub reopen_votes: i64,
+}
+
+/// Votes needed to close or reopen a question. A moderator's vote is binding
+/// on its own.
+pub const CLOSE_VOTES_NEEDED: i64 = 3;
+pub const REOPEN_VOTES_NEEDED: i64 = 3;
+
+/// Days a deleted post is kept for undeletion before it is purged, unless
+/// `DELETED_POST_RETENTION_DAYS` is set.
+pub const DEFAULT_DELETED_POST_RETENTION_DAYS: i32 = 30;
+
+pub const MAX_TAGS_PER_QUESTION: usize = 5;
+pub const MAX_TAG_LENGTH: usize = 35;
+
+/// A tag with the number of questions carrying it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TagSummary {
+    pub name: String,
+    pub question_count: i64,
+    pub created_at: String,
+}
+
+/// A tag with its wiki and the synonyms that are rewritten to it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TagDetail {
+    pub name: String,
+    pub question_count: i64,
+    pub created_at: String,
+    pub excerpt: String,
+    pub body: String,
+    pub synonyms: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TagWikiUpdate {
+    pub excerpt: Option<String>,
+    pub body: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TagWikiRevision {
+    pub tag: String,
+    pub revision: i32,
+    pub excerpt: String,
+    pub body: String,
+    pub author_uuid: Option<String>,
+    pub created_at: String,
+}
+
+pub const MAX_TAG_EXCERPT_LENGTH: usize = 255;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SynonymProposal {
+    pub synonym: String,
+}
+
+/// Maps `synonym` to the canonical `tag`. Pending until a moderator approves it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TagSynonym {
+    pub synonym: String,
+    pub tag: String,
+    pub approved: bool,
+    pub proposed_by: Option<String>,
+    pub approved_by: Option<String>,
+    pub created_at: String,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct Answer {
     pub question_uuid: String,
     pub content: String,
+    /// Set from the authenticated user rather than the request body.
+    #[serde(skip_deserializing)]
+    pub author_uuid: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -32,6 +305,12 @@ pub struct AnswerDetail {
     pub question_uuid: String,
     pub content: String,
     pub created_at: String,
+    pub author_uuid: Option<String>,
+    pub score: i32,
+    pub is_accepted: bool,
+    pub comment_count: i64,
+    /// Only set on deleted answers listed for moderators.
+    pub deleted_at: Option<String>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -39,10 +318,757 @@ pub struct AnswerId {
     pub answer_uuid: String,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AnswerUpdate {
+    pub content: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AnswerRevision {
+    pub answer_uuid: String,
+    pub revision: i32,
+    pub content: String,
+    pub created_at: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Comment {
+    pub content: String,
+    /// Set from the authenticated user rather than the request body.
+    #[serde(skip_deserializing)]
+    pub author_uuid: Option<String>,
+}
+
+/// The post a comment is attached to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommentParent {
+    Question(String),
+    Answer(String),
+}
+
+/// Exactly one of `question_uuid` and `answer_uuid` is set.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CommentDetail {
+    pub comment_uuid: String,
+    pub question_uuid: Option<String>,
+    pub answer_uuid: Option<String>,
+    pub content: String,
+    pub created_at: String,
+    pub author_uuid: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CommentId {
+    pub comment_uuid: String,
+}
+
+pub const MAX_COMMENT_LENGTH: usize = 600;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RevisionDiff {
+    pub from_revision: i32,
+    pub to_revision: i32,
+    pub changes: Vec<FieldChange>,
+}
+
+/// An upvote (`1`) or downvote (`-1`).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Vote {
+    pub value: i16,
+}
+
+/// Score of a post after a vote, with the caller's own vote if they have one.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct VoteSummary {
+    pub score: i32,
+    pub vote: Option<i16>,
+}
+
+/// A question or answer matching a search. `snippet` is an excerpt of its text
+/// with the matching words wrapped in `<mark>` tags.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    pub question_uuid: String,
+    pub answer_uuid: Option<String>,
+    pub title: String,
+    pub snippet: String,
+    pub created_at: String,
+    pub author_uuid: Option<String>,
+    pub score: i32,
+}
+
+pub const MAX_SEARCH_QUERY_LENGTH: usize = 200;
+
+/// A parsed search query such as `[rust] is:question answers:0 "exact phrase"`.
+/// A post must match every term.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchQuery {
+    pub terms: Vec<SearchTerm>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchTerm {
+    /// `word`, or `-word` to exclude it.
+    Word { word: String, excluded: bool },
+    /// `"exact phrase"`, or `-"exact phrase"` to exclude it.
+    Phrase { phrase: String, excluded: bool },
+    /// `[tag]`, or `-[tag]` to exclude it.
+    Tag { tag: String, excluded: bool },
+    /// `is:question` or `is:answer`.
+    PostType(PostType),
+    /// `answers:3`, `answers:1..5`, `answers:1..` or `answers:..5`. Only
+    /// questions have answers.
+    Answers(Bounds<i64>),
+    /// `score:3`, `score:1..5`, `score:1..` or `score:..5`.
+    Score(Bounds<i64>),
+    /// `created:2024`, `created:2024-03..2024-04-15`, `created:2024..`, etc.
+    /// A year or month covers all of its days.
+    Created(Bounds<Date>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PostType {
+    Question,
+    Answer,
+}
+
+/// An inclusive range, open-ended on the sides that are `None`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds<T> {
+    pub min: Option<T>,
+    pub max: Option<T>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct User {
+    pub username: String,
+    pub email: String,
+    #[serde(skip_serializing)]
+    pub password: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    #[default]
+    User,
+    Moderator,
+    Admin,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::User => "user",
+            Role::Moderator => "moderator",
+            Role::Admin => "admin",
+        }
+    }
+
+    /// Moderators and admins may act on any post.
+    pub fn is_moderator(&self) -> bool {
+        matches!(self, Role::Moderator | Role::Admin)
+    }
+}
+
+impl FromStr for Role {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "user" => Ok(Role::User),
+            "moderator" => Ok(Role::Moderator),
+            "admin" => Ok(Role::Admin),
+            _ => Err(format!("Unknown role: {s}")),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UserDetail {
+    pub user_uuid: String,
+    pub username: String,
+    pub role: Role,
+    pub created_at: String,
+    pub question_count: i64,
+    pub answer_count: i64,
+    pub reputation: i32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct UserId {
+    pub user_uuid: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RoleUpdate {
+    pub role: Role,
+}
+
+/// What earned or cost a user reputation.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReputationKind {
+    QuestionUpvoted,
+    QuestionDownvoted,
+    AnswerUpvoted,
+    AnswerDownvoted,
+    /// The user's answer was accepted.
+    AnswerAccepted,
+    /// The user accepted an answer to their question.
+    AcceptedAnswer,
+    /// The user put reputation in escrow for a bounty.
+    BountyOffered,
+    /// The user's answer was awarded a bounty.
+    BountyAwarded,
+    /// The user's bounty expired without being awarded.
+    BountyRefunded,
+}
+
+impl ReputationKind {
+    /// Kinds counted against the daily reputation cap.
+    pub const CAPPED: [ReputationKind; 2] = [
+        ReputationKind::QuestionUpvoted,
+        ReputationKind::AnswerUpvoted,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReputationKind::QuestionUpvoted => "question_upvoted",
+            ReputationKind::QuestionDownvoted => "question_downvoted",
+            ReputationKind::AnswerUpvoted => "answer_upvoted",
+            ReputationKind::AnswerDownvoted => "answer_downvoted",
+            ReputationKind::AnswerAccepted => "answer_accepted",
+            ReputationKind::AcceptedAnswer => "accepted_answer",
+            ReputationKind::BountyOffered => "bounty_offered",
+            ReputationKind::BountyAwarded => "bounty_awarded",
+            ReputationKind::BountyRefunded => "bounty_refunded",
+        }
+    }
+
+    /// Reputation earned before the daily cap is applied. Bounty kinds move
+    /// the amount of their bounty instead and return 0.
+    pub fn amount(&self) -> i32 {
+        match self {
+            ReputationKind::QuestionUpvoted | ReputationKind::AnswerUpvoted => 10,
+            ReputationKind::QuestionDownvoted | ReputationKind::AnswerDownvoted => -2,
+            ReputationKind::AnswerAccepted => 15,
+            ReputationKind::AcceptedAnswer => 2,
+            ReputationKind::BountyOffered
+            | ReputationKind::BountyAwarded
+            | ReputationKind::BountyRefunded => 0,
+        }
+    }
+
+    pub fn is_capped(&self) -> bool {
+        Self::CAPPED.contains(self)
+    }
+}
+
+impl FromStr for ReputationKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "question_upvoted" => Ok(ReputationKind::QuestionUpvoted),
+            "question_downvoted" => Ok(ReputationKind::QuestionDownvoted),
+            "answer_upvoted" => Ok(ReputationKind::AnswerUpvoted),
+            "answer_downvoted" => Ok(ReputationKind::AnswerDownvoted),
+            "answer_accepted" => Ok(ReputationKind::AnswerAccepted),
+            "accepted_answer" => Ok(ReputationKind::AcceptedAnswer),
+            "bounty_offered" => Ok(ReputationKind::BountyOffered),
+            "bounty_awarded" => Ok(ReputationKind::BountyAwarded),
+            "bounty_refunded" => Ok(ReputationKind::BountyRefunded),
+            _ => Err(format!("Unknown reputation kind: {s}")),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ReputationEvent {
+    pub event_uuid: String,
+    pub kind: ReputationKind,
+    /// May be less than the kind's amount, down to zero, once the daily cap is reached.
+    pub amount: i32,
+    pub question_uuid: Option<String>,
+    pub answer_uuid: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ReputationRecalculation {
+    /// Users whose total did not match their ledger.
+    pub corrected_users: u64,
+}
+
+/// Reputation every user starts with.
+pub const BASE_REPUTATION: i32 = 1;
+/// Most reputation a user can earn from upvotes in a day.
+pub const DAILY_REPUTATION_CAP: i32 = 200;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BountyState {
+    Active,
+    Awarded,
+    /// Ran out without being awarded; the amount went back to the offerer.
+    Expired,
+}
+
+impl FromStr for BountyState {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "active" => Ok(BountyState::Active),
+            "awarded" => Ok(BountyState::Awarded),
+            "expired" => Ok(BountyState::Expired),
+            _ => Err(format!("Unknown bounty state: {s}")),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Bounty {
+    pub bounty_uuid: String,
+    pub question_uuid: String,
+    pub offered_by: Option<String>,
+    pub amount: i32,
+    pub state: BountyState,
+    pub started_at: String,
+    pub expires_at: String,
+    pub awarded_answer_uuid: Option<String>,
+    pub awarded_at: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BountyOffer {
+    pub amount: i32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BountyAward {
+    pub answer_uuid: String,
+}
+
+/// A question with an active bounty.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FeaturedQuestion {
+    pub question_uuid: String,
+    pub title: String,
+    pub score: i32,
+    pub bounty_amount: i32,
+    pub bounty_expires_at: String,
+}
+
+pub const MIN_BOUNTY: i32 = 50;
+pub const MAX_BOUNTY: i32 = 500;
+/// Days a bounty stays open before it expires.
+pub const BOUNTY_DURATION_DAYS: i32 = 7;
+
+/// A question followed by a user, who is told about its activity.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FollowedQuestion {
+    pub question_uuid: String,
+    pub title: String,
+    pub followed_at: String,
+}
+
+/// What a notification tells its recipient about.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    /// A question one asked or follows got an answer.
+    AnswerPosted,
+    /// One's question or answer got a comment.
+    CommentPosted,
+    /// One's question or answer was edited by someone else.
+    PostEdited,
+    AnswerAccepted,
+    BountyAwarded,
+}
+
+impl NotificationKind {
+    pub const ALL: [NotificationKind; 5] = [
+        NotificationKind::AnswerPosted,
+        NotificationKind::CommentPosted,
+        NotificationKind::PostEdited,
+        NotificationKind::AnswerAccepted,
+        NotificationKind::BountyAwarded,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationKind::AnswerPosted => "answer_posted",
+            NotificationKind::CommentPosted => "comment_posted",
+            NotificationKind::PostEdited => "post_edited",
+            NotificationKind::AnswerAccepted => "answer_accepted",
+            NotificationKind::BountyAwarded => "bounty_awarded",
+        }
+    }
+}
+
+impl FromStr for NotificationKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        NotificationKind::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == s)
+            .ok_or_else(|| format!("Unknown notification kind: {s}"))
+    }
+}
+
+/// Something done to a post, told to the users concerned except the one who
+/// did it. Answer events need no `question_uuid`, it is looked up.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NotificationEvent {
+    pub kind: NotificationKind,
+    pub actor_uuid: Option<String>,
+    pub question_uuid: Option<String>,
+    pub answer_uuid: Option<String>,
+    pub comment_uuid: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Notification {
+    pub notification_uuid: String,
+    pub kind: NotificationKind,
+    pub actor_uuid: Option<String>,
+    pub question_uuid: String,
+    pub answer_uuid: Option<String>,
+    pub comment_uuid: Option<String>,
+    pub created_at: String,
+    pub read_at: Option<String>,
+}
+
+/// A page of notifications along with the recipient's unread count.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct NotificationInbox {
+    pub items: Vec<Notification>,
+    pub next_cursor: Option<String>,
+    pub unread_count: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UnreadCount {
+    pub unread_count: i64,
+}
+
+/// Activity on a question, streamed to the clients watching it. Scores are the
+/// totals after the vote.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum QuestionEvent {
+    AnswerPosted { answer: AnswerDetail },
+    QuestionEdited { question: QuestionDetail },
+    AnswerEdited { answer: AnswerDetail },
+    QuestionVoted { score: i32 },
+    AnswerVoted { answer_uuid: String, score: i32 },
+    QuestionDeleted,
+    AnswerDeleted { answer_uuid: String },
+}
+
+/// Whether a user receives notifications of a kind. Every kind is enabled
+/// until turned off.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct NotificationPreference {
+    pub kind: NotificationKind,
+    pub enabled: bool,
+}
+
+/// Badges awarded for activity. A user earns each badge at most once.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Badge {
+    FirstQuestion,
+    FirstAnswer,
+    /// Accepted an answer to one's own question.
+    Scholar,
+    NiceQuestion,
+    GoodQuestion,
+    GreatQuestion,
+    NiceAnswer,
+    GoodAnswer,
+    GreatAnswer,
+    /// Answered a long-standing question well.
+    Necromancer,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BadgeClass {
+    Bronze,
+    Silver,
+    Gold,
+}
+
+/// What a user has to do to earn a badge.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BadgeCriterion {
+    AskedQuestion,
+    PostedAnswer,
+    AcceptedAnswer,
+    QuestionScore(i32),
+    AnswerScore(i32),
+    /// An answer posted `days` after its question, with at least `score`.
+    LateAnswer {
+        days: i32,
+        score: i32,
+    },
+}
+
+impl Badge {
+    pub const ALL: [Badge; 10] = [
+        Badge::FirstQuestion,
+        Badge::FirstAnswer,
+        Badge::Scholar,
+        Badge::NiceQuestion,
+        Badge::GoodQuestion,
+        Badge::GreatQuestion,
+        Badge::NiceAnswer,
+        Badge::GoodAnswer,
+        Badge::GreatAnswer,
+        Badge::Necromancer,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Badge::FirstQuestion => "first_question",
+            Badge::FirstAnswer => "first_answer",
+            Badge::Scholar => "scholar",
+            Badge::NiceQuestion => "nice_question",
+            Badge::GoodQuestion => "good_question",
+            Badge::GreatQuestion => "great_question",
+            Badge::NiceAnswer => "nice_answer",
+            Badge::GoodAnswer => "good_answer",
+            Badge::GreatAnswer => "great_answer",
+            Badge::Necromancer => "necromancer",
+        }
+    }
+
+    pub fn class(&self) -> BadgeClass {
+        match self {
+            Badge::FirstQuestion
+            | Badge::FirstAnswer
+            | Badge::Scholar
+            | Badge::NiceQuestion
+            | Badge::NiceAnswer => BadgeClass::Bronze,
+            Badge::GoodQuestion | Badge::GoodAnswer | Badge::Necromancer => BadgeClass::Silver,
+            Badge::GreatQuestion | Badge::GreatAnswer => BadgeClass::Gold,
+        }
+    }
+
+    pub fn criterion(&self) -> BadgeCriterion {
+        match self {
+            Badge::FirstQuestion => BadgeCriterion::AskedQuestion,
+            Badge::FirstAnswer => BadgeCriterion::PostedAnswer,
+            Badge::Scholar => BadgeCriterion::AcceptedAnswer,
+            Badge::NiceQuestion => BadgeCriterion::QuestionScore(10),
+            Badge::GoodQuestion => BadgeCriterion::QuestionScore(25),
+            Badge::GreatQuestion => BadgeCriterion::QuestionScore(100),
+            Badge::NiceAnswer => BadgeCriterion::AnswerScore(10),
+            Badge::GoodAnswer => BadgeCriterion::AnswerScore(25),
+            Badge::GreatAnswer => BadgeCriterion::AnswerScore(100),
+            Badge::Necromancer => BadgeCriterion::LateAnswer { days: 60, score: 5 },
+        }
+    }
+}
+
+impl FromStr for Badge {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Badge::ALL
+            .into_iter()
+            .find(|badge| badge.as_str() == s)
+            .ok_or_else(|| format!("Unknown badge: {s}"))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UserBadge {
+    pub badge: Badge,
+    pub class: BadgeClass,
+    pub awarded_at: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BadgeRecipient {
+    pub user_uuid: String,
+    pub username: String,
+    pub awarded_at: String,
+}
+
+/// The authenticated user performing a request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Actor {
+    pub user_uuid: String,
+    pub role: Role,
+    pub reputation: i32,
+}
+
+/// Actions unlocked by reputation. Moderators hold every privilege.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Privilege {
+    VoteDown,
+    /// Commenting on posts other than one's own and the answers to one's own
+    /// questions.
+    CommentEverywhere,
+    /// Editing posts of other users.
+    EditPosts,
+    /// Casting close and reopen votes.
+    CloseVote,
+    /// Offering reputation as a bounty on a question.
+    OfferBounty,
+}
+
+impl Privilege {
+    pub const ALL: [Privilege; 5] = [
+        Privilege::VoteDown,
+        Privilege::CommentEverywhere,
+        Privilege::EditPosts,
+        Privilege::CloseVote,
+        Privilege::OfferBounty,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Privilege::VoteDown => "vote_down",
+            Privilege::CommentEverywhere => "comment_everywhere",
+            Privilege::EditPosts => "edit_posts",
+            Privilege::CloseVote => "close_vote",
+            Privilege::OfferBounty => "offer_bounty",
+        }
+    }
+}
+
+/// Reputation required for each privilege.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrivilegeTable {
+    pub vote_down: i32,
+    pub comment_everywhere: i32,
+    pub edit_posts: i32,
+    pub close_vote: i32,
+    pub offer_bounty: i32,
+}
+
+impl PrivilegeTable {
+    pub fn required(&self, privilege: Privilege) -> i32 {
+        match privilege {
+            Privilege::VoteDown => self.vote_down,
+            Privilege::CommentEverywhere => self.comment_everywhere,
+            Privilege::EditPosts => self.edit_posts,
+            Privilege::CloseVote => self.close_vote,
+            Privilege::OfferBounty => self.offer_bounty,
+        }
+    }
+
+    pub fn set(&mut self, privilege: Privilege, reputation: i32) {
+        match privilege {
+            Privilege::VoteDown => self.vote_down = reputation,
+            Privilege::CommentEverywhere => self.comment_everywhere = reputation,
+            Privilege::EditPosts => self.edit_posts = reputation,
+            Privilege::CloseVote => self.close_vote = reputation,
+            Privilege::OfferBounty => self.offer_bounty = reputation,
+        }
+    }
+
+    pub fn thresholds(&self) -> Vec<PrivilegeThreshold> {
+        Privilege::ALL
+            .into_iter()
+            .map(|privilege| PrivilegeThreshold {
+                privilege,
+                reputation: self.required(privilege),
+            })
+            .collect()
+    }
+}
+
+impl Default for PrivilegeTable {
+    fn default() -> Self {
+        Self {
+            vote_down: 125,
+            comment_everywhere: 50,
+            edit_posts: 2000,
+            close_vote: 3000,
+            offer_bounty: 75,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PrivilegeThreshold {
+    pub privilege: Privilege,
+    pub reputation: i32,
+}
+
+#[derive(Deserialize)]
+pub struct Credentials {
+    pub username: String,
+    pub password: String,
+}
+
+/// Password hash stored for a user. It is `None` for accounts registered
+/// before passwords were introduced, which cannot log in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredCredentials {
+    pub user_uuid: String,
+    pub password_hash: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Session {
+    pub token: String,
+    pub user_uuid: String,
+    pub expires_at: String,
+}
+
+pub const SESSION_TTL_DAYS: i32 = 7;
+
+pub const DEFAULT_PAGE_LIMIT: i64 = 20;
+pub const MAX_PAGE_LIMIT: i64 = 100;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PageRequest {
+    pub cursor: Option<String>,
+    pub limit: Option<i64>,
+}
+
+impl PageRequest {
+    /// Requested page size, falling back to the default and capped at the
+    /// server-enforced maximum.
+    pub fn limit(&self) -> i64 {
+        self.limit
+            .unwrap_or(DEFAULT_PAGE_LIMIT)
+            .clamp(1, MAX_PAGE_LIMIT)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+}
+
 #[derive(Error, Debug)]
 pub enum DBError {
     #[error("Invalid UUID provided: {0}")]
     InvalidUUID(String),
+    #[error("Invalid cursor provided: {0}")]
+    InvalidCursor(String),
+    #[error("Resource not found: {0}")]
+    NotFound(String),
+    #[error("Resource already exists: {0}")]
+    Conflict(String),
     #[error("Database error occurred")]
     Other(#[from] Box<dyn std::error::Error + Send + Sync>),
 }
@@ -50,4 +1076,5 @@ pub enum DBError {
 // source: https://www.postgresql.org/docs/current/errcodes-appendix.html
 pub mod postgres_error_codes {
     pub const FOREIGN_KEY_VIOLATION: &str = "23503";
+    pub const UNIQUE_VIOLATION: &str = "23505";
 }
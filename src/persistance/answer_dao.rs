@@ -1,12 +1,54 @@
-use sqlx::PgPool;
+use sqlx::{types::Uuid, PgConnection, PgPool};
 
-use crate::models::{postgres_error_codes, Answer, AnswerDetail, DBError};
+use crate::{
+    models::{
+        postgres_error_codes, Answer, AnswerDetail, AnswerRevision, AnswerUpdate, BadgeCriterion,
+        DBError, Page, PageRequest, QuestionState,
+    },
+    persistance::{
+        badge_dao,
+        cursor::{next_page_cursor, Cursor},
+        question_dao::{parse_state, refuse_locked},
+        reputation_dao,
+    },
+};
 
 #[async_trait]
 pub trait AnswerDao {
     async fn create_answer(&self, answer: Answer) -> Result<AnswerDetail, DBError>;
-    async fn get_answers(&self, question_uuid: String) -> Result<Vec<AnswerDetail>, DBError>;
-    async fn delete_answer(&self, answer_uuid: String) -> Result<(), DBError>;
+    async fn get_answer(&self, answer_uuid: String) -> Result<AnswerDetail, DBError>;
+    /// Lists the answers of a question. Deleted answers, and the answers of a
+    /// deleted question, are only listed if `include_deleted` is set.
+    async fn get_answers(
+        &self,
+        question_uuid: String,
+        include_deleted: bool,
+        page: PageRequest,
+    ) -> Result<Page<AnswerDetail>, DBError>;
+    async fn update_answer(
+        &self,
+        answer_uuid: String,
+        update: AnswerUpdate,
+    ) -> Result<AnswerDetail, DBError>;
+    /// Hides the answer until it is undeleted or purged.
+    async fn delete_answer(&self, answer_uuid: String, deleted_by: String) -> Result<(), DBError>;
+    async fn undelete_answer(&self, answer_uuid: String) -> Result<AnswerDetail, DBError>;
+    /// Hard-deletes answers deleted more than `retention_days` ago and returns
+    /// how many were removed.
+    async fn purge_deleted_answers(&self, retention_days: i32) -> Result<u64, DBError>;
+    /// Lists the revisions of an answer. Those of a deleted answer, or of an
+    /// answer to a deleted question, are only found if `include_deleted` is set.
+    async fn get_answer_revisions(
+        &self,
+        answer_uuid: String,
+        include_deleted: bool,
+    ) -> Result<Vec<AnswerRevision>, DBError>;
+    async fn get_answer_revision(
+        &self,
+        answer_uuid: String,
+        revision: i32,
+        include_deleted: bool,
+    ) -> Result<AnswerRevision, DBError>;
 }
 
 pub struct AnswerDaoImpl {
@@ -19,6 +61,25 @@ impl AnswerDaoImpl {
     }
 }
 
+/// Keeps the question's answer count in step as its answers are deleted and
+/// undeleted.
+async fn count_answers(
+    conn: &mut PgConnection,
+    question_uuid: Uuid,
+    delta: i64,
+) -> Result<(), DBError> {
+    sqlx::query!(
+        "UPDATE question SET answer_count = answer_count + $2 WHERE question_uuid = $1",
+        question_uuid,
+        delta
+    )
+    .execute(conn)
+    .await
+    .map_err(|e| DBError::Other(Box::new(e)))?;
+
+    Ok(())
+}
+
 #[async_trait]
 impl AnswerDao for AnswerDaoImpl {
     async fn create_answer(&self, answer: Answer) -> Result<AnswerDetail, DBError> {
@@ -29,23 +90,72 @@ impl AnswerDao for AnswerDaoImpl {
             ))
         })?;
 
+        let author_uuid = answer
+            .author_uuid
+            .as_deref()
+            .map(sqlx::types::Uuid::parse_str)
+            .transpose()
+            .map_err(|_| {
+                DBError::InvalidUUID(format!(
+                    "Could not parse author UUID: {}",
+                    answer.author_uuid.clone().unwrap_or_default()
+                ))
+            })?;
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        // Counting the answer locks the question row, which keeps the question
+        // from being closed while the answer is being added.
+        let question = sqlx::query!(
+            r#"
+              UPDATE question SET answer_count = answer_count + 1
+              WHERE question_uuid = $1 AND deleted_at IS NULL
+              RETURNING state
+            "#,
+            uuid
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .ok_or_else(|| {
+            DBError::InvalidUUID(format!(
+                "Invalid question or author UUID: {}",
+                answer.question_uuid
+            ))
+        })?;
+
+        let state = parse_state(&question.state)?;
+
+        if state != QuestionState::Open {
+            return Err(DBError::Conflict(format!(
+                "Question is {} and does not accept answers: {}",
+                state.as_str(),
+                answer.question_uuid
+            )));
+        }
+
         let record = sqlx::query!(
             r#"
-                INSERT INTO answer ( question_uuid, content )
-                VALUES ( $1, $2 )
-                RETURNING *
+                INSERT INTO answer ( question_uuid, content, author_uuid )
+                VALUES ( $1, $2, $3 )
+                RETURNING answer_uuid, question_uuid, content, created_at, author_uuid, score
             "#,
             uuid,
-            answer.content
+            answer.content,
+            author_uuid
         )
-        .fetch_one(&self.db)
+        .fetch_one(&mut *tx)
         .await
         .map_err(|e: sqlx::Error| match e {
             sqlx::Error::Database(e) => {
                 if let Some(code) = e.code() {
                     if code.eq(postgres_error_codes::FOREIGN_KEY_VIOLATION) {
                         return DBError::InvalidUUID(format!(
-                            "Invalid question UUID: {}",
+                            "Invalid question or author UUID: {}",
                             answer.question_uuid
                         ));
                     }
@@ -55,11 +165,38 @@ impl AnswerDao for AnswerDaoImpl {
             e => DBError::Other(Box::new(e)),
         })?;
 
+        sqlx::query!(
+            r#"
+                INSERT INTO answer_revision ( answer_uuid, revision, content )
+                VALUES ( $1, 1, $2 )
+            "#,
+            record.answer_uuid,
+            record.content
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        badge_dao::award_author_badges(&mut tx, record.author_uuid, |c| {
+            matches!(
+                c,
+                BadgeCriterion::PostedAnswer | BadgeCriterion::LateAnswer { .. }
+            )
+        })
+        .await?;
+
+        tx.commit().await.map_err(|e| DBError::Other(Box::new(e)))?;
+
         let answer_detail = AnswerDetail {
             answer_uuid: record.answer_uuid.to_string(),
             question_uuid: record.question_uuid.to_string(),
             content: record.content,
             created_at: record.created_at.to_string(),
+            author_uuid: record.author_uuid.map(|u| u.to_string()),
+            score: record.score,
+            is_accepted: false,
+            comment_count: 0,
+            deleted_at: None,
         };
 
         debug!("answer detail: {answer_detail:?}");
@@ -67,23 +204,101 @@ impl AnswerDao for AnswerDaoImpl {
         Ok(answer_detail)
     }
 
-    async fn get_answers(&self, question_uuid: String) -> Result<Vec<AnswerDetail>, DBError> {
+    async fn get_answer(&self, answer_uuid: String) -> Result<AnswerDetail, DBError> {
+        let uuid = sqlx::types::Uuid::parse_str(&answer_uuid).map_err(|_| {
+            DBError::InvalidUUID(format!("Could not parse answer UUID: {answer_uuid}"))
+        })?;
+
+        let record = sqlx::query!(
+            r#"
+              SELECT answer_uuid, question_uuid, content, created_at, author_uuid, score, deleted_at,
+                EXISTS (
+                  SELECT 1 FROM question q WHERE q.accepted_answer_uuid = answer.answer_uuid
+                ) AS "is_accepted!",
+                (SELECT COUNT(*) FROM comment c WHERE c.answer_uuid = answer.answer_uuid) AS "comment_count!"
+              FROM answer
+              WHERE answer_uuid = $1 AND deleted_at IS NULL AND EXISTS (
+                SELECT 1 FROM question q
+                WHERE q.question_uuid = answer.question_uuid AND q.deleted_at IS NULL
+              )
+            "#,
+            uuid
+        )
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .ok_or_else(|| DBError::NotFound(format!("Answer not found: {answer_uuid}")))?;
+
+        let answer_detail = AnswerDetail {
+            answer_uuid: record.answer_uuid.to_string(),
+            question_uuid: record.question_uuid.to_string(),
+            content: record.content,
+            created_at: record.created_at.to_string(),
+            author_uuid: record.author_uuid.map(|u| u.to_string()),
+            score: record.score,
+            is_accepted: record.is_accepted,
+            comment_count: record.comment_count,
+            deleted_at: record.deleted_at.map(|t| t.to_string()),
+        };
+
+        debug!("get answer: {answer_detail:?}");
+
+        Ok(answer_detail)
+    }
+
+    async fn get_answers(
+        &self,
+        question_uuid: String,
+        include_deleted: bool,
+        page: PageRequest,
+    ) -> Result<Page<AnswerDetail>, DBError> {
         let uuid = sqlx::types::Uuid::parse_str(&question_uuid).map_err(|_| {
             DBError::InvalidUUID(format!("Could not parse question UUID: {question_uuid}"))
         })?;
+        let cursor = Cursor::decode_optional(page.cursor.as_deref())?;
+        let limit = page.limit();
 
-        let records = sqlx::query!(
+        // The accepted answer is pinned first (pin 0), the rest follow oldest
+        // first. The pin is carried as the cursor rank; cursors issued before
+        // pinning have none and resume among the unpinned answers. One extra row
+        // is fetched to tell whether a next page exists.
+        let mut records = sqlx::query!(
             r#"
-              SELECT answer_uuid, question_uuid, content, created_at
-              FROM answer 
-              WHERE question_uuid = $1
+              WITH a AS (
+                SELECT answer_uuid, question_uuid, content, created_at, author_uuid, score, deleted_at,
+                  CASE WHEN EXISTS (
+                    SELECT 1 FROM question q WHERE q.accepted_answer_uuid = answer.answer_uuid
+                  ) THEN 0 ELSE 1 END AS pin
+                FROM answer
+                WHERE question_uuid = $1 AND ($6 OR deleted_at IS NULL AND EXISTS (
+                  SELECT 1 FROM question q
+                  WHERE q.question_uuid = answer.question_uuid AND q.deleted_at IS NULL
+                ))
+              )
+              SELECT answer_uuid, question_uuid, content, created_at, author_uuid, score, deleted_at,
+                pin AS "pin!",
+                (SELECT COUNT(*) FROM comment c WHERE c.answer_uuid = a.answer_uuid) AS "comment_count!"
+              FROM a
+              WHERE $2::timestamp IS NULL
+                OR (pin, created_at, answer_uuid) > (COALESCE($4::bigint, 1), $2, $3)
+              ORDER BY pin, created_at, answer_uuid
+              LIMIT $5
           "#,
-            uuid
+            uuid,
+            cursor.map(|c| c.created_at),
+            cursor.map(|c| c.uuid),
+            cursor.and_then(|c| c.rank),
+            limit + 1,
+            include_deleted
         )
         .fetch_all(&self.db)
         .await
         .map_err(|e| DBError::Other(Box::new(e)))?;
 
+        let next_cursor = next_page_cursor(&mut records, limit, |r| {
+            Cursor::with_rank(r.pin.into(), r.created_at, r.answer_uuid)
+        });
+
         let answers = records
             .iter()
             .map(|r| AnswerDetail {
@@ -91,24 +306,315 @@ impl AnswerDao for AnswerDaoImpl {
                 question_uuid: r.question_uuid.to_string(),
                 content: r.content.to_string(),
                 created_at: r.created_at.to_string(),
+                author_uuid: r.author_uuid.map(|u| u.to_string()),
+                score: r.score,
+                is_accepted: r.pin == 0,
+                comment_count: r.comment_count,
+                deleted_at: r.deleted_at.map(|t| t.to_string()),
             })
             .collect();
 
         debug!("get answers: {answers:?}");
 
-        Ok(answers)
+        Ok(Page {
+            items: answers,
+            next_cursor,
+        })
+    }
+
+    async fn update_answer(
+        &self,
+        answer_uuid: String,
+        update: AnswerUpdate,
+    ) -> Result<AnswerDetail, DBError> {
+        let uuid = sqlx::types::Uuid::parse_str(&answer_uuid).map_err(|_| {
+            DBError::InvalidUUID(format!("Could not parse answer UUID: {answer_uuid}"))
+        })?;
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        // The answer row lock serializes concurrent edits, so the revision number
+        // computed below cannot be claimed twice.
+        let question = sqlx::query!(
+            r#"
+              SELECT q.question_uuid, q.state
+              FROM answer a
+              JOIN question q ON q.question_uuid = a.question_uuid
+              WHERE a.answer_uuid = $1 AND a.deleted_at IS NULL
+              FOR UPDATE OF a FOR SHARE OF q
+            "#,
+            uuid
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .ok_or_else(|| DBError::NotFound(format!("Answer not found: {answer_uuid}")))?;
+
+        refuse_locked(&question.state, &question.question_uuid.to_string())?;
+
+        let record = sqlx::query!(
+            r#"
+              UPDATE answer
+              SET content = $2
+              WHERE answer_uuid = $1 AND deleted_at IS NULL
+              RETURNING answer_uuid, question_uuid, content, created_at, author_uuid, score, deleted_at, EXISTS (
+                SELECT 1 FROM question q WHERE q.accepted_answer_uuid = answer.answer_uuid
+              ) AS "is_accepted!",
+                (SELECT COUNT(*) FROM comment c WHERE c.answer_uuid = answer.answer_uuid) AS "comment_count!"
+            "#,
+            uuid,
+            update.content
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .ok_or_else(|| DBError::NotFound(format!("Answer not found: {answer_uuid}")))?;
+
+        sqlx::query!(
+            r#"
+              INSERT INTO answer_revision ( answer_uuid, revision, content )
+              SELECT $1, COALESCE(MAX(revision), 0) + 1, $2
+              FROM answer_revision
+              WHERE answer_uuid = $1
+            "#,
+            record.answer_uuid,
+            record.content
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        tx.commit().await.map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let answer_detail = AnswerDetail {
+            answer_uuid: record.answer_uuid.to_string(),
+            question_uuid: record.question_uuid.to_string(),
+            content: record.content,
+            created_at: record.created_at.to_string(),
+            author_uuid: record.author_uuid.map(|u| u.to_string()),
+            score: record.score,
+            is_accepted: record.is_accepted,
+            comment_count: record.comment_count,
+            deleted_at: record.deleted_at.map(|t| t.to_string()),
+        };
+
+        debug!("update answer: {answer_detail:?}");
+
+        Ok(answer_detail)
     }
 
-    async fn delete_answer(&self, answer_uuid: String) -> Result<(), DBError> {
+    async fn delete_answer(&self, answer_uuid: String, deleted_by: String) -> Result<(), DBError> {
         let uuid = sqlx::types::Uuid::parse_str(&answer_uuid).map_err(|_| {
             DBError::InvalidUUID(format!("Could not parse answer UUID: {answer_uuid}"))
         })?;
+        let deleted_by = sqlx::types::Uuid::parse_str(&deleted_by).map_err(|_| {
+            DBError::InvalidUUID(format!("Could not parse user UUID: {deleted_by}"))
+        })?;
 
-        sqlx::query!("DELETE FROM answer WHERE answer_uuid = $1", uuid)
-            .execute(&self.db)
+        let mut tx = self
+            .db
+            .begin()
             .await
             .map_err(|e| DBError::Other(Box::new(e)))?;
 
+        let deleted = sqlx::query!(
+            r#"
+              UPDATE answer
+              SET deleted_at = CURRENT_TIMESTAMP, deleted_by = $2
+              WHERE answer_uuid = $1 AND deleted_at IS NULL
+              RETURNING question_uuid
+            "#,
+            uuid,
+            deleted_by
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        if let Some(answer) = deleted {
+            count_answers(&mut tx, answer.question_uuid, -1).await?;
+        }
+
+        // A deleted answer cannot stay accepted; undeleting it does not accept
+        // it again.
+        let unaccepted = sqlx::query!(
+            r#"
+              UPDATE question SET accepted_answer_uuid = NULL
+              WHERE accepted_answer_uuid = $1
+              RETURNING question_uuid
+            "#,
+            uuid
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        if let Some(question) = unaccepted {
+            reputation_dao::sync_acceptance(&mut tx, question.question_uuid).await?;
+        }
+
+        tx.commit().await.map_err(|e| DBError::Other(Box::new(e)))?;
+
         Ok(())
     }
+
+    async fn undelete_answer(&self, answer_uuid: String) -> Result<AnswerDetail, DBError> {
+        let uuid = sqlx::types::Uuid::parse_str(&answer_uuid).map_err(|_| {
+            DBError::InvalidUUID(format!("Could not parse answer UUID: {answer_uuid}"))
+        })?;
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let record = sqlx::query!(
+            r#"
+              UPDATE answer
+              SET deleted_at = NULL, deleted_by = NULL
+              WHERE answer_uuid = $1 AND deleted_at IS NOT NULL
+              RETURNING answer_uuid, question_uuid, content, created_at, author_uuid, score, deleted_at,
+                EXISTS (
+                  SELECT 1 FROM question q WHERE q.accepted_answer_uuid = answer.answer_uuid
+                ) AS "is_accepted!",
+                (SELECT COUNT(*) FROM comment c WHERE c.answer_uuid = answer.answer_uuid) AS "comment_count!"
+            "#,
+            uuid
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .ok_or_else(|| DBError::NotFound(format!("Deleted answer not found: {answer_uuid}")))?;
+
+        count_answers(&mut tx, record.question_uuid, 1).await?;
+
+        tx.commit().await.map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let answer_detail = AnswerDetail {
+            answer_uuid: record.answer_uuid.to_string(),
+            question_uuid: record.question_uuid.to_string(),
+            content: record.content,
+            created_at: record.created_at.to_string(),
+            author_uuid: record.author_uuid.map(|u| u.to_string()),
+            score: record.score,
+            is_accepted: record.is_accepted,
+            comment_count: record.comment_count,
+            deleted_at: record.deleted_at.map(|t| t.to_string()),
+        };
+
+        debug!("undelete answer: {answer_detail:?}");
+
+        Ok(answer_detail)
+    }
+
+    async fn purge_deleted_answers(&self, retention_days: i32) -> Result<u64, DBError> {
+        let result = sqlx::query!(
+            r#"
+              DELETE FROM answer
+              WHERE deleted_at < CURRENT_TIMESTAMP - make_interval(days => $1)
+            "#,
+            retention_days
+        )
+        .execute(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn get_answer_revisions(
+        &self,
+        answer_uuid: String,
+        include_deleted: bool,
+    ) -> Result<Vec<AnswerRevision>, DBError> {
+        let uuid = sqlx::types::Uuid::parse_str(&answer_uuid).map_err(|_| {
+            DBError::InvalidUUID(format!("Could not parse answer UUID: {answer_uuid}"))
+        })?;
+
+        let records = sqlx::query!(
+            r#"
+              SELECT r.answer_uuid, r.revision, r.content, r.created_at
+              FROM answer_revision r
+              JOIN answer a ON a.answer_uuid = r.answer_uuid
+              JOIN question q ON q.question_uuid = a.question_uuid
+              WHERE r.answer_uuid = $1 AND ($2 OR a.deleted_at IS NULL AND q.deleted_at IS NULL)
+              ORDER BY r.revision
+            "#,
+            uuid,
+            include_deleted
+        )
+        .fetch_all(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        // Every answer has at least its initial revision.
+        if records.is_empty() {
+            return Err(DBError::NotFound(format!(
+                "Answer not found: {answer_uuid}"
+            )));
+        }
+
+        let revisions = records
+            .iter()
+            .map(|r| AnswerRevision {
+                answer_uuid: r.answer_uuid.to_string(),
+                revision: r.revision,
+                content: r.content.to_string(),
+                created_at: r.created_at.to_string(),
+            })
+            .collect();
+
+        debug!("get answer revisions: {revisions:?}");
+
+        Ok(revisions)
+    }
+
+    async fn get_answer_revision(
+        &self,
+        answer_uuid: String,
+        revision: i32,
+        include_deleted: bool,
+    ) -> Result<AnswerRevision, DBError> {
+        let uuid = sqlx::types::Uuid::parse_str(&answer_uuid).map_err(|_| {
+            DBError::InvalidUUID(format!("Could not parse answer UUID: {answer_uuid}"))
+        })?;
+
+        let record = sqlx::query!(
+            r#"
+              SELECT r.answer_uuid, r.revision, r.content, r.created_at
+              FROM answer_revision r
+              JOIN answer a ON a.answer_uuid = r.answer_uuid
+              JOIN question q ON q.question_uuid = a.question_uuid
+              WHERE r.answer_uuid = $1 AND r.revision = $2
+                AND ($3 OR a.deleted_at IS NULL AND q.deleted_at IS NULL)
+            "#,
+            uuid,
+            revision,
+            include_deleted
+        )
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .ok_or_else(|| {
+            DBError::NotFound(format!(
+                "Revision {revision} not found for answer: {answer_uuid}"
+            ))
+        })?;
+
+        let answer_revision = AnswerRevision {
+            answer_uuid: record.answer_uuid.to_string(),
+            revision: record.revision,
+            content: record.content,
+            created_at: record.created_at.to_string(),
+        };
+
+        debug!("get answer revision: {answer_revision:?}");
+
+        Ok(answer_revision)
+    }
 }
@@ -0,0 +1,291 @@
+use async_trait::async_trait;
+use sqlx::{types::Uuid, PgConnection, PgPool};
+
+use crate::{
+    models::{Badge, BadgeCriterion, BadgeRecipient, DBError, Page, PageRequest, UserBadge},
+    persistance::cursor::{next_page_cursor, Cursor},
+};
+
+#[async_trait]
+pub trait BadgeDao {
+    /// Lists the badges of a user, newest first.
+    async fn get_user_badges(&self, user_uuid: String) -> Result<Vec<UserBadge>, DBError>;
+    /// Lists the users holding a badge, most recent recipients first.
+    async fn get_badge_recipients(
+        &self,
+        badge: Badge,
+        page: PageRequest,
+    ) -> Result<Page<BadgeRecipient>, DBError>;
+    /// Awards every badge earned by any user and returns how many were new.
+    async fn award_badges(&self) -> Result<u64, DBError>;
+}
+
+pub struct BadgeDaoImpl {
+    db: PgPool,
+}
+
+impl BadgeDaoImpl {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+}
+
+fn parse_badge(badge: &str) -> Result<Badge, DBError> {
+    badge.parse().map_err(|e: String| DBError::Other(e.into()))
+}
+
+/// Badge definitions grouped by criterion, as the parallel arrays the award
+/// query takes.
+#[derive(Default)]
+struct Criteria {
+    asked_question: Vec<&'static str>,
+    posted_answer: Vec<&'static str>,
+    accepted_answer: Vec<&'static str>,
+    question_score: (Vec<&'static str>, Vec<i32>),
+    answer_score: (Vec<&'static str>, Vec<i32>),
+    late_answer: (Vec<&'static str>, Vec<i32>, Vec<i32>),
+}
+
+impl Criteria {
+    /// Groups the badges whose criterion passes `filter`.
+    fn of_badges(filter: impl Fn(&BadgeCriterion) -> bool) -> Self {
+        let mut criteria = Criteria::default();
+
+        for badge in Badge::ALL {
+            let name = badge.as_str();
+            let criterion = badge.criterion();
+
+            if !filter(&criterion) {
+                continue;
+            }
+
+            match criterion {
+                BadgeCriterion::AskedQuestion => criteria.asked_question.push(name),
+                BadgeCriterion::PostedAnswer => criteria.posted_answer.push(name),
+                BadgeCriterion::AcceptedAnswer => criteria.accepted_answer.push(name),
+                BadgeCriterion::QuestionScore(score) => {
+                    criteria.question_score.0.push(name);
+                    criteria.question_score.1.push(score);
+                }
+                BadgeCriterion::AnswerScore(score) => {
+                    criteria.answer_score.0.push(name);
+                    criteria.answer_score.1.push(score);
+                }
+                BadgeCriterion::LateAnswer { days, score } => {
+                    criteria.late_answer.0.push(name);
+                    criteria.late_answer.1.push(days);
+                    criteria.late_answer.2.push(score);
+                }
+            }
+        }
+
+        criteria
+    }
+}
+
+/// Awards the badges passing `filter` earned by `users`, or by everyone when
+/// `None`. Badges already held are left as they are, so this can run as often
+/// as needed. Deleted posts earn nothing.
+pub(crate) async fn award_badges(
+    conn: &mut PgConnection,
+    users: Option<&[Uuid]>,
+    filter: impl Fn(&BadgeCriterion) -> bool,
+) -> Result<u64, DBError> {
+    let criteria = Criteria::of_badges(filter);
+
+    // Every branch starts from the authors, so that only their posts are read
+    // through the author indexes rather than the whole question and answer
+    // tables.
+
+    let awarded = sqlx::query!(
+        r#"
+          WITH authors ( user_uuid ) AS (
+            SELECT unnest(COALESCE($1::uuid[], ARRAY(SELECT user_uuid FROM users)))
+          ),
+          earned ( user_uuid, badge ) AS (
+            SELECT q.author_uuid, b.badge
+            FROM authors u
+            JOIN question q ON q.author_uuid = u.user_uuid, UNNEST($2::text[]) AS b ( badge )
+            WHERE q.deleted_at IS NULL
+            UNION ALL
+            SELECT a.author_uuid, b.badge
+            FROM authors u
+            JOIN answer a ON a.author_uuid = u.user_uuid, UNNEST($3::text[]) AS b ( badge )
+            WHERE a.deleted_at IS NULL
+            UNION ALL
+            SELECT q.author_uuid, b.badge
+            FROM authors u
+            JOIN question q ON q.author_uuid = u.user_uuid, UNNEST($4::text[]) AS b ( badge )
+            WHERE q.deleted_at IS NULL AND q.accepted_answer_uuid IS NOT NULL
+            UNION ALL
+            SELECT q.author_uuid, b.badge
+            FROM authors u
+            JOIN question q ON q.author_uuid = u.user_uuid
+            JOIN UNNEST($5::text[], $6::int[]) AS b ( badge, score ) ON q.score >= b.score
+            WHERE q.deleted_at IS NULL
+            UNION ALL
+            SELECT a.author_uuid, b.badge
+            FROM authors u
+            JOIN answer a ON a.author_uuid = u.user_uuid
+            JOIN UNNEST($7::text[], $8::int[]) AS b ( badge, score ) ON a.score >= b.score
+            WHERE a.deleted_at IS NULL
+            UNION ALL
+            SELECT a.author_uuid, b.badge
+            FROM authors u
+            JOIN answer a ON a.author_uuid = u.user_uuid
+            JOIN question q ON q.question_uuid = a.question_uuid
+            JOIN UNNEST($9::text[], $10::int[], $11::int[]) AS b ( badge, days, score )
+              ON a.created_at >= q.created_at + make_interval(days => b.days) AND a.score >= b.score
+            WHERE a.deleted_at IS NULL
+          )
+          INSERT INTO user_badge ( user_uuid, badge )
+          SELECT DISTINCT user_uuid, badge
+          FROM earned
+          ON CONFLICT ( user_uuid, badge ) DO NOTHING
+        "#,
+        users,
+        &criteria.asked_question as &[&str],
+        &criteria.posted_answer as &[&str],
+        &criteria.accepted_answer as &[&str],
+        &criteria.question_score.0 as &[&str],
+        &criteria.question_score.1,
+        &criteria.answer_score.0 as &[&str],
+        &criteria.answer_score.1,
+        &criteria.late_answer.0 as &[&str],
+        &criteria.late_answer.1,
+        &criteria.late_answer.2
+    )
+    .execute(conn)
+    .await
+    .map_err(|e| DBError::Other(Box::new(e)))?;
+
+    Ok(awarded.rows_affected())
+}
+
+/// Awards the badges the author of a post has earned, if the post has one.
+/// Only the badges whose criterion passes `filter`, those the triggering
+/// change can affect, are checked, and only against the author's own posts.
+pub(crate) async fn award_author_badges(
+    conn: &mut PgConnection,
+    author: Option<Uuid>,
+    filter: impl Fn(&BadgeCriterion) -> bool,
+) -> Result<(), DBError> {
+    if let Some(author) = author {
+        award_badges(conn, Some(&[author]), filter).await?;
+    }
+
+    Ok(())
+}
+
+#[async_trait]
+impl BadgeDao for BadgeDaoImpl {
+    async fn get_user_badges(&self, user_uuid: String) -> Result<Vec<UserBadge>, DBError> {
+        let uuid = Uuid::parse_str(&user_uuid)
+            .map_err(|_| DBError::InvalidUUID(format!("Could not parse user UUID: {user_uuid}")))?;
+
+        let exists = sqlx::query!(
+            r#"SELECT EXISTS (SELECT 1 FROM users WHERE user_uuid = $1) AS "exists!""#,
+            uuid
+        )
+        .fetch_one(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .exists;
+
+        if !exists {
+            return Err(DBError::NotFound(format!("User not found: {user_uuid}")));
+        }
+
+        let records = sqlx::query!(
+            r#"
+              SELECT badge, awarded_at
+              FROM user_badge
+              WHERE user_uuid = $1
+              ORDER BY awarded_at DESC, badge
+            "#,
+            uuid
+        )
+        .fetch_all(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let badges = records
+            .iter()
+            .map(|r| {
+                let badge = parse_badge(&r.badge)?;
+
+                Ok(UserBadge {
+                    badge,
+                    class: badge.class(),
+                    awarded_at: r.awarded_at.to_string(),
+                })
+            })
+            .collect::<Result<Vec<_>, DBError>>()?;
+
+        debug!("get user badges: {badges:?}");
+
+        Ok(badges)
+    }
+
+    async fn get_badge_recipients(
+        &self,
+        badge: Badge,
+        page: PageRequest,
+    ) -> Result<Page<BadgeRecipient>, DBError> {
+        let cursor = Cursor::decode_optional(page.cursor.as_deref())?;
+        let limit = page.limit();
+
+        let mut records = sqlx::query!(
+            r#"
+              SELECT b.user_uuid, u.username, b.awarded_at
+              FROM user_badge b
+              JOIN users u ON u.user_uuid = b.user_uuid
+              WHERE b.badge = $1
+                AND ($2::timestamp IS NULL OR (b.awarded_at, b.user_uuid) < ($2, $3))
+              ORDER BY b.awarded_at DESC, b.user_uuid DESC
+              LIMIT $4
+            "#,
+            badge.as_str(),
+            cursor.map(|c| c.created_at),
+            cursor.map(|c| c.uuid),
+            limit + 1
+        )
+        .fetch_all(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let next_cursor = next_page_cursor(&mut records, limit, |r| {
+            Cursor::new(r.awarded_at, r.user_uuid)
+        });
+
+        let recipients = records
+            .into_iter()
+            .map(|r| BadgeRecipient {
+                user_uuid: r.user_uuid.to_string(),
+                username: r.username,
+                awarded_at: r.awarded_at.to_string(),
+            })
+            .collect::<Vec<_>>();
+
+        debug!("get badge recipients: {recipients:?}");
+
+        Ok(Page {
+            items: recipients,
+            next_cursor,
+        })
+    }
+
+    async fn award_badges(&self) -> Result<u64, DBError> {
+        let mut conn = self
+            .db
+            .acquire()
+            .await
+            .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let awarded = award_badges(&mut conn, None, |_| true).await?;
+
+        debug!("award badges: {awarded} awarded");
+
+        Ok(awarded)
+    }
+}
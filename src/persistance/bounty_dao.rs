@@ -0,0 +1,377 @@
+use async_trait::async_trait;
+use sqlx::{types::Uuid, PgConnection, PgPool};
+
+use crate::{
+    models::{
+        Bounty, BountyState, DBError, FeaturedQuestion, Page, PageRequest, QuestionState,
+        ReputationKind, BOUNTY_DURATION_DAYS,
+    },
+    persistance::{
+        cursor::{next_page_cursor, Cursor},
+        question_dao::{lock_state, refuse_locked},
+        reputation_dao,
+    },
+};
+
+#[async_trait]
+pub trait BountyDao {
+    /// Starts a bounty on an open question, taking `amount` from the offerer's
+    /// reputation.
+    async fn start_bounty(
+        &self,
+        question_uuid: String,
+        user_uuid: String,
+        amount: i32,
+    ) -> Result<Bounty, DBError>;
+    async fn get_active_bounty(&self, question_uuid: String) -> Result<Bounty, DBError>;
+    /// Hands the bounty's amount to the author of an answer of its question.
+    async fn award_bounty(
+        &self,
+        bounty_uuid: String,
+        answer_uuid: String,
+    ) -> Result<Bounty, DBError>;
+    /// Lists questions with an active bounty, newest bounty first.
+    async fn get_featured_questions(
+        &self,
+        page: PageRequest,
+    ) -> Result<Page<FeaturedQuestion>, DBError>;
+    /// Ends the bounties past their expiry, refunding their offerers, and
+    /// returns how many expired.
+    async fn expire_bounties(&self) -> Result<u64, DBError>;
+}
+
+pub struct BountyDaoImpl {
+    db: PgPool,
+}
+
+impl BountyDaoImpl {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+}
+
+fn parse_uuid(uuid: &str, name: &str) -> Result<Uuid, DBError> {
+    Uuid::parse_str(uuid)
+        .map_err(|_| DBError::InvalidUUID(format!("Could not parse {name} UUID: {uuid}")))
+}
+
+fn parse_state(state: &str) -> Result<BountyState, DBError> {
+    state.parse().map_err(|e: String| DBError::Other(e.into()))
+}
+
+async fn fetch_bounty(conn: &mut PgConnection, bounty_uuid: Uuid) -> Result<Bounty, DBError> {
+    let record = sqlx::query!(
+        r#"
+          SELECT bounty_uuid, question_uuid, offered_by, amount, state, started_at, expires_at,
+            awarded_answer_uuid, awarded_at
+          FROM bounty
+          WHERE bounty_uuid = $1
+        "#,
+        bounty_uuid
+    )
+    .fetch_optional(conn)
+    .await
+    .map_err(|e| DBError::Other(Box::new(e)))?
+    .ok_or_else(|| DBError::NotFound(format!("Bounty not found: {bounty_uuid}")))?;
+
+    Ok(Bounty {
+        bounty_uuid: record.bounty_uuid.to_string(),
+        question_uuid: record.question_uuid.to_string(),
+        offered_by: record.offered_by.map(|u| u.to_string()),
+        amount: record.amount,
+        state: parse_state(&record.state)?,
+        started_at: record.started_at.to_string(),
+        expires_at: record.expires_at.to_string(),
+        awarded_answer_uuid: record.awarded_answer_uuid.map(|u| u.to_string()),
+        awarded_at: record.awarded_at.map(|t| t.to_string()),
+    })
+}
+
+#[async_trait]
+impl BountyDao for BountyDaoImpl {
+    async fn start_bounty(
+        &self,
+        question_uuid: String,
+        user_uuid: String,
+        amount: i32,
+    ) -> Result<Bounty, DBError> {
+        let question = parse_uuid(&question_uuid, "question")?;
+        let user = parse_uuid(&user_uuid, "user")?;
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let state = lock_state(&mut tx, question, &question_uuid).await?;
+
+        if state != QuestionState::Open {
+            return Err(DBError::Conflict(format!(
+                "Cannot offer a bounty on a {} question",
+                state.as_str()
+            )));
+        }
+
+        let active = sqlx::query!(
+            r#"SELECT EXISTS (SELECT 1 FROM bounty WHERE question_uuid = $1 AND state = 'active') AS "exists!""#,
+            question
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .exists;
+
+        if active {
+            return Err(DBError::Conflict(format!(
+                "Question already has an active bounty: {question_uuid}"
+            )));
+        }
+
+        // The escrow may not leave the offerer with negative reputation.
+        let reputation = sqlx::query!(
+            "SELECT reputation FROM users WHERE user_uuid = $1 FOR UPDATE",
+            user
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .ok_or_else(|| DBError::NotFound(format!("User not found: {user_uuid}")))?
+        .reputation;
+
+        if reputation < amount {
+            return Err(DBError::Conflict(format!(
+                "Not enough reputation for a bounty of {amount} (you have {reputation})"
+            )));
+        }
+
+        let bounty_uuid = sqlx::query!(
+            r#"
+              INSERT INTO bounty ( question_uuid, offered_by, amount, expires_at )
+              VALUES ( $1, $2, $3, CURRENT_TIMESTAMP + make_interval(days => $4) )
+              RETURNING bounty_uuid
+            "#,
+            question,
+            user,
+            amount,
+            BOUNTY_DURATION_DAYS
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .bounty_uuid;
+
+        reputation_dao::record_event(
+            &mut tx,
+            user,
+            ReputationKind::BountyOffered,
+            -amount,
+            Some(question),
+            None,
+            None,
+        )
+        .await?;
+
+        let bounty = fetch_bounty(&mut tx, bounty_uuid).await?;
+
+        tx.commit().await.map_err(|e| DBError::Other(Box::new(e)))?;
+
+        debug!("start bounty: {bounty:?}");
+
+        Ok(bounty)
+    }
+
+    async fn get_active_bounty(&self, question_uuid: String) -> Result<Bounty, DBError> {
+        let question = parse_uuid(&question_uuid, "question")?;
+
+        let mut conn = self
+            .db
+            .acquire()
+            .await
+            .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let bounty_uuid = sqlx::query!(
+            "SELECT bounty_uuid FROM bounty WHERE question_uuid = $1 AND state = 'active'",
+            question
+        )
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .ok_or_else(|| DBError::NotFound(format!("No active bounty on question: {question_uuid}")))?
+        .bounty_uuid;
+
+        fetch_bounty(&mut conn, bounty_uuid).await
+    }
+
+    async fn award_bounty(
+        &self,
+        bounty_uuid: String,
+        answer_uuid: String,
+    ) -> Result<Bounty, DBError> {
+        let bounty = parse_uuid(&bounty_uuid, "bounty")?;
+        let answer = parse_uuid(&answer_uuid, "answer")?;
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let record = sqlx::query!(
+            r#"
+              SELECT b.state, b.expires_at <= CURRENT_TIMESTAMP AS "expired!", q.question_uuid,
+                q.state AS question_state
+              FROM bounty b
+              JOIN question q ON q.question_uuid = b.question_uuid
+              WHERE b.bounty_uuid = $1 AND q.deleted_at IS NULL
+              FOR UPDATE OF b FOR SHARE OF q
+            "#,
+            bounty
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .ok_or_else(|| DBError::NotFound(format!("Bounty not found: {bounty_uuid}")))?;
+
+        // Expired bounties stay active until the expiry job refunds them, but
+        // can no longer be awarded in the meantime.
+        if record.state != "active" || record.expired {
+            return Err(DBError::Conflict(format!(
+                "Bounty is no longer active: {bounty_uuid}"
+            )));
+        }
+
+        refuse_locked(&record.question_state, &record.question_uuid.to_string())?;
+
+        let record = sqlx::query!(
+            r#"
+              UPDATE bounty b
+              SET state = 'awarded', awarded_answer_uuid = a.answer_uuid, awarded_at = CURRENT_TIMESTAMP
+              FROM answer a
+              WHERE b.bounty_uuid = $1 AND a.answer_uuid = $2
+                AND a.question_uuid = b.question_uuid AND a.deleted_at IS NULL
+              RETURNING b.question_uuid, b.offered_by, b.amount, a.author_uuid
+            "#,
+            bounty,
+            answer
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .ok_or_else(|| {
+            DBError::InvalidUUID(format!(
+                "Answer {answer_uuid} does not belong to the question of bounty {bounty_uuid}"
+            ))
+        })?;
+
+        if let Some(author) = record.author_uuid {
+            reputation_dao::record_event(
+                &mut tx,
+                author,
+                ReputationKind::BountyAwarded,
+                record.amount,
+                Some(record.question_uuid),
+                Some(answer),
+                record.offered_by,
+            )
+            .await?;
+        }
+
+        let bounty = fetch_bounty(&mut tx, bounty).await?;
+
+        tx.commit().await.map_err(|e| DBError::Other(Box::new(e)))?;
+
+        debug!("award bounty: {bounty:?}");
+
+        Ok(bounty)
+    }
+
+    async fn get_featured_questions(
+        &self,
+        page: PageRequest,
+    ) -> Result<Page<FeaturedQuestion>, DBError> {
+        let cursor = Cursor::decode_optional(page.cursor.as_deref())?;
+        let limit = page.limit();
+
+        let mut records = sqlx::query!(
+            r#"
+              SELECT b.bounty_uuid, b.started_at, b.amount, b.expires_at, q.question_uuid, q.title, q.score
+              FROM bounty b
+              JOIN question q ON q.question_uuid = b.question_uuid
+              WHERE b.state = 'active' AND b.expires_at > CURRENT_TIMESTAMP AND q.deleted_at IS NULL
+                AND ($1::timestamp IS NULL OR (b.started_at, b.bounty_uuid) < ($1, $2))
+              ORDER BY b.started_at DESC, b.bounty_uuid DESC
+              LIMIT $3
+            "#,
+            cursor.map(|c| c.created_at),
+            cursor.map(|c| c.uuid),
+            limit + 1
+        )
+        .fetch_all(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let next_cursor = next_page_cursor(&mut records, limit, |r| {
+            Cursor::new(r.started_at, r.bounty_uuid)
+        });
+
+        let questions = records
+            .into_iter()
+            .map(|r| FeaturedQuestion {
+                question_uuid: r.question_uuid.to_string(),
+                title: r.title,
+                score: r.score,
+                bounty_amount: r.amount,
+                bounty_expires_at: r.expires_at.to_string(),
+            })
+            .collect::<Vec<_>>();
+
+        debug!("get featured questions: {questions:?}");
+
+        Ok(Page {
+            items: questions,
+            next_cursor,
+        })
+    }
+
+    async fn expire_bounties(&self) -> Result<u64, DBError> {
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let expired = sqlx::query!(
+            r#"
+              UPDATE bounty
+              SET state = 'expired'
+              WHERE state = 'active' AND expires_at <= CURRENT_TIMESTAMP
+              RETURNING question_uuid, offered_by, amount
+            "#
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        for bounty in &expired {
+            if let Some(offerer) = bounty.offered_by {
+                reputation_dao::record_event(
+                    &mut tx,
+                    offerer,
+                    ReputationKind::BountyRefunded,
+                    bounty.amount,
+                    Some(bounty.question_uuid),
+                    None,
+                    None,
+                )
+                .await?;
+            }
+        }
+
+        tx.commit().await.map_err(|e| DBError::Other(Box::new(e)))?;
+
+        debug!("expire bounties: {} expired", expired.len());
+
+        Ok(expired.len() as u64)
+    }
+}
@@ -0,0 +1,278 @@
+use async_trait::async_trait;
+use sqlx::{types::Uuid, PgPool};
+
+use crate::{
+    models::{
+        postgres_error_codes, Comment, CommentDetail, CommentParent, DBError, Page, PageRequest,
+    },
+    persistance::{
+        cursor::{next_page_cursor, Cursor},
+        question_dao::refuse_locked,
+    },
+};
+
+#[async_trait]
+pub trait CommentDao {
+    async fn create_comment(
+        &self,
+        parent: CommentParent,
+        comment: Comment,
+    ) -> Result<CommentDetail, DBError>;
+    async fn get_comment(&self, comment_uuid: String) -> Result<CommentDetail, DBError>;
+    /// Lists the comments of a post. The comments of a deleted post, or of an
+    /// answer to a deleted question, are only listed if `include_deleted` is set.
+    async fn get_comments(
+        &self,
+        parent: CommentParent,
+        include_deleted: bool,
+        page: PageRequest,
+    ) -> Result<Page<CommentDetail>, DBError>;
+    async fn delete_comment(&self, comment_uuid: String) -> Result<(), DBError>;
+}
+
+pub struct CommentDaoImpl {
+    db: PgPool,
+}
+
+impl CommentDaoImpl {
+    pub fn new(db: PgPool) -> Self {
+        CommentDaoImpl { db }
+    }
+}
+
+fn parent_not_found(parent: &CommentParent) -> DBError {
+    match parent {
+        CommentParent::Question(uuid) => DBError::NotFound(format!("Question not found: {uuid}")),
+        CommentParent::Answer(uuid) => DBError::NotFound(format!("Answer not found: {uuid}")),
+    }
+}
+
+/// Splits a parent into the `(question_uuid, answer_uuid)` column pair.
+fn parent_uuids(parent: &CommentParent) -> Result<(Option<Uuid>, Option<Uuid>), DBError> {
+    match parent {
+        CommentParent::Question(question_uuid) => Uuid::parse_str(question_uuid)
+            .map(|uuid| (Some(uuid), None))
+            .map_err(|_| {
+                DBError::InvalidUUID(format!("Could not parse question UUID: {question_uuid}"))
+            }),
+        CommentParent::Answer(answer_uuid) => Uuid::parse_str(answer_uuid)
+            .map(|uuid| (None, Some(uuid)))
+            .map_err(|_| {
+                DBError::InvalidUUID(format!("Could not parse answer UUID: {answer_uuid}"))
+            }),
+    }
+}
+
+#[async_trait]
+impl CommentDao for CommentDaoImpl {
+    async fn create_comment(
+        &self,
+        parent: CommentParent,
+        comment: Comment,
+    ) -> Result<CommentDetail, DBError> {
+        let (question_uuid, answer_uuid) = parent_uuids(&parent)?;
+        let author_uuid = comment
+            .author_uuid
+            .as_deref()
+            .map(Uuid::parse_str)
+            .transpose()
+            .map_err(|_| {
+                DBError::InvalidUUID(format!(
+                    "Could not parse author UUID: {}",
+                    comment.author_uuid.clone().unwrap_or_default()
+                ))
+            })?;
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        // The shared row lock keeps the question from being locked or deleted
+        // while the comment is being added. Deleted posts take no comments.
+        let question = sqlx::query!(
+            r#"
+              SELECT question_uuid, state
+              FROM question
+              WHERE question_uuid = COALESCE($1, (
+                  SELECT question_uuid FROM answer WHERE answer_uuid = $2 AND deleted_at IS NULL
+                ))
+                AND deleted_at IS NULL
+              FOR SHARE
+            "#,
+            question_uuid,
+            answer_uuid
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .ok_or_else(|| parent_not_found(&parent))?;
+
+        refuse_locked(&question.state, &question.question_uuid.to_string())?;
+
+        let record = sqlx::query!(
+            r#"
+              INSERT INTO comment ( question_uuid, answer_uuid, content, author_uuid )
+              VALUES ( $1, $2, $3, $4 )
+              RETURNING *
+            "#,
+            question_uuid,
+            answer_uuid,
+            comment.content,
+            author_uuid
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e: sqlx::Error| match e {
+            sqlx::Error::Database(e) => {
+                if let Some(code) = e.code() {
+                    if code.eq(postgres_error_codes::FOREIGN_KEY_VIOLATION) {
+                        return match e.constraint() {
+                            Some("comment_author_uuid_fkey") => DBError::InvalidUUID(format!(
+                                "Invalid author UUID: {}",
+                                comment.author_uuid.clone().unwrap_or_default()
+                            )),
+                            _ => parent_not_found(&parent),
+                        };
+                    }
+                }
+                DBError::Other(Box::new(e))
+            }
+            e => DBError::Other(Box::new(e)),
+        })?;
+
+        tx.commit().await.map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let comment_detail = CommentDetail {
+            comment_uuid: record.comment_uuid.to_string(),
+            question_uuid: record.question_uuid.map(|u| u.to_string()),
+            answer_uuid: record.answer_uuid.map(|u| u.to_string()),
+            content: record.content,
+            created_at: record.created_at.to_string(),
+            author_uuid: record.author_uuid.map(|u| u.to_string()),
+        };
+
+        debug!("create_comment: {comment_detail:?}");
+
+        Ok(comment_detail)
+    }
+
+    async fn get_comment(&self, comment_uuid: String) -> Result<CommentDetail, DBError> {
+        let uuid = Uuid::parse_str(&comment_uuid).map_err(|_| {
+            DBError::InvalidUUID(format!("Could not parse comment UUID: {comment_uuid}"))
+        })?;
+
+        let record = sqlx::query!(
+            r#"
+              SELECT comment_uuid, question_uuid, answer_uuid, content, created_at, author_uuid
+              FROM comment
+              WHERE comment_uuid = $1
+            "#,
+            uuid
+        )
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .ok_or_else(|| DBError::NotFound(format!("Comment not found: {comment_uuid}")))?;
+
+        let comment_detail = CommentDetail {
+            comment_uuid: record.comment_uuid.to_string(),
+            question_uuid: record.question_uuid.map(|u| u.to_string()),
+            answer_uuid: record.answer_uuid.map(|u| u.to_string()),
+            content: record.content,
+            created_at: record.created_at.to_string(),
+            author_uuid: record.author_uuid.map(|u| u.to_string()),
+        };
+
+        debug!("get_comment: {comment_detail:?}");
+
+        Ok(comment_detail)
+    }
+
+    async fn get_comments(
+        &self,
+        parent: CommentParent,
+        include_deleted: bool,
+        page: PageRequest,
+    ) -> Result<Page<CommentDetail>, DBError> {
+        let (question_uuid, answer_uuid) = parent_uuids(&parent)?;
+        let cursor = Cursor::decode_optional(page.cursor.as_deref())?;
+        let limit = page.limit();
+
+        sqlx::query!(
+            r#"
+              SELECT question_uuid
+              FROM question
+              WHERE question_uuid = COALESCE($1, (
+                  SELECT question_uuid FROM answer WHERE answer_uuid = $2 AND ($3 OR deleted_at IS NULL)
+                ))
+                AND ($3 OR deleted_at IS NULL)
+            "#,
+            question_uuid,
+            answer_uuid,
+            include_deleted
+        )
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .ok_or_else(|| parent_not_found(&parent))?;
+
+        // Oldest first, like a conversation. One extra row is fetched to tell
+        // whether a next page exists.
+        let mut records = sqlx::query!(
+            r#"
+              SELECT comment_uuid, question_uuid, answer_uuid, content, created_at, author_uuid
+              FROM comment
+              WHERE (question_uuid = $1 OR answer_uuid = $2)
+                AND ($3::timestamp IS NULL OR (created_at, comment_uuid) > ($3, $4))
+              ORDER BY created_at, comment_uuid
+              LIMIT $5
+            "#,
+            question_uuid,
+            answer_uuid,
+            cursor.map(|c| c.created_at),
+            cursor.map(|c| c.uuid),
+            limit + 1
+        )
+        .fetch_all(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let next_cursor = next_page_cursor(&mut records, limit, |r| {
+            Cursor::new(r.created_at, r.comment_uuid)
+        });
+
+        let comments = records
+            .iter()
+            .map(|r| CommentDetail {
+                comment_uuid: r.comment_uuid.to_string(),
+                question_uuid: r.question_uuid.map(|u| u.to_string()),
+                answer_uuid: r.answer_uuid.map(|u| u.to_string()),
+                content: r.content.to_string(),
+                created_at: r.created_at.to_string(),
+                author_uuid: r.author_uuid.map(|u| u.to_string()),
+            })
+            .collect();
+
+        debug!("get_comments: {comments:?}");
+
+        Ok(Page {
+            items: comments,
+            next_cursor,
+        })
+    }
+
+    async fn delete_comment(&self, comment_uuid: String) -> Result<(), DBError> {
+        let uuid = Uuid::parse_str(&comment_uuid).map_err(|_| {
+            DBError::InvalidUUID(format!("Could not parse comment UUID: {comment_uuid}"))
+        })?;
+
+        sqlx::query!("DELETE FROM comment WHERE comment_uuid = $1", uuid)
+            .execute(&self.db)
+            .await
+            .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,149 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use sqlx::types::{
+    time::{OffsetDateTime, PrimitiveDateTime},
+    Uuid,
+};
+
+use crate::models::DBError;
+
+/// Position of the last row of a page in a listing ordered by
+/// `(created_at, uuid)`, optionally preceded by a numeric `rank` such as an
+/// answer count. Clients only ever see it as an opaque string.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cursor {
+    pub rank: Option<i64>,
+    pub created_at: PrimitiveDateTime,
+    pub uuid: Uuid,
+}
+
+impl Cursor {
+    pub fn new(created_at: PrimitiveDateTime, uuid: Uuid) -> Self {
+        Self {
+            rank: None,
+            created_at,
+            uuid,
+        }
+    }
+
+    pub fn with_rank(rank: i64, created_at: PrimitiveDateTime, uuid: Uuid) -> Self {
+        Self {
+            rank: Some(rank),
+            created_at,
+            uuid,
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        let nanos = self.created_at.assume_utc().unix_timestamp_nanos();
+
+        let plain = match self.rank {
+            Some(rank) => format!("{rank}_{nanos}_{}", self.uuid),
+            None => format!("{nanos}_{}", self.uuid),
+        };
+
+        URL_SAFE_NO_PAD.encode(plain)
+    }
+
+    pub fn decode(cursor: &str) -> Result<Self, DBError> {
+        let invalid = || DBError::InvalidCursor(format!("Could not parse cursor: {cursor}"));
+
+        let bytes = URL_SAFE_NO_PAD.decode(cursor).map_err(|_| invalid())?;
+        let decoded = String::from_utf8(bytes).map_err(|_| invalid())?;
+        let parts: Vec<&str> = decoded.split('_').collect();
+
+        let (rank, nanos, uuid) = match parts.as_slice() {
+            [nanos, uuid] => (None, nanos, uuid),
+            [rank, nanos, uuid] => (
+                Some(rank.parse::<i64>().map_err(|_| invalid())?),
+                nanos,
+                uuid,
+            ),
+            _ => return Err(invalid()),
+        };
+
+        let nanos = nanos.parse::<i128>().map_err(|_| invalid())?;
+        let created_at = OffsetDateTime::from_unix_timestamp_nanos(nanos).map_err(|_| invalid())?;
+        let uuid = Uuid::parse_str(uuid).map_err(|_| invalid())?;
+
+        Ok(Self {
+            rank,
+            created_at: PrimitiveDateTime::new(created_at.date(), created_at.time()),
+            uuid,
+        })
+    }
+
+    pub fn decode_optional(cursor: Option<&str>) -> Result<Option<Self>, DBError> {
+        cursor.map(Self::decode).transpose()
+    }
+}
+
+/// Trims rows fetched with `limit + 1` back down to `limit` and, if the extra
+/// row was present, returns the cursor of the last row kept.
+pub fn next_page_cursor<T>(
+    records: &mut Vec<T>,
+    limit: i64,
+    cursor_of: impl Fn(&T) -> Cursor,
+) -> Option<String> {
+    let limit = limit as usize;
+
+    if records.len() <= limit {
+        return None;
+    }
+
+    records.truncate(limit);
+    records.last().map(|r| cursor_of(r).encode())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timestamp() -> PrimitiveDateTime {
+        // 2024-01-01 12:34:56.789012
+        let timestamp =
+            OffsetDateTime::from_unix_timestamp_nanos(1_704_112_496_789_012_000).unwrap();
+
+        PrimitiveDateTime::new(timestamp.date(), timestamp.time())
+    }
+
+    #[test]
+    fn cursor_should_round_trip() {
+        let cursor = Cursor::new(
+            timestamp(),
+            Uuid::parse_str("b068cd2f-edac-479e-98f1-c5f91008dcbd").unwrap(),
+        );
+
+        assert_eq!(Cursor::decode(&cursor.encode()).unwrap(), cursor);
+    }
+
+    #[test]
+    fn ranked_cursor_should_round_trip() {
+        let cursor = Cursor::with_rank(
+            3,
+            timestamp(),
+            Uuid::parse_str("b068cd2f-edac-479e-98f1-c5f91008dcbd").unwrap(),
+        );
+
+        assert_eq!(Cursor::decode(&cursor.encode()).unwrap(), cursor);
+    }
+
+    #[test]
+    fn cursor_should_reject_malformed_input() {
+        let result = Cursor::decode("malformed");
+
+        assert!(matches!(result, Err(DBError::InvalidCursor(_))));
+    }
+
+    #[test]
+    fn next_page_cursor_should_only_be_set_when_rows_remain() {
+        let uuid = Uuid::parse_str("b068cd2f-edac-479e-98f1-c5f91008dcbd").unwrap();
+        let cursor_of = |_: &i32| Cursor::new(timestamp(), uuid);
+
+        let mut full = vec![1, 2, 3];
+        let mut partial = vec![1, 2];
+
+        assert!(next_page_cursor(&mut full, 2, cursor_of).is_some());
+        assert_eq!(full, vec![1, 2]);
+        assert!(next_page_cursor(&mut partial, 2, cursor_of).is_none());
+    }
+}
@@ -0,0 +1,165 @@
+use async_trait::async_trait;
+use sqlx::{types::Uuid, PgPool};
+
+use crate::{
+    models::{DBError, FollowedQuestion, Page, PageRequest},
+    persistance::cursor::{next_page_cursor, Cursor},
+};
+
+#[async_trait]
+pub trait FollowDao {
+    /// Follows a question. Following it again keeps the original follow.
+    async fn follow_question(
+        &self,
+        question_uuid: String,
+        user_uuid: String,
+    ) -> Result<FollowedQuestion, DBError>;
+    /// Stops following a question, whether it was followed or not.
+    async fn unfollow_question(
+        &self,
+        question_uuid: String,
+        user_uuid: String,
+    ) -> Result<(), DBError>;
+    /// Lists the questions a user follows, most recently followed first.
+    async fn get_followed_questions(
+        &self,
+        user_uuid: String,
+        page: PageRequest,
+    ) -> Result<Page<FollowedQuestion>, DBError>;
+}
+
+pub struct FollowDaoImpl {
+    db: PgPool,
+}
+
+impl FollowDaoImpl {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+}
+
+fn parse_uuid(uuid: &str, name: &str) -> Result<Uuid, DBError> {
+    Uuid::parse_str(uuid)
+        .map_err(|_| DBError::InvalidUUID(format!("Could not parse {name} UUID: {uuid}")))
+}
+
+#[async_trait]
+impl FollowDao for FollowDaoImpl {
+    async fn follow_question(
+        &self,
+        question_uuid: String,
+        user_uuid: String,
+    ) -> Result<FollowedQuestion, DBError> {
+        let question = parse_uuid(&question_uuid, "question")?;
+        let user = parse_uuid(&user_uuid, "user")?;
+
+        sqlx::query!(
+            r#"
+              INSERT INTO question_follow ( user_uuid, question_uuid )
+              SELECT $1, question_uuid FROM question WHERE question_uuid = $2 AND deleted_at IS NULL
+              ON CONFLICT ( user_uuid, question_uuid ) DO NOTHING
+            "#,
+            user,
+            question
+        )
+        .execute(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let record = sqlx::query!(
+            r#"
+              SELECT q.question_uuid, q.title, f.followed_at
+              FROM question_follow f
+              JOIN question q ON q.question_uuid = f.question_uuid
+              WHERE f.user_uuid = $1 AND f.question_uuid = $2 AND q.deleted_at IS NULL
+            "#,
+            user,
+            question
+        )
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .ok_or_else(|| DBError::NotFound(format!("Question not found: {question_uuid}")))?;
+
+        let followed = FollowedQuestion {
+            question_uuid: record.question_uuid.to_string(),
+            title: record.title,
+            followed_at: record.followed_at.to_string(),
+        };
+
+        debug!("follow question: {followed:?}");
+
+        Ok(followed)
+    }
+
+    async fn unfollow_question(
+        &self,
+        question_uuid: String,
+        user_uuid: String,
+    ) -> Result<(), DBError> {
+        let question = parse_uuid(&question_uuid, "question")?;
+        let user = parse_uuid(&user_uuid, "user")?;
+
+        let result = sqlx::query!(
+            "DELETE FROM question_follow WHERE user_uuid = $1 AND question_uuid = $2",
+            user,
+            question
+        )
+        .execute(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        debug!("unfollow question: {} removed", result.rows_affected());
+
+        Ok(())
+    }
+
+    async fn get_followed_questions(
+        &self,
+        user_uuid: String,
+        page: PageRequest,
+    ) -> Result<Page<FollowedQuestion>, DBError> {
+        let user = parse_uuid(&user_uuid, "user")?;
+        let cursor = Cursor::decode_optional(page.cursor.as_deref())?;
+        let limit = page.limit();
+
+        let mut records = sqlx::query!(
+            r#"
+              SELECT q.question_uuid, q.title, f.followed_at
+              FROM question_follow f
+              JOIN question q ON q.question_uuid = f.question_uuid
+              WHERE f.user_uuid = $1 AND q.deleted_at IS NULL
+                AND ($2::timestamp IS NULL OR (f.followed_at, f.question_uuid) < ($2, $3))
+              ORDER BY f.followed_at DESC, f.question_uuid DESC
+              LIMIT $4
+            "#,
+            user,
+            cursor.map(|c| c.created_at),
+            cursor.map(|c| c.uuid),
+            limit + 1
+        )
+        .fetch_all(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let next_cursor = next_page_cursor(&mut records, limit, |r| {
+            Cursor::new(r.followed_at, r.question_uuid)
+        });
+
+        let questions = records
+            .into_iter()
+            .map(|r| FollowedQuestion {
+                question_uuid: r.question_uuid.to_string(),
+                title: r.title,
+                followed_at: r.followed_at.to_string(),
+            })
+            .collect::<Vec<_>>();
+
+        debug!("get followed questions: {questions:?}");
+
+        Ok(Page {
+            items: questions,
+            next_cursor,
+        })
+    }
+}
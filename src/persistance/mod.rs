@@ -1,5 +1,18 @@
 pub mod answer_dao;
+pub mod badge_dao;
+pub mod bounty_dao;
+pub mod comment_dao;
+pub mod cursor;
+pub mod follow_dao;
+pub mod notification_dao;
 pub mod question_dao;
+pub mod reputation_dao;
+pub mod search_dao;
+pub mod session_dao;
+pub mod tag_dao;
+pub mod user_dao;
+pub mod vote_dao;
 
 #[cfg(test)]
+#[allow(clippy::cmp_owned, clippy::get_first, clippy::len_zero)]
 mod tests;
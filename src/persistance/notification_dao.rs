@@ -0,0 +1,314 @@
+use async_trait::async_trait;
+use sqlx::{types::Uuid, PgPool};
+
+use crate::{
+    models::{
+        DBError, Notification, NotificationEvent, NotificationInbox, NotificationKind,
+        NotificationPreference, PageRequest, UnreadCount,
+    },
+    persistance::cursor::{next_page_cursor, Cursor},
+};
+
+#[async_trait]
+pub trait NotificationDao {
+    /// Notifies the users concerned by an event who have not turned its kind
+    /// off, and returns how many were notified.
+    async fn notify(&self, event: NotificationEvent) -> Result<u64, DBError>;
+    /// Lists the notifications of a user, newest first.
+    async fn get_notifications(
+        &self,
+        user_uuid: String,
+        unread_only: bool,
+        page: PageRequest,
+    ) -> Result<NotificationInbox, DBError>;
+    async fn mark_read(
+        &self,
+        user_uuid: String,
+        notification_uuid: String,
+    ) -> Result<UnreadCount, DBError>;
+    async fn mark_all_read(&self, user_uuid: String) -> Result<UnreadCount, DBError>;
+    /// Lists the preference of a user for every notification kind.
+    async fn get_preferences(
+        &self,
+        user_uuid: String,
+    ) -> Result<Vec<NotificationPreference>, DBError>;
+    /// Stores the given preferences, leaving the other kinds as they are.
+    async fn update_preferences(
+        &self,
+        user_uuid: String,
+        preferences: Vec<NotificationPreference>,
+    ) -> Result<Vec<NotificationPreference>, DBError>;
+}
+
+pub struct NotificationDaoImpl {
+    db: PgPool,
+}
+
+impl NotificationDaoImpl {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+}
+
+fn parse_uuid(uuid: &str, name: &str) -> Result<Uuid, DBError> {
+    Uuid::parse_str(uuid)
+        .map_err(|_| DBError::InvalidUUID(format!("Could not parse {name} UUID: {uuid}")))
+}
+
+fn parse_optional_uuid(uuid: Option<&str>, name: &str) -> Result<Option<Uuid>, DBError> {
+    uuid.map(|uuid| parse_uuid(uuid, name)).transpose()
+}
+
+fn parse_kind(kind: &str) -> Result<NotificationKind, DBError> {
+    kind.parse().map_err(|e: String| DBError::Other(e.into()))
+}
+
+impl NotificationDaoImpl {
+    async fn unread_count(&self, user: Uuid) -> Result<UnreadCount, DBError> {
+        let unread_count = sqlx::query!(
+            r#"SELECT COUNT(*) AS "count!" FROM notification WHERE user_uuid = $1 AND read_at IS NULL"#,
+            user
+        )
+        .fetch_one(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .count;
+
+        Ok(UnreadCount { unread_count })
+    }
+}
+
+#[async_trait]
+impl NotificationDao for NotificationDaoImpl {
+    async fn notify(&self, event: NotificationEvent) -> Result<u64, DBError> {
+        let actor = parse_optional_uuid(event.actor_uuid.as_deref(), "user")?;
+        let question = parse_optional_uuid(event.question_uuid.as_deref(), "question")?;
+        let answer = parse_optional_uuid(event.answer_uuid.as_deref(), "answer")?;
+        let comment = parse_optional_uuid(event.comment_uuid.as_deref(), "comment")?;
+
+        let post = sqlx::query!(
+            r#"
+              SELECT q.question_uuid, q.author_uuid AS question_author, a.author_uuid AS "answer_author?",
+                ARRAY(SELECT f.user_uuid FROM question_follow f WHERE f.question_uuid = q.question_uuid) AS "followers!"
+              FROM question q
+              LEFT JOIN answer a ON a.answer_uuid = $2 AND a.question_uuid = q.question_uuid
+              WHERE q.question_uuid = COALESCE($1, (SELECT question_uuid FROM answer WHERE answer_uuid = $2))
+            "#,
+            question,
+            answer
+        )
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .ok_or_else(|| DBError::NotFound(format!("Post not found for notification: {event:?}")))?;
+
+        // Comments and edits concern the post they are on, which is the answer
+        // when there is one.
+        let recipients: Vec<Uuid> = match event.kind {
+            NotificationKind::AnswerPosted => post
+                .question_author
+                .into_iter()
+                .chain(post.followers)
+                .collect(),
+            NotificationKind::CommentPosted | NotificationKind::PostEdited => match answer {
+                Some(_) => post.answer_author.into_iter().collect(),
+                None => post.question_author.into_iter().collect(),
+            },
+            NotificationKind::AnswerAccepted | NotificationKind::BountyAwarded => {
+                post.answer_author.into_iter().collect()
+            }
+        };
+
+        let notified = sqlx::query!(
+            r#"
+              INSERT INTO notification ( user_uuid, kind, actor_uuid, question_uuid, answer_uuid, comment_uuid )
+              SELECT DISTINCT r.user_uuid, $2::text, $3::uuid, $4::uuid, $5::uuid, $6::uuid
+              FROM UNNEST($1::uuid[]) AS r ( user_uuid )
+              WHERE r.user_uuid IS DISTINCT FROM $3
+                AND NOT EXISTS (
+                  SELECT 1 FROM notification_preference p
+                  WHERE p.user_uuid = r.user_uuid AND p.kind = $2 AND NOT p.enabled
+                )
+            "#,
+            &recipients,
+            event.kind.as_str(),
+            actor,
+            post.question_uuid,
+            answer,
+            comment
+        )
+        .execute(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .rows_affected();
+
+        debug!("notify: {notified} notified of {event:?}");
+
+        Ok(notified)
+    }
+
+    async fn get_notifications(
+        &self,
+        user_uuid: String,
+        unread_only: bool,
+        page: PageRequest,
+    ) -> Result<NotificationInbox, DBError> {
+        let user = parse_uuid(&user_uuid, "user")?;
+        let cursor = Cursor::decode_optional(page.cursor.as_deref())?;
+        let limit = page.limit();
+
+        let mut records = sqlx::query!(
+            r#"
+              SELECT notification_uuid, kind, actor_uuid, question_uuid, answer_uuid, comment_uuid,
+                created_at, read_at
+              FROM notification
+              WHERE user_uuid = $1 AND (NOT $2 OR read_at IS NULL)
+                AND ($3::timestamp IS NULL OR (created_at, notification_uuid) < ($3, $4))
+              ORDER BY created_at DESC, notification_uuid DESC
+              LIMIT $5
+            "#,
+            user,
+            unread_only,
+            cursor.map(|c| c.created_at),
+            cursor.map(|c| c.uuid),
+            limit + 1
+        )
+        .fetch_all(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let next_cursor = next_page_cursor(&mut records, limit, |r| {
+            Cursor::new(r.created_at, r.notification_uuid)
+        });
+
+        let notifications = records
+            .into_iter()
+            .map(|r| {
+                Ok(Notification {
+                    notification_uuid: r.notification_uuid.to_string(),
+                    kind: parse_kind(&r.kind)?,
+                    actor_uuid: r.actor_uuid.map(|u| u.to_string()),
+                    question_uuid: r.question_uuid.to_string(),
+                    answer_uuid: r.answer_uuid.map(|u| u.to_string()),
+                    comment_uuid: r.comment_uuid.map(|u| u.to_string()),
+                    created_at: r.created_at.to_string(),
+                    read_at: r.read_at.map(|t| t.to_string()),
+                })
+            })
+            .collect::<Result<Vec<_>, DBError>>()?;
+
+        let unread_count = self.unread_count(user).await?.unread_count;
+
+        debug!("get notifications: {notifications:?}");
+
+        Ok(NotificationInbox {
+            items: notifications,
+            next_cursor,
+            unread_count,
+        })
+    }
+
+    async fn mark_read(
+        &self,
+        user_uuid: String,
+        notification_uuid: String,
+    ) -> Result<UnreadCount, DBError> {
+        let user = parse_uuid(&user_uuid, "user")?;
+        let notification = parse_uuid(&notification_uuid, "notification")?;
+
+        // Reading a notification twice keeps the first read time.
+        let result = sqlx::query!(
+            r#"
+              UPDATE notification
+              SET read_at = COALESCE(read_at, CURRENT_TIMESTAMP)
+              WHERE notification_uuid = $1 AND user_uuid = $2
+            "#,
+            notification,
+            user
+        )
+        .execute(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        if result.rows_affected() == 0 {
+            return Err(DBError::NotFound(format!(
+                "Notification not found: {notification_uuid}"
+            )));
+        }
+
+        self.unread_count(user).await
+    }
+
+    async fn mark_all_read(&self, user_uuid: String) -> Result<UnreadCount, DBError> {
+        let user = parse_uuid(&user_uuid, "user")?;
+
+        let result = sqlx::query!(
+            "UPDATE notification SET read_at = CURRENT_TIMESTAMP WHERE user_uuid = $1 AND read_at IS NULL",
+            user
+        )
+        .execute(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        debug!("mark all read: {} read", result.rows_affected());
+
+        self.unread_count(user).await
+    }
+
+    async fn get_preferences(
+        &self,
+        user_uuid: String,
+    ) -> Result<Vec<NotificationPreference>, DBError> {
+        let user = parse_uuid(&user_uuid, "user")?;
+
+        let disabled = sqlx::query!(
+            "SELECT kind FROM notification_preference WHERE user_uuid = $1 AND NOT enabled",
+            user
+        )
+        .fetch_all(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .into_iter()
+        .map(|r| r.kind)
+        .collect::<Vec<_>>();
+
+        let preferences = NotificationKind::ALL
+            .into_iter()
+            .map(|kind| NotificationPreference {
+                kind,
+                enabled: !disabled.iter().any(|d| d == kind.as_str()),
+            })
+            .collect();
+
+        Ok(preferences)
+    }
+
+    async fn update_preferences(
+        &self,
+        user_uuid: String,
+        preferences: Vec<NotificationPreference>,
+    ) -> Result<Vec<NotificationPreference>, DBError> {
+        let user = parse_uuid(&user_uuid, "user")?;
+        let kinds: Vec<&str> = preferences.iter().map(|p| p.kind.as_str()).collect();
+        let enabled: Vec<bool> = preferences.iter().map(|p| p.enabled).collect();
+
+        sqlx::query!(
+            r#"
+              INSERT INTO notification_preference ( user_uuid, kind, enabled )
+              SELECT $1, p.kind, p.enabled
+              FROM UNNEST($2::text[], $3::bool[]) AS p ( kind, enabled )
+              ON CONFLICT ( user_uuid, kind ) DO UPDATE SET enabled = EXCLUDED.enabled
+            "#,
+            user,
+            &kinds as &[&str],
+            &enabled
+        )
+        .execute(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        debug!("update notification preferences: {preferences:?}");
+
+        self.get_preferences(user_uuid).await
+    }
+}
@@ -1,13 +1,96 @@
 use async_trait::async_trait;
-use sqlx::PgPool;
+use sqlx::{
+    types::{time::PrimitiveDateTime, Uuid},
+    PgConnection, PgExecutor, PgPool,
+};
 
-use crate::models::{DBError, Question, QuestionDetail};
+use crate::{
+    models::{
+        postgres_error_codes, BadgeCriterion, CloseReason, CloseVote, DBError, Page, PageRequest,
+        Question, QuestionDetail, QuestionQuery, QuestionRevision, QuestionSort, QuestionState,
+        QuestionStatus, QuestionUpdate, SimilarQuestion, CLOSE_VOTES_NEEDED, MAX_SIMILAR_QUESTIONS,
+        REOPEN_VOTES_NEEDED,
+    },
+    persistance::{
+        badge_dao,
+        cursor::{next_page_cursor, Cursor},
+        reputation_dao,
+    },
+};
 
 #[async_trait]
 pub trait QuestionDao {
     async fn create_question(&self, question: Question) -> Result<QuestionDetail, DBError>;
-    async fn get_questions(&self) -> Result<Vec<QuestionDetail>, DBError>;
-    async fn delete_question(&self, question_uuid: String) -> Result<(), DBError>;
+    async fn get_question(&self, question_uuid: String) -> Result<QuestionDetail, DBError>;
+    async fn get_similar_questions(
+        &self,
+        title: String,
+        description: Option<String>,
+    ) -> Result<Vec<SimilarQuestion>, DBError>;
+    /// Lists questions, restricted to those followed by `follower` when set.
+    async fn get_questions(
+        &self,
+        query: QuestionQuery,
+        page: PageRequest,
+        follower: Option<String>,
+    ) -> Result<Page<QuestionDetail>, DBError>;
+    async fn update_question(
+        &self,
+        question_uuid: String,
+        update: QuestionUpdate,
+    ) -> Result<QuestionDetail, DBError>;
+    /// Hides the question until it is undeleted or purged.
+    async fn delete_question(
+        &self,
+        question_uuid: String,
+        deleted_by: String,
+    ) -> Result<(), DBError>;
+    async fn undelete_question(&self, question_uuid: String) -> Result<QuestionDetail, DBError>;
+    /// Hard-deletes questions deleted more than `retention_days` ago and
+    /// returns how many were removed.
+    async fn purge_deleted_questions(&self, retention_days: i32) -> Result<u64, DBError>;
+    async fn accept_answer(
+        &self,
+        question_uuid: String,
+        answer_uuid: String,
+    ) -> Result<QuestionDetail, DBError>;
+    async fn unaccept_answer(
+        &self,
+        question_uuid: String,
+        answer_uuid: String,
+    ) -> Result<QuestionDetail, DBError>;
+    /// Lists the revisions of a question. Those of a deleted question are only
+    /// found if `include_deleted` is set.
+    async fn get_question_revisions(
+        &self,
+        question_uuid: String,
+        include_deleted: bool,
+    ) -> Result<Vec<QuestionRevision>, DBError>;
+    async fn get_question_revision(
+        &self,
+        question_uuid: String,
+        revision: i32,
+        include_deleted: bool,
+    ) -> Result<QuestionRevision, DBError>;
+    /// Records a vote to close an open question and closes it once enough
+    /// votes are in, or right away if the vote is `binding`.
+    async fn vote_to_close(
+        &self,
+        question_uuid: String,
+        user_uuid: String,
+        vote: CloseVote,
+        binding: bool,
+    ) -> Result<QuestionStatus, DBError>;
+    /// Records a vote to reopen a closed question, reopening it like
+    /// `vote_to_close` closes it.
+    async fn vote_to_reopen(
+        &self,
+        question_uuid: String,
+        user_uuid: String,
+        binding: bool,
+    ) -> Result<QuestionStatus, DBError>;
+    async fn lock_question(&self, question_uuid: String) -> Result<QuestionStatus, DBError>;
+    async fn unlock_question(&self, question_uuid: String) -> Result<QuestionStatus, DBError>;
 }
 
 pub struct QuestionDaoImpl {
@@ -20,66 +103,1120 @@ impl QuestionDaoImpl {
     }
 }
 
+/// States and close reasons are stored as text and constrained by the
+/// database, so an unknown value means the schema and the code have drifted
+/// apart.
+pub(crate) fn parse_state(state: &str) -> Result<QuestionState, DBError> {
+    state.parse().map_err(|e: String| DBError::Other(e.into()))
+}
+
+fn parse_close_reason(reason: Option<&str>) -> Result<Option<CloseReason>, DBError> {
+    reason
+        .map(str::parse)
+        .transpose()
+        .map_err(|e: String| DBError::Other(e.into()))
+}
+
+/// Locks the question row until the end of the transaction and returns its
+/// state, so concurrent changes to the question are made one after the other.
+pub(crate) async fn lock_state(
+    conn: &mut PgConnection,
+    uuid: Uuid,
+    question_uuid: &str,
+) -> Result<QuestionState, DBError> {
+    let record = sqlx::query!(
+        "SELECT state FROM question WHERE question_uuid = $1 AND deleted_at IS NULL FOR UPDATE",
+        uuid
+    )
+    .fetch_optional(conn)
+    .await
+    .map_err(|e| DBError::Other(Box::new(e)))?
+    .ok_or_else(|| DBError::NotFound(format!("Question not found: {question_uuid}")))?;
+
+    parse_state(&record.state)
+}
+
+/// Locked questions are frozen by a moderator: neither they nor their answers
+/// can be edited, voted on, commented on or accepted until unlocked. Callers
+/// hold a lock on the question row so the state cannot change meanwhile.
+pub(crate) fn refuse_locked(state: &str, question_uuid: &str) -> Result<(), DBError> {
+    if state == QuestionState::Locked.as_str() {
+        Err(DBError::Conflict(format!(
+            "Question is locked: {question_uuid}"
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+/// A row of a question listing, holding what its cursor is made of.
+struct ListedQuestion {
+    question_uuid: Uuid,
+    created_at: PrimitiveDateTime,
+    answer_count: i64,
+}
+
+/// Loads the questions with the given UUIDs, in the same order, whether
+/// deleted or not. Every method returning questions goes through here, so the
+/// columns of a `QuestionDetail` are only listed once.
+async fn get_details(
+    executor: impl PgExecutor<'_>,
+    uuids: &[Uuid],
+) -> Result<Vec<QuestionDetail>, DBError> {
+    let records = sqlx::query!(
+        r#"
+          SELECT q.question_uuid, q.title, q.description, q.created_at, q.author_uuid, q.score,
+            q.accepted_answer_uuid, q.state, q.close_reason, q.duplicate_of, q.deleted_at,
+            ARRAY(
+              SELECT t.name FROM question_tag qt JOIN tag t ON t.tag_uuid = qt.tag_uuid
+              WHERE qt.question_uuid = q.question_uuid ORDER BY t.name
+            ) AS "tags!",
+            (SELECT COUNT(*) FROM comment c WHERE c.question_uuid = q.question_uuid) AS "comment_count!"
+          FROM unnest($1::uuid[]) WITH ORDINALITY AS page ( question_uuid, position )
+          JOIN question q ON q.question_uuid = page.question_uuid
+          ORDER BY page.position
+        "#,
+        uuids
+    )
+    .fetch_all(executor)
+    .await
+    .map_err(|e| DBError::Other(Box::new(e)))?;
+
+    records
+        .into_iter()
+        .map(|r| {
+            Ok(QuestionDetail {
+                question_uuid: r.question_uuid.to_string(),
+                title: r.title,
+                description: r.description,
+                created_at: r.created_at.to_string(),
+                author_uuid: r.author_uuid.map(|u| u.to_string()),
+                score: r.score,
+                accepted_answer_uuid: r.accepted_answer_uuid.map(|u| u.to_string()),
+                tags: r.tags,
+                comment_count: r.comment_count,
+                state: parse_state(&r.state)?,
+                close_reason: parse_close_reason(r.close_reason.as_deref())?,
+                duplicate_of: r.duplicate_of.map(|u| u.to_string()),
+                deleted_at: r.deleted_at.map(|t| t.to_string()),
+            })
+        })
+        .collect()
+}
+
+/// Loads a single question through `get_details`.
+async fn get_detail(
+    executor: impl PgExecutor<'_>,
+    uuid: Uuid,
+) -> Result<Option<QuestionDetail>, DBError> {
+    Ok(get_details(executor, &[uuid]).await?.pop())
+}
+
+async fn get_status(conn: &mut PgConnection, uuid: Uuid) -> Result<QuestionStatus, DBError> {
+    let record = sqlx::query!(
+        r#"
+          SELECT question_uuid, state, close_reason, duplicate_of,
+            (SELECT COUNT(*) FROM question_close_vote v
+              WHERE v.question_uuid = question.question_uuid AND v.kind = 'close') AS "close_votes!",
+            (SELECT COUNT(*) FROM question_close_vote v
+              WHERE v.question_uuid = question.question_uuid AND v.kind = 'reopen') AS "reopen_votes!"
+          FROM question
+          WHERE question_uuid = $1
+        "#,
+        uuid
+    )
+    .fetch_one(conn)
+    .await
+    .map_err(|e| DBError::Other(Box::new(e)))?;
+
+    Ok(QuestionStatus {
+        question_uuid: record.question_uuid.to_string(),
+        state: parse_state(&record.state)?,
+        close_reason: parse_close_reason(record.close_reason.as_deref())?,
+        duplicate_of: record.duplicate_of.map(|u| u.to_string()),
+        close_votes: record.close_votes,
+        reopen_votes: record.reopen_votes,
+    })
+}
+
+/// Records a close or reopen vote, failing if the user already cast one.
+async fn insert_close_vote(
+    conn: &mut PgConnection,
+    uuid: Uuid,
+    user: Uuid,
+    kind: &str,
+    vote: Option<&CloseVote>,
+    question_uuid: &str,
+) -> Result<(), DBError> {
+    let duplicate_of = vote
+        .and_then(|v| v.duplicate_of.as_deref())
+        .map(|d| {
+            Uuid::parse_str(d)
+                .map_err(|_| DBError::InvalidUUID(format!("Could not parse question UUID: {d}")))
+        })
+        .transpose()?;
+
+    let result = sqlx::query!(
+        r#"
+          INSERT INTO question_close_vote ( question_uuid, user_uuid, kind, close_reason, duplicate_of )
+          VALUES ( $1, $2, $3, $4, $5 )
+          ON CONFLICT DO NOTHING
+        "#,
+        uuid,
+        user,
+        kind,
+        vote.map(|v| v.reason.as_str()),
+        duplicate_of
+    )
+    .execute(conn)
+    .await
+    .map_err(|e: sqlx::Error| match e {
+        sqlx::Error::Database(e) => {
+            if let Some(code) = e.code() {
+                if code.eq(postgres_error_codes::FOREIGN_KEY_VIOLATION) {
+                    return match e.constraint() {
+                        Some("question_close_vote_duplicate_of_fkey") => DBError::NotFound(format!(
+                            "Question not found: {}",
+                            vote.and_then(|v| v.duplicate_of.clone()).unwrap_or_default()
+                        )),
+                        _ => DBError::InvalidUUID(format!("Invalid user UUID: {user}")),
+                    };
+                }
+            }
+            DBError::Other(Box::new(e))
+        }
+        e => DBError::Other(Box::new(e)),
+    })?;
+
+    if result.rows_affected() == 0 {
+        return Err(DBError::Conflict(format!(
+            "Already voted to {kind} question: {question_uuid}"
+        )));
+    }
+
+    Ok(())
+}
+
+/// Votes only count towards the next change of state.
+async fn clear_close_votes(conn: &mut PgConnection, uuid: Uuid) -> Result<(), DBError> {
+    sqlx::query!(
+        "DELETE FROM question_close_vote WHERE question_uuid = $1",
+        uuid
+    )
+    .execute(conn)
+    .await
+    .map_err(|e| DBError::Other(Box::new(e)))?;
+
+    Ok(())
+}
+
 #[async_trait]
 impl QuestionDao for QuestionDaoImpl {
     async fn create_question(&self, question: Question) -> Result<QuestionDetail, DBError> {
+        let author_uuid = question
+            .author_uuid
+            .as_deref()
+            .map(sqlx::types::Uuid::parse_str)
+            .transpose()
+            .map_err(|_| {
+                DBError::InvalidUUID(format!(
+                    "Could not parse author UUID: {}",
+                    question.author_uuid.clone().unwrap_or_default()
+                ))
+            })?;
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| DBError::Other(Box::new(e)))?;
+
         let record = sqlx::query!(
             r#"
-              INSERT INTO question ( title, description )
-              VALUES ( $1, $2 )
-              RETURNING *
+              INSERT INTO question ( title, description, author_uuid )
+              VALUES ( $1, $2, $3 )
+              RETURNING question_uuid, title, description, author_uuid
             "#,
             question.title,
-            question.description
+            question.description,
+            author_uuid
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e: sqlx::Error| match e {
+            sqlx::Error::Database(e) => {
+                if let Some(code) = e.code() {
+                    if code.eq(postgres_error_codes::FOREIGN_KEY_VIOLATION) {
+                        return DBError::InvalidUUID(format!(
+                            "Invalid author UUID: {}",
+                            question.author_uuid.clone().unwrap_or_default()
+                        ));
+                    }
+                }
+                DBError::Other(Box::new(e))
+            }
+            e => DBError::Other(Box::new(e)),
+        })?;
+
+        sqlx::query!(
+            r#"
+              INSERT INTO question_revision ( question_uuid, revision, title, description )
+              VALUES ( $1, 1, $2, $3 )
+            "#,
+            record.question_uuid,
+            record.title,
+            record.description
         )
-        .fetch_one(&self.db)
+        .execute(&mut *tx)
         .await
         .map_err(|e| DBError::Other(Box::new(e)))?;
 
-        let question_detail = QuestionDetail {
-            question_uuid: record.question_uuid.to_string(),
-            title: record.title,
-            description: record.description,
-            created_at: record.created_at.to_string(),
-        };
+        // Approved synonyms are rewritten to their canonical tag, which may
+        // leave duplicates to drop.
+        let tags: Vec<String> = sqlx::query!(
+            r#"
+              SELECT DISTINCT COALESCE(t.name, s.name) AS "name!"
+              FROM unnest($1::varchar[]) AS s ( name )
+              LEFT JOIN tag_synonym ts ON ts.source_name = s.name AND ts.approved_at IS NOT NULL
+              LEFT JOIN tag t ON t.tag_uuid = ts.target_tag_uuid
+              ORDER BY 1
+            "#,
+            &question.tags
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .into_iter()
+        .map(|r| r.name)
+        .collect();
+
+        // Tags are created on first use. A tag inserted concurrently by another
+        // question makes the INSERT wait for it, so the lookup below sees it.
+        sqlx::query!(
+            r#"
+              INSERT INTO tag ( name )
+              SELECT unnest($1::varchar[])
+              ON CONFLICT ( name ) DO NOTHING
+            "#,
+            &tags
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        sqlx::query!(
+            r#"
+              INSERT INTO question_tag ( question_uuid, tag_uuid )
+              SELECT $1, tag_uuid FROM tag WHERE name = ANY($2)
+            "#,
+            record.question_uuid,
+            &tags
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        badge_dao::award_author_badges(&mut tx, record.author_uuid, |c| {
+            matches!(c, BadgeCriterion::AskedQuestion)
+        })
+        .await?;
+
+        let question_detail = get_detail(&mut *tx, record.question_uuid)
+            .await?
+            .ok_or_else(|| DBError::Other("Created question not found".into()))?;
+
+        tx.commit().await.map_err(|e| DBError::Other(Box::new(e)))?;
 
         debug!("create_question: {question_detail:?}");
 
         Ok(question_detail)
     }
 
-    async fn get_questions(&self) -> Result<Vec<QuestionDetail>, DBError> {
-        let records =
-            sqlx::query!("SELECT question_uuid, title, description, created_at FROM question")
-                .fetch_all(&self.db)
-                .await
-                .map_err(|e| DBError::Other(Box::new(e)))?;
+    async fn get_question(&self, question_uuid: String) -> Result<QuestionDetail, DBError> {
+        let uuid = sqlx::types::Uuid::parse_str(&question_uuid).map_err(|_| {
+            DBError::InvalidUUID(format!("Could not parse question UUID: {question_uuid}"))
+        })?;
+
+        let question_detail = get_detail(&self.db, uuid)
+            .await?
+            .filter(|question| question.deleted_at.is_none())
+            .ok_or_else(|| DBError::NotFound(format!("Question not found: {question_uuid}")))?;
+
+        debug!("get_question: {question_detail:?}");
+
+        Ok(question_detail)
+    }
+
+    async fn get_similar_questions(
+        &self,
+        title: String,
+        description: Option<String>,
+    ) -> Result<Vec<SimilarQuestion>, DBError> {
+        // Titles are compared by trigram similarity (`%` keeps those above 0.3),
+        // descriptions by the share of their words, once stemmed, found in the
+        // candidate question. The GIN indexes narrow down the candidates first.
+        let records = sqlx::query!(
+            r#"
+              WITH input AS (
+                SELECT tsvector_to_array(to_tsvector('english', COALESCE($2, ''))) AS words
+              ),
+              candidates AS (
+                SELECT q.question_uuid, q.title, q.created_at, q.score, q.answer_count,
+                  similarity(q.title, $1) AS title_similarity,
+                  CASE WHEN cardinality(input.words) = 0 THEN 0 ELSE
+                    cardinality(ARRAY(
+                      SELECT unnest(tsvector_to_array(q.search_vector))
+                      INTERSECT
+                      SELECT unnest(input.words)
+                    ))::real / cardinality(input.words)
+                  END AS description_similarity
+                FROM question q, input
+                WHERE q.deleted_at IS NULL AND (q.title % $1 OR q.search_vector @@ to_tsquery(
+                  'simple',
+                  array_to_string(ARRAY(SELECT quote_literal(w) FROM unnest(input.words) w), ' | ')
+                ))
+              )
+              SELECT question_uuid, title, created_at, score, answer_count,
+                GREATEST(title_similarity, description_similarity)::real AS "similarity!"
+              FROM candidates c
+              WHERE title_similarity >= 0.3 OR description_similarity >= 0.5
+              ORDER BY "similarity!" DESC, created_at DESC
+              LIMIT $3
+            "#,
+            title,
+            description,
+            MAX_SIMILAR_QUESTIONS
+        )
+        .fetch_all(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
 
         let questions = records
             .iter()
-            .map(|r| QuestionDetail {
+            .map(|r| SimilarQuestion {
                 question_uuid: r.question_uuid.to_string(),
                 title: r.title.to_string(),
-                description: r.description.to_string(),
                 created_at: r.created_at.to_string(),
+                score: r.score,
+                answer_count: r.answer_count,
+                similarity: r.similarity,
             })
             .collect();
 
-        debug!("get_questions: {questions:?}");
+        debug!("get_similar_questions: {questions:?}");
 
         Ok(questions)
     }
 
-    async fn delete_question(&self, question_uuid: String) -> Result<(), DBError> {
+    async fn get_questions(
+        &self,
+        query: QuestionQuery,
+        page: PageRequest,
+        follower: Option<String>,
+    ) -> Result<Page<QuestionDetail>, DBError> {
+        let follower = follower
+            .map(|uuid| {
+                Uuid::parse_str(&uuid)
+                    .map_err(|_| DBError::InvalidUUID(format!("Could not parse user UUID: {uuid}")))
+            })
+            .transpose()?;
+        let cursor = Cursor::decode_optional(page.cursor.as_deref())?;
+        let limit = page.limit();
+
+        if query.sort == QuestionSort::MostAnswered && cursor.is_some_and(|c| c.rank.is_none()) {
+            return Err(DBError::InvalidCursor(format!(
+                "Cursor does not match sort order: {}",
+                query.sort.as_str()
+            )));
+        }
+
+        // Each sort order has its own statement, so that the keyset comparison
+        // and the ORDER BY match one of the indexes on the question table. One
+        // extra row is fetched to tell whether a next page exists.
+        let tags = query.tags();
+        let mut records = match query.sort {
+            QuestionSort::Newest => sqlx::query_as!(
+                ListedQuestion,
+                r#"
+                  SELECT question_uuid, created_at, answer_count
+                  FROM question q
+                  WHERE ($1::timestamp IS NULL OR (created_at, question_uuid) < ($1, $2))
+                AND ($3::timestamp IS NULL OR created_at > $3)
+                AND ($4::timestamp IS NULL OR created_at < $4)
+                AND (NOT $5 OR answer_count = 0)
+                AND cardinality($6::varchar[]) = (
+                  SELECT COUNT(*) FROM question_tag qt JOIN tag t ON t.tag_uuid = qt.tag_uuid
+                  WHERE qt.question_uuid = q.question_uuid AND t.name = ANY($6)
+                )
+                AND ($7 OR deleted_at IS NULL)
+                AND ($8::uuid IS NULL OR EXISTS (
+                  SELECT 1 FROM question_follow f
+                  WHERE f.question_uuid = q.question_uuid AND f.user_uuid = $8
+                ))
+                  ORDER BY created_at DESC, question_uuid DESC
+                  LIMIT $9
+                "#,
+            cursor.map(|c| c.created_at),
+            cursor.map(|c| c.uuid),
+            query.created_after,
+            query.created_before,
+            query.unanswered,
+            &tags,
+            query.include_deleted,
+            follower,
+            limit + 1
+            )
+            .fetch_all(&self.db)
+            .await,
+            QuestionSort::Oldest => sqlx::query_as!(
+                ListedQuestion,
+                r#"
+                  SELECT question_uuid, created_at, answer_count
+                  FROM question q
+                  WHERE ($1::timestamp IS NULL OR (created_at, question_uuid) > ($1, $2))
+                AND ($3::timestamp IS NULL OR created_at > $3)
+                AND ($4::timestamp IS NULL OR created_at < $4)
+                AND (NOT $5 OR answer_count = 0)
+                AND cardinality($6::varchar[]) = (
+                  SELECT COUNT(*) FROM question_tag qt JOIN tag t ON t.tag_uuid = qt.tag_uuid
+                  WHERE qt.question_uuid = q.question_uuid AND t.name = ANY($6)
+                )
+                AND ($7 OR deleted_at IS NULL)
+                AND ($8::uuid IS NULL OR EXISTS (
+                  SELECT 1 FROM question_follow f
+                  WHERE f.question_uuid = q.question_uuid AND f.user_uuid = $8
+                ))
+                  ORDER BY created_at, question_uuid
+                  LIMIT $9
+                "#,
+            cursor.map(|c| c.created_at),
+            cursor.map(|c| c.uuid),
+            query.created_after,
+            query.created_before,
+            query.unanswered,
+            &tags,
+            query.include_deleted,
+            follower,
+            limit + 1
+            )
+            .fetch_all(&self.db)
+            .await,
+            QuestionSort::MostAnswered => sqlx::query_as!(
+                ListedQuestion,
+                r#"
+                  SELECT question_uuid, created_at, answer_count
+                  FROM question q
+                  WHERE ($1::timestamp IS NULL OR (answer_count, created_at, question_uuid) < ($10, $1, $2))
+                AND ($3::timestamp IS NULL OR created_at > $3)
+                AND ($4::timestamp IS NULL OR created_at < $4)
+                AND (NOT $5 OR answer_count = 0)
+                AND cardinality($6::varchar[]) = (
+                  SELECT COUNT(*) FROM question_tag qt JOIN tag t ON t.tag_uuid = qt.tag_uuid
+                  WHERE qt.question_uuid = q.question_uuid AND t.name = ANY($6)
+                )
+                AND ($7 OR deleted_at IS NULL)
+                AND ($8::uuid IS NULL OR EXISTS (
+                  SELECT 1 FROM question_follow f
+                  WHERE f.question_uuid = q.question_uuid AND f.user_uuid = $8
+                ))
+                  ORDER BY answer_count DESC, created_at DESC, question_uuid DESC
+                  LIMIT $9
+                "#,
+            cursor.map(|c| c.created_at),
+            cursor.map(|c| c.uuid),
+            query.created_after,
+            query.created_before,
+            query.unanswered,
+            &tags,
+            query.include_deleted,
+            follower,
+            limit + 1,
+            cursor.and_then(|c| c.rank)
+            )
+            .fetch_all(&self.db)
+            .await,
+        }
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let next_cursor = next_page_cursor(&mut records, limit, |r| match query.sort {
+            QuestionSort::MostAnswered => {
+                Cursor::with_rank(r.answer_count, r.created_at, r.question_uuid)
+            }
+            _ => Cursor::new(r.created_at, r.question_uuid),
+        });
+
+        let uuids: Vec<Uuid> = records.iter().map(|r| r.question_uuid).collect();
+        let questions = get_details(&self.db, &uuids).await?;
+
+        debug!("get_questions: {questions:?}");
+
+        Ok(Page {
+            items: questions,
+            next_cursor,
+        })
+    }
+
+    async fn update_question(
+        &self,
+        question_uuid: String,
+        update: QuestionUpdate,
+    ) -> Result<QuestionDetail, DBError> {
         let uuid = sqlx::types::Uuid::parse_str(&question_uuid).map_err(|_| {
             DBError::InvalidUUID(format!("Could not parse question UUID: {question_uuid}"))
         })?;
 
-        sqlx::query!("DELETE FROM question WHERE question_uuid = $1", uuid)
-            .execute(&self.db)
+        let mut tx = self
+            .db
+            .begin()
             .await
             .map_err(|e| DBError::Other(Box::new(e)))?;
 
+        // The row lock serializes concurrent edits, so the revision number
+        // computed below cannot be claimed twice.
+        let state = lock_state(&mut tx, uuid, &question_uuid).await?;
+        refuse_locked(state.as_str(), &question_uuid)?;
+
+        let record = sqlx::query!(
+            r#"
+              UPDATE question
+              SET title = COALESCE($2, title), description = COALESCE($3, description)
+              WHERE question_uuid = $1 AND deleted_at IS NULL
+              RETURNING question_uuid, title, description
+            "#,
+            uuid,
+            update.title,
+            update.description
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .ok_or_else(|| DBError::NotFound(format!("Question not found: {question_uuid}")))?;
+
+        sqlx::query!(
+            r#"
+              INSERT INTO question_revision ( question_uuid, revision, title, description )
+              SELECT $1, COALESCE(MAX(revision), 0) + 1, $2, $3
+              FROM question_revision
+              WHERE question_uuid = $1
+            "#,
+            record.question_uuid,
+            record.title,
+            record.description
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let question_detail = get_detail(&mut *tx, uuid)
+            .await?
+            .ok_or_else(|| DBError::NotFound(format!("Question not found: {question_uuid}")))?;
+
+        tx.commit().await.map_err(|e| DBError::Other(Box::new(e)))?;
+
+        debug!("update_question: {question_detail:?}");
+
+        Ok(question_detail)
+    }
+
+    async fn delete_question(
+        &self,
+        question_uuid: String,
+        deleted_by: String,
+    ) -> Result<(), DBError> {
+        let uuid = sqlx::types::Uuid::parse_str(&question_uuid).map_err(|_| {
+            DBError::InvalidUUID(format!("Could not parse question UUID: {question_uuid}"))
+        })?;
+        let deleted_by = sqlx::types::Uuid::parse_str(&deleted_by).map_err(|_| {
+            DBError::InvalidUUID(format!("Could not parse user UUID: {deleted_by}"))
+        })?;
+
+        // Answers stay as they are, hidden along with their question, so that
+        // undeleting the question brings them back.
+        sqlx::query!(
+            r#"
+              UPDATE question
+              SET deleted_at = CURRENT_TIMESTAMP, deleted_by = $2
+              WHERE question_uuid = $1 AND deleted_at IS NULL
+            "#,
+            uuid,
+            deleted_by
+        )
+        .execute(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
         Ok(())
     }
+
+    async fn undelete_question(&self, question_uuid: String) -> Result<QuestionDetail, DBError> {
+        let uuid = sqlx::types::Uuid::parse_str(&question_uuid).map_err(|_| {
+            DBError::InvalidUUID(format!("Could not parse question UUID: {question_uuid}"))
+        })?;
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let undeleted = sqlx::query!(
+            r#"
+              UPDATE question
+              SET deleted_at = NULL, deleted_by = NULL
+              WHERE question_uuid = $1 AND deleted_at IS NOT NULL
+            "#,
+            uuid
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .rows_affected()
+            > 0;
+
+        if !undeleted {
+            return Err(DBError::NotFound(format!(
+                "Deleted question not found: {question_uuid}"
+            )));
+        }
+
+        let question_detail = get_detail(&mut *tx, uuid)
+            .await?
+            .ok_or_else(|| DBError::NotFound(format!("Question not found: {question_uuid}")))?;
+
+        tx.commit().await.map_err(|e| DBError::Other(Box::new(e)))?;
+
+        debug!("undelete_question: {question_detail:?}");
+
+        Ok(question_detail)
+    }
+
+    async fn purge_deleted_questions(&self, retention_days: i32) -> Result<u64, DBError> {
+        // Answers, revisions, votes and comments go along through the cascade.
+        let result = sqlx::query!(
+            r#"
+              DELETE FROM question
+              WHERE deleted_at < CURRENT_TIMESTAMP - make_interval(days => $1)
+            "#,
+            retention_days
+        )
+        .execute(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn accept_answer(
+        &self,
+        question_uuid: String,
+        answer_uuid: String,
+    ) -> Result<QuestionDetail, DBError> {
+        let uuid = sqlx::types::Uuid::parse_str(&question_uuid).map_err(|_| {
+            DBError::InvalidUUID(format!("Could not parse question UUID: {question_uuid}"))
+        })?;
+        let answer = sqlx::types::Uuid::parse_str(&answer_uuid).map_err(|_| {
+            DBError::InvalidUUID(format!("Could not parse answer UUID: {answer_uuid}"))
+        })?;
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let state = lock_state(&mut tx, uuid, &question_uuid).await?;
+        refuse_locked(state.as_str(), &question_uuid)?;
+
+        // Accepting another answer replaces the previous one.
+        let record = sqlx::query!(
+            r#"
+              UPDATE question
+              SET accepted_answer_uuid = $2
+              WHERE question_uuid = $1 AND deleted_at IS NULL
+              RETURNING author_uuid
+            "#,
+            uuid,
+            answer
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e: sqlx::Error| match e {
+            sqlx::Error::Database(e) => {
+                if let Some(code) = e.code() {
+                    if code.eq(postgres_error_codes::FOREIGN_KEY_VIOLATION) {
+                        return DBError::InvalidUUID(format!(
+                            "Answer {answer_uuid} does not belong to question {question_uuid}"
+                        ));
+                    }
+                }
+                DBError::Other(Box::new(e))
+            }
+            e => DBError::Other(Box::new(e)),
+        })?
+        .ok_or_else(|| DBError::NotFound(format!("Question not found: {question_uuid}")))?;
+
+        reputation_dao::sync_acceptance(&mut tx, uuid).await?;
+        badge_dao::award_author_badges(&mut tx, record.author_uuid, |c| {
+            matches!(c, BadgeCriterion::AcceptedAnswer)
+        })
+        .await?;
+
+        let question_detail = get_detail(&mut *tx, uuid)
+            .await?
+            .ok_or_else(|| DBError::NotFound(format!("Question not found: {question_uuid}")))?;
+
+        tx.commit().await.map_err(|e| DBError::Other(Box::new(e)))?;
+
+        debug!("accept_answer: {question_detail:?}");
+
+        Ok(question_detail)
+    }
+
+    async fn unaccept_answer(
+        &self,
+        question_uuid: String,
+        answer_uuid: String,
+    ) -> Result<QuestionDetail, DBError> {
+        let uuid = sqlx::types::Uuid::parse_str(&question_uuid).map_err(|_| {
+            DBError::InvalidUUID(format!("Could not parse question UUID: {question_uuid}"))
+        })?;
+        let answer = sqlx::types::Uuid::parse_str(&answer_uuid).map_err(|_| {
+            DBError::InvalidUUID(format!("Could not parse answer UUID: {answer_uuid}"))
+        })?;
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let state = lock_state(&mut tx, uuid, &question_uuid).await?;
+        refuse_locked(state.as_str(), &question_uuid)?;
+
+        // Only clears the reference if it still points at this answer, so a
+        // stale request cannot unaccept an answer accepted in the meantime.
+        sqlx::query!(
+            r#"
+              UPDATE question
+              SET accepted_answer_uuid = NULLIF(accepted_answer_uuid, $2)
+              WHERE question_uuid = $1 AND deleted_at IS NULL
+            "#,
+            uuid,
+            answer
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        reputation_dao::sync_acceptance(&mut tx, uuid).await?;
+
+        let question_detail = get_detail(&mut *tx, uuid)
+            .await?
+            .ok_or_else(|| DBError::NotFound(format!("Question not found: {question_uuid}")))?;
+
+        tx.commit().await.map_err(|e| DBError::Other(Box::new(e)))?;
+
+        debug!("unaccept_answer: {question_detail:?}");
+
+        Ok(question_detail)
+    }
+
+    async fn get_question_revisions(
+        &self,
+        question_uuid: String,
+        include_deleted: bool,
+    ) -> Result<Vec<QuestionRevision>, DBError> {
+        let uuid = sqlx::types::Uuid::parse_str(&question_uuid).map_err(|_| {
+            DBError::InvalidUUID(format!("Could not parse question UUID: {question_uuid}"))
+        })?;
+
+        let records = sqlx::query!(
+            r#"
+              SELECT r.question_uuid, r.revision, r.title, r.description, r.created_at
+              FROM question_revision r
+              JOIN question q ON q.question_uuid = r.question_uuid
+              WHERE r.question_uuid = $1 AND ($2 OR q.deleted_at IS NULL)
+              ORDER BY r.revision
+            "#,
+            uuid,
+            include_deleted
+        )
+        .fetch_all(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        // Every question has at least its initial revision.
+        if records.is_empty() {
+            return Err(DBError::NotFound(format!(
+                "Question not found: {question_uuid}"
+            )));
+        }
+
+        let revisions = records
+            .iter()
+            .map(|r| QuestionRevision {
+                question_uuid: r.question_uuid.to_string(),
+                revision: r.revision,
+                title: r.title.to_string(),
+                description: r.description.to_string(),
+                created_at: r.created_at.to_string(),
+            })
+            .collect();
+
+        debug!("get_question_revisions: {revisions:?}");
+
+        Ok(revisions)
+    }
+
+    async fn get_question_revision(
+        &self,
+        question_uuid: String,
+        revision: i32,
+        include_deleted: bool,
+    ) -> Result<QuestionRevision, DBError> {
+        let uuid = sqlx::types::Uuid::parse_str(&question_uuid).map_err(|_| {
+            DBError::InvalidUUID(format!("Could not parse question UUID: {question_uuid}"))
+        })?;
+
+        let record = sqlx::query!(
+            r#"
+              SELECT r.question_uuid, r.revision, r.title, r.description, r.created_at
+              FROM question_revision r
+              JOIN question q ON q.question_uuid = r.question_uuid
+              WHERE r.question_uuid = $1 AND r.revision = $2 AND ($3 OR q.deleted_at IS NULL)
+            "#,
+            uuid,
+            revision,
+            include_deleted
+        )
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .ok_or_else(|| {
+            DBError::NotFound(format!(
+                "Revision {revision} not found for question: {question_uuid}"
+            ))
+        })?;
+
+        let question_revision = QuestionRevision {
+            question_uuid: record.question_uuid.to_string(),
+            revision: record.revision,
+            title: record.title,
+            description: record.description,
+            created_at: record.created_at.to_string(),
+        };
+
+        debug!("get_question_revision: {question_revision:?}");
+
+        Ok(question_revision)
+    }
+
+    async fn vote_to_close(
+        &self,
+        question_uuid: String,
+        user_uuid: String,
+        vote: CloseVote,
+        binding: bool,
+    ) -> Result<QuestionStatus, DBError> {
+        let uuid = Uuid::parse_str(&question_uuid).map_err(|_| {
+            DBError::InvalidUUID(format!("Could not parse question UUID: {question_uuid}"))
+        })?;
+        let user = Uuid::parse_str(&user_uuid)
+            .map_err(|_| DBError::InvalidUUID(format!("Could not parse user UUID: {user_uuid}")))?;
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let state = lock_state(&mut tx, uuid, &question_uuid).await?;
+
+        if state != QuestionState::Open {
+            return Err(DBError::Conflict(format!(
+                "Question is {} and cannot be closed: {question_uuid}",
+                state.as_str()
+            )));
+        }
+
+        insert_close_vote(&mut tx, uuid, user, "close", Some(&vote), &question_uuid).await?;
+
+        // A binding vote decides the reason on its own. Otherwise the most
+        // voted reason wins, then the most voted duplicate target within it,
+        // ties going to the earliest vote.
+        let closed = sqlx::query!(
+            r#"
+              WITH reason AS (
+                SELECT close_reason, duplicate_of FROM question_close_vote
+                WHERE question_uuid = $1 AND kind = 'close' AND (NOT $2 OR user_uuid = $3)
+                GROUP BY close_reason, duplicate_of
+                HAVING $2 OR (SELECT COUNT(*) FROM question_close_vote
+                  WHERE question_uuid = $1 AND kind = 'close') >= $4
+                ORDER BY SUM(COUNT(*)) OVER (PARTITION BY close_reason) DESC, COUNT(*) DESC,
+                  MIN(created_at)
+                LIMIT 1
+              )
+              UPDATE question
+              SET state = 'closed', close_reason = reason.close_reason, duplicate_of = reason.duplicate_of
+              FROM reason
+              WHERE question_uuid = $1
+            "#,
+            uuid,
+            binding,
+            user,
+            CLOSE_VOTES_NEEDED
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .rows_affected()
+            > 0;
+
+        if closed {
+            clear_close_votes(&mut tx, uuid).await?;
+        }
+
+        let status = get_status(&mut tx, uuid).await?;
+
+        tx.commit().await.map_err(|e| DBError::Other(Box::new(e)))?;
+
+        debug!("vote_to_close: {status:?}");
+
+        Ok(status)
+    }
+
+    async fn vote_to_reopen(
+        &self,
+        question_uuid: String,
+        user_uuid: String,
+        binding: bool,
+    ) -> Result<QuestionStatus, DBError> {
+        let uuid = Uuid::parse_str(&question_uuid).map_err(|_| {
+            DBError::InvalidUUID(format!("Could not parse question UUID: {question_uuid}"))
+        })?;
+        let user = Uuid::parse_str(&user_uuid)
+            .map_err(|_| DBError::InvalidUUID(format!("Could not parse user UUID: {user_uuid}")))?;
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let state = lock_state(&mut tx, uuid, &question_uuid).await?;
+
+        if state != QuestionState::Closed {
+            return Err(DBError::Conflict(format!(
+                "Question is {} and cannot be reopened: {question_uuid}",
+                state.as_str()
+            )));
+        }
+
+        insert_close_vote(&mut tx, uuid, user, "reopen", None, &question_uuid).await?;
+
+        let reopened = sqlx::query!(
+            r#"
+              UPDATE question
+              SET state = 'open', close_reason = NULL, duplicate_of = NULL
+              WHERE question_uuid = $1
+                AND ($2 OR (SELECT COUNT(*) FROM question_close_vote
+                  WHERE question_uuid = $1 AND kind = 'reopen') >= $3)
+            "#,
+            uuid,
+            binding,
+            REOPEN_VOTES_NEEDED
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .rows_affected()
+            > 0;
+
+        if reopened {
+            clear_close_votes(&mut tx, uuid).await?;
+        }
+
+        let status = get_status(&mut tx, uuid).await?;
+
+        tx.commit().await.map_err(|e| DBError::Other(Box::new(e)))?;
+
+        debug!("vote_to_reopen: {status:?}");
+
+        Ok(status)
+    }
+
+    async fn lock_question(&self, question_uuid: String) -> Result<QuestionStatus, DBError> {
+        let uuid = Uuid::parse_str(&question_uuid).map_err(|_| {
+            DBError::InvalidUUID(format!("Could not parse question UUID: {question_uuid}"))
+        })?;
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        if lock_state(&mut tx, uuid, &question_uuid).await? == QuestionState::Locked {
+            return Err(DBError::Conflict(format!(
+                "Question is already locked: {question_uuid}"
+            )));
+        }
+
+        // The close reason is kept so that unlocking restores a closed question.
+        sqlx::query!(
+            "UPDATE question SET state = 'locked' WHERE question_uuid = $1",
+            uuid
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        clear_close_votes(&mut tx, uuid).await?;
+
+        let status = get_status(&mut tx, uuid).await?;
+
+        tx.commit().await.map_err(|e| DBError::Other(Box::new(e)))?;
+
+        debug!("lock_question: {status:?}");
+
+        Ok(status)
+    }
+
+    async fn unlock_question(&self, question_uuid: String) -> Result<QuestionStatus, DBError> {
+        let uuid = Uuid::parse_str(&question_uuid).map_err(|_| {
+            DBError::InvalidUUID(format!("Could not parse question UUID: {question_uuid}"))
+        })?;
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        if lock_state(&mut tx, uuid, &question_uuid).await? != QuestionState::Locked {
+            return Err(DBError::Conflict(format!(
+                "Question is not locked: {question_uuid}"
+            )));
+        }
+
+        sqlx::query!(
+            r#"
+              UPDATE question
+              SET state = CASE WHEN close_reason IS NULL THEN 'open' ELSE 'closed' END
+              WHERE question_uuid = $1
+            "#,
+            uuid
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let status = get_status(&mut tx, uuid).await?;
+
+        tx.commit().await.map_err(|e| DBError::Other(Box::new(e)))?;
+
+        debug!("unlock_question: {status:?}");
+
+        Ok(status)
+    }
 }
@@ -0,0 +1,394 @@
+use async_trait::async_trait;
+use sqlx::{types::Uuid, PgConnection, PgPool};
+
+use crate::{
+    models::{
+        DBError, Page, PageRequest, ReputationEvent, ReputationKind, BASE_REPUTATION,
+        DAILY_REPUTATION_CAP,
+    },
+    persistance::cursor::{next_page_cursor, Cursor},
+};
+
+#[async_trait]
+pub trait ReputationDao {
+    /// Lists the reputation events of a user, newest first.
+    async fn get_reputation_history(
+        &self,
+        user_uuid: String,
+        page: PageRequest,
+    ) -> Result<Page<ReputationEvent>, DBError>;
+    /// Rebuilds every user's total from the ledger and returns how many totals
+    /// had to be corrected.
+    async fn recalculate_reputation(&self) -> Result<u64, DBError>;
+}
+
+pub struct ReputationDaoImpl {
+    db: PgPool,
+}
+
+impl ReputationDaoImpl {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+}
+
+/// A post whose votes earn its author reputation.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum VotedPost {
+    Question(Uuid),
+    Answer(Uuid),
+}
+
+fn parse_kind(kind: &str) -> Result<ReputationKind, DBError> {
+    kind.parse().map_err(|e: String| DBError::Other(e.into()))
+}
+
+fn kind_names(kinds: &[ReputationKind]) -> Vec<&'static str> {
+    kinds.iter().map(ReputationKind::as_str).collect()
+}
+
+async fn adjust_reputation(conn: &mut PgConnection, user: Uuid, delta: i32) -> Result<(), DBError> {
+    sqlx::query!(
+        "UPDATE users SET reputation = reputation + $2 WHERE user_uuid = $1",
+        user,
+        delta
+    )
+    .execute(conn)
+    .await
+    .map_err(|e| DBError::Other(Box::new(e)))?;
+
+    Ok(())
+}
+
+/// Deletes the matching events and takes back the reputation they earned.
+async fn revoke_events(
+    conn: &mut PgConnection,
+    kinds: &[ReputationKind],
+    post_uuid: Uuid,
+    source: Option<Uuid>,
+    keep_answer: Option<Uuid>,
+) -> Result<(), DBError> {
+    let revoked = sqlx::query!(
+        r#"
+          DELETE FROM reputation_event
+          WHERE kind = ANY($1)
+            AND (question_uuid = $2 OR answer_uuid = $2)
+            AND ($3::uuid IS NULL OR source_uuid = $3)
+            AND ($4::uuid IS NULL OR answer_uuid <> $4)
+          RETURNING user_uuid, amount
+        "#,
+        &kind_names(kinds) as &[&str],
+        post_uuid,
+        source,
+        keep_answer
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| DBError::Other(Box::new(e)))?;
+
+    for event in revoked {
+        adjust_reputation(conn, event.user_uuid, -event.amount).await?;
+    }
+
+    Ok(())
+}
+
+/// Records an event moving `amount` for `user`, trimming capped kinds to what
+/// is left of the user's daily cap.
+pub(crate) async fn record_event(
+    conn: &mut PgConnection,
+    user: Uuid,
+    kind: ReputationKind,
+    amount: i32,
+    question: Option<Uuid>,
+    answer: Option<Uuid>,
+    source: Option<Uuid>,
+) -> Result<(), DBError> {
+    // Locking the user row serializes reputation changes for the user, so
+    // concurrent upvotes cannot both claim the rest of the daily cap.
+    sqlx::query!(
+        "SELECT user_uuid FROM users WHERE user_uuid = $1 FOR UPDATE",
+        user
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| DBError::Other(Box::new(e)))?;
+
+    let amount = if kind.is_capped() {
+        let earned_today = sqlx::query!(
+            r#"
+              SELECT COALESCE(SUM(amount), 0)::int AS "earned!"
+              FROM reputation_event
+              WHERE user_uuid = $1 AND kind = ANY($2)
+                AND created_at >= date_trunc('day', CURRENT_TIMESTAMP)
+            "#,
+            user,
+            &kind_names(&ReputationKind::CAPPED) as &[&str]
+        )
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .earned;
+
+        amount.min(DAILY_REPUTATION_CAP - earned_today).max(0)
+    } else {
+        amount
+    };
+
+    sqlx::query!(
+        r#"
+          INSERT INTO reputation_event ( user_uuid, kind, amount, question_uuid, answer_uuid, source_uuid )
+          VALUES ( $1, $2, $3, $4, $5, $6 )
+        "#,
+        user,
+        kind.as_str(),
+        amount,
+        question,
+        answer,
+        source
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| DBError::Other(Box::new(e)))?;
+
+    adjust_reputation(conn, user, amount).await
+}
+
+/// Replaces the reputation a voter's vote earned the author of a post. A
+/// `value` of 0 only takes it back. Votes on one's own posts earn nothing.
+pub(crate) async fn record_vote(
+    conn: &mut PgConnection,
+    post: VotedPost,
+    voter: Uuid,
+    value: i16,
+) -> Result<(), DBError> {
+    let (post_uuid, kinds) = match post {
+        VotedPost::Question(uuid) => (
+            uuid,
+            [
+                ReputationKind::QuestionUpvoted,
+                ReputationKind::QuestionDownvoted,
+            ],
+        ),
+        VotedPost::Answer(uuid) => (
+            uuid,
+            [
+                ReputationKind::AnswerUpvoted,
+                ReputationKind::AnswerDownvoted,
+            ],
+        ),
+    };
+
+    revoke_events(conn, &kinds, post_uuid, Some(voter), None).await?;
+
+    if value == 0 {
+        return Ok(());
+    }
+
+    let (author, question, answer) = match post {
+        VotedPost::Question(uuid) => sqlx::query!(
+            "SELECT author_uuid FROM question WHERE question_uuid = $1",
+            uuid
+        )
+        .fetch_one(&mut *conn)
+        .await
+        .map(|r| (r.author_uuid, uuid, None)),
+        VotedPost::Answer(uuid) => sqlx::query!(
+            "SELECT author_uuid, question_uuid FROM answer WHERE answer_uuid = $1",
+            uuid
+        )
+        .fetch_one(&mut *conn)
+        .await
+        .map(|r| (r.author_uuid, r.question_uuid, Some(uuid))),
+    }
+    .map_err(|e| DBError::Other(Box::new(e)))?;
+
+    match author {
+        Some(author) if author != voter => {
+            let kind = if value > 0 { kinds[0] } else { kinds[1] };
+
+            record_event(
+                conn,
+                author,
+                kind,
+                kind.amount(),
+                Some(question),
+                answer,
+                Some(voter),
+            )
+            .await
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Brings the acceptance events of a question in line with its accepted
+/// answer. Accepting one's own answer earns nothing.
+pub(crate) async fn sync_acceptance(
+    conn: &mut PgConnection,
+    question_uuid: Uuid,
+) -> Result<(), DBError> {
+    let kinds = [
+        ReputationKind::AnswerAccepted,
+        ReputationKind::AcceptedAnswer,
+    ];
+
+    let record = sqlx::query!(
+        r#"
+          SELECT q.author_uuid AS asker, q.accepted_answer_uuid, a.author_uuid AS "answerer?",
+            EXISTS (
+              SELECT 1 FROM reputation_event e
+              WHERE e.answer_uuid = q.accepted_answer_uuid AND e.kind = ANY($2)
+            ) AS "recorded!"
+          FROM question q
+          LEFT JOIN answer a ON a.answer_uuid = q.accepted_answer_uuid
+          WHERE q.question_uuid = $1
+        "#,
+        question_uuid,
+        &kind_names(&kinds) as &[&str]
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| DBError::Other(Box::new(e)))?;
+
+    revoke_events(
+        conn,
+        &kinds,
+        question_uuid,
+        None,
+        record.accepted_answer_uuid,
+    )
+    .await?;
+
+    let (Some(answer), Some(answerer)) = (record.accepted_answer_uuid, record.answerer) else {
+        return Ok(());
+    };
+
+    if record.recorded || record.asker == Some(answerer) {
+        return Ok(());
+    }
+
+    record_event(
+        conn,
+        answerer,
+        ReputationKind::AnswerAccepted,
+        ReputationKind::AnswerAccepted.amount(),
+        Some(question_uuid),
+        Some(answer),
+        record.asker,
+    )
+    .await?;
+
+    if let Some(asker) = record.asker {
+        record_event(
+            conn,
+            asker,
+            ReputationKind::AcceptedAnswer,
+            ReputationKind::AcceptedAnswer.amount(),
+            Some(question_uuid),
+            Some(answer),
+            Some(asker),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+#[async_trait]
+impl ReputationDao for ReputationDaoImpl {
+    async fn get_reputation_history(
+        &self,
+        user_uuid: String,
+        page: PageRequest,
+    ) -> Result<Page<ReputationEvent>, DBError> {
+        let uuid = Uuid::parse_str(&user_uuid)
+            .map_err(|_| DBError::InvalidUUID(format!("Could not parse user UUID: {user_uuid}")))?;
+        let cursor = Cursor::decode_optional(page.cursor.as_deref())?;
+        let limit = page.limit();
+
+        let exists = sqlx::query!(
+            r#"SELECT EXISTS (SELECT 1 FROM users WHERE user_uuid = $1) AS "exists!""#,
+            uuid
+        )
+        .fetch_one(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .exists;
+
+        if !exists {
+            return Err(DBError::NotFound(format!("User not found: {user_uuid}")));
+        }
+
+        let mut records = sqlx::query!(
+            r#"
+              SELECT event_uuid, kind, amount, question_uuid, answer_uuid, created_at
+              FROM reputation_event
+              WHERE user_uuid = $1
+                AND ($2::timestamp IS NULL OR (created_at, event_uuid) < ($2, $3))
+              ORDER BY created_at DESC, event_uuid DESC
+              LIMIT $4
+            "#,
+            uuid,
+            cursor.map(|c| c.created_at),
+            cursor.map(|c| c.uuid),
+            limit + 1
+        )
+        .fetch_all(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let next_cursor = next_page_cursor(&mut records, limit, |r| {
+            Cursor::new(r.created_at, r.event_uuid)
+        });
+
+        let events = records
+            .iter()
+            .map(|r| {
+                Ok(ReputationEvent {
+                    event_uuid: r.event_uuid.to_string(),
+                    kind: parse_kind(&r.kind)?,
+                    amount: r.amount,
+                    question_uuid: r.question_uuid.map(|u| u.to_string()),
+                    answer_uuid: r.answer_uuid.map(|u| u.to_string()),
+                    created_at: r.created_at.to_string(),
+                })
+            })
+            .collect::<Result<Vec<_>, DBError>>()?;
+
+        debug!("get reputation history: {events:?}");
+
+        Ok(Page {
+            items: events,
+            next_cursor,
+        })
+    }
+
+    async fn recalculate_reputation(&self) -> Result<u64, DBError> {
+        let updated = sqlx::query!(
+            r#"
+              WITH totals AS (
+                SELECT u.user_uuid, $1 + COALESCE(SUM(e.amount), 0)::int AS total
+                FROM users u
+                LEFT JOIN reputation_event e ON e.user_uuid = u.user_uuid
+                GROUP BY u.user_uuid
+              )
+              UPDATE users
+              SET reputation = totals.total
+              FROM totals
+              WHERE users.user_uuid = totals.user_uuid AND users.reputation <> totals.total
+            "#,
+            BASE_REPUTATION
+        )
+        .execute(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        debug!(
+            "recalculate reputation: {} totals corrected",
+            updated.rows_affected()
+        );
+
+        Ok(updated.rows_affected())
+    }
+}
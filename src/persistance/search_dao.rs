@@ -0,0 +1,313 @@
+use async_trait::async_trait;
+use sqlx::{types::time::Date, PgPool};
+
+use crate::{
+    models::{Bounds, DBError, Page, PageRequest, PostType, SearchQuery, SearchResult, SearchTerm},
+    persistance::cursor::{next_page_cursor, Cursor},
+};
+
+#[async_trait]
+pub trait SearchDao {
+    async fn search(
+        &self,
+        query: SearchQuery,
+        page: PageRequest,
+    ) -> Result<Page<SearchResult>, DBError>;
+}
+
+pub struct SearchDaoImpl {
+    db: PgPool,
+}
+
+impl SearchDaoImpl {
+    pub fn new(db: PgPool) -> Self {
+        SearchDaoImpl { db }
+    }
+}
+
+/// Bind parameters of the search statement. Terms of the same kind narrow
+/// each other down, so any query fits in a fixed set of parameters.
+#[derive(Debug, PartialEq)]
+struct SearchParams {
+    /// Words and phrases in `websearch_to_tsquery` syntax, `None` when the
+    /// query only has filters.
+    text: Option<String>,
+    tags: Vec<String>,
+    excluded_tags: Vec<String>,
+    questions: bool,
+    answers: bool,
+    answer_count: Bounds<i64>,
+    score: Bounds<i64>,
+    created: Bounds<Date>,
+}
+
+/// Narrows `bounds` down to the part also covered by `other`.
+fn intersect<T: Ord + Copy>(bounds: &mut Bounds<T>, other: &Bounds<T>) {
+    bounds.min = bounds.min.max(other.min);
+    bounds.max = match (bounds.max, other.max) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    };
+}
+
+fn compile(query: &SearchQuery) -> SearchParams {
+    let mut text = Vec::new();
+    let mut params = SearchParams {
+        text: None,
+        tags: Vec::new(),
+        excluded_tags: Vec::new(),
+        questions: true,
+        answers: true,
+        answer_count: Bounds {
+            min: None,
+            max: None,
+        },
+        score: Bounds {
+            min: None,
+            max: None,
+        },
+        created: Bounds {
+            min: None,
+            max: None,
+        },
+    };
+
+    for term in &query.terms {
+        match term {
+            SearchTerm::Word { word, excluded } => {
+                text.push(format!("{}{word}", if *excluded { "-" } else { "" }));
+            }
+            SearchTerm::Phrase { phrase, excluded } => {
+                text.push(format!("{}\"{phrase}\"", if *excluded { "-" } else { "" }));
+            }
+            SearchTerm::Tag { tag, excluded } => {
+                if *excluded {
+                    params.excluded_tags.push(tag.clone());
+                } else {
+                    params.tags.push(tag.clone());
+                }
+            }
+            SearchTerm::PostType(PostType::Question) => params.answers = false,
+            SearchTerm::PostType(PostType::Answer) => params.questions = false,
+            SearchTerm::Answers(bounds) => {
+                params.answers = false;
+                intersect(&mut params.answer_count, bounds);
+            }
+            SearchTerm::Score(bounds) => intersect(&mut params.score, bounds),
+            SearchTerm::Created(bounds) => intersect(&mut params.created, bounds),
+        }
+    }
+
+    params.text = (!text.is_empty()).then(|| text.join(" "));
+    params
+}
+
+#[async_trait]
+impl SearchDao for SearchDaoImpl {
+    async fn search(
+        &self,
+        query: SearchQuery,
+        page: PageRequest,
+    ) -> Result<Page<SearchResult>, DBError> {
+        let cursor = Cursor::decode_optional(page.cursor.as_deref())?;
+        let limit = page.limit();
+
+        if cursor.is_some_and(|c| c.rank.is_none()) {
+            return Err(DBError::InvalidCursor(
+                "Cursor does not match search results".to_string(),
+            ));
+        }
+
+        let params = compile(&query);
+
+        // `websearch_to_tsquery` understands "quoted phrases", `or` and
+        // `-excluded` words; without any text every post matches with a rank of
+        // 0. Tags are resolved through approved synonyms first. The rank is
+        // scaled to an integer so it can be carried in the cursor, and snippets
+        // are only built for the rows of the page since `ts_headline` is
+        // expensive.
+        let mut records = sqlx::query!(
+            r#"
+              WITH query AS (
+                SELECT websearch_to_tsquery('english', $1) AS tsquery,
+                  ARRAY(
+                    SELECT DISTINCT COALESCE(t.name, n) FROM unnest($2::varchar[]) n
+                    LEFT JOIN tag_synonym s ON s.source_name = n AND s.approved_at IS NOT NULL
+                    LEFT JOIN tag t ON t.tag_uuid = s.target_tag_uuid
+                  ) AS tags,
+                  ARRAY(
+                    SELECT COALESCE(t.name, n) FROM unnest($3::varchar[]) n
+                    LEFT JOIN tag_synonym s ON s.source_name = n AND s.approved_at IS NOT NULL
+                    LEFT JOIN tag t ON t.tag_uuid = s.target_tag_uuid
+                  ) AS excluded_tags
+              ),
+              questions AS (
+                SELECT q.question_uuid, q.title, q.description, q.created_at, q.author_uuid, q.score,
+                  q.search_vector,
+                  (SELECT COUNT(*) FROM answer a
+                    WHERE a.question_uuid = q.question_uuid AND a.deleted_at IS NULL) AS answer_count
+                FROM question q, query
+                WHERE q.deleted_at IS NULL
+                  AND cardinality(query.tags) = (
+                    SELECT COUNT(*) FROM question_tag qt JOIN tag t ON t.tag_uuid = qt.tag_uuid
+                    WHERE qt.question_uuid = q.question_uuid AND t.name = ANY(query.tags)
+                  )
+                  AND NOT EXISTS (
+                    SELECT 1 FROM question_tag qt JOIN tag t ON t.tag_uuid = qt.tag_uuid
+                    WHERE qt.question_uuid = q.question_uuid AND t.name = ANY(query.excluded_tags)
+                  )
+              ),
+              hits AS (
+                SELECT q.question_uuid AS post_uuid, q.question_uuid, NULL::uuid AS answer_uuid,
+                  q.title, q.description AS body, q.created_at, q.author_uuid, q.score,
+                  COALESCE((ts_rank(q.search_vector, query.tsquery) * 1000000)::bigint, 0) AS rank
+                FROM questions q, query
+                WHERE $4
+                  AND (query.tsquery IS NULL OR q.search_vector @@ query.tsquery)
+                  AND ($6::bigint IS NULL OR q.answer_count >= $6)
+                  AND ($7::bigint IS NULL OR q.answer_count <= $7)
+                  AND ($8::int IS NULL OR q.score >= $8)
+                  AND ($9::int IS NULL OR q.score <= $9)
+                  AND ($10::date IS NULL OR q.created_at >= $10)
+                  AND ($11::date IS NULL OR q.created_at < $11 + 1)
+                UNION ALL
+                SELECT a.answer_uuid, a.question_uuid, a.answer_uuid,
+                  q.title, a.content, a.created_at, a.author_uuid, a.score,
+                  COALESCE((ts_rank(a.search_vector, query.tsquery) * 1000000)::bigint, 0)
+                FROM answer a JOIN questions q ON q.question_uuid = a.question_uuid, query
+                WHERE $5 AND a.deleted_at IS NULL
+                  AND (query.tsquery IS NULL OR a.search_vector @@ query.tsquery)
+                  AND ($8::int IS NULL OR a.score >= $8)
+                  AND ($9::int IS NULL OR a.score <= $9)
+                  AND ($10::date IS NULL OR a.created_at >= $10)
+                  AND ($11::date IS NULL OR a.created_at < $11 + 1)
+              ),
+              page AS (
+                SELECT * FROM hits
+                WHERE $12::timestamp IS NULL OR (rank, created_at, post_uuid) < ($14, $12, $13)
+                ORDER BY rank DESC, created_at DESC, post_uuid DESC
+                LIMIT $15
+              )
+              SELECT post_uuid AS "post_uuid!", question_uuid AS "question_uuid!", answer_uuid,
+                title AS "title!", created_at AS "created_at!", author_uuid, score AS "score!",
+                rank AS "rank!",
+                COALESCE(
+                  ts_headline('english', body, query.tsquery,
+                    'StartSel=<mark>, StopSel=</mark>, MaxWords=35, MinWords=15'),
+                  body
+                ) AS "snippet!"
+              FROM page, query
+              ORDER BY rank DESC, created_at DESC, post_uuid DESC
+            "#,
+            params.text,
+            &params.tags,
+            &params.excluded_tags,
+            params.questions,
+            params.answers,
+            params.answer_count.min,
+            params.answer_count.max,
+            params.score.min.map(|s| s.clamp(i32::MIN as i64, i32::MAX as i64) as i32),
+            params.score.max.map(|s| s.clamp(i32::MIN as i64, i32::MAX as i64) as i32),
+            params.created.min,
+            params.created.max,
+            cursor.map(|c| c.created_at),
+            cursor.map(|c| c.uuid),
+            cursor.and_then(|c| c.rank),
+            limit + 1
+        )
+        .fetch_all(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let next_cursor = next_page_cursor(&mut records, limit, |r| {
+            Cursor::with_rank(r.rank, r.created_at, r.post_uuid)
+        });
+
+        let results = records
+            .iter()
+            .map(|r| SearchResult {
+                question_uuid: r.question_uuid.to_string(),
+                answer_uuid: r.answer_uuid.map(|u| u.to_string()),
+                title: r.title.to_string(),
+                snippet: r.snippet.to_string(),
+                created_at: r.created_at.to_string(),
+                author_uuid: r.author_uuid.map(|u| u.to_string()),
+                score: r.score,
+            })
+            .collect();
+
+        debug!("search: {results:?}");
+
+        Ok(Page {
+            items: results,
+            next_cursor,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compile_should_join_text_terms_in_websearch_syntax() {
+        let query = SearchQuery {
+            terms: vec![
+                SearchTerm::Word {
+                    word: "tokio".to_string(),
+                    excluded: false,
+                },
+                SearchTerm::Phrase {
+                    phrase: "spawn a task".to_string(),
+                    excluded: false,
+                },
+                SearchTerm::Word {
+                    word: "async".to_string(),
+                    excluded: true,
+                },
+                SearchTerm::Phrase {
+                    phrase: "block on".to_string(),
+                    excluded: true,
+                },
+            ],
+        };
+
+        assert_eq!(
+            compile(&query).text.as_deref(),
+            Some(r#"tokio "spawn a task" -async -"block on""#)
+        );
+    }
+
+    #[test]
+    fn compile_should_narrow_down_repeated_filters() {
+        let query = SearchQuery {
+            terms: vec![
+                SearchTerm::Score(Bounds {
+                    min: Some(1),
+                    max: None,
+                }),
+                SearchTerm::Score(Bounds {
+                    min: Some(-5),
+                    max: Some(10),
+                }),
+                SearchTerm::Answers(Bounds {
+                    min: None,
+                    max: Some(0),
+                }),
+            ],
+        };
+
+        let params = compile(&query);
+
+        assert_eq!(params.text, None);
+        assert_eq!(
+            params.score,
+            Bounds {
+                min: Some(1),
+                max: Some(10)
+            }
+        );
+        assert!(params.questions);
+        assert!(!params.answers);
+    }
+}
@@ -0,0 +1,110 @@
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+
+use crate::{
+    models::{Actor, DBError, Session, SESSION_TTL_DAYS},
+    persistance::user_dao::parse_role,
+};
+
+#[async_trait]
+pub trait SessionDao {
+    async fn create_session(&self, user_uuid: String, token: String) -> Result<Session, DBError>;
+    async fn get_session_user(&self, token: String) -> Result<Actor, DBError>;
+    async fn delete_session(&self, token: String) -> Result<(), DBError>;
+}
+
+pub struct SessionDaoImpl {
+    db: PgPool,
+}
+
+impl SessionDaoImpl {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+}
+
+/// Only a digest of each token is stored, so a leaked table cannot be used to
+/// impersonate users.
+fn token_hash(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+#[async_trait]
+impl SessionDao for SessionDaoImpl {
+    async fn create_session(&self, user_uuid: String, token: String) -> Result<Session, DBError> {
+        let uuid = sqlx::types::Uuid::parse_str(&user_uuid)
+            .map_err(|_| DBError::InvalidUUID(format!("Could not parse user UUID: {user_uuid}")))?;
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        sqlx::query!(
+            "DELETE FROM user_session WHERE user_uuid = $1 AND expires_at <= CURRENT_TIMESTAMP",
+            uuid
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let record = sqlx::query!(
+            r#"
+              INSERT INTO user_session ( token_hash, user_uuid, expires_at )
+              VALUES ( $1, $2, CURRENT_TIMESTAMP + make_interval(days => $3) )
+              RETURNING user_uuid, expires_at
+            "#,
+            token_hash(&token),
+            uuid,
+            SESSION_TTL_DAYS
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        tx.commit().await.map_err(|e| DBError::Other(Box::new(e)))?;
+
+        debug!("create session for user: {}", record.user_uuid);
+
+        Ok(Session {
+            token,
+            user_uuid: record.user_uuid.to_string(),
+            expires_at: record.expires_at.to_string(),
+        })
+    }
+
+    async fn get_session_user(&self, token: String) -> Result<Actor, DBError> {
+        let record = sqlx::query!(
+            r#"
+              SELECT u.user_uuid, u.role, u.reputation FROM user_session s
+              JOIN users u ON u.user_uuid = s.user_uuid
+              WHERE s.token_hash = $1 AND s.expires_at > CURRENT_TIMESTAMP
+            "#,
+            token_hash(&token)
+        )
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .ok_or_else(|| DBError::NotFound("Session not found or expired".to_string()))?;
+
+        Ok(Actor {
+            user_uuid: record.user_uuid.to_string(),
+            role: parse_role(&record.role)?,
+            reputation: record.reputation,
+        })
+    }
+
+    async fn delete_session(&self, token: String) -> Result<(), DBError> {
+        sqlx::query!(
+            "DELETE FROM user_session WHERE token_hash = $1",
+            token_hash(&token)
+        )
+        .execute(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,476 @@
+use async_trait::async_trait;
+use sqlx::{PgExecutor, PgPool};
+
+use crate::{
+    models::{
+        postgres_error_codes, DBError, Page, PageRequest, TagDetail, TagSummary, TagSynonym,
+        TagWikiRevision, TagWikiUpdate,
+    },
+    persistance::cursor::{next_page_cursor, Cursor},
+};
+
+#[async_trait]
+pub trait TagDao {
+    async fn get_tags(&self, page: PageRequest) -> Result<Page<TagSummary>, DBError>;
+    async fn get_tag(&self, name: String) -> Result<TagDetail, DBError>;
+    async fn update_tag_wiki(
+        &self,
+        name: String,
+        update: TagWikiUpdate,
+        author_uuid: String,
+    ) -> Result<TagDetail, DBError>;
+    async fn get_tag_wiki_revisions(&self, name: String) -> Result<Vec<TagWikiRevision>, DBError>;
+    async fn get_tag_synonyms(&self, name: String) -> Result<Vec<TagSynonym>, DBError>;
+    async fn propose_tag_synonym(
+        &self,
+        name: String,
+        synonym: String,
+        user_uuid: String,
+    ) -> Result<TagSynonym, DBError>;
+    async fn approve_tag_synonym(
+        &self,
+        name: String,
+        synonym: String,
+        user_uuid: String,
+    ) -> Result<TagSynonym, DBError>;
+}
+
+pub struct TagDaoImpl {
+    db: PgPool,
+}
+
+impl TagDaoImpl {
+    pub fn new(db: PgPool) -> Self {
+        TagDaoImpl { db }
+    }
+
+    async fn get_tag_uuid(&self, name: &str) -> Result<sqlx::types::Uuid, DBError> {
+        sqlx::query!("SELECT tag_uuid FROM tag WHERE name = $1", name)
+            .fetch_optional(&self.db)
+            .await
+            .map_err(|e| DBError::Other(Box::new(e)))?
+            .map(|r| r.tag_uuid)
+            .ok_or_else(|| DBError::NotFound(format!("Tag not found: {name}")))
+    }
+}
+
+/// Synonyms are only one level deep: a tag that others are merged into cannot
+/// itself become a synonym.
+async fn refuse_synonym_target(
+    executor: impl PgExecutor<'_>,
+    synonym: &str,
+) -> Result<(), DBError> {
+    let is_target = sqlx::query!(
+        r#"
+          SELECT EXISTS(
+            SELECT 1 FROM tag_synonym s JOIN tag t ON t.tag_uuid = s.target_tag_uuid
+            WHERE t.name = $1 AND s.approved_at IS NOT NULL
+          ) AS "is_target!"
+        "#,
+        synonym
+    )
+    .fetch_one(executor)
+    .await
+    .map_err(|e| DBError::Other(Box::new(e)))?
+    .is_target;
+
+    if is_target {
+        return Err(DBError::Conflict(format!(
+            "Tag {synonym} has synonyms of its own"
+        )));
+    }
+
+    Ok(())
+}
+
+#[async_trait]
+impl TagDao for TagDaoImpl {
+    async fn get_tags(&self, page: PageRequest) -> Result<Page<TagSummary>, DBError> {
+        let cursor = Cursor::decode_optional(page.cursor.as_deref())?;
+        let limit = page.limit();
+
+        if cursor.is_some_and(|c| c.rank.is_none()) {
+            return Err(DBError::InvalidCursor(
+                "Cursor does not match tag listing".to_string(),
+            ));
+        }
+
+        // Most used tags first, newest first among equally used ones.
+        let mut records = sqlx::query!(
+            r#"
+              WITH t AS (
+                SELECT tag_uuid, name, created_at,
+                  (SELECT COUNT(*) FROM question_tag qt JOIN question q ON q.question_uuid = qt.question_uuid
+                    WHERE qt.tag_uuid = tag.tag_uuid AND q.deleted_at IS NULL) AS question_count
+                FROM tag
+              )
+              SELECT tag_uuid, name, created_at, question_count AS "question_count!"
+              FROM t
+              WHERE $1::timestamp IS NULL OR (question_count, created_at, tag_uuid) < ($3, $1, $2)
+              ORDER BY question_count DESC, created_at DESC, tag_uuid DESC
+              LIMIT $4
+            "#,
+            cursor.map(|c| c.created_at),
+            cursor.map(|c| c.uuid),
+            cursor.and_then(|c| c.rank),
+            limit + 1
+        )
+        .fetch_all(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let next_cursor = next_page_cursor(&mut records, limit, |r| {
+            Cursor::with_rank(r.question_count, r.created_at, r.tag_uuid)
+        });
+
+        let tags = records
+            .iter()
+            .map(|r| TagSummary {
+                name: r.name.to_string(),
+                question_count: r.question_count,
+                created_at: r.created_at.to_string(),
+            })
+            .collect();
+
+        debug!("get_tags: {tags:?}");
+
+        Ok(Page {
+            items: tags,
+            next_cursor,
+        })
+    }
+
+    async fn get_tag(&self, name: String) -> Result<TagDetail, DBError> {
+        // An approved synonym resolves to the tag it was merged into.
+        let record = sqlx::query!(
+            r#"
+              SELECT name, created_at, excerpt, body,
+                (SELECT COUNT(*) FROM question_tag qt JOIN question q ON q.question_uuid = qt.question_uuid
+                  WHERE qt.tag_uuid = tag.tag_uuid AND q.deleted_at IS NULL) AS "question_count!",
+                ARRAY(
+                  SELECT s.source_name FROM tag_synonym s
+                  WHERE s.target_tag_uuid = tag.tag_uuid AND s.approved_at IS NOT NULL
+                  ORDER BY s.source_name
+                ) AS "synonyms!"
+              FROM tag
+              WHERE name = $1 OR tag_uuid = (
+                SELECT target_tag_uuid FROM tag_synonym
+                WHERE source_name = $1 AND approved_at IS NOT NULL
+              )
+            "#,
+            name
+        )
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .ok_or_else(|| DBError::NotFound(format!("Tag not found: {name}")))?;
+
+        let tag_detail = TagDetail {
+            name: record.name,
+            question_count: record.question_count,
+            created_at: record.created_at.to_string(),
+            excerpt: record.excerpt,
+            body: record.body,
+            synonyms: record.synonyms,
+        };
+
+        debug!("get_tag: {tag_detail:?}");
+
+        Ok(tag_detail)
+    }
+
+    async fn update_tag_wiki(
+        &self,
+        name: String,
+        update: TagWikiUpdate,
+        author_uuid: String,
+    ) -> Result<TagDetail, DBError> {
+        let author = sqlx::types::Uuid::parse_str(&author_uuid).map_err(|_| {
+            DBError::InvalidUUID(format!("Could not parse author UUID: {author_uuid}"))
+        })?;
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        // As with questions, the row lock taken by the UPDATE serializes
+        // concurrent edits of the same wiki.
+        let record = sqlx::query!(
+            r#"
+              UPDATE tag
+              SET excerpt = COALESCE($2, excerpt), body = COALESCE($3, body)
+              WHERE name = $1
+              RETURNING tag_uuid, name, created_at, excerpt, body,
+                (SELECT COUNT(*) FROM question_tag qt JOIN question q ON q.question_uuid = qt.question_uuid
+                  WHERE qt.tag_uuid = tag.tag_uuid AND q.deleted_at IS NULL) AS "question_count!",
+                ARRAY(
+                  SELECT s.source_name FROM tag_synonym s
+                  WHERE s.target_tag_uuid = tag.tag_uuid AND s.approved_at IS NOT NULL
+                  ORDER BY s.source_name
+                ) AS "synonyms!"
+            "#,
+            name,
+            update.excerpt,
+            update.body
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .ok_or_else(|| DBError::NotFound(format!("Tag not found: {name}")))?;
+
+        sqlx::query!(
+            r#"
+              INSERT INTO tag_wiki_revision ( tag_uuid, revision, excerpt, body, author_uuid )
+              SELECT $1, COALESCE(MAX(revision), 0) + 1, $2, $3, $4
+              FROM tag_wiki_revision
+              WHERE tag_uuid = $1
+            "#,
+            record.tag_uuid,
+            record.excerpt,
+            record.body,
+            author
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e: sqlx::Error| match e {
+            sqlx::Error::Database(e) => {
+                if let Some(code) = e.code() {
+                    if code.eq(postgres_error_codes::FOREIGN_KEY_VIOLATION) {
+                        return DBError::InvalidUUID(format!("Invalid author UUID: {author_uuid}"));
+                    }
+                }
+                DBError::Other(Box::new(e))
+            }
+            e => DBError::Other(Box::new(e)),
+        })?;
+
+        tx.commit().await.map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let tag_detail = TagDetail {
+            name: record.name,
+            question_count: record.question_count,
+            created_at: record.created_at.to_string(),
+            excerpt: record.excerpt,
+            body: record.body,
+            synonyms: record.synonyms,
+        };
+
+        debug!("update_tag_wiki: {tag_detail:?}");
+
+        Ok(tag_detail)
+    }
+
+    async fn get_tag_wiki_revisions(&self, name: String) -> Result<Vec<TagWikiRevision>, DBError> {
+        let tag_uuid = self.get_tag_uuid(&name).await?;
+
+        let records = sqlx::query!(
+            r#"
+              SELECT revision, excerpt, body, author_uuid, created_at
+              FROM tag_wiki_revision
+              WHERE tag_uuid = $1
+              ORDER BY revision
+            "#,
+            tag_uuid
+        )
+        .fetch_all(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let revisions = records
+            .iter()
+            .map(|r| TagWikiRevision {
+                tag: name.clone(),
+                revision: r.revision,
+                excerpt: r.excerpt.to_string(),
+                body: r.body.to_string(),
+                author_uuid: r.author_uuid.map(|u| u.to_string()),
+                created_at: r.created_at.to_string(),
+            })
+            .collect();
+
+        debug!("get_tag_wiki_revisions: {revisions:?}");
+
+        Ok(revisions)
+    }
+
+    async fn get_tag_synonyms(&self, name: String) -> Result<Vec<TagSynonym>, DBError> {
+        let tag_uuid = self.get_tag_uuid(&name).await?;
+
+        let records = sqlx::query!(
+            r#"
+              SELECT source_name, approved_at IS NOT NULL AS "approved!", proposed_by, approved_by, created_at
+              FROM tag_synonym
+              WHERE target_tag_uuid = $1
+              ORDER BY created_at, source_name
+            "#,
+            tag_uuid
+        )
+        .fetch_all(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let synonyms = records
+            .iter()
+            .map(|r| TagSynonym {
+                synonym: r.source_name.to_string(),
+                tag: name.clone(),
+                approved: r.approved,
+                proposed_by: r.proposed_by.map(|u| u.to_string()),
+                approved_by: r.approved_by.map(|u| u.to_string()),
+                created_at: r.created_at.to_string(),
+            })
+            .collect();
+
+        debug!("get_tag_synonyms: {synonyms:?}");
+
+        Ok(synonyms)
+    }
+
+    async fn propose_tag_synonym(
+        &self,
+        name: String,
+        synonym: String,
+        user_uuid: String,
+    ) -> Result<TagSynonym, DBError> {
+        let user = sqlx::types::Uuid::parse_str(&user_uuid)
+            .map_err(|_| DBError::InvalidUUID(format!("Could not parse user UUID: {user_uuid}")))?;
+
+        let tag_uuid = self.get_tag_uuid(&name).await?;
+
+        refuse_synonym_target(&self.db, &synonym).await?;
+
+        let record = sqlx::query!(
+            r#"
+              INSERT INTO tag_synonym ( source_name, target_tag_uuid, proposed_by )
+              VALUES ( $1, $2, $3 )
+              RETURNING source_name, proposed_by, created_at
+            "#,
+            synonym,
+            tag_uuid,
+            user
+        )
+        .fetch_one(&self.db)
+        .await
+        .map_err(|e: sqlx::Error| match e {
+            sqlx::Error::Database(e) => {
+                if let Some(code) = e.code() {
+                    if code.eq(postgres_error_codes::UNIQUE_VIOLATION) {
+                        return DBError::Conflict(format!("Synonym already proposed: {synonym}"));
+                    }
+                    if code.eq(postgres_error_codes::FOREIGN_KEY_VIOLATION) {
+                        return DBError::InvalidUUID(format!("Invalid user UUID: {user_uuid}"));
+                    }
+                }
+                DBError::Other(Box::new(e))
+            }
+            e => DBError::Other(Box::new(e)),
+        })?;
+
+        let tag_synonym = TagSynonym {
+            synonym: record.source_name,
+            tag: name,
+            approved: false,
+            proposed_by: record.proposed_by.map(|u| u.to_string()),
+            approved_by: None,
+            created_at: record.created_at.to_string(),
+        };
+
+        debug!("propose_tag_synonym: {tag_synonym:?}");
+
+        Ok(tag_synonym)
+    }
+
+    async fn approve_tag_synonym(
+        &self,
+        name: String,
+        synonym: String,
+        user_uuid: String,
+    ) -> Result<TagSynonym, DBError> {
+        let user = sqlx::types::Uuid::parse_str(&user_uuid)
+            .map_err(|_| DBError::InvalidUUID(format!("Could not parse user UUID: {user_uuid}")))?;
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let tag_uuid = sqlx::query!("SELECT tag_uuid FROM tag WHERE name = $1 FOR UPDATE", name)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| DBError::Other(Box::new(e)))?
+            .map(|r| r.tag_uuid)
+            .ok_or_else(|| DBError::NotFound(format!("Tag not found: {name}")))?;
+
+        // The synonym tag is locked too, so that approving a synonym of it
+        // concurrently either sees this approval or finds the tag gone. Another
+        // synonym may have been merged into it since the proposal, in which case
+        // deleting it would cascade those away.
+        sqlx::query!(
+            "SELECT tag_uuid FROM tag WHERE name = $1 FOR UPDATE",
+            synonym
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        refuse_synonym_target(&mut *tx, &synonym).await?;
+
+        let record = sqlx::query!(
+            r#"
+              UPDATE tag_synonym
+              SET approved_at = CURRENT_TIMESTAMP, approved_by = $3
+              WHERE source_name = $1 AND target_tag_uuid = $2 AND approved_at IS NULL
+              RETURNING source_name, proposed_by, approved_by, created_at
+            "#,
+            synonym,
+            tag_uuid,
+            user
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .ok_or_else(|| {
+            DBError::NotFound(format!("No pending synonym {synonym} for tag: {name}"))
+        })?;
+
+        // Questions already carrying the synonym are retagged, then the synonym
+        // tag is dropped along with its wiki, so it only lives on as a rewrite.
+        sqlx::query!(
+            r#"
+              INSERT INTO question_tag ( question_uuid, tag_uuid )
+              SELECT qt.question_uuid, $2
+              FROM question_tag qt JOIN tag t ON t.tag_uuid = qt.tag_uuid
+              WHERE t.name = $1
+              ON CONFLICT DO NOTHING
+            "#,
+            synonym,
+            tag_uuid
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        sqlx::query!("DELETE FROM tag WHERE name = $1", synonym)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        tx.commit().await.map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let tag_synonym = TagSynonym {
+            synonym: record.source_name,
+            tag: name,
+            approved: true,
+            proposed_by: record.proposed_by.map(|u| u.to_string()),
+            approved_by: record.approved_by.map(|u| u.to_string()),
+            created_at: record.created_at.to_string(),
+        };
+
+        debug!("approve_tag_synonym: {tag_synonym:?}");
+
+        Ok(tag_synonym)
+    }
+}
@@ -1,11 +1,128 @@
+/// Fixtures shared by the DAO test modules.
+mod fixtures {
+    use sqlx::PgPool;
+
+    use crate::{
+        models::{Answer, Question, User},
+        persistance::{
+            answer_dao::{AnswerDao, AnswerDaoImpl},
+            question_dao::{QuestionDao, QuestionDaoImpl},
+            user_dao::{UserDao, UserDaoImpl},
+        },
+    };
+
+    /// Registers a user, starting them at `reputation` when given.
+    pub async fn create_user(
+        pool: PgPool,
+        username: &str,
+        reputation: Option<i32>,
+    ) -> Result<String, String> {
+        let user_uuid = UserDaoImpl::new(pool.clone())
+            .create_user(
+                User {
+                    username: username.to_string(),
+                    email: format!("{username}@example.com"),
+                    password: "password123".to_string(),
+                },
+                "hash".to_string(),
+            )
+            .await
+            .map(|user| user.user_uuid)
+            .map_err(|e| format!("{e:?}"))?;
+
+        if let Some(reputation) = reputation {
+            sqlx::query!(
+                "UPDATE users SET reputation = $2 WHERE user_uuid::text = $1",
+                user_uuid,
+                reputation
+            )
+            .execute(&pool)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        }
+
+        Ok(user_uuid)
+    }
+
+    pub async fn create_question(
+        pool: PgPool,
+        author_uuid: Option<&str>,
+        tags: &[&str],
+    ) -> Result<String, String> {
+        QuestionDaoImpl::new(pool)
+            .create_question(Question {
+                title: "test title".to_string(),
+                description: "test description".to_string(),
+                author_uuid: author_uuid.map(str::to_string),
+                tags: tags.iter().map(|t| t.to_string()).collect(),
+            })
+            .await
+            .map(|question| question.question_uuid)
+            .map_err(|e| format!("{e:?}"))
+    }
+
+    pub async fn create_answer(
+        pool: PgPool,
+        question_uuid: &str,
+        author_uuid: Option<&str>,
+    ) -> Result<String, String> {
+        AnswerDaoImpl::new(pool)
+            .create_answer(Answer {
+                question_uuid: question_uuid.to_string(),
+                content: "test content".to_string(),
+                author_uuid: author_uuid.map(str::to_string),
+            })
+            .await
+            .map(|answer| answer.answer_uuid)
+            .map_err(|e| format!("{e:?}"))
+    }
+
+    pub async fn reputation(pool: PgPool, user_uuid: &str) -> Result<i32, String> {
+        UserDaoImpl::new(pool)
+            .get_user(user_uuid.to_string())
+            .await
+            .map(|user| user.reputation)
+            .map_err(|e| format!("{e:?}"))
+    }
+}
+
 mod questions_tests {
     use sqlx::PgPool;
 
+    use super::fixtures::create_user;
     use crate::{
-        models::{DBError, Question},
-        persistance::question_dao::{QuestionDao, QuestionDaoImpl},
+        models::{
+            Answer, DBError, PageRequest, Question, QuestionDetail, QuestionQuery, QuestionSort,
+            QuestionUpdate,
+        },
+        persistance::{
+            answer_dao::{AnswerDao, AnswerDaoImpl},
+            question_dao::{QuestionDao, QuestionDaoImpl},
+        },
     };
 
+    async fn create_questions(
+        dao: &QuestionDaoImpl,
+        count: usize,
+    ) -> Result<Vec<QuestionDetail>, String> {
+        let mut questions = Vec::new();
+
+        for i in 0..count {
+            let question = dao
+                .create_question(Question {
+                    title: format!("test title {i}"),
+                    description: "test description".to_string(),
+                    author_uuid: None,
+                    tags: vec![],
+                })
+                .await
+                .map_err(|e| format!("{e:?}"))?;
+            questions.push(question);
+        }
+
+        Ok(questions)
+    }
+
     #[sqlx::test]
     async fn create_question_should_fail_if_database_error_occurs(
         pool: PgPool,
@@ -18,6 +135,8 @@ mod questions_tests {
             .create_question(Question {
                 title: "test title".to_string(),
                 description: "test description".to_string(),
+                author_uuid: None,
+                tags: vec![],
             })
             .await;
 
@@ -46,6 +165,8 @@ mod questions_tests {
             .create_question(Question {
                 title: "test title".to_string(),
                 description: "test description".to_string(),
+                author_uuid: None,
+                tags: vec![],
             })
             .await
             .map_err(|e| format!("{e:?}"))?;
@@ -59,6 +180,63 @@ mod questions_tests {
         }
     }
 
+    #[sqlx::test]
+    async fn get_question_should_fail_with_malformed_uuid(pool: PgPool) -> Result<(), String> {
+        let dao = QuestionDaoImpl::new(pool);
+        let result = dao.get_question("malformed".to_string()).await;
+
+        if let Err(DBError::InvalidUUID(_)) = result {
+            Ok(())
+        } else {
+            Err(format!(
+                "Expected an invalid UUID error but got the following result: {:?}",
+                result
+            ))
+        }
+    }
+
+    #[sqlx::test]
+    async fn get_question_should_fail_with_non_existent_uuid(pool: PgPool) -> Result<(), String> {
+        let dao = QuestionDaoImpl::new(pool);
+        let result = dao
+            .get_question("aaaaaaaa-aaaa-aaaa-aaaa-aaaaaaaaaaaa".to_string())
+            .await;
+
+        if let Err(DBError::NotFound(_)) = result {
+            Ok(())
+        } else {
+            Err(format!(
+                "Expected a not found error but got the following result: {:?}",
+                result
+            ))
+        }
+    }
+
+    #[sqlx::test]
+    async fn get_question_should_succeed(pool: PgPool) -> Result<(), String> {
+        let dao = QuestionDaoImpl::new(pool);
+        let created = dao
+            .create_question(Question {
+                title: "test title".to_string(),
+                description: "test description".to_string(),
+                author_uuid: None,
+                tags: vec![],
+            })
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let result = dao
+            .get_question(created.question_uuid.clone())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        if result == created {
+            Ok(())
+        } else {
+            Err("Incorrect question returned.".to_string())
+        }
+    }
+
     #[sqlx::test]
     async fn get_questions_should_fail_if_database_error_occurs(
         pool: PgPool,
@@ -67,7 +245,9 @@ mod questions_tests {
 
         pool.close().await;
 
-        let result = dao.get_questions().await;
+        let result = dao
+            .get_questions(QuestionQuery::default(), PageRequest::default(), None)
+            .await;
 
         if result.is_ok() {
             return Err(format!(
@@ -93,25 +273,373 @@ mod questions_tests {
             .create_question(Question {
                 title: "test title".to_string(),
                 description: "test description".to_string(),
+                author_uuid: None,
+                tags: vec![],
             })
             .await
             .map_err(|e| format!("{e:?}"))?;
 
-        let results = dao.get_questions().await.map_err(|e| format!("{e:?}"))?;
+        let results = dao
+            .get_questions(QuestionQuery::default(), PageRequest::default(), None)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
 
-        if results.len() != 1 {
+        if results.items.len() != 1 {
             Err("incorrect number of results returned.".to_string())
-        } else if results.get(0).unwrap().question_uuid != result.question_uuid {
+        } else if results.items.get(0).unwrap().question_uuid != result.question_uuid {
             Err("Incorrect question returned.".to_string())
         } else {
             Ok(())
         }
     }
 
+    #[sqlx::test]
+    async fn update_question_should_fail_with_non_existent_uuid(
+        pool: PgPool,
+    ) -> Result<(), String> {
+        let dao = QuestionDaoImpl::new(pool);
+        let result = dao
+            .update_question(
+                "aaaaaaaa-aaaa-aaaa-aaaa-aaaaaaaaaaaa".to_string(),
+                QuestionUpdate {
+                    title: Some("new title".to_string()),
+                    description: None,
+                },
+            )
+            .await;
+
+        if let Err(DBError::NotFound(_)) = result {
+            Ok(())
+        } else {
+            Err(format!(
+                "Expected a not found error but got the following result: {:?}",
+                result
+            ))
+        }
+    }
+
+    #[sqlx::test]
+    async fn update_question_should_record_revision(pool: PgPool) -> Result<(), String> {
+        let dao = QuestionDaoImpl::new(pool);
+        let question = dao
+            .create_question(Question {
+                title: "test title".to_string(),
+                description: "test description".to_string(),
+                author_uuid: None,
+                tags: vec![],
+            })
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let updated = dao
+            .update_question(
+                question.question_uuid.clone(),
+                QuestionUpdate {
+                    title: Some("new title".to_string()),
+                    description: None,
+                },
+            )
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        if updated.title != "new title" || updated.description != "test description" {
+            return Err("Incorrect title or description".to_string());
+        }
+
+        let revisions = dao
+            .get_question_revisions(question.question_uuid, false)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        match revisions.as_slice() {
+            [first, second]
+                if first.revision == 1
+                    && first.title == "test title"
+                    && second.revision == 2
+                    && second.title == "new title" =>
+            {
+                Ok(())
+            }
+            _ => Err(format!("Incorrect revisions returned: {revisions:?}")),
+        }
+    }
+
+    #[sqlx::test]
+    async fn get_question_revision_should_fail_with_non_existent_revision(
+        pool: PgPool,
+    ) -> Result<(), String> {
+        let dao = QuestionDaoImpl::new(pool);
+        let question = dao
+            .create_question(Question {
+                title: "test title".to_string(),
+                description: "test description".to_string(),
+                author_uuid: None,
+                tags: vec![],
+            })
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let result = dao
+            .get_question_revision(question.question_uuid, 2, false)
+            .await;
+
+        if let Err(DBError::NotFound(_)) = result {
+            Ok(())
+        } else {
+            Err(format!(
+                "Expected a not found error but got the following result: {:?}",
+                result
+            ))
+        }
+    }
+
+    #[sqlx::test]
+    async fn get_questions_should_paginate(pool: PgPool) -> Result<(), String> {
+        let dao = QuestionDaoImpl::new(pool);
+
+        for i in 0..3 {
+            dao.create_question(Question {
+                title: format!("test title {i}"),
+                description: "test description".to_string(),
+                author_uuid: None,
+                tags: vec![],
+            })
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        }
+
+        let first_page = dao
+            .get_questions(
+                QuestionQuery::default(),
+                PageRequest {
+                    cursor: None,
+                    limit: Some(2),
+                },
+                None,
+            )
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        if first_page.items.len() != 2 || first_page.next_cursor.is_none() {
+            return Err(format!("Incorrect first page returned: {first_page:?}"));
+        }
+
+        let second_page = dao
+            .get_questions(
+                QuestionQuery::default(),
+                PageRequest {
+                    cursor: first_page.next_cursor,
+                    limit: Some(2),
+                },
+                None,
+            )
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        if second_page.items.len() != 1 || second_page.next_cursor.is_some() {
+            return Err(format!("Incorrect second page returned: {second_page:?}"));
+        }
+
+        if first_page
+            .items
+            .iter()
+            .any(|q| q.question_uuid == second_page.items[0].question_uuid)
+        {
+            Err("Question returned on more than one page".to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    #[sqlx::test]
+    async fn get_questions_should_sort_by_most_answered(pool: PgPool) -> Result<(), String> {
+        let question_dao = QuestionDaoImpl::new(pool.clone());
+        let answer_dao = AnswerDaoImpl::new(pool);
+
+        let questions = create_questions(&question_dao, 3).await?;
+
+        for (question, answers) in questions.iter().zip([1, 2, 0]) {
+            for _ in 0..answers {
+                answer_dao
+                    .create_answer(Answer {
+                        question_uuid: question.question_uuid.clone(),
+                        content: "test content".to_string(),
+                        author_uuid: None,
+                    })
+                    .await
+                    .map_err(|e| format!("{e:?}"))?;
+            }
+        }
+
+        let query = QuestionQuery {
+            sort: QuestionSort::MostAnswered,
+            ..QuestionQuery::default()
+        };
+        let mut returned = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = question_dao
+                .get_questions(
+                    query.clone(),
+                    PageRequest {
+                        cursor,
+                        limit: Some(1),
+                    },
+                    None,
+                )
+                .await
+                .map_err(|e| format!("{e:?}"))?;
+
+            returned.extend(page.items.into_iter().map(|q| q.question_uuid));
+
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        let expected = vec![
+            questions[1].question_uuid.clone(),
+            questions[0].question_uuid.clone(),
+            questions[2].question_uuid.clone(),
+        ];
+
+        if returned == expected {
+            Ok(())
+        } else {
+            Err(format!("Expected {expected:?} but got {returned:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn get_questions_should_filter_unanswered_and_sort_oldest(
+        pool: PgPool,
+    ) -> Result<(), String> {
+        let question_dao = QuestionDaoImpl::new(pool.clone());
+        let answer_dao = AnswerDaoImpl::new(pool);
+
+        let questions = create_questions(&question_dao, 3).await?;
+
+        answer_dao
+            .create_answer(Answer {
+                question_uuid: questions[1].question_uuid.clone(),
+                content: "test content".to_string(),
+                author_uuid: None,
+            })
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let page = question_dao
+            .get_questions(
+                QuestionQuery {
+                    sort: QuestionSort::Oldest,
+                    unanswered: true,
+                    ..QuestionQuery::default()
+                },
+                PageRequest::default(),
+                None,
+            )
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let returned: Vec<String> = page.items.into_iter().map(|q| q.question_uuid).collect();
+        let expected = vec![
+            questions[0].question_uuid.clone(),
+            questions[2].question_uuid.clone(),
+        ];
+
+        if returned == expected {
+            Ok(())
+        } else {
+            Err(format!("Expected {expected:?} but got {returned:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn get_questions_should_not_count_deleted_answers(pool: PgPool) -> Result<(), String> {
+        let moderator_uuid = create_user(pool.clone(), "moderator", None).await?;
+        let question_dao = QuestionDaoImpl::new(pool.clone());
+        let answer_dao = AnswerDaoImpl::new(pool);
+
+        let questions = create_questions(&question_dao, 1).await?;
+        let answer = answer_dao
+            .create_answer(Answer {
+                question_uuid: questions[0].question_uuid.clone(),
+                content: "test content".to_string(),
+                author_uuid: None,
+            })
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let unanswered = || async {
+            question_dao
+                .get_questions(
+                    QuestionQuery {
+                        unanswered: true,
+                        ..QuestionQuery::default()
+                    },
+                    PageRequest::default(),
+                    None,
+                )
+                .await
+                .map(|page| page.items.len())
+                .map_err(|e| format!("{e:?}"))
+        };
+
+        let before = unanswered().await?;
+        answer_dao
+            .delete_answer(answer.answer_uuid.clone(), moderator_uuid)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        let deleted = unanswered().await?;
+        answer_dao
+            .undelete_answer(answer.answer_uuid)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        let undeleted = unanswered().await?;
+
+        if (before, deleted, undeleted) == (0, 1, 0) {
+            Ok(())
+        } else {
+            Err(format!(
+                "Incorrect unanswered counts: {before}, {deleted}, {undeleted}"
+            ))
+        }
+    }
+
+    #[sqlx::test]
+    async fn get_questions_should_fail_with_malformed_cursor(pool: PgPool) -> Result<(), String> {
+        let dao = QuestionDaoImpl::new(pool);
+        let result = dao
+            .get_questions(
+                QuestionQuery::default(),
+                PageRequest {
+                    cursor: Some("malformed".to_string()),
+                    limit: None,
+                },
+                None,
+            )
+            .await;
+
+        if let Err(DBError::InvalidCursor(_)) = result {
+            Ok(())
+        } else {
+            Err(format!(
+                "Expected an invalid cursor error but got the following result: {:?}",
+                result
+            ))
+        }
+    }
+
     #[sqlx::test]
     async fn delete_qeustion_should_fail_with_malformed_uuid(pool: PgPool) -> Result<(), String> {
         let dao = QuestionDaoImpl::new(pool);
-        let result = dao.delete_question("malformed".to_string()).await;
+        let result = dao
+            .delete_question(
+                "malformed".to_string(),
+                "c4d24be8-8655-414f-81f0-8cf3ff11245a".to_string(),
+            )
+            .await;
 
         if result.is_ok() {
             return Err(format!(
@@ -139,7 +667,10 @@ mod questions_tests {
         pool.close().await;
 
         let result = dao
-            .delete_question("c4d24be8-8655-414f-81f0-8cf3ff11245a".to_string())
+            .delete_question(
+                "c4d24be8-8655-414f-81f0-8cf3ff11245a".to_string(),
+                "c4d24be8-8655-414f-81f0-8cf3ff11245a".to_string(),
+            )
             .await;
 
         if let Err(DBError::Other(_)) = result {
@@ -154,22 +685,28 @@ mod questions_tests {
 
     #[sqlx::test]
     async fn delete_question_should_succeed(pool: PgPool) -> Result<(), String> {
+        let user_uuid = create_user(pool.clone(), "moderator", None).await?;
         let dao = QuestionDaoImpl::new(pool);
         let result = dao
             .create_question(Question {
                 title: "test title".to_string(),
                 description: "test description".to_string(),
+                author_uuid: None,
+                tags: vec![],
             })
             .await
             .map_err(|e| format!("{e:?}"))?;
 
-        dao.delete_question(result.question_uuid)
+        dao.delete_question(result.question_uuid, user_uuid)
             .await
             .map_err(|e| format!("{e:?}"))?;
 
-        let results = dao.get_questions().await.map_err(|e| format!("{e:?}"))?;
+        let results = dao
+            .get_questions(QuestionQuery::default(), PageRequest::default(), None)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
 
-        if results.len() == 0 {
+        if results.items.len() == 0 {
             Ok(())
         } else {
             Err("Qeustion was not deleted".to_string())
@@ -180,8 +717,9 @@ mod questions_tests {
 mod answer_tests {
     use sqlx::PgPool;
 
+    use super::fixtures::create_user;
     use crate::{
-        models::{Answer, DBError, Question},
+        models::{Answer, AnswerUpdate, DBError, PageRequest, Question},
         persistance::{
             answer_dao::{AnswerDao, AnswerDaoImpl},
             question_dao::{QuestionDao, QuestionDaoImpl},
@@ -195,6 +733,7 @@ mod answer_tests {
             .create_answer(Answer {
                 question_uuid: "malformed".to_string(),
                 content: "test content".to_string(),
+                author_uuid: None,
             })
             .await;
 
@@ -222,6 +761,7 @@ mod answer_tests {
             .create_answer(Answer {
                 question_uuid: "aaaaaaaa-aaaa-aaaa-aaaa-aaaaaaaaaaaa".to_string(),
                 content: "test content".to_string(),
+                author_uuid: None,
             })
             .await;
 
@@ -254,6 +794,7 @@ mod answer_tests {
             .create_answer(Answer {
                 question_uuid: "b068cd2f-edac-479e-98f1-c5f91008dcbd".to_string(),
                 content: "test content".to_string(),
+                author_uuid: None,
             })
             .await;
 
@@ -283,6 +824,8 @@ mod answer_tests {
             .create_question(Question {
                 title: "test title".to_string(),
                 description: "test description".to_string(),
+                author_uuid: None,
+                tags: vec![],
             })
             .await
             .map_err(|e| format!("{e:?}"))?;
@@ -291,6 +834,7 @@ mod answer_tests {
             .create_answer(Answer {
                 question_uuid: question.question_uuid,
                 content: "test content".to_string(),
+                author_uuid: None,
             })
             .await
             .map_err(|e| format!("{e:?}"))?;
@@ -303,9 +847,64 @@ mod answer_tests {
     }
 
     #[sqlx::test]
-    async fn get_answers_should_fail_with_malformd_uuid(pool: PgPool) -> Result<(), String> {
+    async fn get_answer_should_fail_with_non_existent_uuid(pool: PgPool) -> Result<(), String> {
         let dao = AnswerDaoImpl::new(pool);
-        let result = dao.get_answers("marformed".to_string()).await;
+        let result = dao
+            .get_answer("aaaaaaaa-aaaa-aaaa-aaaa-aaaaaaaaaaaa".to_string())
+            .await;
+
+        if let Err(DBError::NotFound(_)) = result {
+            Ok(())
+        } else {
+            Err(format!(
+                "Expected a not found error but got the following result: {:?}",
+                result
+            ))
+        }
+    }
+
+    #[sqlx::test]
+    async fn get_answer_should_succeed(pool: PgPool) -> Result<(), String> {
+        let question_dao = QuestionDaoImpl::new(pool.clone());
+        let answer_dao = AnswerDaoImpl::new(pool);
+
+        let question_detail = question_dao
+            .create_question(Question {
+                title: "test title".to_string(),
+                description: "test description".to_string(),
+                author_uuid: None,
+                tags: vec![],
+            })
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let answer_detail = answer_dao
+            .create_answer(Answer {
+                question_uuid: question_detail.question_uuid,
+                content: "test content".to_string(),
+                author_uuid: None,
+            })
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let result = answer_dao
+            .get_answer(answer_detail.answer_uuid.clone())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        if result == answer_detail {
+            Ok(())
+        } else {
+            Err("Incorrect answer returned.".to_string())
+        }
+    }
+
+    #[sqlx::test]
+    async fn get_answers_should_fail_with_malformd_uuid(pool: PgPool) -> Result<(), String> {
+        let dao = AnswerDaoImpl::new(pool);
+        let result = dao
+            .get_answers("marformed".to_string(), false, PageRequest::default())
+            .await;
 
         if result.is_ok() {
             return Err(format!(
@@ -333,13 +932,17 @@ mod answer_tests {
             .create_question(Question {
                 title: "test title".to_string(),
                 description: "test description".to_string(),
+                author_uuid: None,
+                tags: vec![],
             })
             .await
             .map_err(|e| format!("{e:?}"))?;
 
         pool.close().await;
 
-        let result = answer_dao.get_answers(question_detail.question_uuid).await;
+        let result = answer_dao
+            .get_answers(question_detail.question_uuid, false, PageRequest::default())
+            .await;
 
         if result.is_ok() {
             return Err(format!(
@@ -367,6 +970,8 @@ mod answer_tests {
             .create_question(Question {
                 title: "test title".to_string(),
                 description: "test description".to_string(),
+                author_uuid: None,
+                tags: vec![],
             })
             .await
             .map_err(|e| format!("{e:?}"))?;
@@ -375,28 +980,139 @@ mod answer_tests {
             .create_answer(Answer {
                 question_uuid: question_detail.question_uuid.clone(),
                 content: "test content".to_string(),
+                author_uuid: None,
             })
             .await
             .map_err(|e| format!("{e:?}"))?;
 
         let answers = answer_dao
-            .get_answers(question_detail.question_uuid)
+            .get_answers(question_detail.question_uuid, false, PageRequest::default())
             .await
             .map_err(|e| format!("{e:?}"))?;
 
-        if answers.len() != 1 {
+        if answers.items.len() != 1 {
             Err("Incorrect number of results returned.".to_string())
-        } else if answers.get(0).unwrap().answer_uuid != answer_detail.answer_uuid {
+        } else if answers.items.get(0).unwrap().answer_uuid != answer_detail.answer_uuid {
             Err("Incorrect answer returned.".to_string())
         } else {
             Ok(())
         }
     }
 
+    #[sqlx::test]
+    async fn get_answers_should_paginate(pool: PgPool) -> Result<(), String> {
+        let question_dao = QuestionDaoImpl::new(pool.clone());
+        let answer_dao = AnswerDaoImpl::new(pool);
+
+        let question_detail = question_dao
+            .create_question(Question {
+                title: "test title".to_string(),
+                description: "test description".to_string(),
+                author_uuid: None,
+                tags: vec![],
+            })
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let mut created = Vec::new();
+        for i in 0..3 {
+            let answer = answer_dao
+                .create_answer(Answer {
+                    question_uuid: question_detail.question_uuid.clone(),
+                    content: format!("test content {i}"),
+                    author_uuid: None,
+                })
+                .await
+                .map_err(|e| format!("{e:?}"))?;
+            created.push(answer.answer_uuid);
+        }
+
+        let mut returned = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = answer_dao
+                .get_answers(
+                    question_detail.question_uuid.clone(),
+                    false,
+                    PageRequest {
+                        cursor,
+                        limit: Some(1),
+                    },
+                )
+                .await
+                .map_err(|e| format!("{e:?}"))?;
+
+            returned.extend(page.items.into_iter().map(|a| a.answer_uuid));
+
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        if returned == created {
+            Ok(())
+        } else {
+            Err(format!(
+                "Expected answers {created:?} in order but got {returned:?}"
+            ))
+        }
+    }
+
+    #[sqlx::test]
+    async fn update_answer_should_record_revision(pool: PgPool) -> Result<(), String> {
+        let question_dao = QuestionDaoImpl::new(pool.clone());
+        let answer_dao = AnswerDaoImpl::new(pool);
+
+        let question_detail = question_dao
+            .create_question(Question {
+                title: "test title".to_string(),
+                description: "test description".to_string(),
+                author_uuid: None,
+                tags: vec![],
+            })
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        let answer_detail = answer_dao
+            .create_answer(Answer {
+                question_uuid: question_detail.question_uuid,
+                content: "test content".to_string(),
+                author_uuid: None,
+            })
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        answer_dao
+            .update_answer(
+                answer_detail.answer_uuid.clone(),
+                AnswerUpdate {
+                    content: "new content".to_string(),
+                },
+            )
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let revision = answer_dao
+            .get_answer_revision(answer_detail.answer_uuid, 2, false)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        if revision.content == "new content" {
+            Ok(())
+        } else {
+            Err("Incorrect revision content".to_string())
+        }
+    }
+
     #[sqlx::test]
     async fn delete_answer_should_fail_with_malformed_uuid(pool: PgPool) -> Result<(), String> {
         let dao = AnswerDaoImpl::new(pool);
-        let result = dao.delete_answer("malformed".to_string()).await;
+        let result = dao
+            .delete_answer(
+                "malformed".to_string(),
+                "c4d24be8-8655-414f-81f0-8cf3ff11245a".to_string(),
+            )
+            .await;
 
         if result.is_ok() {
             return Err(format!(
@@ -417,6 +1133,7 @@ mod answer_tests {
 
     #[sqlx::test]
     async fn delete_answer_should_succeed(pool: PgPool) -> Result<(), String> {
+        let user_uuid = create_user(pool.clone(), "moderator", None).await?;
         let question_dao = QuestionDaoImpl::new(pool.clone());
         let answer_dao = AnswerDaoImpl::new(pool);
 
@@ -424,6 +1141,8 @@ mod answer_tests {
             .create_question(Question {
                 title: "test title".to_string(),
                 description: "test description".to_string(),
+                author_uuid: None,
+                tags: vec![],
             })
             .await
             .map_err(|e| format!("{e:?}"))?;
@@ -431,24 +1150,3346 @@ mod answer_tests {
             .create_answer(Answer {
                 question_uuid: question_detail.question_uuid.clone(),
                 content: "test content".to_string(),
+                author_uuid: None,
             })
             .await
             .map_err(|e| format!("{e:?}"))?;
 
         answer_dao
-            .delete_answer(answer_detail.answer_uuid)
+            .delete_answer(answer_detail.answer_uuid, user_uuid)
             .await
             .map_err(|e| format!("{e:?}"))?;
 
         let answers = answer_dao
-            .get_answers(question_detail.question_uuid)
+            .get_answers(question_detail.question_uuid, false, PageRequest::default())
             .await
             .map_err(|e| format!("{e:?}"))?;
 
-        if answers.len() == 0 {
+        if answers.items.len() == 0 {
             Ok(())
         } else {
             Err("Answer was not deleted".to_string())
         }
     }
 }
+
+mod user_tests {
+    use sqlx::PgPool;
+
+    use crate::{
+        models::{Answer, DBError, Question, User},
+        persistance::{
+            answer_dao::{AnswerDao, AnswerDaoImpl},
+            question_dao::{QuestionDao, QuestionDaoImpl},
+            user_dao::{UserDao, UserDaoImpl},
+        },
+    };
+
+    #[sqlx::test]
+    async fn create_user_should_fail_with_duplicate_username(pool: PgPool) -> Result<(), String> {
+        let dao = UserDaoImpl::new(pool);
+
+        dao.create_user(
+            User {
+                username: "test_user".to_string(),
+                email: "test@example.com".to_string(),
+                password: "password123".to_string(),
+            },
+            "hash".to_string(),
+        )
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+
+        let result = dao
+            .create_user(
+                User {
+                    username: "test_user".to_string(),
+                    email: "other@example.com".to_string(),
+                    password: "password123".to_string(),
+                },
+                "hash".to_string(),
+            )
+            .await;
+
+        if let Err(DBError::Conflict(_)) = result {
+            Ok(())
+        } else {
+            Err(format!(
+                "Expected a conflict error but got the following result: {:?}",
+                result
+            ))
+        }
+    }
+
+    #[sqlx::test]
+    async fn get_user_should_fail_with_non_existent_uuid(pool: PgPool) -> Result<(), String> {
+        let dao = UserDaoImpl::new(pool);
+        let result = dao
+            .get_user("aaaaaaaa-aaaa-aaaa-aaaa-aaaaaaaaaaaa".to_string())
+            .await;
+
+        if let Err(DBError::NotFound(_)) = result {
+            Ok(())
+        } else {
+            Err(format!(
+                "Expected a not found error but got the following result: {:?}",
+                result
+            ))
+        }
+    }
+
+    #[sqlx::test]
+    async fn get_user_should_count_authored_posts(pool: PgPool) -> Result<(), String> {
+        let user_dao = UserDaoImpl::new(pool.clone());
+        let question_dao = QuestionDaoImpl::new(pool.clone());
+        let answer_dao = AnswerDaoImpl::new(pool);
+
+        let user = user_dao
+            .create_user(
+                User {
+                    username: "test_user".to_string(),
+                    email: "test@example.com".to_string(),
+                    password: "password123".to_string(),
+                },
+                "hash".to_string(),
+            )
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let question = question_dao
+            .create_question(Question {
+                title: "test title".to_string(),
+                description: "test description".to_string(),
+                author_uuid: Some(user.user_uuid.clone()),
+                tags: vec![],
+            })
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        if question.author_uuid.as_ref() != Some(&user.user_uuid) {
+            return Err("Incorrect question author".to_string());
+        }
+
+        answer_dao
+            .create_answer(Answer {
+                question_uuid: question.question_uuid,
+                content: "test content".to_string(),
+                author_uuid: Some(user.user_uuid.clone()),
+            })
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let result = user_dao
+            .get_user(user.user_uuid)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        if result.question_count == 1 && result.answer_count == 1 {
+            Ok(())
+        } else {
+            Err(format!("Incorrect post counts: {result:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn create_question_should_fail_with_non_existent_author(
+        pool: PgPool,
+    ) -> Result<(), String> {
+        let dao = QuestionDaoImpl::new(pool);
+        let result = dao
+            .create_question(Question {
+                title: "test title".to_string(),
+                description: "test description".to_string(),
+                author_uuid: Some("aaaaaaaa-aaaa-aaaa-aaaa-aaaaaaaaaaaa".to_string()),
+                tags: vec![],
+            })
+            .await;
+
+        if let Err(DBError::InvalidUUID(_)) = result {
+            Ok(())
+        } else {
+            Err(format!(
+                "Expected an invalid UUID error but got the following result: {:?}",
+                result
+            ))
+        }
+    }
+}
+
+mod session_tests {
+    use sqlx::PgPool;
+
+    use super::fixtures::create_user;
+    use crate::{
+        models::{DBError, Role},
+        persistance::{
+            session_dao::{SessionDao, SessionDaoImpl},
+            user_dao::{UserDao, UserDaoImpl},
+        },
+    };
+
+    #[sqlx::test]
+    async fn get_credentials_should_return_password_hash(pool: PgPool) -> Result<(), String> {
+        let user_uuid = create_user(pool.clone(), "test_user", None).await?;
+
+        let credentials = UserDaoImpl::new(pool)
+            .get_credentials("test_user".to_string())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        if credentials.user_uuid == user_uuid
+            && credentials.password_hash.as_deref() == Some("hash")
+        {
+            Ok(())
+        } else {
+            Err(format!("Incorrect credentials: {credentials:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn get_session_user_should_return_session_owner(pool: PgPool) -> Result<(), String> {
+        let user_uuid = create_user(pool.clone(), "test_user", None).await?;
+        let dao = SessionDaoImpl::new(pool);
+
+        let session = dao
+            .create_session(user_uuid.clone(), "token".to_string())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let result = dao
+            .get_session_user(session.token)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        if result.user_uuid == user_uuid && result.role == Role::User {
+            Ok(())
+        } else {
+            Err(format!("Incorrect session owner: {result:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn get_session_user_should_fail_with_expired_session(pool: PgPool) -> Result<(), String> {
+        let user_uuid = create_user(pool.clone(), "test_user", None).await?;
+        let dao = SessionDaoImpl::new(pool.clone());
+
+        dao.create_session(user_uuid, "token".to_string())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        sqlx::query!("UPDATE user_session SET expires_at = CURRENT_TIMESTAMP - INTERVAL '1 day'")
+            .execute(&pool)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let result = dao.get_session_user("token".to_string()).await;
+
+        if let Err(DBError::NotFound(_)) = result {
+            Ok(())
+        } else {
+            Err(format!(
+                "Expected a not found error but got the following result: {:?}",
+                result
+            ))
+        }
+    }
+
+    #[sqlx::test]
+    async fn delete_session_should_invalidate_token(pool: PgPool) -> Result<(), String> {
+        let user_uuid = create_user(pool.clone(), "test_user", None).await?;
+        let dao = SessionDaoImpl::new(pool);
+
+        dao.create_session(user_uuid, "token".to_string())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        dao.delete_session("token".to_string())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let result = dao.get_session_user("token".to_string()).await;
+
+        if let Err(DBError::NotFound(_)) = result {
+            Ok(())
+        } else {
+            Err(format!(
+                "Expected a not found error but got the following result: {:?}",
+                result
+            ))
+        }
+    }
+
+    #[sqlx::test]
+    async fn update_role_should_apply_to_sessions(pool: PgPool) -> Result<(), String> {
+        let user_uuid = create_user(pool.clone(), "test_user", None).await?;
+        let user_dao = UserDaoImpl::new(pool.clone());
+        let session_dao = SessionDaoImpl::new(pool);
+
+        session_dao
+            .create_session(user_uuid.clone(), "token".to_string())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let updated = user_dao
+            .update_role(user_uuid, Role::Moderator)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let actor = session_dao
+            .get_session_user("token".to_string())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        if updated.role == Role::Moderator && actor.role == Role::Moderator {
+            Ok(())
+        } else {
+            Err(format!("Incorrect roles: {updated:?}, {actor:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn update_role_should_fail_with_non_existent_uuid(pool: PgPool) -> Result<(), String> {
+        let result = UserDaoImpl::new(pool)
+            .update_role(
+                "aaaaaaaa-aaaa-aaaa-aaaa-aaaaaaaaaaaa".to_string(),
+                Role::Admin,
+            )
+            .await;
+
+        if let Err(DBError::NotFound(_)) = result {
+            Ok(())
+        } else {
+            Err(format!(
+                "Expected a not found error but got the following result: {:?}",
+                result
+            ))
+        }
+    }
+}
+
+mod vote_tests {
+    use sqlx::PgPool;
+
+    use super::fixtures::{create_question, create_user};
+    use crate::{
+        models::{Answer, DBError, VoteSummary},
+        persistance::{
+            answer_dao::{AnswerDao, AnswerDaoImpl},
+            question_dao::{QuestionDao, QuestionDaoImpl},
+            vote_dao::{VoteDao, VoteDaoImpl},
+        },
+    };
+
+    #[sqlx::test]
+    async fn vote_question_should_replace_previous_vote(pool: PgPool) -> Result<(), String> {
+        let user_uuid = create_user(pool.clone(), "test_user", None).await?;
+        let question_uuid = create_question(pool.clone(), None, &[]).await?;
+        let dao = VoteDaoImpl::new(pool.clone());
+
+        dao.vote_question(question_uuid.clone(), user_uuid.clone(), 1)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let result = dao
+            .vote_question(question_uuid.clone(), user_uuid, -1)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let question = QuestionDaoImpl::new(pool)
+            .get_question(question_uuid)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        if result
+            == (VoteSummary {
+                score: -1,
+                vote: Some(-1),
+            })
+            && question.score == -1
+        {
+            Ok(())
+        } else {
+            Err(format!("Incorrect score: {result:?}, {question:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn retract_question_vote_should_restore_score(pool: PgPool) -> Result<(), String> {
+        let user_uuid = create_user(pool.clone(), "test_user", None).await?;
+        let question_uuid = create_question(pool.clone(), None, &[]).await?;
+        let dao = VoteDaoImpl::new(pool);
+
+        dao.vote_question(question_uuid.clone(), user_uuid.clone(), 1)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let retracted = dao
+            .retract_question_vote(question_uuid.clone(), user_uuid.clone())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        // A second retraction has nothing left to undo.
+        let repeated = dao
+            .retract_question_vote(question_uuid, user_uuid)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let expected = VoteSummary {
+            score: 0,
+            vote: None,
+        };
+
+        if retracted == expected && repeated == expected {
+            Ok(())
+        } else {
+            Err(format!("Incorrect score: {retracted:?}, {repeated:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn vote_question_should_count_concurrent_votes(pool: PgPool) -> Result<(), String> {
+        let question_uuid = create_question(pool.clone(), None, &[]).await?;
+        let mut user_uuids = Vec::new();
+
+        for i in 0..10 {
+            user_uuids.push(create_user(pool.clone(), &format!("user_{i}"), None).await?);
+        }
+
+        let votes = user_uuids.into_iter().map(|user_uuid| {
+            let dao = VoteDaoImpl::new(pool.clone());
+            let question_uuid = question_uuid.clone();
+
+            tokio::spawn(async move { dao.vote_question(question_uuid, user_uuid, 1).await })
+        });
+
+        for vote in votes {
+            vote.await
+                .map_err(|e| format!("{e:?}"))?
+                .map_err(|e| format!("{e:?}"))?;
+        }
+
+        let question = QuestionDaoImpl::new(pool)
+            .get_question(question_uuid)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        if question.score == 10 {
+            Ok(())
+        } else {
+            Err(format!("Incorrect score: {}", question.score))
+        }
+    }
+
+    #[sqlx::test]
+    async fn vote_answer_should_fail_with_non_existent_answer(pool: PgPool) -> Result<(), String> {
+        let user_uuid = create_user(pool.clone(), "test_user", None).await?;
+        let dao = VoteDaoImpl::new(pool);
+
+        let result = dao
+            .vote_answer(
+                "aaaaaaaa-aaaa-aaaa-aaaa-aaaaaaaaaaaa".to_string(),
+                user_uuid,
+                1,
+            )
+            .await;
+
+        if let Err(DBError::NotFound(_)) = result {
+            Ok(())
+        } else {
+            Err(format!(
+                "Expected a not found error but got the following result: {:?}",
+                result
+            ))
+        }
+    }
+
+    #[sqlx::test]
+    async fn vote_answer_should_update_answer_score(pool: PgPool) -> Result<(), String> {
+        let user_uuid = create_user(pool.clone(), "test_user", None).await?;
+        let question_uuid = create_question(pool.clone(), None, &[]).await?;
+        let answer_dao = AnswerDaoImpl::new(pool.clone());
+
+        let answer = answer_dao
+            .create_answer(Answer {
+                question_uuid,
+                content: "test content".to_string(),
+                author_uuid: None,
+            })
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        VoteDaoImpl::new(pool)
+            .vote_answer(answer.answer_uuid.clone(), user_uuid, 1)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let answer = answer_dao
+            .get_answer(answer.answer_uuid)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        if answer.score == 1 {
+            Ok(())
+        } else {
+            Err(format!("Incorrect score: {}", answer.score))
+        }
+    }
+}
+
+mod accepted_answer_tests {
+    use sqlx::PgPool;
+
+    use super::fixtures::{create_question, create_user};
+    use crate::{
+        models::{Answer, AnswerDetail, DBError, PageRequest},
+        persistance::{
+            answer_dao::{AnswerDao, AnswerDaoImpl},
+            question_dao::{QuestionDao, QuestionDaoImpl},
+        },
+    };
+
+    async fn create_answers(
+        pool: PgPool,
+        question_uuid: &str,
+        count: usize,
+    ) -> Result<Vec<AnswerDetail>, String> {
+        let dao = AnswerDaoImpl::new(pool);
+        let mut answers = Vec::new();
+
+        for i in 0..count {
+            let answer = dao
+                .create_answer(Answer {
+                    question_uuid: question_uuid.to_string(),
+                    content: format!("test content {i}"),
+                    author_uuid: None,
+                })
+                .await
+                .map_err(|e| format!("{e:?}"))?;
+
+            answers.push(answer);
+        }
+
+        Ok(answers)
+    }
+
+    #[sqlx::test]
+    async fn get_answers_should_pin_accepted_answer_first(pool: PgPool) -> Result<(), String> {
+        let question_uuid = create_question(pool.clone(), None, &[]).await?;
+        let answers = create_answers(pool.clone(), &question_uuid, 3).await?;
+        let question_dao = QuestionDaoImpl::new(pool.clone());
+        let answer_dao = AnswerDaoImpl::new(pool);
+
+        let question = question_dao
+            .accept_answer(question_uuid.clone(), answers[2].answer_uuid.clone())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        if question.accepted_answer_uuid.as_ref() != Some(&answers[2].answer_uuid) {
+            return Err(format!("Incorrect accepted answer: {question:?}"));
+        }
+
+        // Page through one answer at a time to exercise the pinned cursor.
+        let mut uuids = Vec::new();
+        let mut accepted = Vec::new();
+        let mut cursor = None;
+
+        loop {
+            let page = answer_dao
+                .get_answers(
+                    question_uuid.clone(),
+                    false,
+                    PageRequest {
+                        cursor,
+                        limit: Some(1),
+                    },
+                )
+                .await
+                .map_err(|e| format!("{e:?}"))?;
+
+            for answer in page.items {
+                uuids.push(answer.answer_uuid);
+                accepted.push(answer.is_accepted);
+            }
+
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        let expected = vec![
+            answers[2].answer_uuid.clone(),
+            answers[0].answer_uuid.clone(),
+            answers[1].answer_uuid.clone(),
+        ];
+
+        if uuids == expected && accepted == vec![true, false, false] {
+            Ok(())
+        } else {
+            Err(format!("Incorrect order: {uuids:?}, {accepted:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn accept_answer_should_fail_with_answer_of_other_question(
+        pool: PgPool,
+    ) -> Result<(), String> {
+        let question_uuid = create_question(pool.clone(), None, &[]).await?;
+        let other_question_uuid = create_question(pool.clone(), None, &[]).await?;
+        let answers = create_answers(pool.clone(), &other_question_uuid, 1).await?;
+
+        let result = QuestionDaoImpl::new(pool)
+            .accept_answer(question_uuid, answers[0].answer_uuid.clone())
+            .await;
+
+        if let Err(DBError::InvalidUUID(_)) = result {
+            Ok(())
+        } else {
+            Err(format!(
+                "Expected an invalid UUID error but got the following result: {:?}",
+                result
+            ))
+        }
+    }
+
+    #[sqlx::test]
+    async fn delete_answer_should_clear_accepted_answer(pool: PgPool) -> Result<(), String> {
+        let user_uuid = create_user(pool.clone(), "moderator", None).await?;
+        let question_uuid = create_question(pool.clone(), None, &[]).await?;
+        let answers = create_answers(pool.clone(), &question_uuid, 1).await?;
+        let question_dao = QuestionDaoImpl::new(pool.clone());
+
+        question_dao
+            .accept_answer(question_uuid.clone(), answers[0].answer_uuid.clone())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        AnswerDaoImpl::new(pool)
+            .delete_answer(answers[0].answer_uuid.clone(), user_uuid)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let question = question_dao
+            .get_question(question_uuid)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        if question.accepted_answer_uuid.is_none() {
+            Ok(())
+        } else {
+            Err(format!("Accepted answer was not cleared: {question:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn unaccept_answer_should_keep_other_accepted_answer(pool: PgPool) -> Result<(), String> {
+        let question_uuid = create_question(pool.clone(), None, &[]).await?;
+        let answers = create_answers(pool.clone(), &question_uuid, 2).await?;
+        let dao = QuestionDaoImpl::new(pool);
+
+        dao.accept_answer(question_uuid.clone(), answers[1].answer_uuid.clone())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let kept = dao
+            .unaccept_answer(question_uuid.clone(), answers[0].answer_uuid.clone())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let cleared = dao
+            .unaccept_answer(question_uuid, answers[1].answer_uuid.clone())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        if kept.accepted_answer_uuid.as_ref() == Some(&answers[1].answer_uuid)
+            && cleared.accepted_answer_uuid.is_none()
+        {
+            Ok(())
+        } else {
+            Err(format!("Incorrect accepted answers: {kept:?}, {cleared:?}"))
+        }
+    }
+}
+
+mod tag_tests {
+    use sqlx::PgPool;
+
+    use super::fixtures::{create_question, create_user};
+    use crate::{
+        models::{DBError, PageRequest, Question, QuestionQuery, TagWikiUpdate},
+        persistance::{
+            question_dao::{QuestionDao, QuestionDaoImpl},
+            tag_dao::{TagDao, TagDaoImpl},
+        },
+    };
+
+    #[sqlx::test]
+    async fn create_question_should_store_tags(pool: PgPool) -> Result<(), String> {
+        let dao = QuestionDaoImpl::new(pool.clone());
+
+        let created = dao
+            .create_question(Question {
+                title: "test title".to_string(),
+                description: "test description".to_string(),
+                author_uuid: None,
+                tags: vec!["tokio".to_string(), "rust".to_string()],
+            })
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let question = dao
+            .get_question(created.question_uuid.clone())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let expected = vec!["rust".to_string(), "tokio".to_string()];
+
+        if created.tags == expected && question.tags == expected {
+            Ok(())
+        } else {
+            Err(format!("Incorrect tags: {created:?}, {question:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn get_questions_should_filter_by_tags(pool: PgPool) -> Result<(), String> {
+        let both = create_question(pool.clone(), None, &["rust", "tokio"]).await?;
+        let rust = create_question(pool.clone(), None, &["rust"]).await?;
+        create_question(pool.clone(), None, &[]).await?;
+
+        let dao = QuestionDaoImpl::new(pool);
+
+        let tagged = |tagged: &str| QuestionQuery {
+            tagged: Some(tagged.to_string()),
+            ..QuestionQuery::default()
+        };
+
+        let mut uuids = Vec::new();
+
+        for query in [tagged("rust tokio"), tagged("RUST"), tagged("missing")] {
+            let page = dao
+                .get_questions(query, PageRequest::default(), None)
+                .await
+                .map_err(|e| format!("{e:?}"))?;
+
+            uuids.push(
+                page.items
+                    .into_iter()
+                    .map(|q| q.question_uuid)
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        if uuids == vec![vec![both.clone()], vec![rust, both], vec![]] {
+            Ok(())
+        } else {
+            Err(format!("Incorrect questions: {uuids:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn get_tags_should_return_most_used_first(pool: PgPool) -> Result<(), String> {
+        create_question(pool.clone(), None, &["rust", "tokio"]).await?;
+        create_question(pool.clone(), None, &["rust"]).await?;
+
+        let dao = TagDaoImpl::new(pool);
+
+        let mut tags = Vec::new();
+        let mut cursor = None;
+
+        loop {
+            let page = dao
+                .get_tags(PageRequest {
+                    cursor,
+                    limit: Some(1),
+                })
+                .await
+                .map_err(|e| format!("{e:?}"))?;
+
+            tags.extend(page.items.into_iter().map(|t| (t.name, t.question_count)));
+
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        if tags == vec![("rust".to_string(), 2), ("tokio".to_string(), 1)] {
+            Ok(())
+        } else {
+            Err(format!("Incorrect tags: {tags:?}"))
+        }
+    }
+
+    async fn get_tags_of(pool: PgPool, question_uuid: &str) -> Result<Vec<String>, String> {
+        QuestionDaoImpl::new(pool)
+            .get_question(question_uuid.to_string())
+            .await
+            .map(|question| question.tags)
+            .map_err(|e| format!("{e:?}"))
+    }
+
+    #[sqlx::test]
+    async fn approve_tag_synonym_should_merge_tags(pool: PgPool) -> Result<(), String> {
+        let user_uuid = create_user(pool.clone(), "test_user", None).await?;
+        let synonym_only = create_question(pool.clone(), None, &["postgres"]).await?;
+        let both = create_question(pool.clone(), None, &["postgresql", "postgres"]).await?;
+
+        let dao = TagDaoImpl::new(pool.clone());
+
+        dao.propose_tag_synonym(
+            "postgresql".to_string(),
+            "postgres".to_string(),
+            user_uuid.clone(),
+        )
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+
+        // Pending synonyms are not rewritten yet.
+        let pending = create_question(pool.clone(), None, &["postgres"]).await?;
+
+        if get_tags_of(pool.clone(), &pending).await? != vec!["postgres".to_string()] {
+            return Err("Pending synonym was rewritten".to_string());
+        }
+
+        let synonym = dao
+            .approve_tag_synonym(
+                "postgresql".to_string(),
+                "postgres".to_string(),
+                user_uuid.clone(),
+            )
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let rewritten = create_question(pool.clone(), None, &["postgres", "sql"]).await?;
+
+        let expected = vec!["postgresql".to_string()];
+
+        for question_uuid in [&synonym_only, &both, &pending] {
+            let tags = get_tags_of(pool.clone(), question_uuid).await?;
+
+            if tags != expected {
+                return Err(format!("Question was not retagged: {tags:?}"));
+            }
+        }
+
+        let tags = get_tags_of(pool.clone(), &rewritten).await?;
+
+        if tags != vec!["postgresql".to_string(), "sql".to_string()] {
+            return Err(format!("Incoming tags were not rewritten: {tags:?}"));
+        }
+
+        let tag = dao
+            .get_tag("postgres".to_string())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        if synonym.approved
+            && synonym.approved_by == Some(user_uuid)
+            && tag.name == "postgresql"
+            && tag.question_count == 4
+            && tag.synonyms == vec!["postgres".to_string()]
+        {
+            Ok(())
+        } else {
+            Err(format!("Incorrect synonym or tag: {synonym:?}, {tag:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn propose_tag_synonym_should_fail_with_duplicate_proposal(
+        pool: PgPool,
+    ) -> Result<(), String> {
+        let user_uuid = create_user(pool.clone(), "test_user", None).await?;
+        create_question(pool.clone(), None, &["postgresql", "pg"]).await?;
+
+        let dao = TagDaoImpl::new(pool);
+
+        dao.propose_tag_synonym(
+            "postgresql".to_string(),
+            "postgres".to_string(),
+            user_uuid.clone(),
+        )
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+
+        let result = dao
+            .propose_tag_synonym("pg".to_string(), "postgres".to_string(), user_uuid)
+            .await;
+
+        if let Err(DBError::Conflict(_)) = result {
+            Ok(())
+        } else {
+            Err(format!(
+                "Expected a conflict error but got the following result: {:?}",
+                result
+            ))
+        }
+    }
+
+    #[sqlx::test]
+    async fn propose_tag_synonym_should_fail_for_canonical_tag(pool: PgPool) -> Result<(), String> {
+        let user_uuid = create_user(pool.clone(), "test_user", None).await?;
+        create_question(pool.clone(), None, &["postgresql", "sql"]).await?;
+
+        let dao = TagDaoImpl::new(pool);
+
+        dao.propose_tag_synonym(
+            "postgresql".to_string(),
+            "postgres".to_string(),
+            user_uuid.clone(),
+        )
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+
+        dao.approve_tag_synonym(
+            "postgresql".to_string(),
+            "postgres".to_string(),
+            user_uuid.clone(),
+        )
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+
+        let result = dao
+            .propose_tag_synonym("sql".to_string(), "postgresql".to_string(), user_uuid)
+            .await;
+
+        if let Err(DBError::Conflict(_)) = result {
+            Ok(())
+        } else {
+            Err(format!(
+                "Expected a conflict error but got the following result: {:?}",
+                result
+            ))
+        }
+    }
+
+    #[sqlx::test]
+    async fn approve_tag_synonym_should_fail_for_canonical_tag(pool: PgPool) -> Result<(), String> {
+        let user_uuid = create_user(pool.clone(), "test_user", None).await?;
+        create_question(pool.clone(), None, &["postgresql", "postgres", "sql"]).await?;
+
+        let dao = TagDaoImpl::new(pool);
+
+        // Both are proposed while neither is merged, then approved in turn.
+        dao.propose_tag_synonym(
+            "postgresql".to_string(),
+            "postgres".to_string(),
+            user_uuid.clone(),
+        )
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+
+        dao.propose_tag_synonym(
+            "sql".to_string(),
+            "postgresql".to_string(),
+            user_uuid.clone(),
+        )
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+
+        dao.approve_tag_synonym(
+            "postgresql".to_string(),
+            "postgres".to_string(),
+            user_uuid.clone(),
+        )
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+
+        let result = dao
+            .approve_tag_synonym("sql".to_string(), "postgresql".to_string(), user_uuid)
+            .await;
+
+        if !matches!(result, Err(DBError::Conflict(_))) {
+            return Err(format!(
+                "Expected a conflict error but got the following result: {:?}",
+                result
+            ));
+        }
+
+        let tag = dao
+            .get_tag("postgresql".to_string())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        if tag.synonyms == vec!["postgres".to_string()] {
+            Ok(())
+        } else {
+            Err(format!("Approved synonym was lost: {tag:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn approve_tag_synonym_should_fail_without_proposal(pool: PgPool) -> Result<(), String> {
+        let user_uuid = create_user(pool.clone(), "test_user", None).await?;
+        create_question(pool.clone(), None, &["postgresql"]).await?;
+
+        let result = TagDaoImpl::new(pool)
+            .approve_tag_synonym("postgresql".to_string(), "postgres".to_string(), user_uuid)
+            .await;
+
+        if let Err(DBError::NotFound(_)) = result {
+            Ok(())
+        } else {
+            Err(format!(
+                "Expected a not found error but got the following result: {:?}",
+                result
+            ))
+        }
+    }
+
+    #[sqlx::test]
+    async fn update_tag_wiki_should_record_revisions(pool: PgPool) -> Result<(), String> {
+        let user_uuid = create_user(pool.clone(), "test_user", None).await?;
+        create_question(pool.clone(), None, &["rust"]).await?;
+
+        let dao = TagDaoImpl::new(pool);
+
+        dao.update_tag_wiki(
+            "rust".to_string(),
+            TagWikiUpdate {
+                excerpt: Some("A systems language".to_string()),
+                body: Some("Rust is fast.".to_string()),
+            },
+            user_uuid.clone(),
+        )
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+
+        let tag = dao
+            .update_tag_wiki(
+                "rust".to_string(),
+                TagWikiUpdate {
+                    excerpt: None,
+                    body: Some("Rust is fast and safe.".to_string()),
+                },
+                user_uuid.clone(),
+            )
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let revisions = dao
+            .get_tag_wiki_revisions("rust".to_string())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let history: Vec<(i32, &str, &str)> = revisions
+            .iter()
+            .map(|r| (r.revision, r.excerpt.as_str(), r.body.as_str()))
+            .collect();
+
+        if tag.excerpt == "A systems language"
+            && tag.body == "Rust is fast and safe."
+            && history
+                == vec![
+                    (1, "A systems language", "Rust is fast."),
+                    (2, "A systems language", "Rust is fast and safe."),
+                ]
+            && revisions[0].author_uuid == Some(user_uuid)
+        {
+            Ok(())
+        } else {
+            Err(format!("Incorrect wiki: {tag:?}, {revisions:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn get_tag_wiki_revisions_should_fail_for_missing_tag(
+        pool: PgPool,
+    ) -> Result<(), String> {
+        let result = TagDaoImpl::new(pool)
+            .get_tag_wiki_revisions("missing".to_string())
+            .await;
+
+        if let Err(DBError::NotFound(_)) = result {
+            Ok(())
+        } else {
+            Err(format!(
+                "Expected a not found error but got the following result: {:?}",
+                result
+            ))
+        }
+    }
+}
+
+mod comment_tests {
+    use sqlx::PgPool;
+
+    use super::fixtures::{create_answer, create_question, create_user};
+    use crate::{
+        models::{Comment, CommentParent, DBError, PageRequest, QuestionQuery},
+        persistance::{
+            answer_dao::{AnswerDao, AnswerDaoImpl},
+            comment_dao::{CommentDao, CommentDaoImpl},
+            question_dao::{QuestionDao, QuestionDaoImpl},
+        },
+    };
+
+    async fn create_comment(pool: PgPool, parent: CommentParent) -> Result<String, String> {
+        CommentDaoImpl::new(pool)
+            .create_comment(
+                parent,
+                Comment {
+                    content: "test comment".to_string(),
+                    author_uuid: None,
+                },
+            )
+            .await
+            .map(|comment| comment.comment_uuid)
+            .map_err(|e| format!("{e:?}"))
+    }
+
+    #[sqlx::test]
+    async fn comment_counts_should_be_included_in_posts(pool: PgPool) -> Result<(), String> {
+        let question_uuid = create_question(pool.clone(), None, &[]).await?;
+        let answer_uuid = create_answer(pool.clone(), &question_uuid, None).await?;
+
+        for _ in 0..2 {
+            create_comment(pool.clone(), CommentParent::Question(question_uuid.clone())).await?;
+        }
+        create_comment(pool.clone(), CommentParent::Answer(answer_uuid.clone())).await?;
+
+        let question_dao = QuestionDaoImpl::new(pool.clone());
+        let answer_dao = AnswerDaoImpl::new(pool);
+
+        let question = question_dao
+            .get_question(question_uuid.clone())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        let questions = question_dao
+            .get_questions(QuestionQuery::default(), PageRequest::default(), None)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        let answer = answer_dao
+            .get_answer(answer_uuid)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        let answers = answer_dao
+            .get_answers(question_uuid, false, PageRequest::default())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        if question.comment_count == 2
+            && questions.items[0].comment_count == 2
+            && answer.comment_count == 1
+            && answers.items[0].comment_count == 1
+        {
+            Ok(())
+        } else {
+            Err(format!(
+                "Incorrect comment counts: {question:?}, {questions:?}, {answer:?}, {answers:?}"
+            ))
+        }
+    }
+
+    #[sqlx::test]
+    async fn get_comments_should_return_comments_of_parent_oldest_first(
+        pool: PgPool,
+    ) -> Result<(), String> {
+        let question_uuid = create_question(pool.clone(), None, &[]).await?;
+        let answer_uuid = create_answer(pool.clone(), &question_uuid, None).await?;
+
+        let mut expected = Vec::new();
+        for _ in 0..3 {
+            expected.push(
+                create_comment(pool.clone(), CommentParent::Question(question_uuid.clone()))
+                    .await?,
+            );
+        }
+        create_comment(pool.clone(), CommentParent::Answer(answer_uuid)).await?;
+
+        let dao = CommentDaoImpl::new(pool);
+
+        let mut uuids = Vec::new();
+        let mut cursor = None;
+
+        loop {
+            let page = dao
+                .get_comments(
+                    CommentParent::Question(question_uuid.clone()),
+                    false,
+                    PageRequest {
+                        cursor,
+                        limit: Some(2),
+                    },
+                )
+                .await
+                .map_err(|e| format!("{e:?}"))?;
+
+            uuids.extend(page.items.into_iter().map(|c| c.comment_uuid));
+
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        if uuids == expected {
+            Ok(())
+        } else {
+            Err(format!("Incorrect comments: {uuids:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn create_comment_should_fail_with_missing_answer(pool: PgPool) -> Result<(), String> {
+        let result = CommentDaoImpl::new(pool)
+            .create_comment(
+                CommentParent::Answer("00000000-0000-0000-0000-000000000000".to_string()),
+                Comment {
+                    content: "test comment".to_string(),
+                    author_uuid: None,
+                },
+            )
+            .await;
+
+        if let Err(DBError::NotFound(_)) = result {
+            Ok(())
+        } else {
+            Err(format!(
+                "Expected a not found error but got the following result: {:?}",
+                result
+            ))
+        }
+    }
+
+    #[sqlx::test]
+    async fn create_comment_should_fail_on_deleted_posts(pool: PgPool) -> Result<(), String> {
+        let moderator_uuid = create_user(pool.clone(), "moderator", None).await?;
+        let question_uuid = create_question(pool.clone(), None, &[]).await?;
+        let answer_uuid = create_answer(pool.clone(), &question_uuid, None).await?;
+        let deleted_answer_uuid = create_answer(pool.clone(), &question_uuid, None).await?;
+
+        AnswerDaoImpl::new(pool.clone())
+            .delete_answer(deleted_answer_uuid.clone(), moderator_uuid.clone())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        let on_deleted_answer =
+            create_comment(pool.clone(), CommentParent::Answer(deleted_answer_uuid)).await;
+
+        QuestionDaoImpl::new(pool.clone())
+            .delete_question(question_uuid.clone(), moderator_uuid)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        let on_deleted_question =
+            create_comment(pool.clone(), CommentParent::Question(question_uuid)).await;
+        let on_answer_of_deleted_question =
+            create_comment(pool, CommentParent::Answer(answer_uuid)).await;
+
+        let results = [
+            on_deleted_answer,
+            on_deleted_question,
+            on_answer_of_deleted_question,
+        ];
+
+        if results
+            .iter()
+            .all(|r| matches!(r, Err(e) if e.starts_with("NotFound")))
+        {
+            Ok(())
+        } else {
+            Err(format!("Expected not found errors but got: {results:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn get_comments_should_hide_comments_of_deleted_posts(
+        pool: PgPool,
+    ) -> Result<(), String> {
+        let moderator_uuid = create_user(pool.clone(), "moderator", None).await?;
+        let question_uuid = create_question(pool.clone(), None, &[]).await?;
+        let answer_uuid = create_answer(pool.clone(), &question_uuid, None).await?;
+        create_comment(pool.clone(), CommentParent::Question(question_uuid.clone())).await?;
+        create_comment(pool.clone(), CommentParent::Answer(answer_uuid.clone())).await?;
+
+        QuestionDaoImpl::new(pool.clone())
+            .delete_question(question_uuid.clone(), moderator_uuid)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let dao = CommentDaoImpl::new(pool);
+        let parents = [
+            CommentParent::Question(question_uuid),
+            CommentParent::Answer(answer_uuid),
+        ];
+
+        for parent in parents {
+            let hidden = dao
+                .get_comments(parent.clone(), false, PageRequest::default())
+                .await;
+            let listed = dao
+                .get_comments(parent, true, PageRequest::default())
+                .await
+                .map_err(|e| format!("{e:?}"))?;
+
+            if !matches!(hidden, Err(DBError::NotFound(_))) || listed.items.len() != 1 {
+                return Err(format!(
+                    "Incorrect comments: {hidden:?} and {:?}",
+                    listed.items
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn comments_should_be_deleted_with_their_purged_post(pool: PgPool) -> Result<(), String> {
+        let question_uuid = create_question(pool.clone(), None, &[]).await?;
+        let answer_uuid = create_answer(pool.clone(), &question_uuid, None).await?;
+        let question_comment =
+            create_comment(pool.clone(), CommentParent::Question(question_uuid.clone())).await?;
+        let answer_comment =
+            create_comment(pool.clone(), CommentParent::Answer(answer_uuid)).await?;
+
+        sqlx::query!(
+            "UPDATE question SET deleted_at = CURRENT_TIMESTAMP - INTERVAL '1 day' WHERE question_uuid::text = $1",
+            question_uuid
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+
+        QuestionDaoImpl::new(pool.clone())
+            .purge_deleted_questions(0)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let dao = CommentDaoImpl::new(pool);
+
+        for comment_uuid in [question_comment, answer_comment] {
+            let result = dao.get_comment(comment_uuid).await;
+
+            if !matches!(result, Err(DBError::NotFound(_))) {
+                return Err(format!("Comment was not deleted: {result:?}"));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn delete_comment_should_remove_comment(pool: PgPool) -> Result<(), String> {
+        let question_uuid = create_question(pool.clone(), None, &[]).await?;
+        let comment_uuid =
+            create_comment(pool.clone(), CommentParent::Question(question_uuid)).await?;
+
+        let dao = CommentDaoImpl::new(pool);
+
+        dao.delete_comment(comment_uuid.clone())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let result = dao.get_comment(comment_uuid).await;
+
+        if let Err(DBError::NotFound(_)) = result {
+            Ok(())
+        } else {
+            Err(format!(
+                "Expected a not found error but got the following result: {:?}",
+                result
+            ))
+        }
+    }
+}
+
+mod search_tests {
+    use sqlx::{types::time::Date, PgPool};
+
+    use crate::{
+        models::{
+            Answer, Bounds, PageRequest, PostType, Question, SearchQuery, SearchResult, SearchTerm,
+        },
+        persistance::{
+            answer_dao::{AnswerDao, AnswerDaoImpl},
+            question_dao::{QuestionDao, QuestionDaoImpl},
+            search_dao::{SearchDao, SearchDaoImpl},
+        },
+    };
+
+    async fn create_question(
+        pool: PgPool,
+        title: &str,
+        description: &str,
+        tags: &[&str],
+    ) -> Result<String, String> {
+        QuestionDaoImpl::new(pool)
+            .create_question(Question {
+                title: title.to_string(),
+                description: description.to_string(),
+                author_uuid: None,
+                tags: tags.iter().map(|t| t.to_string()).collect(),
+            })
+            .await
+            .map(|question| question.question_uuid)
+            .map_err(|e| format!("{e:?}"))
+    }
+
+    async fn create_answer(
+        pool: PgPool,
+        question_uuid: &str,
+        content: &str,
+    ) -> Result<String, String> {
+        AnswerDaoImpl::new(pool)
+            .create_answer(Answer {
+                question_uuid: question_uuid.to_string(),
+                content: content.to_string(),
+                author_uuid: None,
+            })
+            .await
+            .map(|answer| answer.answer_uuid)
+            .map_err(|e| format!("{e:?}"))
+    }
+
+    fn word(word: &str) -> SearchTerm {
+        SearchTerm::Word {
+            word: word.to_string(),
+            excluded: false,
+        }
+    }
+
+    fn tag(tag: &str, excluded: bool) -> SearchTerm {
+        SearchTerm::Tag {
+            tag: tag.to_string(),
+            excluded,
+        }
+    }
+
+    async fn search(pool: PgPool, terms: Vec<SearchTerm>) -> Result<Vec<SearchResult>, String> {
+        SearchDaoImpl::new(pool)
+            .search(SearchQuery { terms }, PageRequest::default())
+            .await
+            .map(|page| page.items)
+            .map_err(|e| format!("{e:?}"))
+    }
+
+    /// `(question_uuid, answer_uuid)` of each result.
+    fn hits(results: &[SearchResult]) -> Vec<(String, Option<String>)> {
+        results
+            .iter()
+            .map(|r| (r.question_uuid.clone(), r.answer_uuid.clone()))
+            .collect()
+    }
+
+    #[sqlx::test]
+    async fn search_should_rank_titles_above_descriptions_and_answers(
+        pool: PgPool,
+    ) -> Result<(), String> {
+        let in_description = create_question(
+            pool.clone(),
+            "Async runtimes",
+            "How do I spawn a tokio task?",
+            &[],
+        )
+        .await?;
+        let in_title =
+            create_question(pool.clone(), "Spawning tokio tasks", "Some details", &[]).await?;
+        let other = create_question(pool.clone(), "Borrow checker", "Lifetimes", &[]).await?;
+        let answer = create_answer(pool.clone(), &other, "Use tokio::spawn instead").await?;
+
+        let results = search(pool, vec![word("tokio")]).await?;
+
+        if hits(&results)
+            == vec![
+                (in_title, None),
+                (in_description, None),
+                (other, Some(answer)),
+            ]
+        {
+            Ok(())
+        } else {
+            Err(format!("Incorrect search results: {results:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn search_should_match_phrases_and_highlight_snippets(
+        pool: PgPool,
+    ) -> Result<(), String> {
+        let phrase = create_question(
+            pool.clone(),
+            "Question",
+            "The borrow checker rejects this",
+            &[],
+        )
+        .await?;
+        create_question(pool.clone(), "Question", "The checker does not borrow", &[]).await?;
+
+        let results = search(
+            pool,
+            vec![SearchTerm::Phrase {
+                phrase: "borrow checker".to_string(),
+                excluded: false,
+            }],
+        )
+        .await?;
+
+        match results.as_slice() {
+            [result]
+                if result.question_uuid == phrase
+                    && result
+                        .snippet
+                        .contains("<mark>borrow</mark> <mark>checker</mark>") =>
+            {
+                Ok(())
+            }
+            _ => Err(format!("Incorrect search results: {results:?}")),
+        }
+    }
+
+    #[sqlx::test]
+    async fn search_should_filter_by_tags_and_post_type(pool: PgPool) -> Result<(), String> {
+        let rust = create_question(pool.clone(), "Question", "Description", &["rust"]).await?;
+        let rust_async =
+            create_question(pool.clone(), "Question", "Description", &["rust", "async"]).await?;
+        create_question(pool.clone(), "Question", "Description", &["python"]).await?;
+        let answer = create_answer(pool.clone(), &rust, "Answer").await?;
+
+        let questions = search(
+            pool.clone(),
+            vec![
+                tag("rust", false),
+                tag("async", true),
+                SearchTerm::PostType(PostType::Question),
+            ],
+        )
+        .await?;
+        let answers = search(
+            pool,
+            vec![tag("rust", false), SearchTerm::PostType(PostType::Answer)],
+        )
+        .await?;
+
+        if hits(&questions) == vec![(rust.clone(), None)]
+            && hits(&answers) == vec![(rust, Some(answer))]
+        {
+            Ok(())
+        } else {
+            Err(format!(
+                "Incorrect search results: {questions:?}, {answers:?}, {rust_async}"
+            ))
+        }
+    }
+
+    #[sqlx::test]
+    async fn search_should_filter_by_answers_score_and_creation_date(
+        pool: PgPool,
+    ) -> Result<(), String> {
+        let unanswered = create_question(pool.clone(), "Question", "Description", &[]).await?;
+        let answered = create_question(pool.clone(), "Question", "Description", &[]).await?;
+        create_answer(pool.clone(), &answered, "Answer").await?;
+
+        sqlx::query!(
+            "UPDATE question SET score = 5, created_at = '2023-06-15' WHERE question_uuid::text = $1",
+            answered
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+
+        let unanswered_results = search(
+            pool.clone(),
+            vec![SearchTerm::Answers(Bounds {
+                min: Some(0),
+                max: Some(0),
+            })],
+        )
+        .await?;
+        let scored_results = search(
+            pool.clone(),
+            vec![
+                SearchTerm::Score(Bounds {
+                    min: Some(1),
+                    max: None,
+                }),
+                SearchTerm::PostType(PostType::Question),
+            ],
+        )
+        .await?;
+        let created_results = search(
+            pool,
+            vec![SearchTerm::Created(Bounds {
+                min: Date::from_ordinal_date(2023, 1).ok(),
+                max: Date::from_ordinal_date(2023, 365).ok(),
+            })],
+        )
+        .await?;
+
+        if hits(&unanswered_results) == vec![(unanswered, None)]
+            && hits(&scored_results) == vec![(answered.clone(), None)]
+            && hits(&created_results) == vec![(answered, None)]
+        {
+            Ok(())
+        } else {
+            Err(format!(
+                "Incorrect search results: {unanswered_results:?}, {scored_results:?}, {created_results:?}"
+            ))
+        }
+    }
+
+    #[sqlx::test]
+    async fn search_should_page_through_all_results(pool: PgPool) -> Result<(), String> {
+        let mut expected = Vec::new();
+        for _ in 0..3 {
+            expected
+                .push(create_question(pool.clone(), "Rust question", "Description", &[]).await?);
+        }
+        create_question(pool.clone(), "Python question", "Description", &[]).await?;
+        expected.reverse();
+
+        let dao = SearchDaoImpl::new(pool);
+
+        let mut uuids = Vec::new();
+        let mut cursor = None;
+
+        loop {
+            let page = dao
+                .search(
+                    SearchQuery {
+                        terms: vec![word("rust")],
+                    },
+                    PageRequest {
+                        cursor,
+                        limit: Some(2),
+                    },
+                )
+                .await
+                .map_err(|e| format!("{e:?}"))?;
+
+            uuids.extend(page.items.into_iter().map(|r| r.question_uuid));
+
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        if uuids == expected {
+            Ok(())
+        } else {
+            Err(format!("Incorrect search results: {uuids:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn get_similar_questions_should_match_titles_and_descriptions(
+        pool: PgPool,
+    ) -> Result<(), String> {
+        let same_title =
+            create_question(pool.clone(), "How do I parse JSON in Rust?", "Details", &[]).await?;
+        let same_description = create_question(
+            pool.clone(),
+            "Deserializing a struct",
+            "serde_json fails to deserialize my struct",
+            &[],
+        )
+        .await?;
+        create_question(
+            pool.clone(),
+            "Installing Python on Windows",
+            "The installer fails",
+            &[],
+        )
+        .await?;
+
+        let similar = QuestionDaoImpl::new(pool)
+            .get_similar_questions(
+                "How to parse JSON in Rust".to_string(),
+                Some("Using serde_json to deserialize a struct".to_string()),
+            )
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let mut uuids: Vec<&str> = similar.iter().map(|q| q.question_uuid.as_str()).collect();
+        let mut expected = vec![same_title.as_str(), same_description.as_str()];
+
+        uuids.sort();
+        expected.sort();
+
+        if uuids == expected && similar.iter().all(|q| (0.0..=1.0).contains(&q.similarity)) {
+            Ok(())
+        } else {
+            Err(format!("Incorrect similar questions: {similar:?}"))
+        }
+    }
+}
+
+mod question_state_tests {
+    use sqlx::PgPool;
+
+    use super::fixtures::{create_answer, create_question, create_user};
+    use crate::{
+        models::{
+            Answer, AnswerUpdate, CloseReason, CloseVote, Comment, CommentParent, DBError,
+            QuestionState, QuestionUpdate, CLOSE_VOTES_NEEDED,
+        },
+        persistance::{
+            answer_dao::{AnswerDao, AnswerDaoImpl},
+            bounty_dao::{BountyDao, BountyDaoImpl},
+            comment_dao::{CommentDao, CommentDaoImpl},
+            question_dao::{QuestionDao, QuestionDaoImpl},
+            vote_dao::{VoteDao, VoteDaoImpl},
+        },
+    };
+
+    fn vote(reason: CloseReason, duplicate_of: Option<&str>) -> CloseVote {
+        CloseVote {
+            reason,
+            duplicate_of: duplicate_of.map(str::to_string),
+        }
+    }
+
+    /// Creates a question with one answer and locks it.
+    async fn create_locked_question(pool: PgPool) -> Result<(String, String), String> {
+        let question_uuid = create_question(pool.clone(), None, &[]).await?;
+        let answer_uuid = create_answer(pool.clone(), &question_uuid, None).await?;
+
+        QuestionDaoImpl::new(pool)
+            .lock_question(question_uuid.clone())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        Ok((question_uuid, answer_uuid))
+    }
+
+    fn expect_locked<T: std::fmt::Debug>(
+        result: Result<T, DBError>,
+        question_uuid: &str,
+    ) -> Result<(), String> {
+        match result {
+            Err(DBError::Conflict(message))
+                if message == format!("Question is locked: {question_uuid}") =>
+            {
+                Ok(())
+            }
+            result => Err(format!("Expected a locked error but got: {result:?}")),
+        }
+    }
+
+    #[sqlx::test]
+    async fn vote_to_close_should_close_with_most_voted_reason(pool: PgPool) -> Result<(), String> {
+        let question_uuid = create_question(pool.clone(), None, &[]).await?;
+        let original_uuid = create_question(pool.clone(), None, &[]).await?;
+        let dao = QuestionDaoImpl::new(pool.clone());
+        let votes = [
+            vote(CloseReason::OffTopic, None),
+            vote(CloseReason::Duplicate, Some(&original_uuid)),
+            vote(CloseReason::Duplicate, Some(&original_uuid)),
+        ];
+        let mut statuses = Vec::new();
+
+        for (i, vote) in votes.into_iter().enumerate() {
+            let user_uuid = create_user(pool.clone(), &format!("voter{i}"), None).await?;
+
+            statuses.push(
+                dao.vote_to_close(question_uuid.clone(), user_uuid, vote, false)
+                    .await
+                    .map_err(|e| format!("{e:?}"))?,
+            );
+        }
+
+        let question = dao
+            .get_question(question_uuid)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        let counts: Vec<(QuestionState, i64)> =
+            statuses.iter().map(|s| (s.state, s.close_votes)).collect();
+
+        if counts
+            == vec![
+                (QuestionState::Open, 1),
+                (QuestionState::Open, CLOSE_VOTES_NEEDED - 1),
+                (QuestionState::Closed, 0),
+            ]
+            && question.state == QuestionState::Closed
+            && question.close_reason == Some(CloseReason::Duplicate)
+            && question.duplicate_of == Some(original_uuid)
+        {
+            Ok(())
+        } else {
+            Err(format!("Incorrect state: {statuses:?}, {question:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn vote_to_close_should_fail_for_repeated_vote(pool: PgPool) -> Result<(), String> {
+        let user_uuid = create_user(pool.clone(), "voter", None).await?;
+        let question_uuid = create_question(pool.clone(), None, &[]).await?;
+        let dao = QuestionDaoImpl::new(pool);
+
+        dao.vote_to_close(
+            question_uuid.clone(),
+            user_uuid.clone(),
+            vote(CloseReason::Unclear, None),
+            false,
+        )
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+
+        let result = dao
+            .vote_to_close(
+                question_uuid,
+                user_uuid,
+                vote(CloseReason::TooBroad, None),
+                false,
+            )
+            .await;
+
+        if let Err(DBError::Conflict(_)) = result {
+            Ok(())
+        } else {
+            Err(format!("Expected a conflict error but got: {result:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn binding_votes_should_close_and_reopen_right_away(pool: PgPool) -> Result<(), String> {
+        let moderator_uuid = create_user(pool.clone(), "moderator", None).await?;
+        let question_uuid = create_question(pool.clone(), None, &[]).await?;
+        let dao = QuestionDaoImpl::new(pool);
+
+        let closed = dao
+            .vote_to_close(
+                question_uuid.clone(),
+                moderator_uuid.clone(),
+                vote(CloseReason::OpinionBased, None),
+                true,
+            )
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        let reopened = dao
+            .vote_to_reopen(question_uuid, moderator_uuid, true)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        if closed.state == QuestionState::Closed
+            && closed.close_reason == Some(CloseReason::OpinionBased)
+            && reopened.state == QuestionState::Open
+            && reopened.close_reason.is_none()
+        {
+            Ok(())
+        } else {
+            Err(format!("Incorrect state: {closed:?}, {reopened:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn vote_to_reopen_should_fail_for_open_question(pool: PgPool) -> Result<(), String> {
+        let user_uuid = create_user(pool.clone(), "voter", None).await?;
+        let question_uuid = create_question(pool.clone(), None, &[]).await?;
+        let result = QuestionDaoImpl::new(pool)
+            .vote_to_reopen(question_uuid, user_uuid, false)
+            .await;
+
+        if let Err(DBError::Conflict(_)) = result {
+            Ok(())
+        } else {
+            Err(format!("Expected a conflict error but got: {result:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn create_answer_should_fail_for_closed_question(pool: PgPool) -> Result<(), String> {
+        let moderator_uuid = create_user(pool.clone(), "moderator", None).await?;
+        let question_uuid = create_question(pool.clone(), None, &[]).await?;
+
+        QuestionDaoImpl::new(pool.clone())
+            .vote_to_close(
+                question_uuid.clone(),
+                moderator_uuid,
+                vote(CloseReason::OffTopic, None),
+                true,
+            )
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let result = AnswerDaoImpl::new(pool)
+            .create_answer(Answer {
+                question_uuid: question_uuid.clone(),
+                content: "test content".to_string(),
+                author_uuid: None,
+            })
+            .await;
+
+        match result {
+            Err(DBError::Conflict(message))
+                if message
+                    == format!(
+                        "Question is closed and does not accept answers: {question_uuid}"
+                    ) =>
+            {
+                Ok(())
+            }
+            result => Err(format!("Expected a conflict error but got: {result:?}")),
+        }
+    }
+
+    #[sqlx::test]
+    async fn locked_question_should_refuse_edits(pool: PgPool) -> Result<(), String> {
+        let (question_uuid, answer_uuid) = create_locked_question(pool.clone()).await?;
+
+        let question = QuestionDaoImpl::new(pool.clone())
+            .update_question(
+                question_uuid.clone(),
+                QuestionUpdate {
+                    title: Some("new title".to_string()),
+                    description: None,
+                },
+            )
+            .await;
+        let answer = AnswerDaoImpl::new(pool)
+            .update_answer(
+                answer_uuid,
+                AnswerUpdate {
+                    content: "new content".to_string(),
+                },
+            )
+            .await;
+
+        expect_locked(question, &question_uuid)?;
+        expect_locked(answer, &question_uuid)
+    }
+
+    #[sqlx::test]
+    async fn locked_question_should_refuse_votes(pool: PgPool) -> Result<(), String> {
+        let voter_uuid = create_user(pool.clone(), "voter", None).await?;
+        let (question_uuid, answer_uuid) = create_locked_question(pool.clone()).await?;
+        let dao = VoteDaoImpl::new(pool);
+
+        let question = dao
+            .vote_question(question_uuid.clone(), voter_uuid.clone(), 1)
+            .await;
+        let answer = dao.vote_answer(answer_uuid, voter_uuid, 1).await;
+
+        expect_locked(question, &question_uuid)?;
+        expect_locked(answer, &question_uuid)
+    }
+
+    #[sqlx::test]
+    async fn locked_question_should_refuse_comments(pool: PgPool) -> Result<(), String> {
+        let (question_uuid, answer_uuid) = create_locked_question(pool.clone()).await?;
+        let dao = CommentDaoImpl::new(pool);
+        let comment = || Comment {
+            content: "test comment".to_string(),
+            author_uuid: None,
+        };
+
+        let question = dao
+            .create_comment(CommentParent::Question(question_uuid.clone()), comment())
+            .await;
+        let answer = dao
+            .create_comment(CommentParent::Answer(answer_uuid), comment())
+            .await;
+
+        expect_locked(question, &question_uuid)?;
+        expect_locked(answer, &question_uuid)
+    }
+
+    #[sqlx::test]
+    async fn locked_question_should_refuse_accepts_and_bounties(
+        pool: PgPool,
+    ) -> Result<(), String> {
+        let offerer_uuid = create_user(pool.clone(), "offerer", Some(100)).await?;
+        let (question_uuid, answer_uuid) = create_locked_question(pool.clone()).await?;
+
+        let accepted = QuestionDaoImpl::new(pool.clone())
+            .accept_answer(question_uuid.clone(), answer_uuid)
+            .await;
+        let bounty = BountyDaoImpl::new(pool)
+            .start_bounty(question_uuid.clone(), offerer_uuid, 50)
+            .await;
+
+        expect_locked(accepted, &question_uuid)?;
+
+        match bounty {
+            Err(DBError::Conflict(_)) => Ok(()),
+            other => Err(format!("Expected Conflict, got {other:?}")),
+        }
+    }
+
+    #[sqlx::test]
+    async fn unlock_question_should_restore_previous_state(pool: PgPool) -> Result<(), String> {
+        let moderator_uuid = create_user(pool.clone(), "moderator", None).await?;
+        let open_uuid = create_question(pool.clone(), None, &[]).await?;
+        let closed_uuid = create_question(pool.clone(), None, &[]).await?;
+        let dao = QuestionDaoImpl::new(pool);
+
+        dao.vote_to_close(
+            closed_uuid.clone(),
+            moderator_uuid,
+            vote(CloseReason::Unclear, None),
+            true,
+        )
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+
+        let mut states = Vec::new();
+
+        for question_uuid in [open_uuid, closed_uuid] {
+            let locked = dao
+                .lock_question(question_uuid.clone())
+                .await
+                .map_err(|e| format!("{e:?}"))?;
+            let unlocked = dao
+                .unlock_question(question_uuid)
+                .await
+                .map_err(|e| format!("{e:?}"))?;
+
+            states.push((locked.state, unlocked.state));
+        }
+
+        if states
+            == vec![
+                (QuestionState::Locked, QuestionState::Open),
+                (QuestionState::Locked, QuestionState::Closed),
+            ]
+        {
+            Ok(())
+        } else {
+            Err(format!("Incorrect states: {states:?}"))
+        }
+    }
+}
+
+mod soft_delete_tests {
+    use sqlx::PgPool;
+
+    use super::fixtures::{create_answer, create_question, create_user};
+    use crate::{
+        models::{DBError, PageRequest, QuestionQuery},
+        persistance::{
+            answer_dao::{AnswerDao, AnswerDaoImpl},
+            question_dao::{QuestionDao, QuestionDaoImpl},
+        },
+    };
+
+    #[sqlx::test]
+    async fn deleted_question_should_only_be_listed_with_include_deleted(
+        pool: PgPool,
+    ) -> Result<(), String> {
+        let moderator_uuid = create_user(pool.clone(), "moderator", None).await?;
+        let question_uuid = create_question(pool.clone(), None, &[]).await?;
+        let dao = QuestionDaoImpl::new(pool);
+
+        dao.delete_question(question_uuid.clone(), moderator_uuid)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let result = dao.get_question(question_uuid.clone()).await;
+
+        if !matches!(result, Err(DBError::NotFound(_))) {
+            return Err(format!("Deleted question was returned: {result:?}"));
+        }
+
+        let listed = dao
+            .get_questions(QuestionQuery::default(), PageRequest::default(), None)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        if !listed.items.is_empty() {
+            return Err(format!("Deleted question was listed: {:?}", listed.items));
+        }
+
+        let listed = dao
+            .get_questions(
+                QuestionQuery {
+                    include_deleted: true,
+                    ..QuestionQuery::default()
+                },
+                PageRequest::default(),
+                None,
+            )
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        match listed.items.as_slice() {
+            [question]
+                if question.question_uuid == question_uuid && question.deleted_at.is_some() =>
+            {
+                Ok(())
+            }
+            items => Err(format!("Incorrect questions: {items:?}")),
+        }
+    }
+
+    #[sqlx::test]
+    async fn revisions_of_deleted_question_should_only_be_found_with_include_deleted(
+        pool: PgPool,
+    ) -> Result<(), String> {
+        let moderator_uuid = create_user(pool.clone(), "moderator", None).await?;
+        let question_uuid = create_question(pool.clone(), None, &[]).await?;
+        let answer_uuid = create_answer(pool.clone(), &question_uuid, None).await?;
+        let question_dao = QuestionDaoImpl::new(pool.clone());
+        let answer_dao = AnswerDaoImpl::new(pool);
+
+        question_dao
+            .delete_question(question_uuid.clone(), moderator_uuid)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let hidden = [
+            question_dao
+                .get_question_revisions(question_uuid.clone(), false)
+                .await
+                .map(|_| ()),
+            question_dao
+                .get_question_revision(question_uuid.clone(), 1, false)
+                .await
+                .map(|_| ()),
+            answer_dao
+                .get_answer_revisions(answer_uuid.clone(), false)
+                .await
+                .map(|_| ()),
+            answer_dao
+                .get_answer_revision(answer_uuid.clone(), 1, false)
+                .await
+                .map(|_| ()),
+        ];
+
+        if !hidden
+            .iter()
+            .all(|r| matches!(r, Err(DBError::NotFound(_))))
+        {
+            return Err(format!("Expected not found errors but got: {hidden:?}"));
+        }
+
+        let questions = question_dao
+            .get_question_revisions(question_uuid, true)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        let answers = answer_dao
+            .get_answer_revisions(answer_uuid, true)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        if questions.len() == 1 && answers.len() == 1 {
+            Ok(())
+        } else {
+            Err(format!("Incorrect revisions: {questions:?}, {answers:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn revisions_of_deleted_answer_should_not_be_found(pool: PgPool) -> Result<(), String> {
+        let moderator_uuid = create_user(pool.clone(), "moderator", None).await?;
+        let question_uuid = create_question(pool.clone(), None, &[]).await?;
+        let answer_uuid = create_answer(pool.clone(), &question_uuid, None).await?;
+        let dao = AnswerDaoImpl::new(pool);
+
+        dao.delete_answer(answer_uuid.clone(), moderator_uuid)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let result = dao.get_answer_revisions(answer_uuid, false).await;
+
+        if let Err(DBError::NotFound(_)) = result {
+            Ok(())
+        } else {
+            Err(format!(
+                "Expected a not found error but got the following result: {result:?}"
+            ))
+        }
+    }
+
+    #[sqlx::test]
+    async fn undelete_question_should_restore_question(pool: PgPool) -> Result<(), String> {
+        let moderator_uuid = create_user(pool.clone(), "moderator", None).await?;
+        let question_uuid = create_question(pool.clone(), None, &[]).await?;
+        let dao = QuestionDaoImpl::new(pool);
+
+        dao.delete_question(question_uuid.clone(), moderator_uuid)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let restored = dao
+            .undelete_question(question_uuid.clone())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        if restored.deleted_at.is_some() {
+            return Err(format!("Question is still deleted: {restored:?}"));
+        }
+
+        dao.get_question(question_uuid)
+            .await
+            .map(|_| ())
+            .map_err(|e| format!("{e:?}"))
+    }
+
+    #[sqlx::test]
+    async fn undelete_question_should_fail_for_live_question(pool: PgPool) -> Result<(), String> {
+        let question_uuid = create_question(pool.clone(), None, &[]).await?;
+        let result = QuestionDaoImpl::new(pool)
+            .undelete_question(question_uuid)
+            .await;
+
+        if let Err(DBError::NotFound(_)) = result {
+            Ok(())
+        } else {
+            Err(format!(
+                "Expected a not found error but got the following result: {:?}",
+                result
+            ))
+        }
+    }
+
+    #[sqlx::test]
+    async fn purge_deleted_questions_should_respect_retention(pool: PgPool) -> Result<(), String> {
+        let moderator_uuid = create_user(pool.clone(), "moderator", None).await?;
+        let expired_uuid = create_question(pool.clone(), None, &[]).await?;
+        let recent_uuid = create_question(pool.clone(), None, &[]).await?;
+        let dao = QuestionDaoImpl::new(pool.clone());
+
+        for question_uuid in [&expired_uuid, &recent_uuid] {
+            dao.delete_question(question_uuid.clone(), moderator_uuid.clone())
+                .await
+                .map_err(|e| format!("{e:?}"))?;
+        }
+
+        sqlx::query!(
+            "UPDATE question SET deleted_at = CURRENT_TIMESTAMP - INTERVAL '31 days' WHERE question_uuid::text = $1",
+            expired_uuid
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+
+        let purged = dao
+            .purge_deleted_questions(30)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        if purged != 1 {
+            return Err(format!("Expected one purged question but got {purged}"));
+        }
+
+        if let Ok(question) = dao.undelete_question(expired_uuid).await {
+            return Err(format!("Expired question was not purged: {question:?}"));
+        }
+
+        dao.undelete_question(recent_uuid)
+            .await
+            .map(|_| ())
+            .map_err(|e| format!("Recent question was purged: {e:?}"))
+    }
+
+    #[sqlx::test]
+    async fn deleted_answer_should_only_be_listed_with_include_deleted(
+        pool: PgPool,
+    ) -> Result<(), String> {
+        let moderator_uuid = create_user(pool.clone(), "moderator", None).await?;
+        let question_uuid = create_question(pool.clone(), None, &[]).await?;
+        let deleted_uuid = create_answer(pool.clone(), &question_uuid, None).await?;
+        let live_uuid = create_answer(pool.clone(), &question_uuid, None).await?;
+        let dao = AnswerDaoImpl::new(pool);
+
+        dao.delete_answer(deleted_uuid.clone(), moderator_uuid)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let listed = dao
+            .get_answers(question_uuid.clone(), false, PageRequest::default())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        let uuids: Vec<_> = listed.items.into_iter().map(|a| a.answer_uuid).collect();
+
+        if uuids != vec![live_uuid] {
+            return Err(format!("Incorrect answers: {uuids:?}"));
+        }
+
+        let listed = dao
+            .get_answers(question_uuid, true, PageRequest::default())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        if listed.items.len() == 2
+            && listed
+                .items
+                .iter()
+                .any(|a| a.answer_uuid == deleted_uuid && a.deleted_at.is_some())
+        {
+            Ok(())
+        } else {
+            Err(format!("Incorrect answers: {:?}", listed.items))
+        }
+    }
+}
+
+mod reputation_tests {
+    use sqlx::PgPool;
+
+    use super::fixtures::{create_answer, create_question, create_user, reputation};
+    use crate::{
+        models::{DBError, PageRequest, ReputationKind, BASE_REPUTATION, DAILY_REPUTATION_CAP},
+        persistance::{
+            answer_dao::{AnswerDao, AnswerDaoImpl},
+            question_dao::{QuestionDao, QuestionDaoImpl},
+            reputation_dao::{ReputationDao, ReputationDaoImpl},
+            vote_dao::{VoteDao, VoteDaoImpl},
+        },
+    };
+
+    #[sqlx::test]
+    async fn votes_should_change_author_reputation(pool: PgPool) -> Result<(), String> {
+        let author_uuid = create_user(pool.clone(), "author", None).await?;
+        let voter_uuid = create_user(pool.clone(), "voter", None).await?;
+        let question_uuid = create_question(pool.clone(), Some(&author_uuid), &[]).await?;
+        let dao = VoteDaoImpl::new(pool.clone());
+        let mut totals = Vec::new();
+
+        for value in [1, -1] {
+            dao.vote_question(question_uuid.clone(), voter_uuid.clone(), value)
+                .await
+                .map_err(|e| format!("{e:?}"))?;
+            totals.push(reputation(pool.clone(), &author_uuid).await?);
+        }
+
+        dao.retract_question_vote(question_uuid, voter_uuid)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        totals.push(reputation(pool.clone(), &author_uuid).await?);
+
+        let expected = vec![
+            BASE_REPUTATION + ReputationKind::QuestionUpvoted.amount(),
+            BASE_REPUTATION + ReputationKind::QuestionDownvoted.amount(),
+            BASE_REPUTATION,
+        ];
+
+        if totals == expected {
+            Ok(())
+        } else {
+            Err(format!("Incorrect totals: {totals:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn own_votes_should_not_change_reputation(pool: PgPool) -> Result<(), String> {
+        let author_uuid = create_user(pool.clone(), "author", None).await?;
+        let question_uuid = create_question(pool.clone(), Some(&author_uuid), &[]).await?;
+        let answer_uuid = create_answer(pool.clone(), &question_uuid, Some(&author_uuid)).await?;
+        let dao = VoteDaoImpl::new(pool.clone());
+
+        dao.vote_question(question_uuid, author_uuid.clone(), 1)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        dao.vote_answer(answer_uuid, author_uuid.clone(), 1)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let total = reputation(pool, &author_uuid).await?;
+
+        if total == BASE_REPUTATION {
+            Ok(())
+        } else {
+            Err(format!("Incorrect total: {total}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn upvotes_should_be_capped_daily(pool: PgPool) -> Result<(), String> {
+        let author_uuid = create_user(pool.clone(), "author", None).await?;
+        let question_uuid = create_question(pool.clone(), Some(&author_uuid), &[]).await?;
+        let dao = VoteDaoImpl::new(pool.clone());
+        let voters = DAILY_REPUTATION_CAP / ReputationKind::QuestionUpvoted.amount() + 1;
+
+        for i in 0..voters {
+            let voter_uuid = create_user(pool.clone(), &format!("voter{i}"), None).await?;
+
+            dao.vote_question(question_uuid.clone(), voter_uuid, 1)
+                .await
+                .map_err(|e| format!("{e:?}"))?;
+        }
+
+        let total = reputation(pool.clone(), &author_uuid).await?;
+        let history = ReputationDaoImpl::new(pool)
+            .get_reputation_history(author_uuid, PageRequest::default())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        if total != BASE_REPUTATION + DAILY_REPUTATION_CAP {
+            return Err(format!("Incorrect total: {total}"));
+        }
+
+        // The newest event came in over the cap and earned nothing.
+        match history.items.first() {
+            Some(event) if event.amount == 0 => Ok(()),
+            _ => Err(format!("Incorrect history: {:?}", history.items)),
+        }
+    }
+
+    #[sqlx::test]
+    async fn acceptance_should_follow_accepted_answer(pool: PgPool) -> Result<(), String> {
+        let asker_uuid = create_user(pool.clone(), "asker", None).await?;
+        let first_uuid = create_user(pool.clone(), "first", None).await?;
+        let second_uuid = create_user(pool.clone(), "second", None).await?;
+        let question_uuid = create_question(pool.clone(), Some(&asker_uuid), &[]).await?;
+        let first_answer = create_answer(pool.clone(), &question_uuid, Some(&first_uuid)).await?;
+        let second_answer = create_answer(pool.clone(), &question_uuid, Some(&second_uuid)).await?;
+        let dao = QuestionDaoImpl::new(pool.clone());
+
+        dao.accept_answer(question_uuid.clone(), first_answer)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        dao.accept_answer(question_uuid.clone(), second_answer.clone())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let totals = [
+            reputation(pool.clone(), &asker_uuid).await?,
+            reputation(pool.clone(), &first_uuid).await?,
+            reputation(pool.clone(), &second_uuid).await?,
+        ];
+        let expected = [
+            BASE_REPUTATION + ReputationKind::AcceptedAnswer.amount(),
+            BASE_REPUTATION,
+            BASE_REPUTATION + ReputationKind::AnswerAccepted.amount(),
+        ];
+
+        if totals != expected {
+            return Err(format!("Incorrect totals after accepting: {totals:?}"));
+        }
+
+        AnswerDaoImpl::new(pool.clone())
+            .delete_answer(second_answer, asker_uuid.clone())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let totals = [
+            reputation(pool.clone(), &asker_uuid).await?,
+            reputation(pool.clone(), &second_uuid).await?,
+        ];
+
+        if totals == [BASE_REPUTATION, BASE_REPUTATION] {
+            Ok(())
+        } else {
+            Err(format!("Incorrect totals after deleting: {totals:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn recalculate_reputation_should_rebuild_totals(pool: PgPool) -> Result<(), String> {
+        let author_uuid = create_user(pool.clone(), "author", None).await?;
+        let voter_uuid = create_user(pool.clone(), "voter", None).await?;
+        let question_uuid = create_question(pool.clone(), Some(&author_uuid), &[]).await?;
+
+        VoteDaoImpl::new(pool.clone())
+            .vote_question(question_uuid, voter_uuid, 1)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        sqlx::query!(
+            "UPDATE users SET reputation = 999 WHERE user_uuid::text = $1",
+            author_uuid
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+
+        let corrected = ReputationDaoImpl::new(pool.clone())
+            .recalculate_reputation()
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        let total = reputation(pool, &author_uuid).await?;
+
+        if corrected == 1 && total == BASE_REPUTATION + ReputationKind::QuestionUpvoted.amount() {
+            Ok(())
+        } else {
+            Err(format!(
+                "Incorrect recalculation: {corrected} corrected, total {total}"
+            ))
+        }
+    }
+
+    #[sqlx::test]
+    async fn get_reputation_history_should_fail_for_missing_user(
+        pool: PgPool,
+    ) -> Result<(), String> {
+        let result = ReputationDaoImpl::new(pool)
+            .get_reputation_history(
+                "00000000-0000-0000-0000-000000000000".to_string(),
+                PageRequest::default(),
+            )
+            .await;
+
+        if let Err(DBError::NotFound(_)) = result {
+            Ok(())
+        } else {
+            Err(format!(
+                "Expected a not found error but got the following result: {:?}",
+                result
+            ))
+        }
+    }
+}
+
+mod badge_tests {
+    use sqlx::PgPool;
+
+    use super::fixtures::{create_answer, create_question, create_user};
+    use crate::{
+        models::{Badge, DBError, PageRequest},
+        persistance::{
+            badge_dao::{BadgeDao, BadgeDaoImpl},
+            vote_dao::{VoteDao, VoteDaoImpl},
+        },
+    };
+
+    async fn badges(pool: PgPool, user_uuid: &str) -> Result<Vec<Badge>, String> {
+        BadgeDaoImpl::new(pool)
+            .get_user_badges(user_uuid.to_string())
+            .await
+            .map(|badges| badges.into_iter().map(|b| b.badge).collect())
+            .map_err(|e| format!("{e:?}"))
+    }
+
+    #[sqlx::test]
+    async fn posting_should_award_first_post_badges(pool: PgPool) -> Result<(), String> {
+        let author_uuid = create_user(pool.clone(), "author", None).await?;
+        let question_uuid = create_question(pool.clone(), Some(&author_uuid), &[]).await?;
+
+        create_answer(pool.clone(), &question_uuid, Some(&author_uuid)).await?;
+
+        let badges = badges(pool, &author_uuid).await?;
+
+        if badges.len() == 2
+            && badges.contains(&Badge::FirstQuestion)
+            && badges.contains(&Badge::FirstAnswer)
+        {
+            Ok(())
+        } else {
+            Err(format!("Incorrect badges: {badges:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn award_badges_should_not_award_twice(pool: PgPool) -> Result<(), String> {
+        let author_uuid = create_user(pool.clone(), "author", None).await?;
+
+        create_question(pool.clone(), Some(&author_uuid), &[]).await?;
+        create_question(pool.clone(), Some(&author_uuid), &[]).await?;
+
+        let awarded = BadgeDaoImpl::new(pool.clone())
+            .award_badges()
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        let badges = badges(pool, &author_uuid).await?;
+
+        if awarded == 0 && badges == vec![Badge::FirstQuestion] {
+            Ok(())
+        } else {
+            Err(format!("Awarded {awarded}, badges: {badges:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn votes_should_award_score_badges(pool: PgPool) -> Result<(), String> {
+        let author_uuid = create_user(pool.clone(), "author", None).await?;
+        let voter_uuid = create_user(pool.clone(), "voter", None).await?;
+        let question_uuid = create_question(pool.clone(), Some(&author_uuid), &[]).await?;
+
+        sqlx::query!(
+            "UPDATE question SET score = 9 WHERE question_uuid::text = $1",
+            question_uuid
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+
+        VoteDaoImpl::new(pool.clone())
+            .vote_question(question_uuid, voter_uuid, 1)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let badges = badges(pool, &author_uuid).await?;
+
+        if badges.contains(&Badge::NiceQuestion) && !badges.contains(&Badge::GoodQuestion) {
+            Ok(())
+        } else {
+            Err(format!("Incorrect badges: {badges:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn late_answers_should_award_necromancer(pool: PgPool) -> Result<(), String> {
+        let asker_uuid = create_user(pool.clone(), "asker", None).await?;
+        let answerer_uuid = create_user(pool.clone(), "answerer", None).await?;
+        let voter_uuid = create_user(pool.clone(), "voter", None).await?;
+        let question_uuid = create_question(pool.clone(), Some(&asker_uuid), &[]).await?;
+
+        sqlx::query!(
+            "UPDATE question SET created_at = created_at - INTERVAL '61 days' WHERE question_uuid::text = $1",
+            question_uuid
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+
+        let answer_uuid = create_answer(pool.clone(), &question_uuid, Some(&answerer_uuid)).await?;
+
+        sqlx::query!(
+            "UPDATE answer SET score = 4 WHERE answer_uuid::text = $1",
+            answer_uuid
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+
+        VoteDaoImpl::new(pool.clone())
+            .vote_answer(answer_uuid, voter_uuid, 1)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let badges = badges(pool, &answerer_uuid).await?;
+
+        if badges.contains(&Badge::Necromancer) {
+            Ok(())
+        } else {
+            Err(format!("Incorrect badges: {badges:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn deleted_posts_should_not_award_badges(pool: PgPool) -> Result<(), String> {
+        let author_uuid = create_user(pool.clone(), "author", None).await?;
+        let question_uuid = create_question(pool.clone(), Some(&author_uuid), &[]).await?;
+
+        sqlx::query!(
+            "UPDATE question SET deleted_at = CURRENT_TIMESTAMP WHERE question_uuid::text = $1",
+            question_uuid
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+        sqlx::query!("DELETE FROM user_badge")
+            .execute(&pool)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let awarded = BadgeDaoImpl::new(pool)
+            .award_badges()
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        if awarded == 0 {
+            Ok(())
+        } else {
+            Err(format!("Awarded {awarded} badges"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn get_badge_recipients_should_page_recipients(pool: PgPool) -> Result<(), String> {
+        for username in ["first", "second"] {
+            let user_uuid = create_user(pool.clone(), username, None).await?;
+
+            create_question(pool.clone(), Some(&user_uuid), &[]).await?;
+        }
+
+        let dao = BadgeDaoImpl::new(pool);
+        let first_page = dao
+            .get_badge_recipients(
+                Badge::FirstQuestion,
+                PageRequest {
+                    cursor: None,
+                    limit: Some(1),
+                },
+            )
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        let second_page = dao
+            .get_badge_recipients(
+                Badge::FirstQuestion,
+                PageRequest {
+                    cursor: first_page.next_cursor.clone(),
+                    limit: Some(1),
+                },
+            )
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let usernames: Vec<_> = first_page
+            .items
+            .iter()
+            .chain(second_page.items.iter())
+            .map(|r| r.username.as_str())
+            .collect();
+
+        if usernames == vec!["second", "first"] && second_page.next_cursor.is_none() {
+            Ok(())
+        } else {
+            Err(format!("Incorrect recipients: {usernames:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn get_user_badges_should_fail_for_unknown_user(pool: PgPool) -> Result<(), String> {
+        let result = BadgeDaoImpl::new(pool)
+            .get_user_badges("b068cd2f-edac-479e-98f1-c5f91008dcbd".to_string())
+            .await;
+
+        match result {
+            Err(DBError::NotFound(_)) => Ok(()),
+            other => Err(format!("Expected NotFound, got {other:?}")),
+        }
+    }
+}
+
+mod bounty_tests {
+    use sqlx::PgPool;
+
+    use super::fixtures::{create_answer, create_question, create_user, reputation};
+    use crate::{
+        models::{BountyState, DBError, PageRequest},
+        persistance::bounty_dao::{BountyDao, BountyDaoImpl},
+    };
+
+    #[sqlx::test]
+    async fn start_bounty_should_escrow_reputation(pool: PgPool) -> Result<(), String> {
+        let offerer_uuid = create_user(pool.clone(), "offerer", Some(100)).await?;
+        let question_uuid = create_question(pool.clone(), Some(&offerer_uuid), &[]).await?;
+
+        let bounty = BountyDaoImpl::new(pool.clone())
+            .start_bounty(question_uuid.clone(), offerer_uuid.clone(), 60)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        let total = reputation(pool, &offerer_uuid).await?;
+
+        if bounty.state == BountyState::Active
+            && bounty.question_uuid == question_uuid
+            && total == 40
+        {
+            Ok(())
+        } else {
+            Err(format!("Incorrect bounty {bounty:?} or total {total}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn start_bounty_should_fail_without_enough_reputation(
+        pool: PgPool,
+    ) -> Result<(), String> {
+        let offerer_uuid = create_user(pool.clone(), "offerer", Some(80)).await?;
+        let question_uuid = create_question(pool.clone(), Some(&offerer_uuid), &[]).await?;
+
+        let result = BountyDaoImpl::new(pool.clone())
+            .start_bounty(question_uuid, offerer_uuid.clone(), 100)
+            .await;
+        let total = reputation(pool, &offerer_uuid).await?;
+
+        match result {
+            Err(DBError::Conflict(_)) if total == 80 => Ok(()),
+            other => Err(format!(
+                "Expected Conflict, got {other:?} with total {total}"
+            )),
+        }
+    }
+
+    #[sqlx::test]
+    async fn start_bounty_should_fail_with_active_bounty(pool: PgPool) -> Result<(), String> {
+        let offerer_uuid = create_user(pool.clone(), "offerer", Some(500)).await?;
+        let question_uuid = create_question(pool.clone(), Some(&offerer_uuid), &[]).await?;
+        let dao = BountyDaoImpl::new(pool);
+
+        dao.start_bounty(question_uuid.clone(), offerer_uuid.clone(), 50)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        match dao.start_bounty(question_uuid, offerer_uuid, 50).await {
+            Err(DBError::Conflict(_)) => Ok(()),
+            other => Err(format!("Expected Conflict, got {other:?}")),
+        }
+    }
+
+    #[sqlx::test]
+    async fn award_bounty_should_pay_answer_author(pool: PgPool) -> Result<(), String> {
+        let offerer_uuid = create_user(pool.clone(), "offerer", Some(100)).await?;
+        let answerer_uuid = create_user(pool.clone(), "answerer", Some(1)).await?;
+        let question_uuid = create_question(pool.clone(), Some(&offerer_uuid), &[]).await?;
+        let answer_uuid = create_answer(pool.clone(), &question_uuid, Some(&answerer_uuid)).await?;
+        let dao = BountyDaoImpl::new(pool.clone());
+
+        let bounty = dao
+            .start_bounty(question_uuid, offerer_uuid, 50)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        let awarded = dao
+            .award_bounty(bounty.bounty_uuid.clone(), answer_uuid.clone())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        let again = dao
+            .award_bounty(bounty.bounty_uuid, answer_uuid.clone())
+            .await;
+        let total = reputation(pool, &answerer_uuid).await?;
+
+        if awarded.state == BountyState::Awarded
+            && awarded.awarded_answer_uuid == Some(answer_uuid)
+            && matches!(again, Err(DBError::Conflict(_)))
+            && total == 51
+        {
+            Ok(())
+        } else {
+            Err(format!("Incorrect bounty {awarded:?} or total {total}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn award_bounty_should_fail_once_expired(pool: PgPool) -> Result<(), String> {
+        let offerer_uuid = create_user(pool.clone(), "offerer", Some(100)).await?;
+        let answerer_uuid = create_user(pool.clone(), "answerer", Some(1)).await?;
+        let question_uuid = create_question(pool.clone(), Some(&offerer_uuid), &[]).await?;
+        let answer_uuid = create_answer(pool.clone(), &question_uuid, Some(&answerer_uuid)).await?;
+        let dao = BountyDaoImpl::new(pool.clone());
+
+        let bounty = dao
+            .start_bounty(question_uuid, offerer_uuid, 50)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        // Past its expiry but not yet swept by the expiry job.
+        sqlx::query!(
+            "UPDATE bounty SET expires_at = CURRENT_TIMESTAMP - INTERVAL '1 minute' WHERE bounty_uuid::text = $1",
+            bounty.bounty_uuid
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+
+        let result = dao.award_bounty(bounty.bounty_uuid, answer_uuid).await;
+        let total = reputation(pool, &answerer_uuid).await?;
+
+        match result {
+            Err(DBError::Conflict(_)) if total == 1 => Ok(()),
+            other => Err(format!(
+                "Expected Conflict, got {other:?} with total {total}"
+            )),
+        }
+    }
+
+    #[sqlx::test]
+    async fn award_bounty_should_fail_for_answers_of_other_questions(
+        pool: PgPool,
+    ) -> Result<(), String> {
+        let offerer_uuid = create_user(pool.clone(), "offerer", Some(100)).await?;
+        let answerer_uuid = create_user(pool.clone(), "answerer", Some(1)).await?;
+        let question_uuid = create_question(pool.clone(), Some(&offerer_uuid), &[]).await?;
+        let other_uuid = create_question(pool.clone(), Some(&offerer_uuid), &[]).await?;
+        let answer_uuid = create_answer(pool.clone(), &other_uuid, Some(&answerer_uuid)).await?;
+        let dao = BountyDaoImpl::new(pool);
+
+        let bounty = dao
+            .start_bounty(question_uuid, offerer_uuid, 50)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        match dao.award_bounty(bounty.bounty_uuid, answer_uuid).await {
+            Err(DBError::InvalidUUID(_)) => Ok(()),
+            other => Err(format!("Expected InvalidUUID, got {other:?}")),
+        }
+    }
+
+    #[sqlx::test]
+    async fn award_bounty_should_fail_for_deleted_question(pool: PgPool) -> Result<(), String> {
+        let offerer_uuid = create_user(pool.clone(), "offerer", Some(100)).await?;
+        let answerer_uuid = create_user(pool.clone(), "answerer", Some(1)).await?;
+        let question_uuid = create_question(pool.clone(), Some(&offerer_uuid), &[]).await?;
+        let answer_uuid = create_answer(pool.clone(), &question_uuid, Some(&answerer_uuid)).await?;
+        let dao = BountyDaoImpl::new(pool.clone());
+
+        let bounty = dao
+            .start_bounty(question_uuid.clone(), offerer_uuid, 50)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        sqlx::query!(
+            "UPDATE question SET deleted_at = CURRENT_TIMESTAMP WHERE question_uuid::text = $1",
+            question_uuid
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+
+        let result = dao.award_bounty(bounty.bounty_uuid, answer_uuid).await;
+        let total = reputation(pool, &answerer_uuid).await?;
+
+        match result {
+            Err(DBError::NotFound(_)) if total == 1 => Ok(()),
+            other => Err(format!(
+                "Expected NotFound, got {other:?} with total {total}"
+            )),
+        }
+    }
+
+    #[sqlx::test]
+    async fn expire_bounties_should_refund_offerer(pool: PgPool) -> Result<(), String> {
+        let offerer_uuid = create_user(pool.clone(), "offerer", Some(100)).await?;
+        let expiring_uuid = create_question(pool.clone(), Some(&offerer_uuid), &[]).await?;
+        let running_uuid = create_question(pool.clone(), Some(&offerer_uuid), &[]).await?;
+        let dao = BountyDaoImpl::new(pool.clone());
+
+        for question_uuid in [&expiring_uuid, &running_uuid] {
+            dao.start_bounty(question_uuid.clone(), offerer_uuid.clone(), 50)
+                .await
+                .map_err(|e| format!("{e:?}"))?;
+        }
+
+        sqlx::query!(
+            "UPDATE bounty SET expires_at = CURRENT_TIMESTAMP - INTERVAL '1 minute' WHERE question_uuid::text = $1",
+            expiring_uuid
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+
+        let expired = dao.expire_bounties().await.map_err(|e| format!("{e:?}"))?;
+        let total = reputation(pool, &offerer_uuid).await?;
+        let featured = dao
+            .get_featured_questions(PageRequest::default())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        let featured: Vec<_> = featured
+            .items
+            .into_iter()
+            .map(|q| q.question_uuid)
+            .collect();
+
+        if expired == 1 && total == 50 && featured == vec![running_uuid] {
+            Ok(())
+        } else {
+            Err(format!(
+                "Expired {expired}, total {total}, featured {featured:?}"
+            ))
+        }
+    }
+}
+
+mod follow_tests {
+    use sqlx::PgPool;
+
+    use super::fixtures::{create_question, create_user};
+    use crate::{
+        models::{DBError, PageRequest, QuestionQuery},
+        persistance::{
+            follow_dao::{FollowDao, FollowDaoImpl},
+            question_dao::{QuestionDao, QuestionDaoImpl},
+        },
+    };
+
+    async fn followed(pool: PgPool, user_uuid: &str) -> Result<Vec<String>, String> {
+        FollowDaoImpl::new(pool)
+            .get_followed_questions(user_uuid.to_string(), PageRequest::default())
+            .await
+            .map(|page| page.items.into_iter().map(|q| q.question_uuid).collect())
+            .map_err(|e| format!("{e:?}"))
+    }
+
+    #[sqlx::test]
+    async fn follow_question_should_keep_first_follow(pool: PgPool) -> Result<(), String> {
+        let user_uuid = create_user(pool.clone(), "follower", None).await?;
+        let question_uuid = create_question(pool.clone(), Some(&user_uuid), &[]).await?;
+        let dao = FollowDaoImpl::new(pool.clone());
+
+        let first = dao
+            .follow_question(question_uuid.clone(), user_uuid.clone())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        let second = dao
+            .follow_question(question_uuid.clone(), user_uuid.clone())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        let followed = followed(pool, &user_uuid).await?;
+
+        if first == second && followed == vec![question_uuid] {
+            Ok(())
+        } else {
+            Err(format!(
+                "Incorrect follows: {first:?}, {second:?}, {followed:?}"
+            ))
+        }
+    }
+
+    #[sqlx::test]
+    async fn follow_question_should_fail_for_deleted_question(pool: PgPool) -> Result<(), String> {
+        let user_uuid = create_user(pool.clone(), "follower", None).await?;
+        let question_uuid = create_question(pool.clone(), Some(&user_uuid), &[]).await?;
+
+        QuestionDaoImpl::new(pool.clone())
+            .delete_question(question_uuid.clone(), user_uuid.clone())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let result = FollowDaoImpl::new(pool)
+            .follow_question(question_uuid, user_uuid)
+            .await;
+
+        match result {
+            Err(DBError::NotFound(_)) => Ok(()),
+            _ => Err(format!("Expected not found error, got {result:?}")),
+        }
+    }
+
+    #[sqlx::test]
+    async fn unfollow_question_should_remove_follow(pool: PgPool) -> Result<(), String> {
+        let user_uuid = create_user(pool.clone(), "follower", None).await?;
+        let question_uuid = create_question(pool.clone(), Some(&user_uuid), &[]).await?;
+        let dao = FollowDaoImpl::new(pool.clone());
+
+        dao.follow_question(question_uuid.clone(), user_uuid.clone())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        for _ in 0..2 {
+            dao.unfollow_question(question_uuid.clone(), user_uuid.clone())
+                .await
+                .map_err(|e| format!("{e:?}"))?;
+        }
+
+        let followed = followed(pool, &user_uuid).await?;
+
+        if followed.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("Incorrect follows: {followed:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn get_followed_questions_should_page_follows(pool: PgPool) -> Result<(), String> {
+        let user_uuid = create_user(pool.clone(), "follower", None).await?;
+        let dao = FollowDaoImpl::new(pool.clone());
+        let mut question_uuids = vec![];
+
+        for _ in 0..3 {
+            let question_uuid = create_question(pool.clone(), Some(&user_uuid), &[]).await?;
+
+            dao.follow_question(question_uuid.clone(), user_uuid.clone())
+                .await
+                .map_err(|e| format!("{e:?}"))?;
+            question_uuids.push(question_uuid);
+        }
+
+        let mut cursor = None;
+        let mut listed = vec![];
+
+        loop {
+            let page = dao
+                .get_followed_questions(
+                    user_uuid.clone(),
+                    PageRequest {
+                        cursor,
+                        limit: Some(2),
+                    },
+                )
+                .await
+                .map_err(|e| format!("{e:?}"))?;
+
+            listed.extend(page.items.into_iter().map(|q| q.question_uuid));
+
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        listed.sort();
+        question_uuids.sort();
+
+        if listed == question_uuids {
+            Ok(())
+        } else {
+            Err(format!("Incorrect follows: {listed:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn get_questions_should_filter_followed_questions(pool: PgPool) -> Result<(), String> {
+        let user_uuid = create_user(pool.clone(), "follower", None).await?;
+        let other_uuid = create_user(pool.clone(), "other", None).await?;
+        let followed_uuid = create_question(pool.clone(), Some(&other_uuid), &[]).await?;
+
+        create_question(pool.clone(), Some(&other_uuid), &[]).await?;
+
+        FollowDaoImpl::new(pool.clone())
+            .follow_question(followed_uuid.clone(), user_uuid.clone())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let page = QuestionDaoImpl::new(pool)
+            .get_questions(
+                QuestionQuery {
+                    followed: true,
+                    ..QuestionQuery::default()
+                },
+                PageRequest::default(),
+                Some(user_uuid),
+            )
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        let listed: Vec<_> = page.items.into_iter().map(|q| q.question_uuid).collect();
+
+        if listed == vec![followed_uuid] {
+            Ok(())
+        } else {
+            Err(format!("Incorrect questions: {listed:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn purging_question_should_remove_follows(pool: PgPool) -> Result<(), String> {
+        let user_uuid = create_user(pool.clone(), "follower", None).await?;
+        let question_uuid = create_question(pool.clone(), Some(&user_uuid), &[]).await?;
+        let question_dao = QuestionDaoImpl::new(pool.clone());
+
+        FollowDaoImpl::new(pool.clone())
+            .follow_question(question_uuid.clone(), user_uuid.clone())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        question_dao
+            .delete_question(question_uuid.clone(), user_uuid.clone())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        question_dao
+            .purge_deleted_questions(0)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let follows = sqlx::query!(
+            r#"SELECT COUNT(*) AS "count!" FROM question_follow WHERE question_uuid::text = $1"#,
+            question_uuid
+        )
+        .fetch_one(&pool)
+        .await
+        .map_err(|e| format!("{e:?}"))?
+        .count;
+
+        if follows == 0 {
+            Ok(())
+        } else {
+            Err(format!("Follows left after purge: {follows}"))
+        }
+    }
+}
+
+mod notification_tests {
+    use sqlx::PgPool;
+
+    use super::fixtures::{create_answer, create_question, create_user};
+    use crate::{
+        models::{
+            DBError, NotificationEvent, NotificationKind, NotificationPreference, PageRequest,
+        },
+        persistance::{
+            follow_dao::{FollowDao, FollowDaoImpl},
+            notification_dao::{NotificationDao, NotificationDaoImpl},
+        },
+    };
+
+    fn answer_posted(answerer_uuid: &str, answer_uuid: &str) -> NotificationEvent {
+        NotificationEvent {
+            kind: NotificationKind::AnswerPosted,
+            actor_uuid: Some(answerer_uuid.to_string()),
+            question_uuid: None,
+            answer_uuid: Some(answer_uuid.to_string()),
+            comment_uuid: None,
+        }
+    }
+
+    async fn unread_count(pool: PgPool, user_uuid: &str) -> Result<i64, String> {
+        NotificationDaoImpl::new(pool)
+            .get_notifications(user_uuid.to_string(), true, PageRequest::default())
+            .await
+            .map(|inbox| inbox.unread_count)
+            .map_err(|e| format!("{e:?}"))
+    }
+
+    #[sqlx::test]
+    async fn notify_should_reach_author_and_followers_but_not_actor(
+        pool: PgPool,
+    ) -> Result<(), String> {
+        let author_uuid = create_user(pool.clone(), "author", None).await?;
+        let follower_uuid = create_user(pool.clone(), "follower", None).await?;
+        let answerer_uuid = create_user(pool.clone(), "answerer", None).await?;
+        let question_uuid = create_question(pool.clone(), Some(&author_uuid), &[]).await?;
+        let follow_dao = FollowDaoImpl::new(pool.clone());
+
+        for user_uuid in [&follower_uuid, &answerer_uuid, &author_uuid] {
+            follow_dao
+                .follow_question(question_uuid.clone(), user_uuid.clone())
+                .await
+                .map_err(|e| format!("{e:?}"))?;
+        }
+
+        let answer_uuid = create_answer(pool.clone(), &question_uuid, Some(&answerer_uuid)).await?;
+        let notified = NotificationDaoImpl::new(pool.clone())
+            .notify(answer_posted(&answerer_uuid, &answer_uuid))
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let inbox = NotificationDaoImpl::new(pool.clone())
+            .get_notifications(author_uuid.clone(), false, PageRequest::default())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        let counts = (
+            unread_count(pool.clone(), &author_uuid).await?,
+            unread_count(pool.clone(), &follower_uuid).await?,
+            unread_count(pool, &answerer_uuid).await?,
+        );
+
+        if notified == 2
+            && counts == (1, 1, 0)
+            && inbox.items[0].question_uuid == question_uuid
+            && inbox.items[0].kind == NotificationKind::AnswerPosted
+        {
+            Ok(())
+        } else {
+            Err(format!(
+                "Notified {notified}, counts {counts:?}, inbox {inbox:?}"
+            ))
+        }
+    }
+
+    #[sqlx::test]
+    async fn notify_should_skip_disabled_kinds(pool: PgPool) -> Result<(), String> {
+        let author_uuid = create_user(pool.clone(), "author", None).await?;
+        let answerer_uuid = create_user(pool.clone(), "answerer", None).await?;
+        let question_uuid = create_question(pool.clone(), Some(&author_uuid), &[]).await?;
+        let answer_uuid = create_answer(pool.clone(), &question_uuid, Some(&answerer_uuid)).await?;
+        let dao = NotificationDaoImpl::new(pool.clone());
+
+        let preferences = dao
+            .update_preferences(
+                author_uuid.clone(),
+                vec![NotificationPreference {
+                    kind: NotificationKind::AnswerPosted,
+                    enabled: false,
+                }],
+            )
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        let notified = dao
+            .notify(answer_posted(&answerer_uuid, &answer_uuid))
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let disabled: Vec<_> = preferences
+            .iter()
+            .filter(|p| !p.enabled)
+            .map(|p| p.kind)
+            .collect();
+
+        if notified == 0
+            && preferences.len() == NotificationKind::ALL.len()
+            && disabled == vec![NotificationKind::AnswerPosted]
+        {
+            Ok(())
+        } else {
+            Err(format!("Notified {notified}, preferences {preferences:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn notify_should_reach_answer_author_for_answer_events(
+        pool: PgPool,
+    ) -> Result<(), String> {
+        let author_uuid = create_user(pool.clone(), "author", None).await?;
+        let answerer_uuid = create_user(pool.clone(), "answerer", None).await?;
+        let question_uuid = create_question(pool.clone(), Some(&author_uuid), &[]).await?;
+        let answer_uuid = create_answer(pool.clone(), &question_uuid, Some(&answerer_uuid)).await?;
+
+        let notified = NotificationDaoImpl::new(pool.clone())
+            .notify(NotificationEvent {
+                kind: NotificationKind::AnswerAccepted,
+                actor_uuid: Some(author_uuid.clone()),
+                question_uuid: Some(question_uuid),
+                answer_uuid: Some(answer_uuid),
+                comment_uuid: None,
+            })
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        let counts = (
+            unread_count(pool.clone(), &author_uuid).await?,
+            unread_count(pool, &answerer_uuid).await?,
+        );
+
+        if notified == 1 && counts == (0, 1) {
+            Ok(())
+        } else {
+            Err(format!("Notified {notified}, counts {counts:?}"))
+        }
+    }
+
+    #[sqlx::test]
+    async fn mark_read_should_update_unread_count(pool: PgPool) -> Result<(), String> {
+        let author_uuid = create_user(pool.clone(), "author", None).await?;
+        let answerer_uuid = create_user(pool.clone(), "answerer", None).await?;
+        let question_uuid = create_question(pool.clone(), Some(&author_uuid), &[]).await?;
+        let dao = NotificationDaoImpl::new(pool.clone());
+
+        for _ in 0..3 {
+            let answer_uuid =
+                create_answer(pool.clone(), &question_uuid, Some(&answerer_uuid)).await?;
+
+            dao.notify(answer_posted(&answerer_uuid, &answer_uuid))
+                .await
+                .map_err(|e| format!("{e:?}"))?;
+        }
+
+        let inbox = dao
+            .get_notifications(author_uuid.clone(), false, PageRequest::default())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        let after_one = dao
+            .mark_read(
+                author_uuid.clone(),
+                inbox.items[0].notification_uuid.clone(),
+            )
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        let unread = dao
+            .get_notifications(author_uuid.clone(), true, PageRequest::default())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        let after_all = dao
+            .mark_all_read(author_uuid)
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        if inbox.unread_count == 3
+            && after_one.unread_count == 2
+            && unread.items.len() == 2
+            && after_all.unread_count == 0
+        {
+            Ok(())
+        } else {
+            Err(format!(
+                "Unread {} then {} then {}",
+                inbox.unread_count, after_one.unread_count, after_all.unread_count
+            ))
+        }
+    }
+
+    #[sqlx::test]
+    async fn mark_read_should_fail_for_notifications_of_others(pool: PgPool) -> Result<(), String> {
+        let author_uuid = create_user(pool.clone(), "author", None).await?;
+        let answerer_uuid = create_user(pool.clone(), "answerer", None).await?;
+        let question_uuid = create_question(pool.clone(), Some(&author_uuid), &[]).await?;
+        let answer_uuid = create_answer(pool.clone(), &question_uuid, Some(&answerer_uuid)).await?;
+        let dao = NotificationDaoImpl::new(pool);
+
+        dao.notify(answer_posted(&answerer_uuid, &answer_uuid))
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+
+        let inbox = dao
+            .get_notifications(author_uuid, false, PageRequest::default())
+            .await
+            .map_err(|e| format!("{e:?}"))?;
+        let result = dao
+            .mark_read(answerer_uuid, inbox.items[0].notification_uuid.clone())
+            .await;
+
+        match result {
+            Err(DBError::NotFound(_)) => Ok(()),
+            _ => Err(format!("Expected not found error, got {result:?}")),
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use crate::models::{postgres_error_codes, DBError, Role, StoredCredentials, User, UserDetail};
+
+#[async_trait]
+pub trait UserDao {
+    async fn create_user(&self, user: User, password_hash: String) -> Result<UserDetail, DBError>;
+    async fn get_user(&self, user_uuid: String) -> Result<UserDetail, DBError>;
+    async fn get_credentials(&self, username: String) -> Result<StoredCredentials, DBError>;
+    async fn update_role(&self, user_uuid: String, role: Role) -> Result<UserDetail, DBError>;
+}
+
+pub struct UserDaoImpl {
+    db: PgPool,
+}
+
+impl UserDaoImpl {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+}
+
+/// Roles are stored as text and constrained by the database, so an unknown
+/// value means the schema and the code have drifted apart.
+pub(crate) fn parse_role(role: &str) -> Result<Role, DBError> {
+    role.parse().map_err(|e: String| DBError::Other(e.into()))
+}
+
+#[async_trait]
+impl UserDao for UserDaoImpl {
+    async fn create_user(&self, user: User, password_hash: String) -> Result<UserDetail, DBError> {
+        let record = sqlx::query!(
+            r#"
+              INSERT INTO users ( username, email, password_hash )
+              VALUES ( $1, $2, $3 )
+              RETURNING user_uuid, username, role, created_at, reputation
+            "#,
+            user.username,
+            user.email,
+            password_hash
+        )
+        .fetch_one(&self.db)
+        .await
+        .map_err(|e: sqlx::Error| match e {
+            sqlx::Error::Database(e) => {
+                if let Some(code) = e.code() {
+                    if code.eq(postgres_error_codes::UNIQUE_VIOLATION) {
+                        return DBError::Conflict(format!(
+                            "Username or email is already taken: {}",
+                            user.username
+                        ));
+                    }
+                }
+                DBError::Other(Box::new(e))
+            }
+            e => DBError::Other(Box::new(e)),
+        })?;
+
+        let user_detail = UserDetail {
+            user_uuid: record.user_uuid.to_string(),
+            username: record.username,
+            role: parse_role(&record.role)?,
+            created_at: record.created_at.to_string(),
+            question_count: 0,
+            answer_count: 0,
+            reputation: record.reputation,
+        };
+
+        debug!("create user: {user_detail:?}");
+
+        Ok(user_detail)
+    }
+
+    async fn get_user(&self, user_uuid: String) -> Result<UserDetail, DBError> {
+        let uuid = sqlx::types::Uuid::parse_str(&user_uuid)
+            .map_err(|_| DBError::InvalidUUID(format!("Could not parse user UUID: {user_uuid}")))?;
+
+        let record = sqlx::query!(
+            r#"
+              SELECT user_uuid, username, role, created_at, reputation,
+                (SELECT COUNT(*) FROM question q
+                  WHERE q.author_uuid = users.user_uuid AND q.deleted_at IS NULL) AS "question_count!",
+                (SELECT COUNT(*) FROM answer a
+                  WHERE a.author_uuid = users.user_uuid AND a.deleted_at IS NULL) AS "answer_count!"
+              FROM users
+              WHERE user_uuid = $1
+            "#,
+            uuid
+        )
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .ok_or_else(|| DBError::NotFound(format!("User not found: {user_uuid}")))?;
+
+        let user_detail = UserDetail {
+            user_uuid: record.user_uuid.to_string(),
+            username: record.username,
+            role: parse_role(&record.role)?,
+            created_at: record.created_at.to_string(),
+            question_count: record.question_count,
+            answer_count: record.answer_count,
+            reputation: record.reputation,
+        };
+
+        debug!("get user: {user_detail:?}");
+
+        Ok(user_detail)
+    }
+
+    async fn get_credentials(&self, username: String) -> Result<StoredCredentials, DBError> {
+        let record = sqlx::query!(
+            "SELECT user_uuid, password_hash FROM users WHERE username = $1",
+            username
+        )
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .ok_or_else(|| DBError::NotFound(format!("User not found: {username}")))?;
+
+        Ok(StoredCredentials {
+            user_uuid: record.user_uuid.to_string(),
+            password_hash: record.password_hash,
+        })
+    }
+
+    async fn update_role(&self, user_uuid: String, role: Role) -> Result<UserDetail, DBError> {
+        let uuid = sqlx::types::Uuid::parse_str(&user_uuid)
+            .map_err(|_| DBError::InvalidUUID(format!("Could not parse user UUID: {user_uuid}")))?;
+
+        let updated = sqlx::query!(
+            "UPDATE users SET role = $2 WHERE user_uuid = $1",
+            uuid,
+            role.as_str()
+        )
+        .execute(&self.db)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        if updated.rows_affected() == 0 {
+            return Err(DBError::NotFound(format!("User not found: {user_uuid}")));
+        }
+
+        self.get_user(user_uuid).await
+    }
+}
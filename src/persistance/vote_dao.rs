@@ -0,0 +1,349 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use crate::{
+    models::{postgres_error_codes, BadgeCriterion, DBError, VoteSummary},
+    persistance::{
+        badge_dao,
+        question_dao::refuse_locked,
+        reputation_dao::{self, VotedPost},
+    },
+};
+
+#[async_trait]
+pub trait VoteDao {
+    async fn vote_question(
+        &self,
+        question_uuid: String,
+        user_uuid: String,
+        value: i16,
+    ) -> Result<VoteSummary, DBError>;
+    async fn retract_question_vote(
+        &self,
+        question_uuid: String,
+        user_uuid: String,
+    ) -> Result<VoteSummary, DBError>;
+    async fn vote_answer(
+        &self,
+        answer_uuid: String,
+        user_uuid: String,
+        value: i16,
+    ) -> Result<VoteSummary, DBError>;
+    async fn retract_answer_vote(
+        &self,
+        answer_uuid: String,
+        user_uuid: String,
+    ) -> Result<VoteSummary, DBError>;
+}
+
+pub struct VoteDaoImpl {
+    db: PgPool,
+}
+
+impl VoteDaoImpl {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+}
+
+fn parse_uuid(uuid: &str, name: &str) -> Result<sqlx::types::Uuid, DBError> {
+    sqlx::types::Uuid::parse_str(uuid)
+        .map_err(|_| DBError::InvalidUUID(format!("Could not parse {name} UUID: {uuid}")))
+}
+
+fn map_user_error(e: sqlx::Error, user_uuid: &str) -> DBError {
+    match e {
+        sqlx::Error::Database(e) => {
+            if let Some(code) = e.code() {
+                if code.eq(postgres_error_codes::FOREIGN_KEY_VIOLATION) {
+                    return DBError::InvalidUUID(format!("Invalid user UUID: {user_uuid}"));
+                }
+            }
+            DBError::Other(Box::new(e))
+        }
+        e => DBError::Other(Box::new(e)),
+    }
+}
+
+#[async_trait]
+impl VoteDao for VoteDaoImpl {
+    async fn vote_question(
+        &self,
+        question_uuid: String,
+        user_uuid: String,
+        value: i16,
+    ) -> Result<VoteSummary, DBError> {
+        let question = parse_uuid(&question_uuid, "question")?;
+        let user = parse_uuid(&user_uuid, "user")?;
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        // Locking the question row serializes votes on it, so the score cannot drift
+        // from the vote table when the same post is voted on concurrently.
+        let state = sqlx::query!(
+            "SELECT state FROM question WHERE question_uuid = $1 AND deleted_at IS NULL FOR UPDATE",
+            question
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .ok_or_else(|| DBError::NotFound(format!("Question not found: {question_uuid}")))?
+        .state;
+
+        refuse_locked(&state, &question_uuid)?;
+
+        let previous = sqlx::query!(
+            "SELECT value FROM question_vote WHERE question_uuid = $1 AND user_uuid = $2",
+            question,
+            user
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .map_or(0, |r| r.value);
+
+        sqlx::query!(
+            r#"
+              INSERT INTO question_vote ( question_uuid, user_uuid, value )
+              VALUES ( $1, $2, $3 )
+              ON CONFLICT ( question_uuid, user_uuid )
+              DO UPDATE SET value = EXCLUDED.value, created_at = CURRENT_TIMESTAMP
+            "#,
+            question,
+            user,
+            value
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| map_user_error(e, &user_uuid))?;
+
+        let record = sqlx::query!(
+            "UPDATE question SET score = score + $2 WHERE question_uuid = $1 RETURNING score, author_uuid",
+            question,
+            i32::from(value - previous)
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        reputation_dao::record_vote(&mut tx, VotedPost::Question(question), user, value).await?;
+        badge_dao::award_author_badges(&mut tx, record.author_uuid, |c| {
+            matches!(c, BadgeCriterion::QuestionScore(_))
+        })
+        .await?;
+
+        tx.commit().await.map_err(|e| DBError::Other(Box::new(e)))?;
+
+        Ok(VoteSummary {
+            score: record.score,
+            vote: Some(value),
+        })
+    }
+
+    async fn retract_question_vote(
+        &self,
+        question_uuid: String,
+        user_uuid: String,
+    ) -> Result<VoteSummary, DBError> {
+        let question = parse_uuid(&question_uuid, "question")?;
+        let user = parse_uuid(&user_uuid, "user")?;
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let state = sqlx::query!(
+            "SELECT state FROM question WHERE question_uuid = $1 AND deleted_at IS NULL FOR UPDATE",
+            question
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .ok_or_else(|| DBError::NotFound(format!("Question not found: {question_uuid}")))?
+        .state;
+
+        refuse_locked(&state, &question_uuid)?;
+
+        // Retracting a vote that was never cast leaves the score unchanged.
+        let previous = sqlx::query!(
+            "DELETE FROM question_vote WHERE question_uuid = $1 AND user_uuid = $2 RETURNING value",
+            question,
+            user
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .map_or(0, |r| r.value);
+
+        let record = sqlx::query!(
+            "UPDATE question SET score = score - $2 WHERE question_uuid = $1 RETURNING score",
+            question,
+            i32::from(previous)
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        reputation_dao::record_vote(&mut tx, VotedPost::Question(question), user, 0).await?;
+
+        tx.commit().await.map_err(|e| DBError::Other(Box::new(e)))?;
+
+        Ok(VoteSummary {
+            score: record.score,
+            vote: None,
+        })
+    }
+
+    async fn vote_answer(
+        &self,
+        answer_uuid: String,
+        user_uuid: String,
+        value: i16,
+    ) -> Result<VoteSummary, DBError> {
+        let answer = parse_uuid(&answer_uuid, "answer")?;
+        let user = parse_uuid(&user_uuid, "user")?;
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        // Locking the answer row serializes votes on it, so the score cannot drift
+        // from the vote table when the same post is voted on concurrently. The
+        // question row is only shared, which keeps it from being locked meanwhile.
+        let question = sqlx::query!(
+            r#"
+              SELECT q.question_uuid, q.state
+              FROM answer a
+              JOIN question q ON q.question_uuid = a.question_uuid
+              WHERE a.answer_uuid = $1 AND a.deleted_at IS NULL
+              FOR UPDATE OF a FOR SHARE OF q
+            "#,
+            answer
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .ok_or_else(|| DBError::NotFound(format!("Answer not found: {answer_uuid}")))?;
+
+        refuse_locked(&question.state, &question.question_uuid.to_string())?;
+
+        let previous = sqlx::query!(
+            "SELECT value FROM answer_vote WHERE answer_uuid = $1 AND user_uuid = $2",
+            answer,
+            user
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .map_or(0, |r| r.value);
+
+        sqlx::query!(
+            r#"
+              INSERT INTO answer_vote ( answer_uuid, user_uuid, value )
+              VALUES ( $1, $2, $3 )
+              ON CONFLICT ( answer_uuid, user_uuid )
+              DO UPDATE SET value = EXCLUDED.value, created_at = CURRENT_TIMESTAMP
+            "#,
+            answer,
+            user,
+            value
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| map_user_error(e, &user_uuid))?;
+
+        let record = sqlx::query!(
+            "UPDATE answer SET score = score + $2 WHERE answer_uuid = $1 RETURNING score, author_uuid",
+            answer,
+            i32::from(value - previous)
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        reputation_dao::record_vote(&mut tx, VotedPost::Answer(answer), user, value).await?;
+        badge_dao::award_author_badges(&mut tx, record.author_uuid, |c| {
+            matches!(
+                c,
+                BadgeCriterion::AnswerScore(_) | BadgeCriterion::LateAnswer { .. }
+            )
+        })
+        .await?;
+
+        tx.commit().await.map_err(|e| DBError::Other(Box::new(e)))?;
+
+        Ok(VoteSummary {
+            score: record.score,
+            vote: Some(value),
+        })
+    }
+
+    async fn retract_answer_vote(
+        &self,
+        answer_uuid: String,
+        user_uuid: String,
+    ) -> Result<VoteSummary, DBError> {
+        let answer = parse_uuid(&answer_uuid, "answer")?;
+        let user = parse_uuid(&user_uuid, "user")?;
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        let question = sqlx::query!(
+            r#"
+              SELECT q.question_uuid, q.state
+              FROM answer a
+              JOIN question q ON q.question_uuid = a.question_uuid
+              WHERE a.answer_uuid = $1 AND a.deleted_at IS NULL
+              FOR UPDATE OF a FOR SHARE OF q
+            "#,
+            answer
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .ok_or_else(|| DBError::NotFound(format!("Answer not found: {answer_uuid}")))?;
+
+        refuse_locked(&question.state, &question.question_uuid.to_string())?;
+
+        // Retracting a vote that was never cast leaves the score unchanged.
+        let previous = sqlx::query!(
+            "DELETE FROM answer_vote WHERE answer_uuid = $1 AND user_uuid = $2 RETURNING value",
+            answer,
+            user
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?
+        .map_or(0, |r| r.value);
+
+        let record = sqlx::query!(
+            "UPDATE answer SET score = score - $2 WHERE answer_uuid = $1 RETURNING score",
+            answer,
+            i32::from(previous)
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| DBError::Other(Box::new(e)))?;
+
+        reputation_dao::record_vote(&mut tx, VotedPost::Answer(answer), user, 0).await?;
+
+        tx.commit().await.map_err(|e| DBError::Other(Box::new(e)))?;
+
+        Ok(VoteSummary {
+            score: record.score,
+            vote: None,
+        })
+    }
+}